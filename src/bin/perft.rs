@@ -0,0 +1,127 @@
+use mons_rust::{FenRepresentable, GameVariant, MonsGame};
+use std::process;
+use std::time::Instant;
+
+const DEFAULT_DEPTH: usize = 2;
+
+#[derive(Debug)]
+struct CliOptions {
+    fen: Option<String>,
+    variant: GameVariant,
+    depth: usize,
+    turns: bool,
+    cold_cache: bool,
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let Some(options) = parse_cli()? else {
+        return Ok(());
+    };
+
+    let game = match options.fen.as_deref() {
+        Some(fen) => {
+            MonsGame::from_fen(fen, false).ok_or_else(|| format!("invalid fen `{fen}`"))?
+        }
+        None => MonsGame::new(false, options.variant),
+    };
+
+    println!("🧮 perft depth {} for {}", options.depth, game.fen());
+    let started_at = Instant::now();
+    let nodes = if options.turns {
+        game.perft_turns(options.depth)
+    } else {
+        let divide = game.perft_divide(options.depth, !options.cold_cache);
+        for (location, count) in divide.iter() {
+            println!("{}: {count}", location.fen());
+        }
+        divide.iter().map(|(_, count)| count).sum()
+    };
+    let elapsed = started_at.elapsed();
+
+    let unit = if options.turns { "turns" } else { "moves" };
+    let nodes_per_second = nodes as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    println!(
+        "✅ {nodes} {unit} in {:.3}s ({nodes_per_second:.0} nodes/s)",
+        elapsed.as_secs_f64()
+    );
+    Ok(())
+}
+
+fn parse_cli() -> Result<Option<CliOptions>, String> {
+    let mut options = CliOptions {
+        fen: None,
+        variant: GameVariant::DEFAULT,
+        depth: DEFAULT_DEPTH,
+        turns: false,
+        cold_cache: false,
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fen" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--fen requires a value".to_string())?;
+                options.fen = Some(value);
+            }
+            "--variant" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--variant requires a value".to_string())?;
+                options.variant = GameVariant::from_fen(value.as_str())
+                    .filter(|variant| !matches!(variant, GameVariant::Custom))
+                    .ok_or_else(|| format!("invalid --variant value `{value}`"))?;
+            }
+            "--depth" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--depth requires a value".to_string())?;
+                options.depth = value
+                    .parse::<usize>()
+                    .map_err(|err| format!("invalid --depth value `{value}`: {err}"))?;
+            }
+            "--turns" => {
+                options.turns = true;
+            }
+            "--cold-cache" => {
+                options.cold_cache = true;
+            }
+            "--help" | "-h" => {
+                print_help();
+                return Ok(None);
+            }
+            _ => {
+                return Err(format!("unknown argument `{arg}`. Use --help for usage."));
+            }
+        }
+    }
+
+    if options.turns && options.cold_cache {
+        return Err("--cold-cache only applies to input chain perft".to_string());
+    }
+
+    Ok(Some(options))
+}
+
+fn print_help() {
+    println!("Count legal input chains (or full turns) to a fixed depth.");
+    println!();
+    println!("Usage:");
+    println!("  cargo run --release --bin perft -- [options]");
+    println!();
+    println!("Options:");
+    println!("  --fen <fen>       Start position (default: initial position of --variant)");
+    println!("  --variant <id>    Game variant id used without --fen (default: 0)");
+    println!("  --depth <n>       Number of plies to count (default: {DEFAULT_DEPTH})");
+    println!("  --turns           Count distinct full-turn positions instead of input chains");
+    println!("  --cold-cache      Clear the process input cache before every query");
+    println!("  --help, -h        Show this help message");
+}
@@ -1,6 +1,13 @@
 pub mod models;
 pub use models::*;
 
+/// Returns `w` / `b` for a game won on score, `<w|b|d>:<reason>` (for
+/// example `b:resign`) for a game ended by a termination its FENs attest,
+/// an empty string while the game is in progress and `x` for inconsistent
+/// data. A resignation counts when the resigning side's FEN records it, any
+/// other termination when both FENs do. A timeout or abandonment only one
+/// FEN records is unverified rather than inconsistent and gives an empty
+/// string; `verifyMovesWithTimestamps` checks flag-falls against the clock.
 #[wasm_bindgen]
 pub fn winner(
     fen_w: &str,
@@ -26,12 +33,60 @@ pub fn winner(
 
     let game_w = game_w.unwrap();
     let game_b = game_b.unwrap();
-    if game_w.variant() != game_b.variant() {
+    if game_w.variant_layout() != game_b.variant_layout()
+        || game_w.rules() != game_b.rules()
+        || game_w.handicap() != game_b.handicap()
+    {
         return "x".to_string();
     }
 
     let normalized_fen_w = game_w.fen();
     let normalized_fen_b = game_b.fen();
+
+    let recorded_termination = |game: &MonsGame| {
+        game.termination()
+            .filter(|termination| termination.reason != TerminationReason::TargetScore)
+    };
+    match (recorded_termination(&game_w), recorded_termination(&game_b)) {
+        (None, None) => {}
+        (Some(termination_w), Some(termination_b)) if termination_w != termination_b => {
+            return "x".to_string();
+        }
+        (termination_w, termination_b) => {
+            let Some(termination) = termination_w.or(termination_b) else {
+                return "x".to_string();
+            };
+            let attested = match (termination.reason, termination.result) {
+                (TerminationReason::Resignation, GameResult::Win(Color::White)) => {
+                    termination_b.is_some()
+                }
+                (TerminationReason::Resignation, GameResult::Win(Color::Black)) => {
+                    termination_w.is_some()
+                }
+                (TerminationReason::Resignation, GameResult::Draw)
+                | (TerminationReason::TargetScore, _) => false,
+                _ => termination_w.is_some() && termination_b.is_some(),
+            };
+            if !attested {
+                return match termination.reason {
+                    TerminationReason::Timeout | TerminationReason::Abandonment => "".to_string(),
+                    _ => "x".to_string(),
+                };
+            }
+            let Some(mut game) = replay_flat_moves(&game_w, &moves_w, &moves_b) else {
+                return "x".to_string();
+            };
+            game.adjudicate(termination.result, termination.reason);
+            let fen = game.fen();
+            let matches_w = termination_w.is_none() || fen == normalized_fen_w;
+            let matches_b = termination_b.is_none() || fen == normalized_fen_b;
+            return if matches_w && matches_b {
+                termination.code()
+            } else {
+                "x".to_string()
+            };
+        }
+    }
     let winner_color_game_w = game_w.winner_color();
     let winner_color_game_b = game_b.winner_color();
 
@@ -39,7 +94,7 @@ pub fn winner(
         return "".to_string();
     }
 
-    let mut game = MonsGame::new(false, game_w.variant());
+    let mut game = game_w.initial_game(false);
 
     let mut w_index = 0;
     let mut b_index = 0;
@@ -82,6 +137,28 @@ pub fn winner(
     "x".to_string()
 }
 
+/// Replays both players' flat move lists from the initial setup of
+/// `template`'s variant and rules, with its handicap applied by
+/// `initial_game`, stopping early if the game ends on score. `None` if a
+/// list runs out while its player is to move.
+fn replay_flat_moves(template: &MonsGame, moves_w: &[&str], moves_b: &[&str]) -> Option<MonsGame> {
+    let moves_w: Vec<&str> = moves_w.iter().copied().filter(|m| !m.is_empty()).collect();
+    let moves_b: Vec<&str> = moves_b.iter().copied().filter(|m| !m.is_empty()).collect();
+    let mut game = template.initial_game(false);
+    let (mut w_index, mut b_index) = (0, 0);
+    while (w_index < moves_w.len() || b_index < moves_b.len()) && !game.is_game_over() {
+        let inputs = if game.active_color == Color::White {
+            w_index += 1;
+            moves_w.get(w_index - 1)?
+        } else {
+            b_index += 1;
+            moves_b.get(b_index - 1)?
+        };
+        _ = game.process_input(Input::array_from_fen(inputs), false, false);
+    }
+    Some(game)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,7 +173,7 @@ mod tests {
 
     #[test]
     fn automove_till_end() -> io::Result<()> {
-        let mut game = MonsGameModel::new(GameVariant::Classic);
+        let mut game = MonsGameModel::new(GameVariant::Classic).unwrap();
         loop {
             _ = game.automove();
             if let Some(winner) = game.winner_color() {
@@ -109,7 +186,7 @@ mod tests {
 
     #[test]
     fn automove() -> io::Result<()> {
-        let mut game = MonsGameModel::new(GameVariant::Classic);
+        let mut game = MonsGameModel::new(GameVariant::Classic).unwrap();
         let output = game.automove();
         println!("{:?}", game.fen());
         match output.kind {
@@ -119,6 +196,83 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn winner_reports_resignation_reason() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let mut moves_w = Vec::new();
+        for inputs in game.legal_turns_up_to(1).remove(0).moves {
+            moves_w.push(Input::fen_from_array(&inputs));
+            game.process_input(inputs, false, false);
+        }
+        let ongoing_fen = game.fen();
+        game.resign(Color::Black);
+        let resigned_fen = game.fen();
+        let moves_w = moves_w.join("-");
+
+        assert_eq!(
+            winner(&ongoing_fen, &resigned_fen, &moves_w, ""),
+            "w:resign"
+        );
+        assert_eq!(
+            winner(&resigned_fen, &resigned_fen, &moves_w, ""),
+            "w:resign"
+        );
+        assert_eq!(winner(&ongoing_fen, &ongoing_fen, &moves_w, ""), "");
+        assert_eq!(winner(&ongoing_fen, &resigned_fen, "", ""), "x");
+    }
+
+    #[test]
+    fn winner_rejects_terminations_claimed_by_the_wrong_side() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let mut moves_w = Vec::new();
+        for inputs in game.legal_turns_up_to(1).remove(0).moves {
+            moves_w.push(Input::fen_from_array(&inputs));
+            game.process_input(inputs, false, false);
+        }
+        let moves_w = moves_w.join("-");
+        let ongoing_fen = game.fen();
+        let ended_fen = |result: GameResult, reason: TerminationReason| {
+            let mut ended = game.clone();
+            ended.adjudicate(result, reason);
+            ended.fen()
+        };
+
+        let black_resigned = ended_fen(
+            GameResult::Win(Color::White),
+            TerminationReason::Resignation,
+        );
+        assert_eq!(winner(&black_resigned, &ongoing_fen, &moves_w, ""), "x");
+        let white_resigned = ended_fen(
+            GameResult::Win(Color::Black),
+            TerminationReason::Resignation,
+        );
+        assert_eq!(
+            winner(&white_resigned, &ongoing_fen, &moves_w, ""),
+            "b:resign"
+        );
+        assert_eq!(winner(&ongoing_fen, &white_resigned, &moves_w, ""), "x");
+
+        let agreed = ended_fen(GameResult::Draw, TerminationReason::Agreement);
+        assert_eq!(winner(&agreed, &ongoing_fen, &moves_w, ""), "x");
+        assert_eq!(winner(&agreed, &agreed, &moves_w, ""), "d:agreement");
+
+        for reason in [TerminationReason::Timeout, TerminationReason::Abandonment] {
+            let claimed = ended_fen(GameResult::Win(Color::White), reason);
+            assert_eq!(winner(&claimed, &ongoing_fen, &moves_w, ""), "");
+            assert_eq!(winner(&ongoing_fen, &claimed, &moves_w, ""), "");
+            assert_eq!(
+                winner(&claimed, &claimed, &moves_w, ""),
+                format!("w:{}", reason.code())
+            );
+        }
+        let abandoned = ended_fen(
+            GameResult::Win(Color::Black),
+            TerminationReason::Abandonment,
+        );
+        assert_eq!(winner(&abandoned, &abandoned, &moves_w, ""), "b:abandon");
+        assert_eq!(winner(&abandoned, &abandoned, "", ""), "x");
+    }
+
     #[test]
     fn check_initial_fen() -> io::Result<()> {
         let game = MonsGame::new(false, GameVariant::Classic);
@@ -172,7 +326,7 @@ mod tests {
 
     #[test]
     fn simulation_model_avoids_verbose_tracking_growth() -> io::Result<()> {
-        let mut game = MonsGameModel::new_for_simulation(GameVariant::Classic);
+        let mut game = MonsGameModel::new_for_simulation(GameVariant::Classic).unwrap();
         let output = game.automove();
         assert_eq!(output.kind, OutputModelKind::Events);
         assert!(game.verbose_tracking_entities().is_empty());
@@ -182,8 +336,8 @@ mod tests {
 
     #[test]
     fn tracked_and_simulation_modes_stay_in_sync_when_replaying_inputs() -> io::Result<()> {
-        let mut tracked = MonsGameModel::new(GameVariant::Classic);
-        let mut simulation = MonsGameModel::new_for_simulation(GameVariant::Classic);
+        let mut tracked = MonsGameModel::new(GameVariant::Classic).unwrap();
+        let mut simulation = MonsGameModel::new_for_simulation(GameVariant::Classic).unwrap();
 
         for _ in 0..512 {
             let tracked_output = tracked.automove();
@@ -208,7 +362,7 @@ mod tests {
 
     #[test]
     fn model_is_later_than_rejects_cross_variant_fens() -> io::Result<()> {
-        let model = MonsGameModel::new(GameVariant::Classic);
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
         let other = MonsGame::new(false, GameVariant::SwappedManaRows);
         assert!(!model.is_later_than(other.fen().as_str()));
         Ok(())
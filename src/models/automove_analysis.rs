@@ -0,0 +1,234 @@
+use super::*;
+
+/// Longest principal variation reported, in moves.
+const ANALYSIS_MAX_PRINCIPAL_VARIATION_MOVES: usize = 16;
+/// Enumeration limit used to recover principal variation moves from
+/// transposition table hashes.
+const ANALYSIS_PRINCIPAL_VARIATION_ENUM_LIMIT: usize = 512;
+
+/// One candidate of a multi-PV analysis.
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AnalysisLine {
+    inputs: Vec<Input>,
+    score: i32,
+    principal_variation: Vec<Vec<Input>>,
+    forced_win: bool,
+}
+
+#[wasm_bindgen]
+impl AnalysisLine {
+    pub fn input_fen(&self) -> String {
+        Input::fen_from_array(&self.inputs)
+    }
+
+    /// Search score from the point of view of the player to move.
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    /// Input fens of the expected continuation, starting with this move and
+    /// running through the end of the opponent's reply when the search saw
+    /// that far.
+    pub fn principal_variation(&self) -> Vec<String> {
+        self.principal_variation
+            .iter()
+            .map(|inputs| Input::fen_from_array(inputs))
+            .collect()
+    }
+
+    /// Whether the move reaches the target score by force.
+    pub fn is_forced_win(&self) -> bool {
+        self.forced_win
+    }
+}
+
+impl AnalysisLine {
+    pub fn inputs(&self) -> &[Input] {
+        &self.inputs
+    }
+
+    pub fn principal_variation_inputs(&self) -> &[Vec<Input>] {
+        &self.principal_variation
+    }
+}
+
+impl MonsGameModel {
+    /// Scores every root move `preference` would consider with a full window,
+    /// so that each score is exact rather than a cutoff bound, and returns the
+    /// best `k`. Root moves share a transposition table and split the node
+    /// budget evenly; the table also yields each principal variation.
+    pub(crate) fn analysis_lines(
+        game: &MonsGame,
+        config: AutomoveSearchConfig,
+        k: usize,
+    ) -> Vec<AnalysisLine> {
+        if k == 0 {
+            return Vec::new();
+        }
+        clear_exact_state_analysis_cache();
+        let perspective = game.active_color;
+        let root_moves = Self::ranked_root_moves(game, perspective, config);
+        if root_moves.is_empty() {
+            return Vec::new();
+        }
+
+        let mut root_config = config;
+        root_config.enable_root_aspiration = false;
+        root_config.max_visited_nodes =
+            (config.max_visited_nodes / root_moves.len()).max(MIN_SMART_MAX_VISITED_NODES);
+        let extension_node_budget = if root_config.enable_selective_extensions
+            && root_config.selective_extension_node_share_bp > 0
+        {
+            ((root_config.max_visited_nodes
+                * root_config.selective_extension_node_share_bp as usize)
+                / 10_000)
+                .max(1)
+        } else {
+            0
+        };
+        let mut transposition_table = U64HashMap::default();
+        let mut killer_table: KillerTable = [[0u64; 2]; MAX_SMART_SEARCH_DEPTH + 2];
+        let mut history_table: HistoryTable = HistoryTable::default();
+
+        let mut scored = root_moves
+            .into_iter()
+            .map(|candidate| {
+                let mut visited_nodes = 1usize;
+                let mut extension_nodes_used = 0usize;
+                let mut quiescence_nodes_used = 0usize;
+                let score = Self::evaluate_root_candidate_score(
+                    &candidate,
+                    perspective,
+                    i32::MIN,
+                    &mut visited_nodes,
+                    root_config,
+                    &mut transposition_table,
+                    &mut extension_nodes_used,
+                    extension_node_budget,
+                    true,
+                    &mut killer_table,
+                    &mut history_table,
+                    &mut quiescence_nodes_used,
+                );
+                (candidate, score)
+            })
+            .collect::<Vec<_>>();
+        scored.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.root_rank.cmp(&b.root_rank))
+        });
+        scored.truncate(k);
+
+        scored
+            .into_iter()
+            .map(|(candidate, score)| {
+                let mut principal_variation = vec![candidate.inputs.clone()];
+                principal_variation.extend(Self::principal_variation_from_table(
+                    &candidate.game,
+                    perspective,
+                    &transposition_table,
+                ));
+                AnalysisLine {
+                    forced_win: candidate.wins_immediately || score >= SMART_TERMINAL_SCORE / 2,
+                    inputs: candidate.inputs,
+                    score,
+                    principal_variation,
+                }
+            })
+            .collect()
+    }
+
+    /// Follows best-child links from `game` until the opponent's reply turn
+    /// is over, the game ends or the table has no further entry.
+    fn principal_variation_from_table(
+        game: &MonsGame,
+        perspective: Color,
+        transposition_table: &U64HashMap<TranspositionEntry>,
+    ) -> Vec<Vec<Input>> {
+        let mut variation = Vec::new();
+        let mut game = game.clone_for_simulation();
+        let mut opponent_moved = false;
+        while variation.len() + 1 < ANALYSIS_MAX_PRINCIPAL_VARIATION_MOVES
+            && !game.is_game_over()
+            && !(opponent_moved && game.active_color == perspective)
+        {
+            let Some(entry) = transposition_table.get(&Self::search_state_hash(&game)) else {
+                break;
+            };
+            if entry.best_child_hash == 0 {
+                break;
+            }
+            let Some(transition) = Self::enumerate_legal_transitions(
+                &game,
+                ANALYSIS_PRINCIPAL_VARIATION_ENUM_LIMIT,
+                SuggestedStartInputOptions::for_automove(),
+            )
+            .into_iter()
+            .find(|transition| Self::search_state_hash(&transition.game) == entry.best_child_hash) else {
+                break;
+            };
+            opponent_moved |= game.active_color != perspective;
+            variation.push(transition.inputs);
+            game = transition.game;
+        }
+        variation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analysis_ranks_legal_candidates_by_score() {
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        let lines = model
+            .analyze_native(SmartAutomovePreference::Fast, 3)
+            .unwrap();
+        assert_eq!(lines.len(), 3);
+        assert!(lines
+            .windows(2)
+            .all(|pair| pair[0].score() >= pair[1].score()));
+        for line in &lines {
+            assert_eq!(line.principal_variation()[0], line.input_fen());
+            assert!(line.principal_variation().len() > 1);
+            let mut game = model.game.clone_for_simulation();
+            for inputs in line.principal_variation_inputs() {
+                assert!(matches!(
+                    game.process_input_slice(inputs, false, false),
+                    Output::Events(_)
+                ));
+            }
+            assert!(!line.is_forced_win());
+        }
+    }
+
+    #[test]
+    fn analysis_flags_forced_score_wins() {
+        let drainer_base = Config::mon_base(MonKind::Drainer, Color::White);
+        let game = PositionBuilder::initial(GameVariant::Classic)
+            .remove(drainer_base)
+            .item(
+                Location::new(9, 1),
+                Item::MonWithMana {
+                    mon: Mon::new(MonKind::Drainer, Color::White, 0),
+                    mana: Mana::Regular(Color::White),
+                },
+            )
+            .score(Color::White, Config::TARGET_SCORE - 1)
+            .turn_number(3)
+            .build_unchecked();
+        let model = MonsGameModel::with_game(game);
+        let lines = model
+            .analyze_native(SmartAutomovePreference::Normal, 2)
+            .unwrap();
+        assert!(lines[0].is_forced_win());
+        let mut game = model.game.clone_for_simulation();
+        for inputs in lines[0].principal_variation_inputs() {
+            game.process_input_slice(inputs, false, false);
+        }
+        assert_eq!(game.winner_color(), Some(Color::White));
+    }
+}
@@ -7,22 +7,16 @@ const EXACT_ANALYSIS_CACHE_MAX_ENTRIES: usize = 512;
 const EXACT_ATTACK_REACH_CACHE_MAX_ENTRIES: usize = 8192;
 const EXACT_CARRIER_DISTANCE_MAP_CACHE_MAX_ENTRIES: usize = 8192;
 const EXACT_CARRIER_STEPS_CACHE_MAX_ENTRIES: usize = 8192;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_DRAINER_SAFETY_CACHE_MAX_ENTRIES: usize = 8192;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_DRAINER_TO_MANA_CACHE_MAX_ENTRIES: usize = 8192;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_DRAINER_PICKUP_WINDOW_CACHE_MAX_ENTRIES: usize = 8192;
 #[cfg(test)]
 const EXACT_FOLLOWUP_SUMMARY_CACHE_MAX_ENTRIES: usize = 4096;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_IMMEDIATE_TACTICAL_WINDOW_CACHE_MAX_ENTRIES: usize = 8192;
 const EXACT_PICKUP_PATH_CACHE_MAX_ENTRIES: usize = 8192;
 const EXACT_SPIRIT_REACH_CACHE_MAX_ENTRIES: usize = 4096;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_SPIRIT_SUMMARY_CACHE_MAX_ENTRIES: usize = 2048;
 const EXACT_WALK_THREAT_CACHE_MAX_ENTRIES: usize = 8192;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_SECURE_MANA_CACHE_MAX_ENTRIES: usize = 4096;
 const EXACT_SPIRIT_UTILITY_CAP: i32 = 6;
 const EXACT_BFS_CAPACITY: usize = 128;
@@ -33,7 +27,6 @@ const EXACT_PAYLOAD_VARIANTS: usize = 5;
 const EXACT_CARRIER_MANA_STATE_CAPACITY: usize =
     EXACT_LOCATION_STATE_CAPACITY * EXACT_CARRIER_MANA_VARIANTS;
 const EXACT_PAYLOAD_STATE_CAPACITY: usize = EXACT_LOCATION_STATE_CAPACITY * EXACT_PAYLOAD_VARIANTS;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_SECURE_TOUCHED_ITEMS_CAPACITY: usize = 12;
 const EXACT_CARRIER_DISTANCE_UNKNOWN: u8 = u8::MAX;
 const EXACT_CARRIER_MANA_VALUES: [Mana; EXACT_CARRIER_MANA_VARIANTS] = [
@@ -70,7 +63,6 @@ impl Hasher for ExactFastHasher {
 
 type ExactBuildHasher = BuildHasherDefault<ExactFastHasher>;
 type ExactHashMap<K, V> = HashMap<K, V, ExactBuildHasher>;
-#[cfg(any(target_arch = "wasm32", test))]
 type ExactHashSet<K> = std::collections::HashSet<K, ExactBuildHasher>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -382,14 +374,11 @@ pub(crate) struct ExactColorSummary {
     pub score_path_window: ExactScorePathWindow,
     pub immediate_window: ExactImmediateScoreWindow,
     pub best_drainer_pickup: Option<ExactDrainerPickupPath>,
-    #[cfg(any(target_arch = "wasm32", test))]
     pub best_carrier_steps: Option<i32>,
-    #[cfg(any(target_arch = "wasm32", test))]
     pub best_drainer_to_mana_steps: Option<i32>,
     pub spirit: ExactSpiritSummary,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct ExactTurnSummary {
     pub can_attack_opponent_drainer: bool,
@@ -408,7 +397,6 @@ pub(crate) struct ExactTurnSummary {
     pub score_path_best_steps: Option<i32>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub(crate) struct ExactTurnTacticalProjection {
     pub safe_supermana_progress: bool,
@@ -422,34 +410,23 @@ pub(crate) struct ExactTurnTacticalProjection {
     pub same_turn_score_window_value: i32,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) const EXACT_TURN_TACTICAL_NEED_SUPERMANA_PROGRESS: u8 = 1 << 0;
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) const EXACT_TURN_TACTICAL_NEED_OPPONENT_MANA_PROGRESS: u8 = 1 << 1;
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) const EXACT_TURN_TACTICAL_NEED_SPIRIT_SCORE: u8 = 1 << 2;
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) const EXACT_TURN_TACTICAL_NEED_SPIRIT_DENIAL: u8 = 1 << 3;
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) const EXACT_TURN_TACTICAL_NEED_SCORE_WINDOW: u8 = 1 << 4;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_TURN_TACTICAL_ALL_FLAGS: u8 = EXACT_TURN_TACTICAL_NEED_SUPERMANA_PROGRESS
     | EXACT_TURN_TACTICAL_NEED_OPPONENT_MANA_PROGRESS
     | EXACT_TURN_TACTICAL_NEED_SPIRIT_SCORE
     | EXACT_TURN_TACTICAL_NEED_SPIRIT_DENIAL
     | EXACT_TURN_TACTICAL_NEED_SCORE_WINDOW;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_TACTICAL_SPIRIT_NEED_SCORE: u8 = 1 << 0;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_TACTICAL_SPIRIT_NEED_DENIAL: u8 = 1 << 1;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_TACTICAL_SPIRIT_NEED_PROGRESS: u8 = 1 << 2;
-#[cfg(any(target_arch = "wasm32", test))]
 const EXACT_TACTICAL_SPIRIT_ALL_FIELDS: u8 = EXACT_TACTICAL_SPIRIT_NEED_SCORE
     | EXACT_TACTICAL_SPIRIT_NEED_DENIAL
     | EXACT_TACTICAL_SPIRIT_NEED_PROGRESS;
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct ExactOpportunityBudget {
     pub remaining_mon_moves: i32,
@@ -457,7 +434,6 @@ pub(crate) struct ExactOpportunityBudget {
     pub can_move_mana: bool,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct ExactOpportunityDelta {
     pub same_turn_score_window_value: i32,
@@ -469,7 +445,6 @@ pub(crate) struct ExactOpportunityDelta {
     pub safe_opponent_mana_progress_steps: Option<i32>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct ExactOpportunityContext {
     pub budget: ExactOpportunityBudget,
@@ -486,7 +461,6 @@ enum ExactColorSummaryMode {
     PassiveStrategic,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_tactical_spirit_summary_for_fields(
     summary: ExactSpiritSummary,
@@ -527,7 +501,6 @@ fn exact_tactical_spirit_summary_for_fields(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_tactical_spirit_superset_fields(fields: u8) -> &'static [u8] {
     match fields {
@@ -552,7 +525,6 @@ fn exact_tactical_spirit_superset_fields(fields: u8) -> &'static [u8] {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_immediate_tactical_window_for_axes(
     window: ExactImmediateTacticalWindow,
@@ -569,7 +541,6 @@ fn exact_immediate_tactical_window_for_axes(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_immediate_tactical_window_for_min_score(
     window: ExactImmediateTacticalWindow,
@@ -589,7 +560,6 @@ fn exact_immediate_tactical_window_for_min_score(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_drainer_pickup_window_for_axes(
     window: ExactDrainerPickupWindow,
@@ -602,7 +572,6 @@ fn exact_drainer_pickup_window_for_axes(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_drainer_pickup_window_for_min_any_score(
     window: ExactDrainerPickupWindow,
@@ -620,7 +589,6 @@ fn exact_drainer_pickup_window_for_min_any_score(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_turn_tactical_projection_for_flags(
     projection: ExactTurnTacticalProjection,
@@ -749,13 +717,11 @@ pub(crate) struct ExactStateAnalysisCache {
     entries: ExactHashMap<u64, ExactStateAnalysis>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactTurnSummaryCache {
     entries: ExactHashMap<u64, ExactTurnSummary>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactTurnTacticalProjectionKey {
     state_hash: u64,
@@ -765,7 +731,6 @@ struct ExactTurnTacticalProjectionKey {
     flags: u8,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactTurnTacticalProjectionCache {
     entries: ExactHashMap<ExactTurnTacticalProjectionKey, ExactTurnTacticalProjection>,
@@ -852,14 +817,13 @@ struct ExactAttackReachCache {
     entries: ExactHashMap<ExactAttackQueryKey, bool>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactDrainerSafetyQueryKey {
     board_hash: u64,
     color: Color,
+    rules: RuleSet,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactDrainerSafetyCache {
     entries: ExactHashMap<ExactDrainerSafetyQueryKey, i32>,
@@ -924,7 +888,6 @@ struct ExactCarrierStepsCache {
     entries: ExactHashMap<ExactCarrierStepsQueryKey, Option<i32>>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactDrainerToManaQueryKey {
     board_hash: u64,
@@ -932,7 +895,6 @@ struct ExactDrainerToManaQueryKey {
     start: Location,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactDrainerToManaCache {
     entries: ExactHashMap<ExactDrainerToManaQueryKey, Option<i32>>,
@@ -944,6 +906,7 @@ struct ExactFollowupSummaryKey {
     board_hash: u64,
     color: Color,
     remaining_moves: i32,
+    rules: RuleSet,
 }
 
 #[cfg(test)]
@@ -966,7 +929,6 @@ struct ExactPickupPathCache {
     entries: ExactHashMap<ExactPickupPathQueryKey, Option<ExactDrainerPickupPath>>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactDrainerPickupWindowQueryKey {
     board_hash: u64,
@@ -979,28 +941,25 @@ struct ExactDrainerPickupWindowQueryKey {
     opponent_mana: Mana,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactDrainerPickupWindowCache {
     entries: ExactHashMap<ExactDrainerPickupWindowQueryKey, ExactDrainerPickupWindow>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactSpiritSummaryKey {
     board_hash: u64,
     color: Color,
     remaining_mon_moves: i32,
     can_use_action: bool,
+    rules: RuleSet,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactSpiritSummaryCache {
     entries: ExactHashMap<ExactSpiritSummaryKey, ExactSpiritSummary>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactTacticalSpiritSummaryKey {
     board_hash: u64,
@@ -1008,15 +967,14 @@ struct ExactTacticalSpiritSummaryKey {
     remaining_mon_moves: i32,
     can_use_action: bool,
     fields: u8,
+    rules: RuleSet,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactSpiritTacticalSummaryCache {
     entries: ExactHashMap<ExactTacticalSpiritSummaryKey, ExactSpiritSummary>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactTacticalSpiritAfterWindowKey {
     board_hash: u64,
@@ -1026,7 +984,6 @@ struct ExactTacticalSpiritAfterWindowKey {
     need_denial: bool,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactImmediateTacticalWindowQueryKey {
     board_hash: u64,
@@ -1037,7 +994,6 @@ struct ExactImmediateTacticalWindowQueryKey {
     need_denial: bool,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactImmediateTacticalWindowCache {
     entries: ExactHashMap<ExactImmediateTacticalWindowQueryKey, ExactImmediateTacticalWindow>,
@@ -1069,17 +1025,17 @@ struct ExactWalkThreatCache {
     entries: ExactHashMap<ExactWalkThreatQueryKey, bool>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactSecureManaStateKey {
     board_hash: u64,
     active_color: Color,
     mons_moves_count: i32,
+    mons_moves_limit: i32,
+    rules: RuleSet,
     white_regular_mana_count: u8,
     black_regular_mana_count: u8,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ExactSecureManaQueryKey {
     state: ExactSecureManaStateKey,
@@ -1087,7 +1043,6 @@ struct ExactSecureManaQueryKey {
     wanted: Mana,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct ExactSecureManaCache {
     entries: ExactHashMap<ExactSecureManaQueryKey, Option<i32>>,
@@ -1131,17 +1086,14 @@ thread_local! {
     #[cfg(test)]
     static EXACT_STATE_ANALYSIS_CACHE: RefCell<ExactStateAnalysisCache> =
         RefCell::new(ExactStateAnalysisCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_TURN_SUMMARY_CACHE: RefCell<ExactTurnSummaryCache> =
         RefCell::new(ExactTurnSummaryCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_TURN_TACTICAL_PROJECTION_CACHE: RefCell<ExactTurnTacticalProjectionCache> =
         RefCell::new(ExactTurnTacticalProjectionCache::default());
     static EXACT_STRATEGIC_ANALYSIS_CACHE: RefCell<ExactStrategicAnalysisCache> =
         RefCell::new(ExactStrategicAnalysisCache::default());
     static EXACT_ATTACK_REACH_CACHE: RefCell<ExactAttackReachCache> =
         RefCell::new(ExactAttackReachCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_DRAINER_SAFETY_CACHE: RefCell<ExactDrainerSafetyCache> =
         RefCell::new(ExactDrainerSafetyCache::default());
     static EXACT_CARRIER_DISTANCE_MAP_CACHE: RefCell<ExactCarrierDistanceMapCache> =
@@ -1150,31 +1102,25 @@ thread_local! {
         RefCell::new(ExactCarrierDistanceMapWarmupCache::default());
     static EXACT_CARRIER_STEPS_CACHE: RefCell<ExactCarrierStepsCache> =
         RefCell::new(ExactCarrierStepsCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_DRAINER_TO_MANA_CACHE: RefCell<ExactDrainerToManaCache> =
         RefCell::new(ExactDrainerToManaCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_DRAINER_PICKUP_WINDOW_CACHE: RefCell<ExactDrainerPickupWindowCache> =
         RefCell::new(ExactDrainerPickupWindowCache::default());
     #[cfg(test)]
     static EXACT_FOLLOWUP_SUMMARY_CACHE: RefCell<ExactFollowupSummaryCache> =
         RefCell::new(ExactFollowupSummaryCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_IMMEDIATE_TACTICAL_WINDOW_CACHE: RefCell<ExactImmediateTacticalWindowCache> =
         RefCell::new(ExactImmediateTacticalWindowCache::default());
     static EXACT_PICKUP_PATH_CACHE: RefCell<ExactPickupPathCache> =
         RefCell::new(ExactPickupPathCache::default());
     static EXACT_SPIRIT_REACH_CACHE: RefCell<ExactSpiritReachCache> =
         RefCell::new(ExactSpiritReachCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_SPIRIT_SUMMARY_CACHE: RefCell<ExactSpiritSummaryCache> =
         RefCell::new(ExactSpiritSummaryCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_SPIRIT_TACTICAL_SUMMARY_CACHE: RefCell<ExactSpiritTacticalSummaryCache> =
         RefCell::new(ExactSpiritTacticalSummaryCache::default());
     static EXACT_WALK_THREAT_CACHE: RefCell<ExactWalkThreatCache> =
         RefCell::new(ExactWalkThreatCache::default());
-    #[cfg(any(target_arch = "wasm32", test))]
     static EXACT_SECURE_MANA_CACHE: RefCell<ExactSecureManaCache> =
         RefCell::new(ExactSecureManaCache::default());
     #[cfg(test)]
@@ -1240,7 +1186,6 @@ pub(crate) fn exact_query_diagnostics_snapshot() -> ExactQueryDiagnostics {
     EXACT_QUERY_DIAGNOSTICS.with(|diagnostics| *diagnostics.borrow())
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 pub(crate) fn clear_exact_state_analysis_cache() {
     #[cfg(test)]
@@ -1322,14 +1267,12 @@ pub(crate) fn exact_strategic_analysis_with_search_hash(
     })
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 pub(crate) fn exact_turn_summary(game: &MonsGame, color: Color) -> ExactTurnSummary {
     let key = exact_search_state_hash(game);
     exact_turn_summary_with_search_hash(game, color, key)
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 pub(crate) fn exact_turn_summary_with_search_hash(
     game: &MonsGame,
@@ -1373,7 +1316,6 @@ pub(crate) fn exact_turn_tactical_projection(
     )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 pub(crate) fn exact_turn_tactical_projection_with_search_hash(
     game: &MonsGame,
@@ -1385,7 +1327,7 @@ pub(crate) fn exact_turn_tactical_projection_with_search_hash(
         return ExactTurnTacticalProjection::default();
     }
 
-    let remaining_mon_moves = (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+    let remaining_mon_moves = (game.mons_moves_limit() - game.mons_moves_count).max(0);
     let can_use_action = game.player_can_use_action();
     let cache_key = ExactTurnTacticalProjectionKey {
         state_hash: key,
@@ -1424,7 +1366,6 @@ pub(crate) fn exact_turn_tactical_projection_with_search_hash(
     })
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 pub(crate) fn exact_same_turn_score_window_with_search_hash(
     game: &MonsGame,
@@ -1440,7 +1381,6 @@ pub(crate) fn exact_same_turn_score_window_with_search_hash(
     .same_turn_score_window_value
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_opportunity_turn_tactical_projection_with_search_hash(
     game: &MonsGame,
@@ -1459,18 +1399,15 @@ fn exact_opportunity_turn_tactical_projection_with_search_hash(
     )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn can_attack_opponent_drainer_this_turn(game: &MonsGame, color: Color) -> bool {
     exact_turn_summary(game, color).can_attack_opponent_drainer
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn exact_opportunity_context(game: &MonsGame, color: Color) -> ExactOpportunityContext {
     let key = exact_search_state_hash(game);
     exact_opportunity_context_with_search_hash(game, color, key)
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn exact_opportunity_context_with_search_hash(
     game: &MonsGame,
     color: Color,
@@ -1481,20 +1418,21 @@ pub(crate) fn exact_opportunity_context_with_search_hash(
     }
 
     let budget = ExactOpportunityBudget {
-        remaining_mon_moves: (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0),
+        remaining_mon_moves: (game.mons_moves_limit() - game.mons_moves_count).max(0),
         can_use_action: game.player_can_use_action(),
         can_move_mana: game.player_can_move_mana(),
     };
     let board_hash = exact_board_hash(&game.board);
     let turn = exact_opportunity_turn_tactical_projection_with_search_hash(game, color, key);
-    let drainer_safety = exact_own_drainer_safety_score_with_hash(&game.board, board_hash, color);
+    let drainer_safety =
+        exact_own_drainer_safety_score_with_hash(&game.board, board_hash, color, game.rules());
     let opponent = color.other();
     let opponent_score = if opponent == Color::White {
         game.white_score
     } else {
         game.black_score
     };
-    let opponent_needed = Config::TARGET_SCORE.saturating_sub(opponent_score);
+    let opponent_needed = game.rules().target_score.saturating_sub(opponent_score);
     let opponent_immediate = exact_strategic_analysis_with_search_hash(game, key)
         .color_summary(opponent)
         .immediate_window
@@ -1527,13 +1465,17 @@ pub(crate) fn exact_opportunity_context_with_search_hash(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn exact_own_drainer_safety_score_with_hash(
     board: &Board,
     board_hash: u64,
     color: Color,
+    rules: &RuleSet,
 ) -> i32 {
-    let key = ExactDrainerSafetyQueryKey { board_hash, color };
+    let key = ExactDrainerSafetyQueryKey {
+        board_hash,
+        color,
+        rules: *rules,
+    };
     if let Some(cached) =
         EXACT_DRAINER_SAFETY_CACHE.with(|cache| cache.borrow().entries.get(&key).copied())
     {
@@ -1563,6 +1505,7 @@ pub(crate) fn exact_own_drainer_safety_score_with_hash(
             board_hash,
             color,
             drainer_location,
+            rules,
         );
 
         if exact_safe && !immediate && !walk {
@@ -1590,7 +1533,6 @@ pub(crate) fn exact_own_drainer_safety_score_with_hash(
     result
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn can_attack_target_on_board(
     board: &Board,
     attacker_color: Color,
@@ -2135,54 +2077,20 @@ fn exact_attack_payload_after_move(
 }
 
 pub(crate) fn exact_board_hash(board: &Board) -> u64 {
-    let mut state = 0x6a09e667f3bcc909u64 ^ exact_board_variant_hash(board.variant());
-    for (index, item) in board.items.iter().enumerate() {
-        let Some(item) = item else { continue };
-        state ^= exact_board_entry_hash(index, *item);
-    }
-    state
+    0x6a09e667f3bcc909u64 ^ exact_board_layout_hash(board.layout()) ^ board.zobrist()
 }
 
+/// Keyed by layout rather than variant id, which is `Custom` for every
+/// registered layout.
 #[inline]
-fn exact_board_entry_hash(index: usize, item: Item) -> u64 {
-    let entry = ((index as u64)
-        .wrapping_add(1)
-        .wrapping_mul(0x9e3779b185ebca87))
-        ^ exact_hash_item(item).wrapping_mul(0x94d049bb133111eb);
-    exact_mix_u64(entry)
+fn exact_board_layout_hash(layout: &VariantLayout) -> u64 {
+    exact_mix_u64((layout.key() as i64 as u64).wrapping_add(0x243f6a8885a308d3))
 }
 
-#[inline]
-fn exact_board_variant_hash(variant: GameVariant) -> u64 {
-    exact_mix_u64((variant.id() as i64 as u64).wrapping_add(0x243f6a8885a308d3))
+fn exact_search_state_hash(game: &MonsGame) -> u64 {
+    exact_search_mix_u64(0x6a09e667f3bcc909u64 ^ game.zobrist())
 }
 
-fn exact_search_state_hash(game: &MonsGame) -> u64 {
-    let mut state = 0x6a09e667f3bcc909u64;
-    for (idx, item) in game.board.items.iter().enumerate() {
-        let Some(item) = item else { continue };
-        let entry = ((idx as u64)
-            .wrapping_add(1)
-            .wrapping_mul(0x9e3779b185ebca87))
-            ^ exact_search_hash_item(*item);
-        state ^= exact_search_mix_u64(entry);
-        state = state.rotate_left(17).wrapping_mul(0x94d049bb133111eb);
-    }
-
-    state ^= exact_search_mix_u64(game.white_score as i64 as u64 ^ 0x11);
-    state ^= exact_search_mix_u64(game.black_score as i64 as u64 ^ 0x23);
-    state ^= exact_search_mix_u64(exact_search_hash_color(game.active_color) ^ 0x35);
-    state ^= exact_search_mix_u64(game.actions_used_count as i64 as u64 ^ 0x47);
-    state ^= exact_search_mix_u64(game.mana_moves_count as i64 as u64 ^ 0x59);
-    state ^= exact_search_mix_u64(game.mons_moves_count as i64 as u64 ^ 0x6b);
-    state ^= exact_search_mix_u64(game.white_potions_count as i64 as u64 ^ 0x7d);
-    state ^= exact_search_mix_u64(game.black_potions_count as i64 as u64 ^ 0x8f);
-    state ^= exact_search_mix_u64(game.turn_number as i64 as u64 ^ 0xa1);
-    state ^= exact_search_mix_u64(game.variant().id() as i64 as u64 ^ 0xb3);
-    exact_search_mix_u64(state)
-}
-
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_walk_destination_plausible(board: &Board, actor: Location, destination: Location) -> bool {
     let Some(actor_mon) = board.item(actor).and_then(|item| item.mon()).copied() else {
@@ -2273,7 +2181,6 @@ fn exact_search_mix_u64(mut value: u64) -> u64 {
     value ^ (value >> 31)
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_secure_board_entry_hash(index: usize, item: Item) -> u64 {
     let entry = ((index as u64)
@@ -2288,9 +2195,8 @@ fn exact_secure_board_hash(board: &Board) -> u64 {
     exact_secure_board_state(board).0
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_secure_board_state(board: &Board) -> (u64, u8, u8) {
-    let mut state = 0xa0761d6478bd642fu64 ^ exact_secure_board_variant_hash(board.variant());
+    let mut state = 0xa0761d6478bd642fu64 ^ exact_board_layout_hash(board.layout());
     let mut white_regular = 0u8;
     let mut black_regular = 0u8;
     for (index, item) in board.items.iter().enumerate() {
@@ -2309,13 +2215,6 @@ fn exact_secure_board_state(board: &Board) -> (u64, u8, u8) {
     (state, white_regular, black_regular)
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
-#[inline]
-fn exact_secure_board_variant_hash(variant: GameVariant) -> u64 {
-    exact_search_mix_u64((variant.id() as i64 as u64).wrapping_add(0x13198a2e03707344))
-}
-
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_adjust_regular_mana_counts(white: &mut u8, black: &mut u8, mana: Mana, delta: i8) {
     let count = match mana {
@@ -2331,18 +2230,24 @@ fn exact_adjust_regular_mana_counts(white: &mut u8, black: &mut u8, mana: Mana,
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_secure_mana_state_key(game: &MonsGame) -> ExactSecureManaStateKey {
-    exact_secure_mana_state_key_from_board(&game.board, game.active_color, game.mons_moves_count)
+    exact_secure_mana_state_key_from_board(
+        &game.board,
+        game.active_color,
+        game.mons_moves_count,
+        game.mons_moves_limit(),
+        game.rules(),
+    )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_secure_mana_state_key_from_board(
     board: &Board,
     active_color: Color,
     mons_moves_count: i32,
+    mons_moves_limit: i32,
+    rules: &RuleSet,
 ) -> ExactSecureManaStateKey {
     let (board_hash, white_regular_mana_count, black_regular_mana_count) =
         exact_secure_board_state(board);
@@ -2350,33 +2255,13 @@ fn exact_secure_mana_state_key_from_board(
         board_hash,
         active_color,
         mons_moves_count,
+        mons_moves_limit,
+        rules: *rules,
         white_regular_mana_count,
         black_regular_mana_count,
     }
 }
 
-#[inline]
-fn exact_hash_item(item: Item) -> u64 {
-    match item {
-        Item::Mon { mon } => 0x100 | exact_hash_mon(mon),
-        Item::Mana { mana } => 0x200 | exact_hash_mana(mana),
-        Item::MonWithMana { mon, mana } => {
-            0x300 | exact_hash_mon(mon) | (exact_hash_mana(mana) << 16)
-        }
-        Item::MonWithConsumable { mon, consumable } => {
-            0x400 | exact_hash_mon(mon) | (exact_hash_consumable(consumable) << 16)
-        }
-        Item::Consumable { consumable } => 0x500 | exact_hash_consumable(consumable),
-    }
-}
-
-#[inline]
-fn exact_hash_mon(mon: Mon) -> u64 {
-    exact_hash_mon_kind(mon.kind)
-        | (exact_hash_color(mon.color) << 4)
-        | (((mon.cooldown as i64 as u64) & 0xff) << 8)
-}
-
 #[inline]
 fn exact_hash_mon_kind(kind: MonKind) -> u64 {
     match kind {
@@ -2388,31 +2273,6 @@ fn exact_hash_mon_kind(kind: MonKind) -> u64 {
     }
 }
 
-#[inline]
-fn exact_hash_color(color: Color) -> u64 {
-    match color {
-        Color::White => 1,
-        Color::Black => 2,
-    }
-}
-
-#[inline]
-fn exact_hash_mana(mana: Mana) -> u64 {
-    match mana {
-        Mana::Regular(color) => 1 | (exact_hash_color(color) << 4),
-        Mana::Supermana => 2,
-    }
-}
-
-#[inline]
-fn exact_hash_consumable(consumable: Consumable) -> u64 {
-    match consumable {
-        Consumable::Bomb => 1,
-        Consumable::Potion => 2,
-        Consumable::BombOrPotion => 3,
-    }
-}
-
 #[inline]
 fn exact_mix_u64(value: u64) -> u64 {
     let mut mixed = value;
@@ -2521,7 +2381,6 @@ pub(crate) fn is_drainer_under_immediate_threat(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn is_drainer_under_walk_threat(
     board: &Board,
     color: Color,
@@ -2653,26 +2512,29 @@ fn is_drainer_under_walk_threat_uncached(
     false
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
+/// Whether no attack the opponent can make in a full turn under `rules`
+/// reaches the drainer at `location`.
 pub(crate) fn is_drainer_exactly_safe_next_turn_on_board(
     board: &Board,
     color: Color,
     location: Location,
+    rules: &RuleSet,
 ) -> bool {
     is_drainer_exactly_safe_next_turn_on_board_with_hash(
         board,
         exact_board_hash(board),
         color,
         location,
+        rules,
     )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn is_drainer_exactly_safe_next_turn_on_board_with_hash(
     board: &Board,
     board_hash: u64,
     color: Color,
     location: Location,
+    rules: &RuleSet,
 ) -> bool {
     let angel_nearby = exact_is_location_guarded_by_angel(board, color, location);
     !can_attack_target_on_board_with_hash(
@@ -2681,7 +2543,7 @@ pub(crate) fn is_drainer_exactly_safe_next_turn_on_board_with_hash(
         color.other(),
         color,
         location,
-        Config::MONS_MOVES_PER_TURN,
+        rules.mons_moves_per_turn,
         true,
     ) && !is_drainer_under_walk_threat_with_hash(board, board_hash, color, location, angel_nearby)
 }
@@ -2737,11 +2599,11 @@ fn build_color_summary(
 
     let (full_turn_moves, can_use_action) = if game.active_color == color {
         (
-            (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0),
+            (game.mons_moves_limit() - game.mons_moves_count).max(0),
             game.player_can_use_action(),
         )
     } else {
-        (Config::MONS_MOVES_PER_TURN, true)
+        (game.rules().mons_moves_per_turn, true)
     };
 
     let board_hash = exact_board_hash(&game.board);
@@ -2773,7 +2635,6 @@ fn build_color_summary(
             board_hash,
         )
     });
-    #[cfg(any(target_arch = "wasm32", test))]
     let best_drainer_to_mana_steps = find_awake_drainer(&game.board, color)
         .and_then(|location| exact_drainer_to_any_mana_steps(&game.board, color, location));
 
@@ -2816,7 +2677,13 @@ fn build_color_summary(
     let spirit = match mode {
         #[cfg(test)]
         ExactColorSummaryMode::ActiveTactical => {
-            let spirit = exact_spirit_summary(&game.board, color, full_turn_moves, can_use_action);
+            let spirit = exact_spirit_summary(
+                &game.board,
+                color,
+                full_turn_moves,
+                can_use_action,
+                game.rules(),
+            );
             if spirit.same_turn_score {
                 immediate_scores.push(spirit.same_turn_score_value.max(1));
             }
@@ -2839,20 +2706,17 @@ fn build_color_summary(
         score_path_window,
         immediate_window,
         best_drainer_pickup,
-        #[cfg(any(target_arch = "wasm32", test))]
         best_carrier_steps,
-        #[cfg(any(target_arch = "wasm32", test))]
         best_drainer_to_mana_steps,
         spirit,
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn build_exact_turn_summary(game: &MonsGame) -> ExactTurnSummary {
     update_exact_query_diagnostics(|diagnostics| diagnostics.exact_turn_summary_builds += 1);
 
     let color = game.active_color;
-    let remaining_moves = (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+    let remaining_moves = (game.mons_moves_limit() - game.mons_moves_count).max(0);
     let can_use_action = game.player_can_use_action();
     let tactical_spirit = exact_tactical_spirit_summary(
         &game.board,
@@ -2862,6 +2726,7 @@ fn build_exact_turn_summary(game: &MonsGame) -> ExactTurnSummary {
         EXACT_TACTICAL_SPIRIT_NEED_SCORE
             | EXACT_TACTICAL_SPIRIT_NEED_DENIAL
             | EXACT_TACTICAL_SPIRIT_NEED_PROGRESS,
+        game.rules(),
     );
     let safe_supermana_progress_steps =
         exact_secure_specific_mana_steps_this_turn(game, color, Mana::Supermana);
@@ -2895,10 +2760,9 @@ fn build_exact_turn_summary(game: &MonsGame) -> ExactTurnSummary {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn build_exact_turn_tactical_projection(game: &MonsGame, flags: u8) -> ExactTurnTacticalProjection {
     let color = game.active_color;
-    let remaining_moves = (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+    let remaining_moves = (game.mons_moves_limit() - game.mons_moves_count).max(0);
     let can_use_action = game.player_can_use_action();
     let need_supermana = flags & EXACT_TURN_TACTICAL_NEED_SUPERMANA_PROGRESS != 0;
     let need_opponent_mana = flags & EXACT_TURN_TACTICAL_NEED_OPPONENT_MANA_PROGRESS != 0;
@@ -2921,6 +2785,7 @@ fn build_exact_turn_tactical_projection(game: &MonsGame, flags: u8) -> ExactTurn
             remaining_moves,
             can_use_action,
             tactical_spirit_fields,
+            game.rules(),
         )
     } else {
         ExactSpiritSummary::default()
@@ -3247,7 +3112,6 @@ fn exact_distance_to_any_pool_steps_lower_bound(location: Location) -> i32 {
     i32::max(i32::min(i, max_index - i), i32::min(j, max_index - j))
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_drainer_pickup_steps_lower_bound(
     board: &Board,
     color: Color,
@@ -3277,7 +3141,6 @@ fn exact_drainer_pickup_steps_lower_bound(
         .min()
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(clippy::too_many_arguments)]
 fn exact_drainer_pickup_remaining_steps_lower_bound(
     board: &Board,
@@ -3622,7 +3485,6 @@ fn exact_carrier_steps_to_any_pool(board: &Board, start: Location, mana: Mana) -
     exact_carrier_steps_to_any_pool_with_hash(board, start, mana, exact_board_hash(board))
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 struct ExactDrainerPickupWindow {
     any: Option<ExactDrainerPickupPath>,
@@ -3673,7 +3535,6 @@ fn exact_pickup_path_future_can_beat_best(
         || (future_metric == best_metric && best.mana_value < max_mana_value)
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(clippy::too_many_arguments)]
 fn exact_update_drainer_pickup_window_candidate(
     board: &Board,
@@ -3720,7 +3581,6 @@ fn exact_update_drainer_pickup_window_candidate(
     score_done && denial_done
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(clippy::too_many_arguments)]
 fn exact_drainer_pickup_window_small_budget_with_hash(
     board: &Board,
@@ -3790,7 +3650,6 @@ fn exact_drainer_pickup_window_small_budget_with_hash(
     best
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(clippy::too_many_arguments)]
 fn exact_drainer_pickup_window_uncached_with_hash(
     board: &Board,
@@ -3864,7 +3723,6 @@ fn exact_drainer_pickup_window_uncached_with_hash(
     best
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(clippy::too_many_arguments)]
 fn exact_drainer_pickup_window_with_hash_min_any_score(
     board: &Board,
@@ -4181,7 +4039,6 @@ fn find_awake_drainer(board: &Board, color: Color) -> Option<Location> {
     })
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_drainer_to_any_mana_steps(board: &Board, color: Color, start: Location) -> Option<i32> {
     let key = ExactDrainerToManaQueryKey {
         board_hash: exact_board_hash(board),
@@ -4218,7 +4075,6 @@ fn exact_drainer_to_any_mana_steps(board: &Board, color: Color, start: Location)
     result
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_distance_to_wanted_mana_steps_lower_bound(
     board: &Board,
@@ -4234,36 +4090,42 @@ fn exact_distance_to_wanted_mana_steps_lower_bound(
         .min()
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_secure_specific_mana_steps_this_turn(
     game: &MonsGame,
     color: Color,
     wanted: Mana,
 ) -> Option<i32> {
     let remaining_moves = if game.active_color == color {
-        (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0)
+        (game.mons_moves_limit() - game.mons_moves_count).max(0)
     } else {
-        Config::MONS_MOVES_PER_TURN
+        game.rules().mons_moves_per_turn
     };
-    exact_secure_specific_mana_steps_on_board(&game.board, color, wanted, remaining_moves)
+    exact_secure_specific_mana_steps_on_board(
+        &game.board,
+        color,
+        wanted,
+        remaining_moves,
+        game.rules(),
+    )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn can_secure_specific_mana_on_board(
     board: &Board,
     color: Color,
     wanted: Mana,
     remaining_moves: i32,
+    rules: &RuleSet,
 ) -> bool {
-    exact_secure_specific_mana_steps_on_board(board, color, wanted, remaining_moves).is_some()
+    exact_secure_specific_mana_steps_on_board(board, color, wanted, remaining_moves, rules)
+        .is_some()
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn exact_secure_specific_mana_steps_on_board(
     board: &Board,
     color: Color,
     wanted: Mana,
     remaining_moves: i32,
+    rules: &RuleSet,
 ) -> Option<i32> {
     if remaining_moves < 0 {
         return None;
@@ -4280,9 +4142,15 @@ pub(crate) fn exact_secure_specific_mana_steps_on_board(
         return None;
     }
 
-    let mons_moves_count =
-        (Config::MONS_MOVES_PER_TURN - remaining_moves).clamp(0, Config::MONS_MOVES_PER_TURN);
-    let state = exact_secure_mana_state_key_from_board(board, color, mons_moves_count);
+    let mons_moves_limit = rules.mons_moves_per_turn;
+    let mons_moves_count = (mons_moves_limit - remaining_moves).clamp(0, mons_moves_limit);
+    let state = exact_secure_mana_state_key_from_board(
+        board,
+        color,
+        mons_moves_count,
+        mons_moves_limit,
+        rules,
+    );
     let key = ExactSecureManaQueryKey {
         state,
         color,
@@ -4299,24 +4167,24 @@ pub(crate) fn exact_secure_specific_mana_steps_on_board(
         return cached;
     }
 
-    let game = MonsGame::new_simulation_state(
+    let mut game = MonsGame::new_simulation_state(
         board.clone(),
         0,
         0,
         color,
-        Config::ACTIONS_PER_TURN,
+        rules.actions_per_turn,
         0,
         mons_moves_count,
         0,
         0,
         2,
     );
+    game.rules = *rules;
     // Non-terminal same-turn states still have the mana move available; exhausting it here
     // would make the synthetic game auto-end after one mon move and miss multi-step drainer paths.
     exact_secure_specific_mana_steps_in_game_with_key(&game, color, wanted, state)
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_secure_specific_mana_steps_in_game_with_key(
     game: &MonsGame,
     color: Color,
@@ -4336,7 +4204,6 @@ fn exact_secure_specific_mana_steps_in_game_with_key(
     })
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_secure_specific_mana_steps_in_game_with_key_mut(
     game: &mut MonsGame,
     color: Color,
@@ -4355,7 +4222,6 @@ fn exact_secure_specific_mana_steps_in_game_with_key_mut(
     )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_secure_specific_mana_steps_in_game_with_key_at_mut(
     game: &mut MonsGame,
     color: Color,
@@ -4397,7 +4263,6 @@ fn exact_secure_specific_mana_steps_in_game_with_key_at_mut(
     result
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_secure_specific_mana_steps_in_game_uncached_at_mut(
     game: &mut MonsGame,
     color: Color,
@@ -4411,7 +4276,12 @@ fn exact_secure_specific_mana_steps_in_game_uncached_at_mut(
         Some(Item::MonWithMana { mana, .. }) if *mana == wanted
     );
     if holding_wanted
-        && is_drainer_exactly_safe_next_turn_on_board(&game.board, color, drainer_location)
+        && is_drainer_exactly_safe_next_turn_on_board(
+            &game.board,
+            color,
+            drainer_location,
+            game.rules(),
+        )
     {
         return Some(0);
     }
@@ -4419,7 +4289,7 @@ fn exact_secure_specific_mana_steps_in_game_uncached_at_mut(
     if game.active_color != color || !game.player_can_move_mon() {
         return None;
     }
-    let remaining_moves = (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+    let remaining_moves = (game.mons_moves_limit() - game.mons_moves_count).max(0);
     if !holding_wanted
         && exact_distance_to_wanted_mana_steps_lower_bound(&game.board, wanted, drainer_location)
             .is_none_or(|lower_bound| lower_bound > remaining_moves)
@@ -4472,7 +4342,6 @@ fn exact_secure_specific_mana_steps_in_game_uncached_at_mut(
     best
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn exact_secure_specific_mana_path_from(
     game: &MonsGame,
     color: Color,
@@ -4491,7 +4360,6 @@ pub(crate) fn exact_secure_specific_mana_path_from(
     )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_secure_specific_mana_path_from_uncached(
     game: &MonsGame,
     color: Color,
@@ -4504,74 +4372,76 @@ fn exact_secure_specific_mana_path_from_uncached(
         return None;
     }
 
-    let result = if matches!(
-        game.board.item(start),
-        Some(Item::MonWithMana { mana, .. }) if *mana == wanted
-    ) && is_drainer_exactly_safe_next_turn_on_board(&game.board, color, start)
-    {
-        Some(Vec::new())
-    } else if game.active_color != color || !game.player_can_move_mon() {
-        None
-    } else {
-        let mut best_path: Option<Vec<Location>> = None;
-
-        for &next in start.nearby_locations_ref() {
-            let Some(transition) = exact_apply_secure_drainer_walk(game, state_key, start, next)
-            else {
-                continue;
-            };
+    let result =
+        if matches!(
+            game.board.item(start),
+            Some(Item::MonWithMana { mana, .. }) if *mana == wanted
+        ) && is_drainer_exactly_safe_next_turn_on_board(&game.board, color, start, game.rules())
+        {
+            Some(Vec::new())
+        } else if game.active_color != color || !game.player_can_move_mon() {
+            None
+        } else {
+            let mut best_path: Option<Vec<Location>> = None;
 
-            let candidate_path = if transition.scored_mana == Some(wanted) {
-                Some(vec![next])
-            } else if exact_secure_specific_mana_steps_in_game_with_key(
-                &transition.after,
-                color,
-                wanted,
-                transition.after_key,
-            )
-            .is_some()
-            {
-                let Some(next_start) = find_awake_drainer(&transition.after.board, color) else {
+            for &next in start.nearby_locations_ref() {
+                let Some(transition) =
+                    exact_apply_secure_drainer_walk(game, state_key, start, next)
+                else {
                     continue;
                 };
-                let Some(mut suffix) = exact_secure_specific_mana_path_from_uncached(
+
+                let candidate_path = if transition.scored_mana == Some(wanted) {
+                    Some(vec![next])
+                } else if exact_secure_specific_mana_steps_in_game_with_key(
                     &transition.after,
                     color,
-                    next_start,
                     wanted,
                     transition.after_key,
-                    visiting,
-                ) else {
-                    continue;
+                )
+                .is_some()
+                {
+                    let Some(next_start) = find_awake_drainer(&transition.after.board, color)
+                    else {
+                        continue;
+                    };
+                    let Some(mut suffix) = exact_secure_specific_mana_path_from_uncached(
+                        &transition.after,
+                        color,
+                        next_start,
+                        wanted,
+                        transition.after_key,
+                        visiting,
+                    ) else {
+                        continue;
+                    };
+                    let mut path = Vec::with_capacity(suffix.len() + 1);
+                    path.push(next);
+                    path.append(&mut suffix);
+                    Some(path)
+                } else {
+                    None
                 };
-                let mut path = Vec::with_capacity(suffix.len() + 1);
-                path.push(next);
-                path.append(&mut suffix);
-                Some(path)
-            } else {
-                None
-            };
 
-            let Some(candidate_path) = candidate_path else {
-                continue;
-            };
-            let replace = match &best_path {
-                None => true,
-                Some(current) => candidate_path.len() < current.len(),
-            };
-            if replace {
-                best_path = Some(candidate_path);
+                let Some(candidate_path) = candidate_path else {
+                    continue;
+                };
+                let replace = match &best_path {
+                    None => true,
+                    Some(current) => candidate_path.len() < current.len(),
+                };
+                if replace {
+                    best_path = Some(candidate_path);
+                }
             }
-        }
 
-        best_path
-    };
+            best_path
+        };
 
     visiting.remove(&state_key);
     result
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone)]
 struct ExactSecureDrainerWalkTransition {
     after: MonsGame,
@@ -4579,7 +4449,6 @@ struct ExactSecureDrainerWalkTransition {
     scored_mana: Option<Mana>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy)]
 struct ExactSecureGameSnapshot {
     white_score: i32,
@@ -4593,7 +4462,6 @@ struct ExactSecureGameSnapshot {
     turn_number: i32,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 impl ExactSecureGameSnapshot {
     #[inline]
     fn capture(game: &MonsGame) -> Self {
@@ -4611,14 +4479,12 @@ impl ExactSecureGameSnapshot {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy)]
 struct ExactSecureTouchedItem {
     location: Location,
     before: Option<Item>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy)]
 struct ExactSecureTouchedItems {
     items: [Option<ExactSecureTouchedItem>; EXACT_SECURE_TOUCHED_ITEMS_CAPACITY],
@@ -4626,7 +4492,6 @@ struct ExactSecureTouchedItems {
     seen_mask: u128,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 impl ExactSecureTouchedItems {
     #[inline]
     fn new() -> Self {
@@ -4654,14 +4519,12 @@ impl ExactSecureTouchedItems {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy)]
 struct ExactSecureDrainerWalkUndo {
     snapshot: ExactSecureGameSnapshot,
     touched_items: ExactSecureTouchedItems,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy)]
 struct ExactSecureDrainerWalkMutation {
     after_key: ExactSecureManaStateKey,
@@ -4669,7 +4532,6 @@ struct ExactSecureDrainerWalkMutation {
     undo: ExactSecureDrainerWalkUndo,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_secure_board_hash_after_touched_items(
     before_hash: u64,
@@ -4690,7 +4552,6 @@ fn exact_secure_board_hash_after_touched_items(
     after_hash
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn exact_undo_secure_drainer_walk(game: &mut MonsGame, undo: ExactSecureDrainerWalkUndo) {
     game.white_score = undo.snapshot.white_score;
@@ -4712,7 +4573,6 @@ fn exact_undo_secure_drainer_walk(game: &mut MonsGame, undo: ExactSecureDrainerW
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_apply_secure_drainer_walk_in_place(
     game: &mut MonsGame,
     state_key: ExactSecureManaStateKey,
@@ -4818,15 +4678,16 @@ fn exact_apply_secure_drainer_walk_in_place(
     }
 
     let first_turn = game.turn_number == 1;
-    let player_can_move_mon = game.mons_moves_count < Config::MONS_MOVES_PER_TURN;
-    let player_can_move_mana = !first_turn && game.mana_moves_count < Config::MANA_MOVES_PER_TURN;
+    let player_can_move_mon = game.mons_moves_count < state_key.mons_moves_limit;
+    let player_can_move_mana =
+        !first_turn && game.mana_moves_count < game.rules().mana_moves_per_turn;
     let active_regular_mana_count = if game.active_color == Color::White {
         white_regular_mana_count
     } else {
         black_regular_mana_count
     };
-    let should_end_turn = game.white_score < Config::TARGET_SCORE
-        && game.black_score < Config::TARGET_SCORE
+    let should_end_turn = game.white_score < game.rules().target_score
+        && game.black_score < game.rules().target_score
         && if first_turn {
             !player_can_move_mon
         } else {
@@ -4851,7 +4712,7 @@ fn exact_apply_secure_drainer_walk_in_place(
             touched_items.push_once(&game.board, mon_location);
             let mut mon = mon;
             mon.decrease_cooldown();
-            game.board.items[index] = Some(Item::Mon { mon });
+            game.board.put(Item::Mon { mon }, mon_location);
         }
     }
 
@@ -4865,6 +4726,7 @@ fn exact_apply_secure_drainer_walk_in_place(
         mons_moves_count: game.mons_moves_count,
         white_regular_mana_count,
         black_regular_mana_count,
+        ..state_key
     };
     Some(ExactSecureDrainerWalkMutation {
         after_key,
@@ -4876,7 +4738,6 @@ fn exact_apply_secure_drainer_walk_in_place(
     })
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_apply_secure_drainer_walk(
     game: &MonsGame,
     state_key: ExactSecureManaStateKey,
@@ -4892,7 +4753,6 @@ fn exact_apply_secure_drainer_walk(
     })
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn can_attack_opponent_drainer_exact_with_hash(
     game: &MonsGame,
     color: Color,
@@ -4908,9 +4768,9 @@ fn can_attack_opponent_drainer_exact_with_hash(
         color.other(),
         target,
         if game.active_color == color {
-            (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0)
+            (game.mons_moves_limit() - game.mons_moves_count).max(0)
         } else {
-            Config::MONS_MOVES_PER_TURN
+            game.rules().mons_moves_per_turn
         },
         if game.active_color == color {
             game.player_can_use_action()
@@ -4940,6 +4800,7 @@ fn exact_spirit_summary(
     color: Color,
     remaining_mon_moves: i32,
     can_use_action: bool,
+    rules: &RuleSet,
 ) -> ExactSpiritSummary {
     update_exact_query_diagnostics(|diagnostics| diagnostics.exact_spirit_summary_calls += 1);
     if remaining_mon_moves < 0 {
@@ -4950,6 +4811,7 @@ fn exact_spirit_summary(
         color,
         remaining_mon_moves,
         can_use_action,
+        rules: *rules,
     };
     if let Some(cached) =
         EXACT_SPIRIT_SUMMARY_CACHE.with(|cache| cache.borrow().entries.get(&key).copied())
@@ -4960,7 +4822,8 @@ fn exact_spirit_summary(
         return cached;
     }
 
-    let summary = exact_spirit_summary_uncached(board, color, remaining_mon_moves, can_use_action);
+    let summary =
+        exact_spirit_summary_uncached(board, color, remaining_mon_moves, can_use_action, rules);
     EXACT_SPIRIT_SUMMARY_CACHE.with(|cache| {
         let mut cache = cache.borrow_mut();
         if cache.entries.len() >= EXACT_SPIRIT_SUMMARY_CACHE_MAX_ENTRIES
@@ -4973,13 +4836,13 @@ fn exact_spirit_summary(
     summary
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_tactical_spirit_summary(
     board: &Board,
     color: Color,
     remaining_mon_moves: i32,
     can_use_action: bool,
     fields: u8,
+    rules: &RuleSet,
 ) -> ExactSpiritSummary {
     update_exact_query_diagnostics(|diagnostics| diagnostics.tactical_spirit_summary_calls += 1);
     if remaining_mon_moves < 0 || fields == 0 {
@@ -4991,6 +4854,7 @@ fn exact_tactical_spirit_summary(
         remaining_mon_moves,
         can_use_action,
         fields,
+        rules: *rules,
     };
     if let Some(cached) =
         EXACT_SPIRIT_TACTICAL_SUMMARY_CACHE.with(|cache| cache.borrow().entries.get(&key).copied())
@@ -5028,6 +4892,7 @@ fn exact_tactical_spirit_summary(
         can_use_action,
         fields,
         key.board_hash,
+        rules,
     );
     EXACT_SPIRIT_TACTICAL_SUMMARY_CACHE.with(|cache| {
         let mut cache = cache.borrow_mut();
@@ -5141,7 +5006,6 @@ fn exact_passive_spirit_summary(
     best
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_tactical_spirit_summary_uncached(
     board: &Board,
     color: Color,
@@ -5149,6 +5013,7 @@ fn exact_tactical_spirit_summary_uncached(
     can_use_action: bool,
     fields: u8,
     board_hash: u64,
+    rules: &RuleSet,
 ) -> ExactSpiritSummary {
     if !can_use_action {
         return ExactSpiritSummary::default();
@@ -5450,6 +5315,7 @@ fn exact_tactical_spirit_summary_uncached(
                                 color,
                                 Mana::Supermana,
                                 remaining_after_action,
+                                rules,
                             ))
                     {
                         best.supermana_progress = true;
@@ -5462,6 +5328,7 @@ fn exact_tactical_spirit_summary_uncached(
                                 color,
                                 Mana::Regular(color.other()),
                                 remaining_after_action,
+                                rules,
                             ))
                     {
                         best.opponent_mana_progress = true;
@@ -5492,11 +5359,12 @@ fn exact_spirit_summary_uncached(
     color: Color,
     remaining_mon_moves: i32,
     can_use_action: bool,
+    rules: &RuleSet,
 ) -> ExactSpiritSummary {
     if !can_use_action {
         return ExactSpiritSummary::default();
     }
-    let before_summary = exact_followup_summary(board, color, remaining_mon_moves);
+    let before_summary = exact_followup_summary(board, color, remaining_mon_moves, rules);
     let before_best_steps = before_summary.best_score_steps;
     let opponent_before = before_summary.opponent_best_score_steps;
     let before_same_turn_score = before_summary.immediate_score;
@@ -5543,7 +5411,7 @@ fn exact_spirit_summary_uncached(
                             color,
                         );
                     let after_summary =
-                        exact_followup_summary(&action_board, color, remaining_after_action);
+                        exact_followup_summary(&action_board, color, remaining_after_action, rules);
                     let after_best_steps = after_summary.best_score_steps;
                     let after_opponent_steps = after_summary.opponent_best_score_steps;
                     let after_same_turn_score = score_delta.max(after_summary.immediate_score);
@@ -5620,6 +5488,7 @@ fn exact_followup_summary(
     board: &Board,
     color: Color,
     remaining_moves: i32,
+    rules: &RuleSet,
 ) -> ExactFollowupSummary {
     update_exact_query_diagnostics(|diagnostics| diagnostics.exact_followup_summary_calls += 1);
     if remaining_moves < 0 {
@@ -5631,6 +5500,7 @@ fn exact_followup_summary(
         board_hash,
         color,
         remaining_moves,
+        rules: *rules,
     };
     if let Some(cached) =
         EXACT_FOLLOWUP_SUMMARY_CACHE.with(|cache| cache.borrow().entries.get(&key).copied())
@@ -5665,12 +5535,14 @@ fn exact_followup_summary(
             color,
             Mana::Supermana,
             remaining_moves,
+            rules,
         ),
         secure_opponent_mana: can_secure_specific_mana_on_board(
             board,
             color,
             Mana::Regular(color.other()),
             remaining_moves,
+            rules,
         ),
     };
 
@@ -5842,7 +5714,6 @@ fn spirit_destination_allowed(
 }
 
 #[derive(Debug, Clone, Copy)]
-#[cfg(any(target_arch = "wasm32", test))]
 struct SpiritPreviewUndo {
     from: Location,
     from_item: Option<Item>,
@@ -5851,14 +5722,12 @@ struct SpiritPreviewUndo {
 }
 
 #[derive(Debug, Clone, Copy)]
-#[cfg(any(target_arch = "wasm32", test))]
 struct ExactTouchedBoardItem {
     location: Location,
     before: Option<Item>,
 }
 
 #[inline]
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_board_hash_after_touched_items(
     before_hash: u64,
     board: &Board,
@@ -5868,16 +5737,15 @@ fn exact_board_hash_after_touched_items(
     for touched in touched_items {
         let index = touched.location.index();
         if let Some(item) = touched.before {
-            after_hash ^= exact_board_entry_hash(index, item);
+            after_hash ^= zobrist_item_key(index, item);
         }
         if let Some(item) = board.items[index] {
-            after_hash ^= exact_board_entry_hash(index, item);
+            after_hash ^= zobrist_item_key(index, item);
         }
     }
     after_hash
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn apply_spirit_move_preview_known_items_in_place(
     board: &mut Board,
     from: Location,
@@ -5947,7 +5815,6 @@ fn apply_spirit_move_preview_known_items_in_place(
     (undo, score_delta, opponent_mana_score_delta)
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn apply_spirit_move_preview_in_place(
     board: &mut Board,
     from: Location,
@@ -5965,7 +5832,6 @@ fn apply_spirit_move_preview_in_place(
     )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn undo_spirit_move_preview(board: &mut Board, undo: SpiritPreviewUndo) {
     if let Some(item) = undo.from_item {
         board.put(item, undo.from);
@@ -6023,12 +5889,10 @@ fn exact_spirit_utility_score(score_delta: i32, opponent_score_delta: i32, setup
     score_bonus.max((1 + setup_gain).min(EXACT_SPIRIT_UTILITY_CAP))
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn exact_best_score_steps_on_board(board: &Board, color: Color) -> Option<i32> {
     exact_best_score_steps_on_board_with_hash(board, color, exact_board_hash(board))
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_best_score_steps_on_board_with_hash(
     board: &Board,
     color: Color,
@@ -6067,17 +5931,14 @@ fn exact_best_score_steps_on_board_with_hash(
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
-#[cfg(any(target_arch = "wasm32", test))]
 struct ExactImmediateTacticalWindow {
     best_score: i32,
     best_opponent_mana_score: i32,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 type ExactImmediateTacticalCounts = ExactZeroMoveTacticalCounts;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
-#[cfg(any(target_arch = "wasm32", test))]
 struct ExactZeroMoveTacticalCounts {
     score_one: u32,
     score_two: u32,
@@ -6085,14 +5946,12 @@ struct ExactZeroMoveTacticalCounts {
 }
 
 #[derive(Debug, Clone, Default)]
-#[cfg(any(target_arch = "wasm32", test))]
 struct ExactBudgetOneTacticalSummary {
     counts: ExactImmediateTacticalCounts,
     by_location: ExactHashMap<Location, ExactImmediateTacticalCounts>,
 }
 
 #[inline]
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_immediate_tactical_counts_for_mana(
     mana: Mana,
     color: Color,
@@ -6112,7 +5971,6 @@ fn exact_immediate_tactical_counts_for_mana(
 }
 
 #[inline]
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_zero_move_tactical_counts_for_item(
     board: &Board,
     location: Location,
@@ -6132,7 +5990,6 @@ fn exact_zero_move_tactical_counts_for_item(
     exact_immediate_tactical_counts_for_mana(mana, color)
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_zero_move_tactical_counts(board: &Board, color: Color) -> ExactZeroMoveTacticalCounts {
     let mut counts = ExactZeroMoveTacticalCounts::default();
     for (location, item) in board.occupied() {
@@ -6146,7 +6003,6 @@ fn exact_zero_move_tactical_counts(board: &Board, color: Color) -> ExactZeroMove
 }
 
 #[inline]
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_zero_move_tactical_window_from_counts(
     counts: ExactZeroMoveTacticalCounts,
     min_score: u8,
@@ -6173,7 +6029,6 @@ fn exact_zero_move_tactical_window_from_counts(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_zero_move_tactical_counts_after_touched_items(
     base: ExactZeroMoveTacticalCounts,
     board: &Board,
@@ -6205,7 +6060,6 @@ fn exact_zero_move_tactical_counts_after_touched_items(
 }
 
 #[inline]
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_budget_one_tactical_counts_for_location(
     board: &Board,
     color: Color,
@@ -6237,7 +6091,6 @@ fn exact_budget_one_tactical_counts_for_location(
 }
 
 #[inline]
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_budget_one_drainer_tactical_counts(
     board: &Board,
     color: Color,
@@ -6288,7 +6141,6 @@ fn exact_budget_one_drainer_tactical_counts(
     counts
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_budget_one_tactical_summary(
     board: &Board,
     color: Color,
@@ -6309,14 +6161,12 @@ fn exact_budget_one_tactical_summary(
 }
 
 #[inline]
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_push_unique_location(locations: &mut Vec<Location>, location: Location) {
     if !locations.contains(&location) {
         locations.push(location);
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_budget_one_tactical_counts_after_touched_locations(
     base: &ExactBudgetOneTacticalSummary,
     board: &Board,
@@ -6347,7 +6197,6 @@ fn exact_budget_one_tactical_counts_after_touched_locations(
     counts
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_mark_locations_within_mon_budget(
     mask: &mut ExactLocationSeen,
     start: Location,
@@ -6372,7 +6221,6 @@ fn exact_mark_locations_within_mon_budget(
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_immediate_tactical_reach_mask(
     board: &Board,
     color: Color,
@@ -6400,7 +6248,6 @@ fn exact_immediate_tactical_reach_mask(
     mask
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_zero_move_immediate_tactical_window_on_board_with_hash(
     board: &Board,
     color: Color,
@@ -6433,7 +6280,6 @@ fn exact_zero_move_immediate_tactical_window_on_board_with_hash(
     best
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_best_immediate_tactical_window_on_board_with_hash(
     board: &Board,
     color: Color,
@@ -6453,7 +6299,6 @@ fn exact_best_immediate_tactical_window_on_board_with_hash(
     )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_best_immediate_tactical_window_on_board_with_hash_min_score(
     board: &Board,
     color: Color,
@@ -6549,7 +6394,6 @@ fn exact_best_immediate_tactical_window_on_board_with_hash_min_score(
     result
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_best_immediate_tactical_window_on_board_with_hash_uncached(
     board: &Board,
     color: Color,
@@ -6652,7 +6496,6 @@ fn exact_best_immediate_tactical_window_on_board_with_hash_uncached(
     best
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_best_immediate_score_on_board(board: &Board, color: Color, move_budget: i32) -> i32 {
     exact_best_immediate_score_on_board_with_hash(
         board,
@@ -6662,7 +6505,6 @@ fn exact_best_immediate_score_on_board(board: &Board, color: Color, move_budget:
     )
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn exact_best_immediate_score_on_board_with_hash(
     board: &Board,
     color: Color,
@@ -6677,18 +6519,19 @@ fn exact_best_immediate_score_on_board_with_hash(
     let mut best = 0;
     for (location, item) in board.occupied() {
         match item {
-            Item::MonWithMana { mon, mana } if mon.color == color && !mon.is_fainted() => {
-                if exact_carrier_steps_to_any_pool_with_hash_bounded(
-                    board,
-                    location,
-                    *mana,
-                    move_budget,
-                    board_hash,
-                )
-                .is_some()
-                {
-                    best = best.max(mana.score(color));
-                }
+            Item::MonWithMana { mon, mana }
+                if mon.color == color
+                    && !mon.is_fainted()
+                    && exact_carrier_steps_to_any_pool_with_hash_bounded(
+                        board,
+                        location,
+                        *mana,
+                        move_budget,
+                        board_hash,
+                    )
+                    .is_some() =>
+            {
+                best = best.max(mana.score(color));
             }
             Item::Mon { mon } | Item::MonWithConsumable { mon, .. }
                 if mon.color == color && mon.kind == MonKind::Drainer && !mon.is_fainted() =>
@@ -6745,19 +6588,19 @@ fn exact_best_immediate_opponent_mana_score_on_board_with_hash(
     for (location, item) in board.occupied() {
         match item {
             Item::MonWithMana { mon, mana }
-                if mon.color == color && !mon.is_fainted() && *mana == opponent_mana =>
+                if mon.color == color
+                    && !mon.is_fainted()
+                    && *mana == opponent_mana
+                    && exact_carrier_steps_to_any_pool_with_hash_bounded(
+                        board,
+                        location,
+                        *mana,
+                        move_budget,
+                        board_hash,
+                    )
+                    .is_some() =>
             {
-                if exact_carrier_steps_to_any_pool_with_hash_bounded(
-                    board,
-                    location,
-                    *mana,
-                    move_budget,
-                    board_hash,
-                )
-                .is_some()
-                {
-                    best = best.max(mana.score(color));
-                }
+                best = best.max(mana.score(color));
             }
             Item::Mon { mon } | Item::MonWithConsumable { mon, .. }
                 if mon.color == color && mon.kind == MonKind::Drainer && !mon.is_fainted() =>
@@ -6821,6 +6664,33 @@ mod tests {
         game
     }
 
+    #[test]
+    fn exact_board_hash_tells_registered_layouts_apart() {
+        let layouts = ["exact-hash-left", "exact-hash-right"].map(|name| {
+            VariantSpec {
+                name: name.to_string(),
+                ..VariantSpec::builtin(GameVariant::Classic).unwrap()
+            }
+            .register()
+            .unwrap()
+        });
+        let boards = layouts.map(|layout| {
+            Board::new_with_items_and_layout(
+                Board::new()
+                    .occupied()
+                    .map(|(at, item)| (at, *item))
+                    .collect::<Vec<_>>(),
+                layout,
+            )
+        });
+        assert_eq!(boards[0].zobrist(), boards[1].zobrist());
+        assert_ne!(exact_board_hash(&boards[0]), exact_board_hash(&boards[1]));
+        assert_ne!(
+            exact_secure_board_hash(&boards[0]),
+            exact_secure_board_hash(&boards[1])
+        );
+    }
+
     fn find_mana_pool(board: &Board, color: Color) -> Location {
         (0..Config::BOARD_SIZE)
             .flat_map(|i| (0..Config::BOARD_SIZE).map(move |j| Location::new(i, j)))
@@ -7005,6 +6875,7 @@ mod tests {
                     | Event::BombExplosion { .. }
                     | Event::MonAwake { .. }
                     | Event::GameOver { .. }
+                    | Event::GameTerminated { .. }
                     | Event::NextTurn { .. }
                     | Event::Takeback
                     | Event::UsePotion { .. } => None,
@@ -8488,7 +8359,12 @@ mod tests {
         );
 
         assert_eq!(
-            is_drainer_exactly_safe_next_turn_on_board(&board, Color::White, Location::new(6, 5)),
+            is_drainer_exactly_safe_next_turn_on_board(
+                &board,
+                Color::White,
+                Location::new(6, 5),
+                &RuleSet::STANDARD
+            ),
             expected
         );
     }
@@ -8518,9 +8394,19 @@ mod tests {
         .board;
         let board_hash = exact_board_hash(&board);
 
-        let first = exact_own_drainer_safety_score_with_hash(&board, board_hash, Color::White);
+        let first = exact_own_drainer_safety_score_with_hash(
+            &board,
+            board_hash,
+            Color::White,
+            &RuleSet::STANDARD,
+        );
         let first_diagnostics = exact_query_diagnostics_snapshot();
-        let second = exact_own_drainer_safety_score_with_hash(&board, board_hash, Color::White);
+        let second = exact_own_drainer_safety_score_with_hash(
+            &board,
+            board_hash,
+            Color::White,
+            &RuleSet::STANDARD,
+        );
         let second_diagnostics = exact_query_diagnostics_snapshot();
 
         assert_eq!(first, second);
@@ -8573,11 +8459,17 @@ mod tests {
             let board_hash = exact_board_hash(&board);
 
             assert_eq!(
-                exact_own_drainer_safety_score_with_hash(&board, board_hash, Color::White) > 0,
+                exact_own_drainer_safety_score_with_hash(
+                    &board,
+                    board_hash,
+                    Color::White,
+                    &RuleSet::STANDARD
+                ) > 0,
                 is_drainer_exactly_safe_next_turn_on_board(
                     &board,
                     Color::White,
-                    Location::new(6, 5)
+                    Location::new(6, 5),
+                    &RuleSet::STANDARD,
                 ),
             );
         }
@@ -8694,7 +8586,7 @@ mod tests {
         );
         game.mons_moves_count = Config::MONS_MOVES_PER_TURN - 1;
 
-        let summary = exact_followup_summary(&game.board, Color::White, 1);
+        let summary = exact_followup_summary(&game.board, Color::White, 1, &RuleSet::STANDARD);
         assert_eq!(
             summary.best_score_steps,
             exact_best_score_steps_on_board(&game.board, Color::White)
@@ -8713,7 +8605,13 @@ mod tests {
         );
         assert_eq!(
             summary.secure_supermana,
-            can_secure_specific_mana_on_board(&game.board, Color::White, Mana::Supermana, 1)
+            can_secure_specific_mana_on_board(
+                &game.board,
+                Color::White,
+                Mana::Supermana,
+                1,
+                &RuleSet::STANDARD
+            )
         );
         assert_eq!(
             summary.secure_opponent_mana,
@@ -8722,6 +8620,7 @@ mod tests {
                 Color::White,
                 Mana::Regular(Color::Black),
                 1,
+                &RuleSet::STANDARD,
             )
         );
     }
@@ -8754,10 +8653,10 @@ mod tests {
         )
         .board;
 
-        let first = exact_followup_summary(&board, Color::White, 2);
-        let second = exact_followup_summary(&board, Color::White, 2);
+        let first = exact_followup_summary(&board, Color::White, 2, &RuleSet::STANDARD);
+        let second = exact_followup_summary(&board, Color::White, 2, &RuleSet::STANDARD);
         clear_exact_state_analysis_cache();
-        let third = exact_followup_summary(&board, Color::White, 2);
+        let third = exact_followup_summary(&board, Color::White, 2, &RuleSet::STANDARD);
 
         assert_eq!(first.best_score_steps, second.best_score_steps);
         assert_eq!(
@@ -9276,12 +9175,14 @@ mod tests {
             Color::White,
             Mana::Supermana,
             5,
+            &RuleSet::STANDARD,
         );
         let second = exact_secure_specific_mana_steps_on_board(
             &game.board,
             Color::White,
             Mana::Supermana,
             5,
+            &RuleSet::STANDARD,
         );
         clear_exact_state_analysis_cache();
         let third = exact_secure_specific_mana_steps_on_board(
@@ -9289,6 +9190,7 @@ mod tests {
             Color::White,
             Mana::Supermana,
             5,
+            &RuleSet::STANDARD,
         );
 
         assert_eq!(first, second);
@@ -9324,11 +9226,21 @@ mod tests {
         )
         .board;
 
-        let first =
-            exact_secure_specific_mana_steps_on_board(&board, Color::White, Mana::Supermana, 5);
+        let first = exact_secure_specific_mana_steps_on_board(
+            &board,
+            Color::White,
+            Mana::Supermana,
+            5,
+            &RuleSet::STANDARD,
+        );
         let first_diagnostics = exact_query_diagnostics_snapshot();
-        let second =
-            exact_secure_specific_mana_steps_on_board(&board, Color::White, Mana::Supermana, 5);
+        let second = exact_secure_specific_mana_steps_on_board(
+            &board,
+            Color::White,
+            Mana::Supermana,
+            5,
+            &RuleSet::STANDARD,
+        );
         let second_diagnostics = exact_query_diagnostics_snapshot();
 
         assert_eq!(first, Some(1));
@@ -9370,7 +9282,13 @@ mod tests {
         .board;
 
         assert_eq!(
-            exact_secure_specific_mana_steps_on_board(&board, Color::White, Mana::Supermana, 5),
+            exact_secure_specific_mana_steps_on_board(
+                &board,
+                Color::White,
+                Mana::Supermana,
+                5,
+                &RuleSet::STANDARD
+            ),
             Some(1)
         );
     }
@@ -9404,7 +9322,13 @@ mod tests {
         .board;
 
         assert_eq!(
-            exact_secure_specific_mana_steps_on_board(&board, Color::White, Mana::Supermana, 1),
+            exact_secure_specific_mana_steps_on_board(
+                &board,
+                Color::White,
+                Mana::Supermana,
+                1,
+                &RuleSet::STANDARD
+            ),
             Some(1)
         );
     }
@@ -9439,7 +9363,13 @@ mod tests {
         .board;
 
         assert_eq!(
-            exact_secure_specific_mana_steps_on_board(&board, Color::White, Mana::Supermana, 3),
+            exact_secure_specific_mana_steps_on_board(
+                &board,
+                Color::White,
+                Mana::Supermana,
+                3,
+                &RuleSet::STANDARD
+            ),
             None
         );
         assert_eq!(
@@ -9737,7 +9667,8 @@ mod tests {
             Location::new(8, 2),
             Color::White,
         );
-        let after_summary = exact_followup_summary(&after_board, Color::White, 2);
+        let after_summary =
+            exact_followup_summary(&after_board, Color::White, 2, &RuleSet::STANDARD);
         assert_eq!(score_delta, 0);
         assert_eq!(opponent_score_delta, 0);
         assert!(after_summary.secure_supermana);
@@ -10262,7 +10193,8 @@ mod tests {
             Location::new(8, 2),
             Color::White,
         );
-        let after_summary = exact_followup_summary(&after_board, Color::White, 2);
+        let after_summary =
+            exact_followup_summary(&after_board, Color::White, 2, &RuleSet::STANDARD);
         assert_eq!(score_delta, 0);
         assert_eq!(opponent_score_delta, 0);
         assert!(after_summary.secure_opponent_mana);
@@ -10320,7 +10252,8 @@ mod tests {
             Location::new(8, 1),
             Color::White,
         );
-        let after_summary = exact_followup_summary(&after_board, Color::White, 2);
+        let after_summary =
+            exact_followup_summary(&after_board, Color::White, 2, &RuleSet::STANDARD);
         assert_eq!(score_delta, 0);
         assert_eq!(opponent_score_delta, 0);
         assert_eq!(
@@ -10380,7 +10313,8 @@ mod tests {
             Location::new(8, 1),
             Color::White,
         );
-        let after_summary = exact_followup_summary(&after_board, Color::White, 2);
+        let after_summary =
+            exact_followup_summary(&after_board, Color::White, 2, &RuleSet::STANDARD);
         assert_eq!(score_delta, 0);
         assert_eq!(opponent_score_delta, 0);
         assert_eq!(
@@ -10449,7 +10383,8 @@ mod tests {
             Location::new(9, 0),
             Color::White,
         );
-        let after_summary = exact_followup_summary(&after_board, Color::White, 1);
+        let after_summary =
+            exact_followup_summary(&after_board, Color::White, 1, &RuleSet::STANDARD);
         assert_eq!(score_delta, 0);
         assert_eq!(opponent_score_delta, 0);
         assert_eq!(
@@ -10521,7 +10456,8 @@ mod tests {
             Location::new(9, 0),
             Color::White,
         );
-        let after_summary = exact_followup_summary(&after_board, Color::White, 1);
+        let after_summary =
+            exact_followup_summary(&after_board, Color::White, 1, &RuleSet::STANDARD);
         assert_eq!(score_delta, 0);
         assert_eq!(opponent_score_delta, 0);
         assert_eq!(
@@ -10630,8 +10566,13 @@ mod tests {
 
         clear_exact_state_analysis_cache();
         assert!(exact_turn_summary(&game, Color::White).spirit_assisted_score);
-        let spirit =
-            exact_spirit_summary(&game.board, Color::White, Config::MONS_MOVES_PER_TURN, true);
+        let spirit = exact_spirit_summary(
+            &game.board,
+            Color::White,
+            Config::MONS_MOVES_PER_TURN,
+            true,
+            &RuleSet::STANDARD,
+        );
         assert!(spirit.same_turn_score);
         assert_eq!(spirit.same_turn_score_value, 2);
         assert!(spirit.same_turn_opponent_mana_score);
@@ -10666,10 +10607,28 @@ mod tests {
         )
         .board;
 
-        let first = exact_spirit_summary(&board, Color::White, Config::MONS_MOVES_PER_TURN, true);
-        let second = exact_spirit_summary(&board, Color::White, Config::MONS_MOVES_PER_TURN, true);
+        let first = exact_spirit_summary(
+            &board,
+            Color::White,
+            Config::MONS_MOVES_PER_TURN,
+            true,
+            &RuleSet::STANDARD,
+        );
+        let second = exact_spirit_summary(
+            &board,
+            Color::White,
+            Config::MONS_MOVES_PER_TURN,
+            true,
+            &RuleSet::STANDARD,
+        );
         clear_exact_state_analysis_cache();
-        let third = exact_spirit_summary(&board, Color::White, Config::MONS_MOVES_PER_TURN, true);
+        let third = exact_spirit_summary(
+            &board,
+            Color::White,
+            Config::MONS_MOVES_PER_TURN,
+            true,
+            &RuleSet::STANDARD,
+        );
 
         assert_eq!(first.utility, second.utility);
         assert_eq!(first.same_turn_score, second.same_turn_score);
@@ -10721,6 +10680,7 @@ mod tests {
             Config::MONS_MOVES_PER_TURN,
             true,
             EXACT_TACTICAL_SPIRIT_ALL_FIELDS,
+            &RuleSet::STANDARD,
         );
         clear_exact_query_diagnostics();
         let score_only = exact_tactical_spirit_summary(
@@ -10729,6 +10689,7 @@ mod tests {
             Config::MONS_MOVES_PER_TURN,
             true,
             EXACT_TACTICAL_SPIRIT_NEED_SCORE,
+            &RuleSet::STANDARD,
         );
 
         assert!(score_only.same_turn_score);
@@ -10855,6 +10816,7 @@ mod tests {
             2,
             true,
             EXACT_TACTICAL_SPIRIT_NEED_SCORE,
+            &RuleSet::STANDARD,
         );
         let diagnostics = exact_query_diagnostics_snapshot();
 
@@ -109,6 +109,7 @@ pub(in super::super) fn automove_variant_label(variant: GameVariant) -> &'static
         GameVariant::SplitFlankManaRows => "split_flank_mana_rows",
         GameVariant::ForwardBridgeManaRows => "forward_bridge_mana_rows",
         GameVariant::CornerChainManaRows => "corner_chain_mana_rows",
+        GameVariant::Custom => "custom",
     }
 }
 
@@ -9718,16 +9718,16 @@ fn pro_v4_root_pool_spirit_target_counts(
         match board.item(target) {
             Some(Item::Mon { mon })
             | Some(Item::MonWithMana { mon, .. })
-            | Some(Item::MonWithConsumable { mon, .. }) => {
-                if !mon.is_fainted() {
-                    mon_targets += 1;
-                    enemy_targets += usize::from(mon.color != actor_color);
-                }
+            | Some(Item::MonWithConsumable { mon, .. })
+                if !mon.is_fainted() =>
+            {
+                mon_targets += 1;
+                enemy_targets += usize::from(mon.color != actor_color);
             }
             Some(Item::Mana { .. }) | Some(Item::Consumable { .. }) => {
                 item_targets += 1;
             }
-            None => {}
+            _ => {}
         }
     }
     (mon_targets + item_targets, enemy_targets, item_targets)
@@ -14054,18 +14054,21 @@ fn pro_v4_root_pool_action_fork_profile_spirit_target_counts(
         match board.item(target) {
             Some(Item::Mon { mon })
             | Some(Item::MonWithMana { mon, .. })
-            | Some(Item::MonWithConsumable { mon, .. }) => {
+            | Some(Item::MonWithConsumable { mon, .. })
                 if mon.color != actor_color
                     && !mon.is_fainted()
-                    && !pro_v4_root_pool_action_target_guarded(board, actor_color.other(), target)
-                {
-                    enemy_targets += 1;
-                }
+                    && !pro_v4_root_pool_action_target_guarded(
+                        board,
+                        actor_color.other(),
+                        target,
+                    ) =>
+            {
+                enemy_targets += 1;
             }
             Some(Item::Mana { .. }) | Some(Item::Consumable { .. }) => {
                 item_targets += 1;
             }
-            None => {}
+            _ => {}
         }
     }
     (enemy_targets, item_targets)
@@ -16104,6 +16107,15 @@ fn pro_v4_root_pool_transition_primary_bucket(
             "winner".to_string(),
             "game_over".to_string(),
         ),
+        Event::GameTerminated { result, reason } => (
+            "game_terminated",
+            result
+                .winner()
+                .map(|winner| pro_v4_root_pool_side_label(winner, perspective).to_string())
+                .unwrap_or_else(|| "draw".to_string()),
+            "winner".to_string(),
+            reason.code().to_string(),
+        ),
         Event::Takeback => (
             "takeback",
             "none".to_string(),
@@ -16175,7 +16187,7 @@ fn pro_v4_root_pool_transition_effect_bucket(
             Event::NextTurn { .. } => {
                 effects.insert("turn_changed");
             }
-            Event::GameOver { .. } => {
+            Event::GameOver { .. } | Event::GameTerminated { .. } => {
                 effects.insert("game_over");
             }
             Event::MonMove { .. }
@@ -16589,6 +16601,7 @@ fn pro_v4_root_pool_event_actor_role(game: &MonsGame, event: &Event) -> Option<M
         | Event::MonAwake { .. }
         | Event::NextTurn { .. }
         | Event::GameOver { .. }
+        | Event::GameTerminated { .. }
         | Event::Takeback => None,
     }
 }
@@ -16622,7 +16635,10 @@ fn pro_v4_root_pool_primary_payload_index(
         }
         Event::MonFainted { .. } | Event::MonAwake { .. } => Some(1),
         Event::SupermanaBackToBase { .. } => Some(2),
-        Event::NextTurn { .. } | Event::GameOver { .. } | Event::Takeback => None,
+        Event::NextTurn { .. }
+        | Event::GameOver { .. }
+        | Event::GameTerminated { .. }
+        | Event::Takeback => None,
     }
 }
 
@@ -16658,7 +16674,10 @@ fn pro_v4_root_pool_primary_target_payload_index(
         Event::MonFainted { to, .. } | Event::SupermanaBackToBase { to, .. } => Some(
             pro_v4_root_pool_location_payload_index(game, *to, perspective),
         ),
-        Event::NextTurn { .. } | Event::GameOver { .. } | Event::Takeback => None,
+        Event::NextTurn { .. }
+        | Event::GameOver { .. }
+        | Event::GameTerminated { .. }
+        | Event::Takeback => None,
     }
 }
 
@@ -16750,7 +16769,9 @@ fn pro_v4_root_pool_legal_fanout(
                 Event::PickupBomb { .. } | Event::PickupPotion { .. } | Event::PickupMana { .. },
             ) => fanout.primary_pickup += 1,
             Some(Event::NextTurn { .. }) => fanout.primary_turn += 1,
-            Some(Event::GameOver { .. }) => fanout.primary_terminal += 1,
+            Some(Event::GameOver { .. } | Event::GameTerminated { .. }) => {
+                fanout.primary_terminal += 1
+            }
             Some(
                 Event::MonFainted { .. }
                 | Event::ManaDropped { .. }
@@ -16776,7 +16797,7 @@ fn pro_v4_root_pool_legal_fanout(
                     fanout.effect_score_payloads
                         [pro_v4_root_pool_payload_index_for_mana(mana, perspective)] += 1;
                 }
-                Event::GameOver { .. } => has_terminal = true,
+                Event::GameOver { .. } | Event::GameTerminated { .. } => has_terminal = true,
                 Event::PickupBomb { .. } | Event::PickupPotion { .. } => {
                     has_pickup = true;
                     fanout.effect_pickup_payloads[5] += 1;
@@ -640,3 +640,128 @@ fn smart_automove_pool_pro_reliability_gate() {
         );
     }
 }
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn parallel_benchmark_options() -> ParallelSearchOptions {
+    let defaults = ParallelSearchOptions::default();
+    ParallelSearchOptions {
+        threads: env_usize("SMART_PARALLEL_THREADS")
+            .unwrap_or(defaults.threads)
+            .max(1),
+        deterministic_seed: env_usize("SMART_PARALLEL_SEED").map(|seed| seed as u64),
+    }
+}
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn select_parallel_shipping_pro_search_inputs(
+    game: &MonsGame,
+    config: AutomoveSearchConfig,
+) -> Vec<Input> {
+    let selector = profile_selector_from_name("shipping_pro_search")
+        .expect("shipping_pro_search selector should exist for parallel benchmark");
+    crate::models::mons_game_model::automove_parallel::with_parallel_search(
+        parallel_benchmark_options(),
+        || selector(game, config),
+    )
+}
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+#[test]
+#[ignore = "benchmark: parallel root search vs single-thread shipping_pro_search at pro budget"]
+fn smart_automove_parallel_pro_search_benchmark() {
+    let shipping_selector = profile_selector_from_name("shipping_pro_search")
+        .expect("shipping_pro_search selector should exist for parallel benchmark");
+    let options = parallel_benchmark_options();
+    let positions = env_usize("SMART_PARALLEL_BENCH_POSITIONS")
+        .unwrap_or(12)
+        .max(1);
+    let openings = generate_opening_fens_cached(
+        seed_for_pairing("parallel_pro_search_benchmark", "shipping_pro_search"),
+        positions,
+    );
+    let budgets = [pro_budget()];
+
+    let single = profile_speed_by_mode_ms(shipping_selector, openings.as_slice(), &budgets);
+    let parallel = profile_speed_by_mode_ms(
+        select_parallel_shipping_pro_search_inputs,
+        openings.as_slice(),
+        &budgets,
+    );
+    let single_ms = single[0].avg_ms.max(0.001);
+    let parallel_ms = parallel[0].avg_ms;
+
+    let mut same_moves = 0usize;
+    for opening in openings.iter() {
+        let game = MonsGame::from_fen(opening, false).expect("valid opening fen");
+        let config = pro_budget().runtime_config_for_game(&game);
+        let parallel_inputs = select_parallel_shipping_pro_search_inputs(&game, config);
+        assert!(
+            MonsGameModel::apply_inputs_for_search_with_events(&game, &parallel_inputs).is_some(),
+            "parallel search selected illegal inputs {} on {}",
+            Input::fen_from_array(&parallel_inputs),
+            opening
+        );
+        if parallel_inputs == shipping_selector(&game, config) {
+            same_moves += 1;
+        }
+    }
+
+    println!(
+        "parallel pro benchmark threads={} seed={:?} positions={} single_ms={:.1} parallel_ms={:.1} speedup={:.2} same_moves={}/{}",
+        options.threads,
+        options.deterministic_seed,
+        openings.len(),
+        single_ms,
+        parallel_ms,
+        single_ms / parallel_ms.max(0.001),
+        same_moves,
+        openings.len()
+    );
+}
+
+#[test]
+#[ignore = "benchmark: share of a shipping_pro_search move spent recomputing the Zobrist state key"]
+fn smart_automove_zobrist_state_key_cost_benchmark() {
+    let selector = profile_selector_from_name("shipping_pro_search")
+        .expect("shipping_pro_search selector should exist for zobrist benchmark");
+    let positions = env_usize("SMART_ZOBRIST_BENCH_POSITIONS")
+        .unwrap_or(12)
+        .max(1);
+    let openings = generate_opening_fens_cached(
+        seed_for_pairing("zobrist_state_key_benchmark", "shipping_pro_search"),
+        positions,
+    );
+
+    let mut search_ns = 0u128;
+    let mut state_key_calls = 0u64;
+    let mut key_ns = 0u128;
+    let mut key_samples = 0u64;
+    for opening in openings.iter() {
+        let game = MonsGame::from_fen(opening, false).expect("valid opening fen");
+        let config = pro_budget().runtime_config_for_game(&game);
+        let calls_before = zobrist_state_key_calls();
+        let started = Instant::now();
+        std::hint::black_box(selector(&game, config));
+        search_ns += started.elapsed().as_nanos();
+        state_key_calls += zobrist_state_key_calls() - calls_before;
+
+        const SAMPLES: u64 = 200_000;
+        let started = Instant::now();
+        for _ in 0..SAMPLES {
+            std::hint::black_box(zobrist_state_key(std::hint::black_box(&game)));
+        }
+        key_ns += started.elapsed().as_nanos();
+        key_samples += SAMPLES;
+    }
+
+    let ns_per_key = key_ns as f64 / key_samples as f64;
+    let key_share = state_key_calls as f64 * ns_per_key / search_ns.max(1) as f64;
+    println!(
+        "zobrist state key benchmark positions={} search_ms={:.1} state_key_calls={} ns_per_key={:.1} key_share={:.4}%",
+        openings.len(),
+        search_ns as f64 / 1_000_000.0 / openings.len() as f64,
+        state_key_calls / openings.len() as u64,
+        ns_per_key,
+        key_share * 100.0
+    );
+}
@@ -0,0 +1,480 @@
+//! Root-splitting parallel search for native builds.
+//!
+//! Workers are scoped threads spawned per search, so every worker starts
+//! with its own empty exact-analysis and turn-engine caches.
+use super::*;
+use automove_runtime_variants::root_evaluation_from_scored_root;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicI32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Slots of the transposition table the workers share. A slot takes three
+/// words, so the table takes 24 MiB.
+const SHARED_TRANSPOSITION_TABLE_SLOTS: usize = 1 << 20;
+const SHARED_ENTRY_OCCUPIED: u64 = 1 << 63;
+/// Matches the main thread stack, as search recursion runs deep.
+const PARALLEL_SEARCH_WORKER_STACK_BYTES: usize = 8 << 20;
+
+/// How a parallel search splits root moves across threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelSearchOptions {
+    /// Worker threads. One runs the regular single-thread search.
+    pub threads: usize,
+    /// When set, every worker scores a fixed share of the root moves, dealt
+    /// in an order drawn from the seed, with its own tables and node budget,
+    /// so the same seed and thread count always pick the same move. When
+    /// unset, workers pull root moves from a common queue and share a
+    /// transposition table, the node budget and the best root score, which
+    /// searches faster but depends on thread timing.
+    pub deterministic_seed: Option<u64>,
+}
+
+impl Default for ParallelSearchOptions {
+    fn default() -> Self {
+        Self {
+            threads: std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+            deterministic_seed: None,
+        }
+    }
+}
+
+/// Lock-free transposition table. Each slot keeps its key xor-ed with the
+/// payload words, so a read racing a write fails the key check rather than
+/// returning a torn entry.
+pub(super) struct SharedTranspositionTable {
+    slots: Box<[[AtomicU64; 3]]>,
+}
+
+impl SharedTranspositionTable {
+    pub(super) fn new(slots: usize) -> Self {
+        Self {
+            slots: (0..slots.max(1).next_power_of_two())
+                .map(|_| Default::default())
+                .collect(),
+        }
+    }
+
+    fn slot(&self, key: u64) -> &[AtomicU64; 3] {
+        &self.slots[key as usize & (self.slots.len() - 1)]
+    }
+
+    pub(super) fn probe(&self, key: u64) -> Option<TranspositionEntry> {
+        let slot = self.slot(key);
+        let check = slot[0].load(Ordering::Relaxed);
+        let data = slot[1].load(Ordering::Relaxed);
+        let best_child_hash = slot[2].load(Ordering::Relaxed);
+        if data & SHARED_ENTRY_OCCUPIED == 0 || check ^ data ^ best_child_hash != key {
+            return None;
+        }
+        Some(TranspositionEntry {
+            depth: ((data >> 32) & 0xffff) as usize,
+            score: data as u32 as i32,
+            bound: match (data >> 48) & 0b11 {
+                0 => TranspositionBound::Exact,
+                1 => TranspositionBound::LowerBound,
+                _ => TranspositionBound::UpperBound,
+            },
+            best_child_hash,
+        })
+    }
+
+    /// Always replaces whatever the slot held.
+    pub(super) fn store(&self, key: u64, entry: TranspositionEntry) {
+        let bound: u64 = match entry.bound {
+            TranspositionBound::Exact => 0,
+            TranspositionBound::LowerBound => 1,
+            TranspositionBound::UpperBound => 2,
+        };
+        let data = SHARED_ENTRY_OCCUPIED
+            | (bound << 48)
+            | ((entry.depth.min(0xffff) as u64) << 32)
+            | entry.score as u32 as u64;
+        let slot = self.slot(key);
+        slot[1].store(data, Ordering::Relaxed);
+        slot[2].store(entry.best_child_hash, Ordering::Relaxed);
+        slot[0].store(key ^ data ^ entry.best_child_hash, Ordering::Relaxed);
+    }
+}
+
+thread_local! {
+    static PARALLEL_SEARCH: Cell<Option<ParallelSearchOptions>> = const { Cell::new(None) };
+    static SHARED_TRANSPOSITION_TABLE: RefCell<Option<Arc<SharedTranspositionTable>>> =
+        const { RefCell::new(None) };
+    #[cfg(test)]
+    static WORKER_SCORED_ROOTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Options of the parallel search requested on this thread, if any asks for
+/// more than one thread.
+pub(super) fn active_parallel_search() -> Option<ParallelSearchOptions> {
+    PARALLEL_SEARCH
+        .with(Cell::get)
+        .filter(|options| options.threads > 1)
+}
+
+/// Entry for `key` in the table shared with the other workers, when this
+/// thread is a worker of a shared-table search.
+#[inline]
+pub(super) fn shared_transposition_probe(key: u64) -> Option<TranspositionEntry> {
+    SHARED_TRANSPOSITION_TABLE.with(|table| table.borrow().as_ref()?.probe(key))
+}
+
+#[inline]
+pub(super) fn shared_transposition_store(key: u64, entry: TranspositionEntry) {
+    SHARED_TRANSPOSITION_TABLE.with(|table| {
+        if let Some(table) = table.borrow().as_ref() {
+            table.store(key, entry);
+        }
+    });
+}
+
+struct ParallelSearchGuard;
+
+impl ParallelSearchGuard {
+    fn install(options: ParallelSearchOptions) -> Self {
+        PARALLEL_SEARCH.with(|parallel| parallel.set(Some(options)));
+        ParallelSearchGuard
+    }
+}
+
+impl Drop for ParallelSearchGuard {
+    fn drop(&mut self) {
+        PARALLEL_SEARCH.with(|parallel| parallel.set(None));
+    }
+}
+
+/// Runs `search` with root moves scored as `options` asks.
+pub(crate) fn with_parallel_search<T>(
+    options: ParallelSearchOptions,
+    search: impl FnOnce() -> T,
+) -> T {
+    let _guard = ParallelSearchGuard::install(options);
+    search()
+}
+
+/// Search state every worker starts from.
+pub(super) struct RootSplit<'a> {
+    pub(super) perspective: Color,
+    pub(super) alpha: i32,
+    /// Nodes the search spent before scoring root moves.
+    pub(super) visited_nodes: usize,
+    pub(super) config: AutomoveSearchConfig,
+    pub(super) transposition_table: &'a U64HashMap<TranspositionEntry>,
+    pub(super) extension_node_budget: usize,
+    pub(super) use_transposition_table: bool,
+    pub(super) killer_table: &'a KillerTable,
+    pub(super) history_table: &'a HistoryTable,
+}
+
+/// Tables and counters of one worker.
+struct RootWorker<'a> {
+    split: &'a RootSplit<'a>,
+    transposition_table: U64HashMap<TranspositionEntry>,
+    killer_table: KillerTable,
+    history_table: HistoryTable,
+    extension_nodes_used: usize,
+    extension_node_budget: usize,
+    quiescence_nodes_used: usize,
+}
+
+impl<'a> RootWorker<'a> {
+    fn new(
+        split: &'a RootSplit<'a>,
+        transposition_table: U64HashMap<TranspositionEntry>,
+        extension_node_budget: usize,
+    ) -> Self {
+        Self {
+            split,
+            transposition_table,
+            killer_table: *split.killer_table,
+            history_table: split.history_table.clone(),
+            extension_nodes_used: 0,
+            extension_node_budget,
+            quiescence_nodes_used: 0,
+        }
+    }
+
+    /// Scores `candidate` without letting `visited_nodes` pass `max_visited_nodes`.
+    fn score(
+        &mut self,
+        candidate: &ScoredRootMove,
+        alpha: i32,
+        visited_nodes: &mut usize,
+        max_visited_nodes: usize,
+    ) -> i32 {
+        let mut config = self.split.config;
+        config.max_visited_nodes = max_visited_nodes;
+        MonsGameModel::evaluate_root_candidate_score(
+            candidate,
+            self.split.perspective,
+            alpha,
+            visited_nodes,
+            config,
+            &mut self.transposition_table,
+            &mut self.extension_nodes_used,
+            self.extension_node_budget,
+            self.split.use_transposition_table,
+            &mut self.killer_table,
+            &mut self.history_table,
+            &mut self.quiescence_nodes_used,
+        )
+    }
+}
+
+fn spawn_root_worker<'scope, 'env, F>(
+    scope: &'scope std::thread::Scope<'scope, 'env>,
+    work: F,
+) -> std::thread::ScopedJoinHandle<'scope, Vec<(usize, i32)>>
+where
+    F: FnOnce() -> Vec<(usize, i32)> + Send + 'scope,
+{
+    std::thread::Builder::new()
+        .stack_size(PARALLEL_SEARCH_WORKER_STACK_BYTES)
+        .spawn_scoped(scope, work)
+        .expect("parallel search worker thread spawns")
+}
+
+impl MonsGameModel {
+    /// `smart_automove_native` with root moves scored on `options.threads`
+    /// threads. Only root scoring runs in parallel; move ranking, the turn
+    /// planner and the final pick stay on the calling thread.
+    pub fn smart_automove_parallel(
+        &self,
+        preference: SmartAutomovePreference,
+        options: ParallelSearchOptions,
+    ) -> Result<OutputModel, AutomoveError> {
+        with_parallel_search(options, || self.smart_automove_native(preference))
+    }
+
+    /// Scores `root_moves` across threads. Root moves left unscored once the
+    /// node budget ran out are dropped, like in the single-thread loop, and
+    /// the rest keep their order.
+    pub(super) fn score_root_moves_in_parallel(
+        options: ParallelSearchOptions,
+        root_moves: Vec<ScoredRootMove>,
+        split: RootSplit,
+    ) -> Vec<RootEvaluation> {
+        let threads = options.threads.clamp(1, root_moves.len().max(1));
+        let results = match options.deterministic_seed {
+            Some(seed) => Self::score_seeded_root_shares(&root_moves, &split, threads, seed),
+            None => Self::score_root_queue(&root_moves, &split, threads),
+        };
+        #[cfg(test)]
+        WORKER_SCORED_ROOTS.with(|scored| scored.set(scored.get() + results.len()));
+        let mut scores = vec![None; root_moves.len()];
+        for (index, score) in results {
+            scores[index] = Some(score);
+        }
+        root_moves
+            .into_iter()
+            .zip(scores)
+            .filter_map(|(candidate, score)| {
+                score.map(|score| root_evaluation_from_scored_root(candidate, score))
+            })
+            .collect()
+    }
+
+    /// Deals root moves to workers in seeded order. Each worker gets the
+    /// share of the node budget its root moves make up and scores them in
+    /// rank order against its own running best.
+    fn score_seeded_root_shares(
+        root_moves: &[ScoredRootMove],
+        split: &RootSplit,
+        threads: usize,
+        seed: u64,
+    ) -> Vec<(usize, i32)> {
+        let mut order = (0..root_moves.len()).collect::<Vec<_>>();
+        order.shuffle(&mut StdRng::seed_from_u64(seed));
+        let remaining_nodes = split
+            .config
+            .max_visited_nodes
+            .saturating_sub(split.visited_nodes);
+
+        std::thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|thread| {
+                    let mut share = order
+                        .iter()
+                        .copied()
+                        .skip(thread)
+                        .step_by(threads)
+                        .collect::<Vec<_>>();
+                    share.sort_unstable();
+                    let budget = remaining_nodes * share.len() / root_moves.len();
+                    let extension_node_budget = (split.extension_node_budget * share.len()
+                        / root_moves.len())
+                    .max(split.extension_node_budget.min(1));
+                    spawn_root_worker(scope, move || {
+                        let mut worker = RootWorker::new(
+                            split,
+                            split.transposition_table.clone(),
+                            extension_node_budget,
+                        );
+                        let mut alpha = split.alpha;
+                        let mut visited_nodes = 0usize;
+                        let mut scores = Vec::with_capacity(share.len());
+                        for index in share {
+                            if visited_nodes >= budget {
+                                break;
+                            }
+                            visited_nodes += 1;
+                            let score =
+                                worker.score(&root_moves[index], alpha, &mut visited_nodes, budget);
+                            alpha = alpha.max(score);
+                            scores.push((index, score));
+                        }
+                        scores
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("parallel search worker finished"))
+                .collect()
+        })
+    }
+
+    /// Workers pull root moves in rank order from a common queue. A root
+    /// move may spend at most a per-thread share of the nodes still left,
+    /// so the workers together stay within the budget.
+    fn score_root_queue(
+        root_moves: &[ScoredRootMove],
+        split: &RootSplit,
+        threads: usize,
+    ) -> Vec<(usize, i32)> {
+        let shared_table = Arc::new(SharedTranspositionTable::new(
+            SHARED_TRANSPOSITION_TABLE_SLOTS,
+        ));
+        for (&key, &entry) in split.transposition_table {
+            shared_table.store(key, entry);
+        }
+        let remaining_nodes = split
+            .config
+            .max_visited_nodes
+            .saturating_sub(split.visited_nodes);
+        let extension_node_budget =
+            (split.extension_node_budget / threads).max(split.extension_node_budget.min(1));
+        let next_index = AtomicUsize::new(0);
+        let spent_nodes = AtomicUsize::new(0);
+        let best_score = AtomicI32::new(split.alpha);
+
+        std::thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|_| {
+                    let shared_table = shared_table.clone();
+                    let (next_index, spent_nodes, best_score) =
+                        (&next_index, &spent_nodes, &best_score);
+                    spawn_root_worker(scope, move || {
+                        SHARED_TRANSPOSITION_TABLE
+                            .with(|table| *table.borrow_mut() = Some(shared_table));
+                        let mut worker =
+                            RootWorker::new(split, U64HashMap::default(), extension_node_budget);
+                        let mut scores = Vec::new();
+                        loop {
+                            let index = next_index.fetch_add(1, Ordering::Relaxed);
+                            let spent = spent_nodes.load(Ordering::Relaxed);
+                            if index >= root_moves.len() || spent >= remaining_nodes {
+                                break;
+                            }
+                            let mut visited_nodes = 1usize;
+                            let score = worker.score(
+                                &root_moves[index],
+                                best_score.load(Ordering::Relaxed),
+                                &mut visited_nodes,
+                                ((remaining_nodes - spent) / threads).max(2),
+                            );
+                            spent_nodes.fetch_add(visited_nodes, Ordering::Relaxed);
+                            best_score.fetch_max(score, Ordering::Relaxed);
+                            scores.push((index, score));
+                        }
+                        SHARED_TRANSPOSITION_TABLE.with(|table| *table.borrow_mut() = None);
+                        scores
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("parallel search worker finished"))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_table_keeps_entries_and_rejects_other_keys() {
+        let table = SharedTranspositionTable::new(8);
+        assert!(table.probe(0).is_none());
+        let entry = TranspositionEntry {
+            depth: 3,
+            score: -1_234,
+            bound: TranspositionBound::LowerBound,
+            best_child_hash: 0xdead_beef,
+        };
+        table.store(5, entry);
+        let stored = table.probe(5).unwrap();
+        assert_eq!(stored.depth, 3);
+        assert_eq!(stored.score, -1_234);
+        assert!(matches!(stored.bound, TranspositionBound::LowerBound));
+        assert_eq!(stored.best_child_hash, 0xdead_beef);
+        assert!(table.probe(5 + 8).is_none());
+
+        table.slot(5)[1].fetch_xor(1, Ordering::Relaxed);
+        assert!(table.probe(5).is_none());
+    }
+
+    /// Pro scores root moves with the turn engine selector on here, the
+    /// only search that splits root scoring across threads.
+    const PRO_ROOT_SCORING_FEN: &str = "0 0 w 0 0 3 0 0 13 n03y0xs0xd0xn01e0xn03/n04xxmn02a0xn03/n11/n06xxmn01xxmn02/n03xxmn01xxmn05/xxQn04xxUn04xxQ/n05xxMxxMxxMn03/n02xxMn03xxMn04/n03D0xn04A0xn02/n06Y0xn04/n03E0xn02S0xn04";
+
+    fn parallel_pro_output(model: &MonsGameModel, options: ParallelSearchOptions) -> OutputModel {
+        WORKER_SCORED_ROOTS.with(|scored| scored.set(0));
+        let output = model
+            .smart_automove_parallel(SmartAutomovePreference::Pro, options)
+            .unwrap();
+        assert!(WORKER_SCORED_ROOTS.with(Cell::get) > 0);
+        let mut game = model.game.clone_for_simulation();
+        assert!(matches!(
+            game.process_input_slice(
+                &Input::array_from_fen(output.input_fen().as_str()),
+                false,
+                false
+            ),
+            Output::Events(_)
+        ));
+        output
+    }
+
+    #[test]
+    fn seeded_parallel_search_repeats_its_move() {
+        let model = MonsGameModel::from_fen(PRO_ROOT_SCORING_FEN).unwrap();
+        let options = ParallelSearchOptions {
+            threads: 4,
+            deterministic_seed: Some(7),
+        };
+        let first = parallel_pro_output(&model, options);
+        for _ in 0..2 {
+            assert_eq!(
+                parallel_pro_output(&model, options).input_fen(),
+                first.input_fen()
+            );
+        }
+        assert_eq!(active_parallel_search(), None);
+    }
+
+    #[test]
+    fn shared_table_parallel_search_plays_legal_moves() {
+        let model = MonsGameModel::from_fen(PRO_ROOT_SCORING_FEN).unwrap();
+        let options = ParallelSearchOptions {
+            threads: 3,
+            deterministic_seed: None,
+        };
+        parallel_pro_output(&model, options);
+    }
+}
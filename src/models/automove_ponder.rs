@@ -0,0 +1,302 @@
+use super::*;
+use std::cell::{Cell, RefCell};
+
+/// Cap on the opponent moves predicted for one turn, in case the predicted
+/// moves never hand the turn back.
+const MAX_PREDICTED_OPPONENT_MOVES: usize = 32;
+
+/// Transposition table of a ponder that hit, kept for the search of the
+/// position it was pondered on.
+struct PonderedTranspositionTable {
+    state_hash: u64,
+    perspective: Color,
+    entries: U64HashMap<TranspositionEntry>,
+}
+
+thread_local! {
+    static PONDERED_TRANSPOSITION_TABLE: RefCell<Option<PonderedTranspositionTable>> =
+        const { RefCell::new(None) };
+    static REUSING_PONDERED_CACHES: Cell<bool> = const { Cell::new(false) };
+    #[cfg(test)]
+    static HANDED_OVER_ENTRIES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Transposition table pondered on `game` from `perspective`, if the search
+/// running now follows a ponder hit on that position.
+pub(super) fn take_pondered_transposition_table(
+    game: &MonsGame,
+    perspective: Color,
+) -> Option<U64HashMap<TranspositionEntry>> {
+    PONDERED_TRANSPOSITION_TABLE.with(|table| {
+        let mut table = table.borrow_mut();
+        let pondered = table.as_ref()?;
+        if pondered.perspective != perspective
+            || pondered.state_hash != MonsGameModel::search_state_hash(game)
+        {
+            return None;
+        }
+        let entries = table.take()?.entries;
+        #[cfg(test)]
+        HANDED_OVER_ENTRIES.with(|handed_over| handed_over.set(entries.len()));
+        Some(entries)
+    })
+}
+
+/// Whether the search running now follows a ponder hit, so the turn planner
+/// keeps the plans it cached while pondering.
+pub(super) fn reusing_pondered_caches() -> bool {
+    REUSING_PONDERED_CACHES.with(Cell::get)
+}
+
+struct PonderHitGuard;
+
+impl PonderHitGuard {
+    fn install(pondered: PonderedTranspositionTable) -> Self {
+        PONDERED_TRANSPOSITION_TABLE.with(|table| *table.borrow_mut() = Some(pondered));
+        REUSING_PONDERED_CACHES.with(|reusing| reusing.set(true));
+        PonderHitGuard
+    }
+}
+
+impl Drop for PonderHitGuard {
+    fn drop(&mut self) {
+        PONDERED_TRANSPOSITION_TABLE.with(|table| *table.borrow_mut() = None);
+        REUSING_PONDERED_CACHES.with(|reusing| reusing.set(false));
+    }
+}
+
+/// Search of the position expected after the opponent's turn, run in slices
+/// while the opponent thinks. Hand it to `smartAutomovePondered` once the
+/// opponent moved: if they played the predicted turn, that search starts
+/// from the pondered transposition table and turn planner caches.
+///
+/// Searches of other positions on the same thread between steps can evict
+/// the cached plans, which only costs the reuse.
+#[wasm_bindgen]
+pub struct Ponder {
+    preference: SmartAutomovePreference,
+    predicted: Option<MonsGame>,
+    state: Option<Box<AsyncSmartSearchState>>,
+    finished: bool,
+}
+
+#[wasm_bindgen]
+impl Ponder {
+    /// Searches until at least `max_nodes` more nodes were visited, always
+    /// making some progress. Returns whether pondering is over.
+    pub fn step(&mut self, max_nodes: usize) -> bool {
+        if self.finished {
+            return true;
+        }
+        let Some(state) = self.state.as_mut() else {
+            return true;
+        };
+        let stop_at = state.visited_nodes.saturating_add(max_nodes.max(1));
+        loop {
+            if MonsGameModel::advance_async_search(state) {
+                self.finished = true;
+                MonsGameModel::warm_pondered_turn_plan(state, self.preference);
+                return true;
+            }
+            if state.visited_nodes >= stop_at {
+                return false;
+            }
+        }
+    }
+
+    /// FEN of the position expected once the opponent's turn is over, or
+    /// nothing when no turn could be predicted.
+    #[wasm_bindgen(js_name = predictedFen)]
+    pub fn predicted_fen(&self) -> Option<String> {
+        self.predicted.as_ref().map(MonsGame::fen)
+    }
+
+    #[wasm_bindgen(js_name = isFinished)]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Nodes visited so far, for progress reporting.
+    #[wasm_bindgen(js_name = visitedNodes)]
+    pub fn visited_nodes(&self) -> usize {
+        self.state
+            .as_ref()
+            .map(|state| state.visited_nodes)
+            .unwrap_or_default()
+    }
+}
+
+impl MonsGameModel {
+    pub(crate) fn begin_ponder_handle(&self, preference: SmartAutomovePreference) -> Ponder {
+        let predicted = Self::predicted_opponent_turn(&self.game);
+        let state = predicted.as_ref().map(|game| {
+            clear_exact_state_analysis_cache();
+            let config = Self::shipping_search_config_for_game(game, preference);
+            Box::new(Self::new_async_smart_search_state(
+                game.clone_for_simulation(),
+                game.active_color,
+                config,
+            ))
+        });
+        Ponder {
+            preference,
+            finished: state.is_none(),
+            predicted,
+            state,
+        }
+    }
+
+    /// Plays out the opponent's turn with Fast smart automove. Nothing when
+    /// the game ends on the way or the turn never comes back.
+    fn predicted_opponent_turn(game: &MonsGame) -> Option<MonsGame> {
+        let opponent = game.active_color;
+        let mut predicted = game.clone_for_simulation();
+        for _ in 0..MAX_PREDICTED_OPPONENT_MOVES {
+            if predicted.active_color != opponent || predicted.is_game_over() {
+                break;
+            }
+            let config =
+                Self::shipping_search_config_for_game(&predicted, SmartAutomovePreference::Fast);
+            let inputs =
+                automove_runtime_variants::select_shipping_search_inputs(&predicted, config);
+            if inputs.is_empty()
+                || !matches!(
+                    predicted.process_input_slice(&inputs, false, false),
+                    Output::Events(_)
+                )
+            {
+                return None;
+            }
+        }
+        (predicted.active_color != opponent && !predicted.is_game_over()).then_some(predicted)
+    }
+
+    /// Builds Pro's head turn plan for the pondered position, so a ponder
+    /// hit finds it in the turn planner cache.
+    fn warm_pondered_turn_plan(state: &AsyncSmartSearchState, preference: SmartAutomovePreference) {
+        if !matches!(preference, SmartAutomovePreference::Pro) {
+            return;
+        }
+        let config = automove_runtime_variants::apply_frontier_pro_v2_guarded_config(
+            Self::shipping_search_config_for_game(&state.game, preference),
+        );
+        if config.enable_turn_engine_selector {
+            let engine_config = Self::turn_engine_config_for_game(&state.game, config);
+            let _ = turn_engine_candidate_plan(&state.game, state.perspective, engine_config);
+        }
+    }
+
+    /// Runs the `preference` search, starting from what `ponder` found when
+    /// this position is the one it pondered on, and counts the ponder hit or
+    /// miss in the totals the turn engine diagnostics report.
+    pub(crate) fn smart_automove_pondered_output(
+        &self,
+        preference: SmartAutomovePreference,
+        ponder: Ponder,
+    ) -> OutputModel {
+        let hit = ponder.predicted.as_ref().is_some_and(|predicted| {
+            predicted.active_color == self.game.active_color
+                && Self::search_state_hash(predicted) == Self::search_state_hash(&self.game)
+        });
+        let guard = ponder.state.filter(|_| hit).map(|state| {
+            PonderHitGuard::install(PonderedTranspositionTable {
+                state_hash: Self::search_state_hash(&self.game),
+                perspective: state.perspective,
+                entries: state.transposition_table,
+            })
+        });
+        let output = self.smart_automove_output(preference);
+        drop(guard);
+        record_ponder_outcome(hit);
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finished_ponder(model: &MonsGameModel, preference: SmartAutomovePreference) -> Ponder {
+        let mut ponder = model.begin_ponder_native(preference).unwrap();
+        while !ponder.step(256) {}
+        assert!(ponder.is_finished());
+        ponder
+    }
+
+    #[test]
+    fn ponder_hit_reuses_the_pondered_search() {
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        for preference in [
+            SmartAutomovePreference::Normal,
+            SmartAutomovePreference::Pro,
+        ] {
+            let ponder = finished_ponder(&model, preference);
+            let predicted = MonsGameModel::from_fen(ponder.predicted_fen().unwrap().as_str())
+                .expect("predicted fen loads");
+            assert_ne!(predicted.game.active_color, model.game.active_color);
+
+            let before = turn_engine_diagnostics_snapshot();
+            HANDED_OVER_ENTRIES.with(|handed_over| handed_over.set(0));
+            let output = predicted
+                .smart_automove_pondered_native(preference, ponder)
+                .unwrap();
+            assert_eq!(output.kind, OutputModelKind::Events);
+            assert!(HANDED_OVER_ENTRIES.with(Cell::get) > 0);
+            let diagnostics = turn_engine_diagnostics_snapshot();
+            assert_eq!(
+                (
+                    diagnostics.ponder_hits - before.ponder_hits,
+                    diagnostics.ponder_misses - before.ponder_misses
+                ),
+                (1, 0)
+            );
+            assert!(!reusing_pondered_caches());
+            assert!(PONDERED_TRANSPOSITION_TABLE.with(|table| table.borrow().is_none()));
+        }
+    }
+
+    #[test]
+    fn ponder_miss_searches_the_actual_position() {
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        let ponder = finished_ponder(&model, SmartAutomovePreference::Normal);
+
+        let before = turn_engine_diagnostics_snapshot();
+        HANDED_OVER_ENTRIES.with(|handed_over| handed_over.set(0));
+        let output = model
+            .smart_automove_pondered_native(SmartAutomovePreference::Normal, ponder)
+            .unwrap();
+        assert_eq!(
+            output.input_fen(),
+            model
+                .smart_automove_native(SmartAutomovePreference::Normal)
+                .unwrap()
+                .input_fen()
+        );
+        let diagnostics = turn_engine_diagnostics_snapshot();
+        assert_eq!(
+            (
+                diagnostics.ponder_hits - before.ponder_hits,
+                diagnostics.ponder_misses - before.ponder_misses
+            ),
+            (0, 1)
+        );
+        assert_eq!(HANDED_OVER_ENTRIES.with(Cell::get), 0);
+    }
+
+    #[test]
+    fn ponder_outcomes_outlive_the_diagnostics_of_later_searches() {
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        let ponder = finished_ponder(&model, SmartAutomovePreference::Fast);
+        let before = turn_engine_diagnostics_snapshot();
+        model
+            .smart_automove_pondered_native(SmartAutomovePreference::Fast, ponder)
+            .unwrap();
+        model
+            .smart_automove_native(SmartAutomovePreference::Normal)
+            .unwrap();
+        clear_turn_engine_diagnostics();
+        let diagnostics = turn_engine_diagnostics_snapshot();
+        assert_eq!(diagnostics.ponder_misses, before.ponder_misses + 1);
+        assert_eq!(diagnostics.ponder_hits, before.ponder_hits);
+    }
+}
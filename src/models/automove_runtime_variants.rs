@@ -1,5 +1,3 @@
-#![cfg(any(target_arch = "wasm32", test))]
-
 use super::*;
 
 #[cfg(test)]
@@ -39,7 +37,11 @@ fn select_shipping_search_inputs_internal(
     game: &MonsGame,
     config: AutomoveSearchConfig,
 ) -> Vec<Input> {
-    let inputs = MonsGameModel::smart_search_best_inputs(game, config);
+    shipping_inputs_or_automove(game, MonsGameModel::smart_search_best_inputs(game, config))
+}
+
+/// `inputs`, or the plain automove when the search found nothing.
+pub(super) fn shipping_inputs_or_automove(game: &MonsGame, inputs: Vec<Input>) -> Vec<Input> {
     if !inputs.is_empty() {
         return inputs;
     }
@@ -57,11 +59,74 @@ fn select_search_inputs_with_fresh_frontier_cache(
     game: &MonsGame,
     config: AutomoveSearchConfig,
 ) -> Vec<Input> {
+    clear_frontier_search_caches(config);
+    select_shipping_search_inputs_internal(game, config)
+}
+
+pub(super) fn clear_frontier_search_caches(config: AutomoveSearchConfig) {
     if config.enable_turn_engine_selector {
-        crate::models::automove_turn_engine::clear_turn_engine_plan_cache();
+        if !super::automove_ponder::reusing_pondered_caches() {
+            crate::models::automove_turn_engine::clear_turn_engine_plan_cache();
+        }
         crate::models::automove_turn_engine::clear_turn_engine_diagnostics();
     }
-    select_shipping_search_inputs_internal(game, config)
+}
+
+/// Search a runtime selector reads the chosen inputs of.
+#[derive(Clone, Copy)]
+pub(super) enum RuntimeSearch {
+    /// As `select_shipping_search_inputs`.
+    Shipping(AutomoveSearchConfig),
+    /// As `select_search_inputs_with_fresh_frontier_cache`.
+    FreshFrontier(AutomoveSearchConfig),
+}
+
+/// Runs the searches a runtime selector asks for. `ImmediateSearches` runs
+/// them on the spot, while a `SearchHandle` runs them in slices and replays
+/// the selector with the results it has so far.
+pub(super) trait RuntimeSearches {
+    /// What the selector stops on when a result is not in yet.
+    type Pending;
+
+    fn inputs(
+        &mut self,
+        game: &MonsGame,
+        search: RuntimeSearch,
+    ) -> Result<Vec<Input>, Self::Pending>;
+
+    /// As `focused_scored_roots_for_frontier_runtime`.
+    fn focused_scored_roots(
+        &mut self,
+        game: &MonsGame,
+        config: AutomoveSearchConfig,
+    ) -> Result<Vec<RootEvaluation>, Self::Pending>;
+}
+
+pub(super) struct ImmediateSearches;
+
+impl RuntimeSearches for ImmediateSearches {
+    type Pending = std::convert::Infallible;
+
+    fn inputs(
+        &mut self,
+        game: &MonsGame,
+        search: RuntimeSearch,
+    ) -> Result<Vec<Input>, Self::Pending> {
+        Ok(match search {
+            RuntimeSearch::Shipping(config) => select_shipping_search_inputs(game, config),
+            RuntimeSearch::FreshFrontier(config) => {
+                select_search_inputs_with_fresh_frontier_cache(game, config)
+            }
+        })
+    }
+
+    fn focused_scored_roots(
+        &mut self,
+        game: &MonsGame,
+        config: AutomoveSearchConfig,
+    ) -> Result<Vec<RootEvaluation>, Self::Pending> {
+        Ok(focused_scored_roots_for_frontier_runtime(game, config))
+    }
 }
 
 pub(crate) fn select_shipping_search_inputs(
@@ -108,10 +173,11 @@ pub(crate) fn apply_frontier_pro_v2_guarded_config(
     runtime
 }
 
-fn select_early_white_fallback_inputs(
+fn select_early_white_fallback_inputs<S: RuntimeSearches>(
     game: &MonsGame,
     config: AutomoveSearchConfig,
-) -> Option<Vec<Input>> {
+    searches: &mut S,
+) -> Result<Option<Vec<Input>>, S::Pending> {
     let early_white_turn_start = game.active_color == Color::White
         && game.turn_number <= 3
         && !game.player_can_use_action()
@@ -139,7 +205,9 @@ fn select_early_white_fallback_inputs(
         || white_turn_three_mid_turn_full_resources
     {
         let shipping_runtime = shipping_search_config_for_game(game, SmartAutomovePreference::Pro);
-        return Some(select_shipping_search_inputs(game, shipping_runtime));
+        return searches
+            .inputs(game, RuntimeSearch::Shipping(shipping_runtime))
+            .map(Some);
     }
 
     let white_turn_three_mana_only = game.active_color == Color::White
@@ -153,7 +221,7 @@ fn select_early_white_fallback_inputs(
         && !white_turn_three_mana_only
         && (game.player_can_use_action() || game.player_can_move_mana());
     if !white_turn_three_mid_turn {
-        return None;
+        return Ok(None);
     }
 
     let drainer_vulnerable = MonsGameModel::is_own_drainer_vulnerable_next_turn(
@@ -167,52 +235,58 @@ fn select_early_white_fallback_inputs(
         config.enable_enhanced_drainer_vulnerability,
     );
     if !drainer_vulnerable && !drainer_walk_vulnerable {
-        return None;
+        return Ok(None);
     }
 
     let fast_runtime = shipping_search_config_for_game(game, SmartAutomovePreference::Fast);
-    Some(select_shipping_search_inputs(
-        game,
-        MonsGameModel::with_pre_exact_runtime_policy(fast_runtime),
-    ))
+    searches
+        .inputs(
+            game,
+            RuntimeSearch::Shipping(MonsGameModel::with_pre_exact_runtime_policy(fast_runtime)),
+        )
+        .map(Some)
 }
 
-fn select_score_window_tactical_fallback_inputs(
+fn select_score_window_tactical_fallback_inputs<S: RuntimeSearches>(
     game: &MonsGame,
     config: AutomoveSearchConfig,
-) -> Option<Vec<Input>> {
+    searches: &mut S,
+) -> Result<Option<Vec<Input>>, S::Pending> {
     let white_turn_three_mid_turn_scoring_action_mana = game.active_color == Color::White
         && game.turn_number == 3
         && matches!(game.mons_moves_count, 1 | 2)
         && game.player_can_use_action()
         && game.player_can_move_mana();
     if !white_turn_three_mid_turn_scoring_action_mana {
-        return None;
+        return Ok(None);
     }
 
     let context = crate::models::automove_exact::exact_opportunity_context(game, game.active_color);
     if context.delta.same_turn_score_window_value <= 0 {
-        return None;
+        return Ok(None);
     }
 
-    Some(select_search_inputs_with_fresh_frontier_cache(
-        game,
-        apply_frontier_pro_v2_guarded_config(config),
-    ))
+    searches
+        .inputs(
+            game,
+            RuntimeSearch::FreshFrontier(apply_frontier_pro_v2_guarded_config(config)),
+        )
+        .map(Some)
 }
 
-fn select_white_early_engine_disabled_fallback_inputs(
+fn select_white_early_engine_disabled_fallback_inputs<S: RuntimeSearches>(
     game: &MonsGame,
     config: AutomoveSearchConfig,
     frontier_inputs: &[Input],
-) -> Option<Vec<Input>> {
+    searches: &mut S,
+) -> Result<Option<Vec<Input>>, S::Pending> {
     let white_turn_five_turn_start_action_mana = game.active_color == Color::White
         && game.turn_number == 5
         && game.mons_moves_count == 0
         && game.player_can_use_action()
         && game.player_can_move_mana();
     if !white_turn_five_turn_start_action_mana || frontier_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
 
     let context = crate::models::automove_exact::exact_opportunity_context(game, game.active_color);
@@ -222,15 +296,18 @@ fn select_white_early_engine_disabled_fallback_inputs(
         || context.delta.drainer_attack_available
         || context.delta.drainer_safety >= 0
     {
-        return None;
+        return Ok(None);
     }
 
     let frontier_runtime = apply_frontier_pro_v2_guarded_config(config);
     let frontier_roots =
         MonsGameModel::ranked_root_moves(game, game.active_color, frontier_runtime);
-    let frontier_selected = frontier_roots
+    let Some(frontier_selected) = frontier_roots
         .iter()
-        .find(|root| root.inputs.as_slice() == frontier_inputs)?;
+        .find(|root| root.inputs.as_slice() == frontier_inputs)
+    else {
+        return Ok(None);
+    };
     if frontier_selected.wins_immediately
         || frontier_selected.attacks_opponent_drainer
         || frontier_selected.spirit_development
@@ -248,20 +325,23 @@ fn select_white_early_engine_disabled_fallback_inputs(
         || frontier_selected.has_roundtrip
         || frontier_selected.same_turn_score_window_value != 1
     {
-        return None;
+        return Ok(None);
     }
 
     let shipping_runtime = shipping_search_config_for_game(game, SmartAutomovePreference::Pro);
-    let shipping_inputs = select_shipping_search_inputs(game, shipping_runtime);
+    let shipping_inputs = searches.inputs(game, RuntimeSearch::Shipping(shipping_runtime))?;
     if shipping_inputs.is_empty() || shipping_inputs == frontier_inputs {
-        return None;
+        return Ok(None);
     }
 
     let shipping_roots =
         MonsGameModel::ranked_root_moves(game, game.active_color, shipping_runtime);
-    let shipping_selected = shipping_roots
+    let Some(shipping_selected) = shipping_roots
         .iter()
-        .find(|root| root.inputs.as_slice() == shipping_inputs.as_slice())?;
+        .find(|root| root.inputs.as_slice() == shipping_inputs.as_slice())
+    else {
+        return Ok(None);
+    };
     if !shipping_selected.spirit_development
         || shipping_selected.spirit_same_turn_score_setup_now
         || !MonsGameModel::turn_engine_root_move_has_progress_surface(shipping_selected)
@@ -277,24 +357,25 @@ fn select_white_early_engine_disabled_fallback_inputs(
         || shipping_selected.own_drainer_walk_vulnerable
         || shipping_selected.same_turn_score_window_value != 0
     {
-        return None;
+        return Ok(None);
     }
 
-    Some(shipping_inputs)
+    Ok(Some(shipping_inputs))
 }
 
-fn select_white_nonnegative_deny_search_only_fallback_inputs(
+fn select_white_nonnegative_deny_search_only_fallback_inputs<S: RuntimeSearches>(
     game: &MonsGame,
     config: AutomoveSearchConfig,
     frontier_inputs: &[Input],
-) -> Option<Vec<Input>> {
+    searches: &mut S,
+) -> Result<Option<Vec<Input>>, S::Pending> {
     let white_turn_three_mana_only = game.active_color == Color::White
         && game.turn_number == 3
         && game.mons_moves_count == 1
         && !game.player_can_use_action()
         && game.player_can_move_mana();
     if !white_turn_three_mana_only || frontier_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
 
     let context = crate::models::automove_exact::exact_opportunity_context(game, game.active_color);
@@ -304,15 +385,18 @@ fn select_white_nonnegative_deny_search_only_fallback_inputs(
         || context.delta.drainer_attack_available
         || context.delta.drainer_safety >= 0
     {
-        return None;
+        return Ok(None);
     }
 
     let frontier_runtime = apply_frontier_pro_v2_guarded_config(config);
     let frontier_roots =
         MonsGameModel::ranked_root_moves(game, game.active_color, frontier_runtime);
-    let frontier_selected = frontier_roots
+    let Some(frontier_selected) = frontier_roots
         .iter()
-        .find(|root| root.inputs.as_slice() == frontier_inputs)?;
+        .find(|root| root.inputs.as_slice() == frontier_inputs)
+    else {
+        return Ok(None);
+    };
     let frontier_family = MonsGameModel::turn_engine_root_move_family(frontier_selected);
     let frontier_utility = MonsGameModel::turn_engine_scored_root_utility(
         game,
@@ -322,7 +406,7 @@ fn select_white_nonnegative_deny_search_only_fallback_inputs(
         frontier_family,
     );
     if !frontier_utility.has_nonnegative_deny_gain() {
-        return None;
+        return Ok(None);
     }
 
     let mut search_only_runtime = frontier_runtime;
@@ -330,26 +414,27 @@ fn select_white_nonnegative_deny_search_only_fallback_inputs(
     search_only_runtime.enable_turn_head_rerank = true;
     search_only_runtime.turn_engine_mode = TurnEngineMode::ProV1;
 
-    let search_only_inputs = select_shipping_search_inputs(game, search_only_runtime);
+    let search_only_inputs = searches.inputs(game, RuntimeSearch::Shipping(search_only_runtime))?;
     if search_only_inputs.is_empty() || search_only_inputs == frontier_inputs {
-        return None;
+        return Ok(None);
     }
 
-    Some(search_only_inputs)
+    Ok(Some(search_only_inputs))
 }
 
-fn select_white_negative_deny_search_only_selected_rank_fallback_inputs(
+fn select_white_negative_deny_search_only_selected_rank_fallback_inputs<S: RuntimeSearches>(
     game: &MonsGame,
     config: AutomoveSearchConfig,
     frontier_inputs: &[Input],
-) -> Option<Vec<Input>> {
+    searches: &mut S,
+) -> Result<Option<Vec<Input>>, S::Pending> {
     let white_turn_three_mana_only = game.active_color == Color::White
         && game.turn_number == 3
         && game.mons_moves_count == 1
         && !game.player_can_use_action()
         && game.player_can_move_mana();
     if !white_turn_three_mana_only || frontier_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
 
     let context = crate::models::automove_exact::exact_opportunity_context(game, game.active_color);
@@ -359,15 +444,18 @@ fn select_white_negative_deny_search_only_selected_rank_fallback_inputs(
         || context.delta.drainer_attack_available
         || context.delta.drainer_safety >= 0
     {
-        return None;
+        return Ok(None);
     }
 
     let frontier_runtime = apply_frontier_pro_v2_guarded_config(config);
     let frontier_roots =
         MonsGameModel::ranked_root_moves(game, game.active_color, frontier_runtime);
-    let frontier_selected = frontier_roots
+    let Some(frontier_selected) = frontier_roots
         .iter()
-        .find(|root| root.inputs.as_slice() == frontier_inputs)?;
+        .find(|root| root.inputs.as_slice() == frontier_inputs)
+    else {
+        return Ok(None);
+    };
     let frontier_family = MonsGameModel::turn_engine_root_move_family(frontier_selected);
     let frontier_utility = MonsGameModel::turn_engine_scored_root_utility(
         game,
@@ -377,7 +465,7 @@ fn select_white_negative_deny_search_only_selected_rank_fallback_inputs(
         frontier_family,
     );
     if frontier_utility.has_nonnegative_deny_gain() {
-        return None;
+        return Ok(None);
     }
 
     let mut search_only_runtime = frontier_runtime;
@@ -390,22 +478,24 @@ fn select_white_negative_deny_search_only_selected_rank_fallback_inputs(
         shipping_runtime.turn_engine_per_node_family_cap;
     search_only_runtime.turn_engine_step_cap = shipping_runtime.turn_engine_step_cap;
 
-    let search_only_inputs = select_shipping_search_inputs(game, search_only_runtime);
+    let search_only_inputs = searches.inputs(game, RuntimeSearch::Shipping(search_only_runtime))?;
     if search_only_inputs.is_empty() || search_only_inputs == frontier_inputs {
-        return None;
+        return Ok(None);
     }
 
-    let selected_rank = MonsGameModel::focused_candidate_rank_for_runtime_inputs(
+    let Some(selected_rank) = MonsGameModel::focused_candidate_rank_for_runtime_inputs(
         game,
         game.active_color,
         search_only_runtime,
         search_only_inputs.as_slice(),
-    )?;
+    ) else {
+        return Ok(None);
+    };
     if selected_rank != 0 {
-        return None;
+        return Ok(None);
     }
 
-    Some(search_only_inputs)
+    Ok(Some(search_only_inputs))
 }
 
 fn is_safe_quiet_mana_tempo_root(root: &RootEvaluation) -> bool {
@@ -435,7 +525,10 @@ fn is_safe_quiet_mana_tempo_root(root: &RootEvaluation) -> bool {
         )
 }
 
-fn root_evaluation_from_scored_root(candidate: ScoredRootMove, score: i32) -> RootEvaluation {
+pub(super) fn root_evaluation_from_scored_root(
+    candidate: ScoredRootMove,
+    score: i32,
+) -> RootEvaluation {
     RootEvaluation {
         root_rank: candidate.root_rank,
         score,
@@ -472,6 +565,17 @@ fn focused_scored_roots_for_frontier_runtime(
     game: &MonsGame,
     config: AutomoveSearchConfig,
 ) -> Vec<RootEvaluation> {
+    let mut search = focused_root_search(game, config);
+    while search.score_next_root() {}
+    search.scored_roots
+}
+
+/// Root scoring over the focused root moves of `config`, without the guards
+/// and the shallow pass of the full search.
+pub(super) fn focused_root_search(
+    game: &MonsGame,
+    config: AutomoveSearchConfig,
+) -> SmartRootSearch {
     let perspective = game.active_color;
     let mut root_moves = MonsGameModel::ranked_root_moves(game, perspective, config);
     let engine_plan = if config.enable_turn_engine_selector {
@@ -505,63 +609,31 @@ fn focused_scored_roots_for_frontier_runtime(
             None,
         );
 
-    let mut visited_nodes = scout_visited_nodes;
-    let mut alpha = i32::MIN;
-    let mut scored_roots = Vec::with_capacity(root_moves.len());
-    let mut transposition_table = U64HashMap::default();
-    let extension_node_budget = if config.enable_selective_extensions
-        && config.selective_extension_node_share_bp > 0
-    {
-        ((config.max_visited_nodes * config.selective_extension_node_share_bp as usize) / 10_000)
-            .max(1)
-    } else {
-        0
-    };
-    let mut extension_nodes_used = 0usize;
-    let mut killer_table: KillerTable = [[0u64; 2]; MAX_SMART_SEARCH_DEPTH + 2];
-    let mut history_table: HistoryTable = HistoryTable::default();
-    let mut quiescence_nodes_used = 0usize;
-
-    for candidate in root_moves {
-        if visited_nodes >= config.max_visited_nodes {
-            break;
-        }
-        visited_nodes += 1;
-        let candidate_score = MonsGameModel::evaluate_root_candidate_score(
-            &candidate,
-            perspective,
-            alpha,
-            &mut visited_nodes,
-            config,
-            &mut transposition_table,
-            &mut extension_nodes_used,
-            extension_node_budget,
-            true,
-            &mut killer_table,
-            &mut history_table,
-            &mut quiescence_nodes_used,
-        );
-        if candidate_score > alpha {
-            alpha = candidate_score;
-        }
-        scored_roots.push(root_evaluation_from_scored_root(candidate, candidate_score));
-    }
-
-    scored_roots
+    SmartRootSearch::new(
+        perspective,
+        config,
+        true,
+        root_moves,
+        scout_visited_nodes,
+        U64HashMap::default(),
+        None,
+    )
+    .without_preliminary_pass()
 }
 
-fn select_white_confirm_prov1_search_only_tiebreak_fallback_inputs(
+fn select_white_confirm_prov1_search_only_tiebreak_fallback_inputs<S: RuntimeSearches>(
     game: &MonsGame,
     config: AutomoveSearchConfig,
     frontier_inputs: &[Input],
-) -> Option<Vec<Input>> {
+    searches: &mut S,
+) -> Result<Option<Vec<Input>>, S::Pending> {
     let white_turn_three_mons2_mana_only = game.active_color == Color::White
         && game.turn_number == 3
         && game.mons_moves_count == 2
         && !game.player_can_use_action()
         && game.player_can_move_mana();
     if !white_turn_three_mons2_mana_only || frontier_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
 
     let context = crate::models::automove_exact::exact_opportunity_context(game, game.active_color);
@@ -574,14 +646,17 @@ fn select_white_confirm_prov1_search_only_tiebreak_fallback_inputs(
         || context.delta.safe_opponent_mana_progress_steps.is_some()
         || context.delta.drainer_safety < 0
     {
-        return None;
+        return Ok(None);
     }
 
     let frontier_runtime = apply_frontier_pro_v2_guarded_config(config);
-    let frontier_roots = focused_scored_roots_for_frontier_runtime(game, frontier_runtime);
-    let frontier_index = frontier_roots
+    let frontier_roots = searches.focused_scored_roots(game, frontier_runtime)?;
+    let Some(frontier_index) = frontier_roots
         .iter()
-        .position(|root| root.inputs.as_slice() == frontier_inputs)?;
+        .position(|root| root.inputs.as_slice() == frontier_inputs)
+    else {
+        return Ok(None);
+    };
     let candidate_indices = MonsGameModel::filtered_root_candidate_indices(
         game,
         frontier_roots.as_slice(),
@@ -589,7 +664,7 @@ fn select_white_confirm_prov1_search_only_tiebreak_fallback_inputs(
         frontier_runtime,
     );
     if candidate_indices.len() != 2 || !candidate_indices.contains(&frontier_index) {
-        return None;
+        return Ok(None);
     }
     let shortlist = MonsGameModel::reply_risk_guard_shortlist_indices(
         frontier_roots.as_slice(),
@@ -597,7 +672,7 @@ fn select_white_confirm_prov1_search_only_tiebreak_fallback_inputs(
         frontier_runtime,
     );
     if shortlist.len() != candidate_indices.len() || !shortlist.contains(&frontier_index) {
-        return None;
+        return Ok(None);
     }
 
     let mut search_only_runtime = frontier_runtime;
@@ -605,16 +680,19 @@ fn select_white_confirm_prov1_search_only_tiebreak_fallback_inputs(
     search_only_runtime.enable_turn_head_rerank = true;
     search_only_runtime.turn_engine_mode = TurnEngineMode::ProV1;
 
-    let search_only_inputs = select_shipping_search_inputs(game, search_only_runtime);
+    let search_only_inputs = searches.inputs(game, RuntimeSearch::Shipping(search_only_runtime))?;
     if search_only_inputs.is_empty() || search_only_inputs == frontier_inputs {
-        return None;
+        return Ok(None);
     }
 
-    let search_only_index = frontier_roots
+    let Some(search_only_index) = frontier_roots
         .iter()
-        .position(|root| root.inputs.as_slice() == search_only_inputs.as_slice())?;
+        .position(|root| root.inputs.as_slice() == search_only_inputs.as_slice())
+    else {
+        return Ok(None);
+    };
     if !candidate_indices.contains(&search_only_index) || !shortlist.contains(&search_only_index) {
-        return None;
+        return Ok(None);
     }
 
     let frontier_selected = &frontier_roots[frontier_index];
@@ -629,24 +707,25 @@ fn select_white_confirm_prov1_search_only_tiebreak_fallback_inputs(
         || !is_safe_quiet_mana_tempo_root(frontier_selected)
         || !is_safe_quiet_mana_tempo_root(search_only_selected)
     {
-        return None;
+        return Ok(None);
     }
 
-    Some(search_only_inputs)
+    Ok(Some(search_only_inputs))
 }
 
-fn select_white_confirm_prov1_better_ordered_search_only_fallback_inputs(
+fn select_white_confirm_prov1_better_ordered_search_only_fallback_inputs<S: RuntimeSearches>(
     game: &MonsGame,
     config: AutomoveSearchConfig,
     frontier_inputs: &[Input],
-) -> Option<Vec<Input>> {
+    searches: &mut S,
+) -> Result<Option<Vec<Input>>, S::Pending> {
     let white_turn_three_late_mana_only = game.active_color == Color::White
         && game.turn_number == 3
         && game.mons_moves_count >= 3
         && !game.player_can_use_action()
         && game.player_can_move_mana();
     if !white_turn_three_late_mana_only || frontier_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
 
     let context = crate::models::automove_exact::exact_opportunity_context(game, game.active_color);
@@ -659,14 +738,17 @@ fn select_white_confirm_prov1_better_ordered_search_only_fallback_inputs(
         || context.delta.safe_opponent_mana_progress_steps.is_some()
         || context.delta.drainer_safety < 0
     {
-        return None;
+        return Ok(None);
     }
 
     let frontier_runtime = apply_frontier_pro_v2_guarded_config(config);
-    let frontier_roots = focused_scored_roots_for_frontier_runtime(game, frontier_runtime);
-    let frontier_index = frontier_roots
+    let frontier_roots = searches.focused_scored_roots(game, frontier_runtime)?;
+    let Some(frontier_index) = frontier_roots
         .iter()
-        .position(|root| root.inputs.as_slice() == frontier_inputs)?;
+        .position(|root| root.inputs.as_slice() == frontier_inputs)
+    else {
+        return Ok(None);
+    };
     let candidate_indices = MonsGameModel::filtered_root_candidate_indices(
         game,
         frontier_roots.as_slice(),
@@ -674,7 +756,7 @@ fn select_white_confirm_prov1_better_ordered_search_only_fallback_inputs(
         frontier_runtime,
     );
     if !candidate_indices.contains(&frontier_index) {
-        return None;
+        return Ok(None);
     }
     let shortlist = MonsGameModel::reply_risk_guard_shortlist_indices(
         frontier_roots.as_slice(),
@@ -682,7 +764,7 @@ fn select_white_confirm_prov1_better_ordered_search_only_fallback_inputs(
         frontier_runtime,
     );
     if !shortlist.contains(&frontier_index) {
-        return None;
+        return Ok(None);
     }
 
     let mut search_only_runtime = frontier_runtime;
@@ -690,16 +772,19 @@ fn select_white_confirm_prov1_better_ordered_search_only_fallback_inputs(
     search_only_runtime.enable_turn_head_rerank = true;
     search_only_runtime.turn_engine_mode = TurnEngineMode::ProV1;
 
-    let search_only_inputs = select_shipping_search_inputs(game, search_only_runtime);
+    let search_only_inputs = searches.inputs(game, RuntimeSearch::Shipping(search_only_runtime))?;
     if search_only_inputs.is_empty() || search_only_inputs == frontier_inputs {
-        return None;
+        return Ok(None);
     }
 
-    let search_only_index = frontier_roots
+    let Some(search_only_index) = frontier_roots
         .iter()
-        .position(|root| root.inputs.as_slice() == search_only_inputs.as_slice())?;
+        .position(|root| root.inputs.as_slice() == search_only_inputs.as_slice())
+    else {
+        return Ok(None);
+    };
     if !candidate_indices.contains(&search_only_index) || !shortlist.contains(&search_only_index) {
-        return None;
+        return Ok(None);
     }
 
     let frontier_selected = &frontier_roots[frontier_index];
@@ -715,16 +800,17 @@ fn select_white_confirm_prov1_better_ordered_search_only_fallback_inputs(
         || !is_safe_quiet_mana_tempo_root(frontier_selected)
         || !is_safe_quiet_mana_tempo_root(search_only_selected)
     {
-        return None;
+        return Ok(None);
     }
 
-    Some(search_only_inputs)
+    Ok(Some(search_only_inputs))
 }
 
-fn select_late_black_search_fallback_inputs(
+fn select_late_black_search_fallback_inputs<S: RuntimeSearches>(
     game: &MonsGame,
     frontier_inputs: &[Input],
-) -> Option<Vec<Input>> {
+    searches: &mut S,
+) -> Result<Option<Vec<Input>>, S::Pending> {
     let black_turn_two_turn_start_action_mana = game.active_color == Color::Black
         && game.turn_number == 2
         && game.mons_moves_count == 0
@@ -745,11 +831,13 @@ fn select_late_black_search_fallback_inputs(
         || black_turn_four_turn_start_action_mana
     {
         let shipping_runtime = shipping_search_config_for_game(game, SmartAutomovePreference::Pro);
-        return Some(select_shipping_search_inputs(game, shipping_runtime));
+        return searches
+            .inputs(game, RuntimeSearch::Shipping(shipping_runtime))
+            .map(Some);
     }
 
     if frontier_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
 
     let black_turn_four_bridge_shipping_fallback = game.active_color == Color::Black
@@ -764,7 +852,7 @@ fn select_late_black_search_fallback_inputs(
         && game.player_can_move_mana();
     if black_turn_four_bridge_shipping_fallback || black_mid_turn_action_mana_shipping_fallback {
         let shipping_runtime = shipping_search_config_for_game(game, SmartAutomovePreference::Pro);
-        let shipping_inputs = select_shipping_search_inputs(game, shipping_runtime);
+        let shipping_inputs = searches.inputs(game, RuntimeSearch::Shipping(shipping_runtime))?;
 
         if black_turn_four_bridge_shipping_fallback
             && !shipping_inputs.is_empty()
@@ -772,100 +860,102 @@ fn select_late_black_search_fallback_inputs(
             && shipping_inputs.len() == 3
             && Input::fen_from_array(&shipping_inputs).ends_with(";mb")
         {
-            return Some(shipping_inputs);
+            return Ok(Some(shipping_inputs));
         }
 
         if black_mid_turn_action_mana_shipping_fallback
             && !shipping_inputs.is_empty()
             && shipping_inputs != frontier_inputs
         {
-            return Some(shipping_inputs);
+            return Ok(Some(shipping_inputs));
         }
     }
 
-    None
+    Ok(None)
 }
 
-fn execute_frontier_candidate_inputs_with_runtime(
-    game: &MonsGame,
-    runtime: AutomoveSearchConfig,
-) -> Vec<Input> {
-    select_search_inputs_with_fresh_frontier_cache(game, runtime)
-}
-
-fn select_frontier_pro_v2_guarded_inputs_with_runtime(
+fn select_frontier_pro_v2_guarded_inputs_with_runtime<S: RuntimeSearches>(
     game: &MonsGame,
     config: AutomoveSearchConfig,
     frontier_runtime: AutomoveSearchConfig,
-) -> Vec<Input> {
-    if let Some(inputs) = select_early_white_fallback_inputs(game, config) {
+    searches: &mut S,
+) -> Result<Vec<Input>, S::Pending> {
+    if let Some(inputs) = select_early_white_fallback_inputs(game, config, searches)? {
         #[cfg(test)]
         set_frontier_runtime_variant_branch("early_white_fallback");
-        return inputs;
+        return Ok(inputs);
     }
-    if let Some(inputs) = select_score_window_tactical_fallback_inputs(game, config) {
+    if let Some(inputs) = select_score_window_tactical_fallback_inputs(game, config, searches)? {
         #[cfg(test)]
         set_frontier_runtime_variant_branch("score_window_tactical_fallback");
-        return inputs;
+        return Ok(inputs);
     }
 
-    let frontier_inputs = execute_frontier_candidate_inputs_with_runtime(game, frontier_runtime);
-    if let Some(inputs) =
-        select_white_early_engine_disabled_fallback_inputs(game, config, frontier_inputs.as_slice())
-    {
+    let frontier_inputs = searches.inputs(game, RuntimeSearch::FreshFrontier(frontier_runtime))?;
+    if let Some(inputs) = select_white_early_engine_disabled_fallback_inputs(
+        game,
+        config,
+        frontier_inputs.as_slice(),
+        searches,
+    )? {
         #[cfg(test)]
         set_frontier_runtime_variant_branch("white_early_engine_disabled_fallback");
-        return inputs;
+        return Ok(inputs);
     }
     if let Some(inputs) = select_white_nonnegative_deny_search_only_fallback_inputs(
         game,
         config,
         frontier_inputs.as_slice(),
-    ) {
+        searches,
+    )? {
         #[cfg(test)]
         set_frontier_runtime_variant_branch("white_nonnegative_deny_search_only_fallback");
-        return inputs;
+        return Ok(inputs);
     }
     if let Some(inputs) = select_white_negative_deny_search_only_selected_rank_fallback_inputs(
         game,
         config,
         frontier_inputs.as_slice(),
-    ) {
+        searches,
+    )? {
         #[cfg(test)]
         set_frontier_runtime_variant_branch(
             "white_negative_deny_search_only_selected_rank_fallback",
         );
-        return inputs;
+        return Ok(inputs);
     }
     if let Some(inputs) = select_white_confirm_prov1_search_only_tiebreak_fallback_inputs(
         game,
         config,
         frontier_inputs.as_slice(),
-    ) {
+        searches,
+    )? {
         #[cfg(test)]
         set_frontier_runtime_variant_branch("white_confirm_prov1_search_only_tiebreak_fallback");
-        return inputs;
+        return Ok(inputs);
     }
     if let Some(inputs) = select_white_confirm_prov1_better_ordered_search_only_fallback_inputs(
         game,
         config,
         frontier_inputs.as_slice(),
-    ) {
+        searches,
+    )? {
         #[cfg(test)]
         set_frontier_runtime_variant_branch(
             "white_confirm_prov1_better_ordered_search_only_fallback",
         );
-        return inputs;
+        return Ok(inputs);
     }
-    if let Some(inputs) = select_late_black_search_fallback_inputs(game, frontier_inputs.as_slice())
+    if let Some(inputs) =
+        select_late_black_search_fallback_inputs(game, frontier_inputs.as_slice(), searches)?
     {
         #[cfg(test)]
         set_frontier_runtime_variant_branch("late_black_shipping_fallback");
-        return inputs;
+        return Ok(inputs);
     }
     #[cfg(test)]
     set_frontier_runtime_variant_branch("frontier_execute");
-    frontier_inputs
+    Ok(frontier_inputs)
 }
 
 #[cfg(test)]
@@ -874,18 +964,47 @@ pub(crate) fn select_frontier_pro_v2_guarded_inputs_with_frontier_runtime(
     config: AutomoveSearchConfig,
     frontier_runtime: AutomoveSearchConfig,
 ) -> Vec<Input> {
-    select_frontier_pro_v2_guarded_inputs_with_runtime(game, config, frontier_runtime)
+    let Ok(inputs) = select_frontier_pro_v2_guarded_inputs_with_runtime(
+        game,
+        config,
+        frontier_runtime,
+        &mut ImmediateSearches,
+    );
+    inputs
 }
 
 pub(crate) fn select_frontier_pro_v2_guarded_inputs(
     game: &MonsGame,
     config: AutomoveSearchConfig,
 ) -> Vec<Input> {
-    select_frontier_pro_v2_guarded_inputs_with_runtime(
+    let Ok(inputs) = select_public_runtime_inputs(
         game,
+        SmartAutomovePreference::Pro,
         config,
-        apply_frontier_pro_v2_guarded_config(config),
-    )
+        &mut ImmediateSearches,
+    );
+    inputs
+}
+
+/// Inputs `smartAutomove` plays for `preference`, reading search results
+/// from `searches`.
+pub(super) fn select_public_runtime_inputs<S: RuntimeSearches>(
+    game: &MonsGame,
+    preference: SmartAutomovePreference,
+    config: AutomoveSearchConfig,
+    searches: &mut S,
+) -> Result<Vec<Input>, S::Pending> {
+    match preference {
+        SmartAutomovePreference::Pro => select_frontier_pro_v2_guarded_inputs_with_runtime(
+            game,
+            config,
+            apply_frontier_pro_v2_guarded_config(config),
+            searches,
+        ),
+        SmartAutomovePreference::Fast | SmartAutomovePreference::Normal => {
+            searches.inputs(game, RuntimeSearch::Shipping(config))
+        }
+    }
 }
 
 pub(crate) fn turn_engine_config_from_search_config(
@@ -0,0 +1,398 @@
+use super::*;
+use automove_runtime_variants::{RuntimeSearch, RuntimeSearches};
+
+/// Smart automove search that runs a slice at a time, so a UI thread can
+/// render between slices and drop the search when the position changes.
+///
+/// The handle runs the same searches as `smartAutomove` for its preference,
+/// fallbacks and Pro's turn planner included, and settles on the same move.
+/// Besides searches of single root moves, a slice runs at most one stage of
+/// those searches: the root move ranking, the turn planner's head plan, the
+/// prepasses and root advisor, the root scout, the final choice among the
+/// scored roots, or a run of the selector itself.
+#[wasm_bindgen]
+pub struct SearchHandle {
+    selection: Option<Box<SteppedSelection>>,
+    output: Option<OutputModel>,
+}
+
+#[wasm_bindgen]
+impl SearchHandle {
+    /// Searches root moves until at least `max_nodes` more nodes were
+    /// visited, stopping early after any other stage, and always making
+    /// some progress. Returns whether the search is over, either finished or
+    /// cancelled.
+    pub fn step(&mut self, max_nodes: usize) -> bool {
+        let Some(selection) = self.selection.as_mut() else {
+            return true;
+        };
+        let stop_at = selection.visited_nodes().saturating_add(max_nodes.max(1));
+        loop {
+            match selection.advance() {
+                SelectionProgress::Chose(inputs) => {
+                    self.output = Some(MonsGameModel::runtime_output_for_game(
+                        &selection.game,
+                        inputs,
+                    ));
+                    self.selection = None;
+                    return true;
+                }
+                SelectionProgress::ScoredRoot if selection.visited_nodes() < stop_at => {}
+                SelectionProgress::ScoredRoot | SelectionProgress::RanStage => return false,
+            }
+        }
+    }
+
+    /// Move the search would play if stopped now: the final choice once it
+    /// finished, otherwise the best root move of the running search, or the
+    /// choice of the last search that finished. Nothing before the first
+    /// root move was scored or after a cancel.
+    #[wasm_bindgen(js_name = bestSoFar)]
+    pub fn best_so_far(&self) -> Option<OutputModel> {
+        if let Some(output) = &self.output {
+            return Some(output.clone());
+        }
+        let selection = self.selection.as_ref()?;
+        let inputs = selection.best_inputs_so_far()?;
+        Some(MonsGameModel::runtime_output_for_game(
+            &selection.game,
+            inputs,
+        ))
+    }
+
+    /// Stops the search and frees its tables.
+    pub fn cancel(&mut self) {
+        self.selection = None;
+        self.output = None;
+    }
+
+    #[wasm_bindgen(js_name = isFinished)]
+    pub fn is_finished(&self) -> bool {
+        self.output.is_some()
+    }
+
+    #[wasm_bindgen(js_name = isCancelled)]
+    pub fn is_cancelled(&self) -> bool {
+        self.selection.is_none() && self.output.is_none()
+    }
+
+    /// Nodes visited so far, for progress reporting.
+    #[wasm_bindgen(js_name = visitedNodes)]
+    pub fn visited_nodes(&self) -> usize {
+        self.selection
+            .as_ref()
+            .map(|selection| selection.visited_nodes())
+            .unwrap_or_default()
+    }
+}
+
+/// Result of a search the runtime selector asked for.
+enum RuntimeSearchResult {
+    Inputs(Vec<Input>),
+    FocusedScoredRoots(Vec<RootEvaluation>),
+}
+
+/// Search the runtime selector stopped on, as its result is not in yet.
+enum PendingRuntimeSearch {
+    Inputs(RuntimeSearch),
+    FocusedScoredRoots(AutomoveSearchConfig),
+}
+
+enum SelectionProgress {
+    /// Searched a root move, or the shallow pass of one.
+    ScoredRoot,
+    RanStage,
+    Chose(Vec<Input>),
+}
+
+enum RunningSearch {
+    Inputs(SmartSearchStage),
+    FocusedScoredRoots(Box<SmartRootSearch>),
+}
+
+/// Runtime selector of a preference, run by replaying it from the start
+/// with the results of the searches it asked for so far, and running the
+/// first search it lacks a stage at a time.
+struct SteppedSelection {
+    game: MonsGame,
+    preference: SmartAutomovePreference,
+    config: AutomoveSearchConfig,
+    results: Vec<RuntimeSearchResult>,
+    running: Option<RunningSearch>,
+    finished_nodes: usize,
+    last_inputs: Option<Vec<Input>>,
+}
+
+impl SteppedSelection {
+    fn advance(&mut self) -> SelectionProgress {
+        let Some(running) = self.running.take() else {
+            let mut searches = ReplayedSearches {
+                results: self.results.as_slice(),
+                next: 0,
+            };
+            match automove_runtime_variants::select_public_runtime_inputs(
+                &self.game,
+                self.preference,
+                self.config,
+                &mut searches,
+            ) {
+                Ok(inputs) => return SelectionProgress::Chose(inputs),
+                Err(pending) => self.running = Some(self.start(pending)),
+            }
+            return SelectionProgress::RanStage;
+        };
+        match running {
+            RunningSearch::Inputs(stage) => {
+                let scoring = matches!(stage, SmartSearchStage::Scoring(_));
+                let nodes = stage_visited_nodes(&stage);
+                match MonsGameModel::advance_smart_search(&self.game, stage) {
+                    SmartSearchStage::Finished(inputs) => {
+                        let inputs = automove_runtime_variants::shipping_inputs_or_automove(
+                            &self.game, inputs,
+                        );
+                        self.finished_nodes += nodes;
+                        self.last_inputs = Some(inputs.clone());
+                        self.results.push(RuntimeSearchResult::Inputs(inputs));
+                        SelectionProgress::RanStage
+                    }
+                    stage => {
+                        let scored_root = scoring && matches!(stage, SmartSearchStage::Scoring(_));
+                        self.running = Some(RunningSearch::Inputs(stage));
+                        if scored_root {
+                            SelectionProgress::ScoredRoot
+                        } else {
+                            SelectionProgress::RanStage
+                        }
+                    }
+                }
+            }
+            RunningSearch::FocusedScoredRoots(mut search) => {
+                if search.score_next_root() {
+                    self.running = Some(RunningSearch::FocusedScoredRoots(search));
+                    SelectionProgress::ScoredRoot
+                } else {
+                    self.finished_nodes += search.visited_nodes;
+                    self.results
+                        .push(RuntimeSearchResult::FocusedScoredRoots(search.scored_roots));
+                    SelectionProgress::RanStage
+                }
+            }
+        }
+    }
+
+    fn start(&self, pending: PendingRuntimeSearch) -> RunningSearch {
+        match pending {
+            PendingRuntimeSearch::Inputs(search) => {
+                let config = match search {
+                    RuntimeSearch::Shipping(config) => config,
+                    RuntimeSearch::FreshFrontier(config) => {
+                        automove_runtime_variants::clear_frontier_search_caches(config);
+                        config
+                    }
+                };
+                RunningSearch::Inputs(SmartSearchStage::Start(Box::new(config)))
+            }
+            PendingRuntimeSearch::FocusedScoredRoots(config) => {
+                RunningSearch::FocusedScoredRoots(Box::new(
+                    automove_runtime_variants::focused_root_search(&self.game, config),
+                ))
+            }
+        }
+    }
+
+    fn visited_nodes(&self) -> usize {
+        let running_nodes = match &self.running {
+            Some(RunningSearch::Inputs(stage)) => stage_visited_nodes(stage),
+            Some(RunningSearch::FocusedScoredRoots(search)) => search.visited_nodes,
+            None => 0,
+        };
+        self.finished_nodes + running_nodes
+    }
+
+    fn best_inputs_so_far(&self) -> Option<Vec<Input>> {
+        match &self.running {
+            Some(RunningSearch::Inputs(SmartSearchStage::Scoring(search))) => {
+                search.best_inputs_so_far(&self.game)
+            }
+            _ => None,
+        }
+        .or_else(|| self.last_inputs.clone())
+    }
+}
+
+fn stage_visited_nodes(stage: &SmartSearchStage) -> usize {
+    match stage {
+        SmartSearchStage::Scoring(search) => search.visited_nodes,
+        _ => 0,
+    }
+}
+
+/// Hands the runtime selector the results of the searches it ran in earlier
+/// replays, in the order it asked for them.
+struct ReplayedSearches<'a> {
+    results: &'a [RuntimeSearchResult],
+    next: usize,
+}
+
+impl ReplayedSearches<'_> {
+    fn next_result(&mut self) -> Option<&RuntimeSearchResult> {
+        let result = self.results.get(self.next)?;
+        self.next += 1;
+        Some(result)
+    }
+}
+
+impl RuntimeSearches for ReplayedSearches<'_> {
+    type Pending = PendingRuntimeSearch;
+
+    fn inputs(
+        &mut self,
+        _game: &MonsGame,
+        search: RuntimeSearch,
+    ) -> Result<Vec<Input>, Self::Pending> {
+        match self.next_result() {
+            Some(RuntimeSearchResult::Inputs(inputs)) => Ok(inputs.clone()),
+            _ => Err(PendingRuntimeSearch::Inputs(search)),
+        }
+    }
+
+    fn focused_scored_roots(
+        &mut self,
+        _game: &MonsGame,
+        config: AutomoveSearchConfig,
+    ) -> Result<Vec<RootEvaluation>, Self::Pending> {
+        match self.next_result() {
+            Some(RuntimeSearchResult::FocusedScoredRoots(roots)) => Ok(roots.clone()),
+            _ => Err(PendingRuntimeSearch::FocusedScoredRoots(config)),
+        }
+    }
+}
+
+impl MonsGameModel {
+    pub(crate) fn begin_search_handle(&self, preference: SmartAutomovePreference) -> SearchHandle {
+        clear_exact_state_analysis_cache();
+        SearchHandle {
+            selection: Some(Box::new(SteppedSelection {
+                game: self.game.clone_for_simulation(),
+                preference,
+                config: self.shipping_search_config_for_preference(preference),
+                results: Vec::new(),
+                running: None,
+                finished_nodes: 0,
+                last_inputs: None,
+            })),
+            output: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_handle_steps_to_a_legal_move_for_every_preference() {
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        for preference in [
+            SmartAutomovePreference::Fast,
+            SmartAutomovePreference::Normal,
+            SmartAutomovePreference::Pro,
+        ] {
+            let mut handle = model.begin_search_native(preference).unwrap();
+            assert_eq!(handle.best_so_far(), None);
+            let mut steps = 0;
+            let mut saw_partial_result = false;
+            while !handle.step(64) {
+                steps += 1;
+                saw_partial_result |= handle.best_so_far().is_some();
+            }
+            assert!(steps > 1, "{}", preference.as_api_value());
+            assert!(saw_partial_result);
+            assert!(handle.is_finished());
+            let output = handle.best_so_far().unwrap();
+            assert_eq!(output.kind, OutputModelKind::Events);
+            assert!(
+                output.game_move().is_some(),
+                "{}",
+                preference.as_api_value()
+            );
+            assert_eq!(
+                output.input_fen(),
+                model.smart_automove_native(preference).unwrap().input_fen(),
+                "{}",
+                preference.as_api_value()
+            );
+            assert!(handle.step(64));
+        }
+    }
+
+    #[test]
+    fn pro_search_handle_plays_the_turn_planner_choice() {
+        // Pro's root search alone picks another move here.
+        let model = MonsGameModel::from_fen(
+            "0 0 w 0 0 1 0 0 3 n03y0xn03e0xn03/n05s0xa0xn01d0mn02/n11/n04xxmn02xxmn03/n03xxmn01xxmn05/xxQn04xxUn04xxQ/n03xxMn01xxMn01xxMn03/n04xxMn01xxMn04/n01E0xn06Y0xn02/n04D0xn01S0xn04/n04A0xn06",
+        )
+        .unwrap();
+        let mut handle = model
+            .begin_search_native(SmartAutomovePreference::Pro)
+            .unwrap();
+        while !handle.step(256) {}
+        assert_eq!(
+            handle.best_so_far().unwrap().input_fen(),
+            model
+                .smart_automove_native(SmartAutomovePreference::Pro)
+                .unwrap()
+                .input_fen()
+        );
+    }
+
+    #[test]
+    fn pro_search_handle_steps_are_a_small_share_of_the_search() {
+        for fen in [
+            "0 0 w 0 0 0 0 0 1 n03y0xs0xd0xa0xe0xn03/n11/n11/n04xxmn01xxmn04/n03xxmn01xxmn01xxmn03/xxQn04xxUn04xxQ/n03xxMn01xxMn01xxMn03/n04xxMn01xxMn04/n11/n11/n03E0xA0xD0xS0xY0xn03",
+            "0 0 w 0 0 3 0 0 13 n03y0xs0xd0xn01e0xn03/n04xxmn02a0xn03/n11/n06xxmn01xxmn02/n03xxmn01xxmn05/xxQn04xxUn04xxQ/n05xxMxxMxxMn03/n02xxMn03xxMn04/n03D0xn04A0xn02/n06Y0xn04/n03E0xn02S0xn04",
+        ] {
+            let model = MonsGameModel::from_fen(fen).unwrap();
+            let mut handle = model
+                .begin_search_native(SmartAutomovePreference::Pro)
+                .unwrap();
+            let mut slowest_step = std::time::Duration::ZERO;
+            let mut total = std::time::Duration::ZERO;
+            loop {
+                let started = std::time::Instant::now();
+                let finished = handle.step(1);
+                let elapsed = started.elapsed();
+                slowest_step = slowest_step.max(elapsed);
+                total += elapsed;
+                if finished {
+                    break;
+                }
+            }
+            assert!(
+                slowest_step * 4 < total,
+                "{fen}: slowest step {slowest_step:?} of {total:?}"
+            );
+            assert_eq!(
+                handle.best_so_far().unwrap().input_fen(),
+                model
+                    .smart_automove_native(SmartAutomovePreference::Pro)
+                    .unwrap()
+                    .input_fen()
+            );
+        }
+    }
+
+    #[test]
+    fn cancelled_search_handle_stops_and_forgets_its_result() {
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        let mut handle = model
+            .begin_search_native(SmartAutomovePreference::Normal)
+            .unwrap();
+        handle.step(1);
+        handle.cancel();
+        assert!(handle.is_cancelled());
+        assert!(handle.step(1_000));
+        assert_eq!(handle.best_so_far(), None);
+        assert_eq!(handle.visited_nodes(), 0);
+    }
+}
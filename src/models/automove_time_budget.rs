@@ -0,0 +1,264 @@
+use super::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Search nodes between two reads of the injected clock, so a clock that is
+/// expensive to call, such as one crossing into JS, stays off the hot path.
+const SEARCH_DEADLINE_CHECK_INTERVAL: u32 = 16;
+/// A deeper level only starts when the time left covers this many times the
+/// duration of the level before it.
+const TIMED_AUTOMOVE_LEVEL_GROWTH: f64 = 3.0;
+/// Node budget multiplier of each level searched past Pro.
+const TIMED_AUTOMOVE_DEEPENING_NODE_GROWTH: usize = 2;
+/// Levels of the timed search, shallowest first. Each one is the shipping
+/// search of its preference, so a level that completes plays exactly like
+/// `smart_automove` would.
+const TIMED_AUTOMOVE_LEVELS: [SmartAutomovePreference; 3] = [
+    SmartAutomovePreference::Fast,
+    SmartAutomovePreference::Normal,
+    SmartAutomovePreference::Pro,
+];
+
+pub(crate) type SearchClock = Rc<dyn Fn() -> f64>;
+
+struct SearchDeadline {
+    clock: SearchClock,
+    deadline_ms: f64,
+    checks: Cell<u32>,
+    expired: Cell<bool>,
+}
+
+thread_local! {
+    static SEARCH_DEADLINE: RefCell<Option<SearchDeadline>> = const { RefCell::new(None) };
+}
+
+/// Whether the deadline of the timed search running on this thread has
+/// passed. Always false outside of a timed search.
+#[inline]
+pub(crate) fn search_deadline_reached() -> bool {
+    SEARCH_DEADLINE.with(|deadline| {
+        let deadline = deadline.borrow();
+        let Some(deadline) = deadline.as_ref() else {
+            return false;
+        };
+        if deadline.expired.get() {
+            return true;
+        }
+        let checks = deadline.checks.get().wrapping_add(1);
+        deadline.checks.set(checks);
+        if checks % SEARCH_DEADLINE_CHECK_INTERVAL == 0
+            && (deadline.clock)() >= deadline.deadline_ms
+        {
+            deadline.expired.set(true);
+        }
+        deadline.expired.get()
+    })
+}
+
+/// Whether a search cut short by the deadline ran since the guard was installed.
+pub(crate) fn search_deadline_cut_search() -> bool {
+    SEARCH_DEADLINE.with(|deadline| {
+        deadline
+            .borrow()
+            .as_ref()
+            .is_some_and(|deadline| deadline.expired.get())
+    })
+}
+
+struct SearchDeadlineGuard;
+
+impl SearchDeadlineGuard {
+    fn install(clock: SearchClock, deadline_ms: f64) -> Self {
+        SEARCH_DEADLINE.with(|deadline| {
+            *deadline.borrow_mut() = Some(SearchDeadline {
+                clock,
+                deadline_ms,
+                checks: Cell::new(0),
+                expired: Cell::new(false),
+            });
+        });
+        SearchDeadlineGuard
+    }
+}
+
+impl Drop for SearchDeadlineGuard {
+    fn drop(&mut self) {
+        SEARCH_DEADLINE.with(|deadline| *deadline.borrow_mut() = None);
+    }
+}
+
+/// Outcome of a timed search: the inputs to play and the deepest level that
+/// ran to completion, if any did.
+pub(crate) struct TimedSearchResult {
+    pub(crate) inputs: Vec<Input>,
+    pub(crate) completed_level: Option<SmartAutomovePreference>,
+    /// Levels past Pro that ran to completion.
+    pub(crate) deepened_levels: usize,
+}
+
+impl MonsGameModel {
+    /// Iterative deepening over the shipping levels until `budget_ms` runs
+    /// out on `clock`. Once Pro finished, further Pro searches follow while
+    /// time is left, each one ply deeper with a larger node budget, up to the
+    /// limits of the search. The inputs come from the deepest level that
+    /// finished; when none did, from the first level as far as it got, and
+    /// when that found nothing the caller falls back to a random legal move.
+    pub(crate) fn timed_search_inputs(
+        &self,
+        budget_ms: f64,
+        clock: SearchClock,
+    ) -> TimedSearchResult {
+        let deadline_ms = clock() + budget_ms.max(0.0);
+        let _guard = SearchDeadlineGuard::install(clock.clone(), deadline_ms);
+        let mut result = TimedSearchResult {
+            inputs: Vec::new(),
+            completed_level: None,
+            deepened_levels: 0,
+        };
+        let pro_config = self.shipping_search_config_for_preference(SmartAutomovePreference::Pro);
+        let deeper_levels =
+            std::iter::successors(Self::deepened_search_config(pro_config), |config| {
+                Self::deepened_search_config(*config)
+            })
+            .map(|config| (SmartAutomovePreference::Pro, config, true));
+        let levels = TIMED_AUTOMOVE_LEVELS
+            .into_iter()
+            .map(|preference| {
+                (
+                    preference,
+                    self.shipping_search_config_for_preference(preference),
+                    false,
+                )
+            })
+            .chain(deeper_levels);
+        let mut last_level_ms = 0.0;
+        for (preference, config, deepened) in levels {
+            let level_start = clock();
+            if result.completed_level.is_some()
+                && level_start + last_level_ms * TIMED_AUTOMOVE_LEVEL_GROWTH > deadline_ms
+            {
+                break;
+            }
+            let inputs = self.public_runtime_inputs(preference, config);
+            last_level_ms = clock() - level_start;
+            if search_deadline_cut_search() {
+                if result.inputs.is_empty() {
+                    result.inputs = inputs;
+                }
+                break;
+            }
+            if inputs.is_empty() {
+                continue;
+            }
+            result.inputs = inputs;
+            result.completed_level = Some(preference);
+            result.deepened_levels += usize::from(deepened);
+        }
+        result
+    }
+
+    /// `config` one ply deeper with a larger node budget, within the limits
+    /// of the search. `None` once both are at their limits.
+    fn deepened_search_config(config: AutomoveSearchConfig) -> Option<AutomoveSearchConfig> {
+        let mut deeper = config;
+        deeper.depth = (config.depth + 1).min(MAX_SMART_SEARCH_DEPTH);
+        deeper.max_visited_nodes = config
+            .max_visited_nodes
+            .saturating_mul(TIMED_AUTOMOVE_DEEPENING_NODE_GROWTH)
+            .min(MAX_SMART_MAX_VISITED_NODES);
+        (deeper.depth > config.depth || deeper.max_visited_nodes > config.max_visited_nodes)
+            .then_some(deeper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frozen_clock() -> SearchClock {
+        Rc::new(|| 0.0)
+    }
+
+    /// Advances by `step_ms` on every read.
+    fn ticking_clock(step_ms: f64) -> SearchClock {
+        let now = Cell::new(0.0);
+        Rc::new(move || {
+            now.set(now.get() + step_ms);
+            now.get()
+        })
+    }
+
+    #[test]
+    fn timed_search_deepens_past_pro_when_time_allows() {
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        let result = model.timed_search_inputs(1_000.0, frozen_clock());
+        assert_eq!(result.completed_level, Some(SmartAutomovePreference::Pro));
+
+        let pro = model.shipping_search_config_for_preference(SmartAutomovePreference::Pro);
+        let deeper_levels = std::iter::successors(Some(pro), |config| {
+            MonsGameModel::deepened_search_config(*config)
+        })
+        .collect::<Vec<_>>();
+        assert!(deeper_levels.len() > 1);
+        assert_eq!(result.deepened_levels, deeper_levels.len() - 1);
+        let deepest = deeper_levels.last().unwrap();
+        assert_eq!(deepest.depth, MAX_SMART_SEARCH_DEPTH);
+        assert_eq!(deepest.max_visited_nodes, MAX_SMART_MAX_VISITED_NODES);
+
+        let mut game = model.game.clone_for_simulation();
+        assert!(matches!(
+            game.process_input_slice(&result.inputs, false, false),
+            Output::Events(_)
+        ));
+    }
+
+    #[test]
+    fn root_scoring_and_turn_planning_stop_at_the_deadline() {
+        let game = MonsGame::new(false, GameVariant::Classic);
+        let perspective = game.active_color;
+        let config = automove_runtime_variants::apply_frontier_pro_v2_guarded_config(
+            MonsGameModel::shipping_search_config_for_game(&game, SmartAutomovePreference::Pro),
+        );
+        let engine_config = MonsGameModel::turn_engine_config_for_game(&game, config);
+        let all_roots = MonsGameModel::ranked_root_moves(&game, perspective, config).len();
+        clear_turn_engine_plan_cache();
+        let full_plan = turn_engine_candidate_plan(&game, perspective, engine_config)
+            .map(|plan| plan.compiled_chunks);
+        clear_turn_engine_plan_cache();
+
+        {
+            let _guard = SearchDeadlineGuard::install(frozen_clock(), -1.0);
+            while !search_deadline_reached() {}
+            let roots = MonsGameModel::ranked_root_moves(&game, perspective, config).len();
+            assert_eq!(roots, 1);
+            assert!(all_roots > 1);
+            let _ = turn_engine_candidate_plan(&game, perspective, engine_config);
+            assert!(search_deadline_cut_search());
+        }
+        assert_eq!(
+            turn_engine_candidate_plan(&game, perspective, engine_config)
+                .map(|plan| plan.compiled_chunks),
+            full_plan
+        );
+    }
+
+    #[test]
+    fn timed_search_falls_back_when_the_deadline_hits() {
+        let model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        let result = model.timed_search_inputs(0.0, ticking_clock(1.0));
+        assert_eq!(result.completed_level, None);
+        assert!(!search_deadline_reached());
+
+        let output = model.smart_automove_within(0.0, || 0.0).unwrap();
+        assert_eq!(output.kind, OutputModelKind::Events);
+        let mut game = model.game.clone_for_simulation();
+        assert!(matches!(
+            game.process_input_slice(
+                &Input::array_from_fen(output.input_fen().as_str()),
+                false,
+                false
+            ),
+            Output::Events(_)
+        ));
+    }
+}
@@ -1,12 +1,13 @@
-#![cfg(any(target_arch = "wasm32", test))]
-
+use crate::models::mons_game_model::automove_time_budget::{
+    search_deadline_cut_search, search_deadline_reached,
+};
 #[cfg(test)]
 use crate::models::scoring::DEFAULT_SCORING_WEIGHTS;
 use crate::models::scoring::{
     evaluate_preferability_with_weights_and_exact_policy, ScoringWeights,
 };
 use crate::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
@@ -301,6 +302,12 @@ pub(crate) struct TurnPackageMeta {
 pub(crate) struct TurnEngineDiagnostics {
     pub cache_hits: usize,
     pub cache_misses: usize,
+    /// Ponder totals since the thread started, filled in by the snapshot
+    /// rather than reset with the rest.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub ponder_hits: usize,
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub ponder_misses: usize,
     pub seed_immediate_score: usize,
     pub seed_deny_window: usize,
     pub seed_drainer_kill: usize,
@@ -565,9 +572,11 @@ thread_local! {
         RefCell::new(HashSet::new());
     static TURN_ENGINE_DIAGNOSTICS: RefCell<TurnEngineDiagnostics> =
         RefCell::new(TurnEngineDiagnostics::default());
+    /// Ponder hits and misses since the thread started. Kept apart from the
+    /// diagnostics, which every search clears.
+    static TURN_ENGINE_PONDER_OUTCOMES: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn clear_turn_engine_plan_cache() {
     TURN_ENGINE_CONTINUATION_CACHE.with(|cache| cache.borrow_mut().clear());
     TURN_ENGINE_ELIGIBILITY_CACHE.with(|cache| cache.borrow_mut().clear());
@@ -577,7 +586,6 @@ pub(crate) fn clear_turn_engine_plan_cache() {
     TURN_ENGINE_NO_PLAN_CACHE.with(|cache| cache.borrow_mut().clear());
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) fn clear_turn_engine_diagnostics() {
     TURN_ENGINE_DIAGNOSTICS.with(|diagnostics| {
         *diagnostics.borrow_mut() = TurnEngineDiagnostics::default();
@@ -586,13 +594,26 @@ pub(crate) fn clear_turn_engine_diagnostics() {
 
 #[cfg(test)]
 pub(crate) fn turn_engine_diagnostics_snapshot() -> TurnEngineDiagnostics {
-    TURN_ENGINE_DIAGNOSTICS.with(|diagnostics| *diagnostics.borrow())
+    let mut snapshot = TURN_ENGINE_DIAGNOSTICS.with(|diagnostics| *diagnostics.borrow());
+    (snapshot.ponder_hits, snapshot.ponder_misses) = TURN_ENGINE_PONDER_OUTCOMES.with(Cell::get);
+    snapshot
 }
 
 fn update_turn_engine_diagnostics(update: impl FnOnce(&mut TurnEngineDiagnostics)) {
     TURN_ENGINE_DIAGNOSTICS.with(|diagnostics| update(&mut diagnostics.borrow_mut()));
 }
 
+pub(crate) fn record_ponder_outcome(hit: bool) {
+    TURN_ENGINE_PONDER_OUTCOMES.with(|outcomes| {
+        let (hits, misses) = outcomes.get();
+        outcomes.set(if hit {
+            (hits + 1, misses)
+        } else {
+            (hits, misses + 1)
+        });
+    });
+}
+
 fn record_accepted_plan_family(family: TurnPlanFamily) {
     update_turn_engine_diagnostics(|diagnostics| match family {
         TurnPlanFamily::ImmediateScore => diagnostics.accepted_immediate_score += 1,
@@ -644,7 +665,7 @@ impl TurnSnapshot {
             .occupied()
             .map(|(location, item)| (location, *item))
             .collect::<Vec<_>>();
-        occupied.sort_by(|a, b| a.0.cmp(&b.0));
+        occupied.sort_by_key(|entry| entry.0);
         Self {
             state_hash: MonsGameModel::search_state_hash(game),
             active_color: game.active_color,
@@ -817,7 +838,12 @@ pub(crate) fn turn_engine_candidate_plan(
     if let Some(plan) = cached_best_plan_if_legal(game, no_plan_key) {
         return Some(plan);
     }
-    match build_best_plan(game, perspective, config) {
+    let plan = build_best_plan(game, perspective, config);
+    // A plan cut short by the search deadline only stands for this search.
+    if search_deadline_cut_search() {
+        return plan.ok().flatten();
+    }
+    match plan {
         Ok(Some(plan)) => {
             TURN_ENGINE_BEST_PLAN_CACHE.with(|cache| {
                 let mut cache = cache.borrow_mut();
@@ -1129,6 +1155,9 @@ fn build_best_plan_v1(
 
     let mut best_plan: Option<TurnPlan> = None;
     for mut plan in plans {
+        if best_plan.is_some() && search_deadline_reached() {
+            break;
+        }
         plan.utility = evaluate_plan_with_replies(game, &plan, perspective, config);
         let replace = best_plan
             .as_ref()
@@ -1161,6 +1190,9 @@ fn best_plan_from_allowed_heads(
     let mut best_plan: Option<(TurnPlan, AllowedHeadSelectionMeta)> = None;
 
     for mut plan in plans {
+        if best_plan.is_some() && search_deadline_reached() {
+            break;
+        }
         let Some(rank) = plan_allowed_head_rank(&plan, &allowed_rank) else {
             continue;
         };
@@ -1292,7 +1324,7 @@ fn fallback_single_action_plan_from_allowed_heads(
             rank,
             allowed_len,
             first_step_opponent_immediate_loss: opponent_can_win_immediately(&after, perspective),
-            first_step_drainer_safety: own_drainer_safety_score(&after.board, perspective),
+            first_step_drainer_safety: own_drainer_safety_score(&after, perspective),
         };
         let replace = best_plan.as_ref().is_none_or(|(current, current_meta)| {
             compare_allowed_head_plans(&plan, meta, current, *current_meta) == Ordering::Greater
@@ -1354,7 +1386,7 @@ fn allowed_head_selection_meta(
             .as_ref()
             .is_some_and(|game| opponent_can_win_immediately(game, perspective)),
         first_step_drainer_safety: after.as_ref().map_or(i32::MIN / 4, |game| {
-            own_drainer_safety_score(&game.board, perspective)
+            own_drainer_safety_score(game, perspective)
         }),
     }
 }
@@ -1615,6 +1647,9 @@ fn build_best_opportunity_plan(
 
     let mut best_plan: Option<TurnPlan> = None;
     for mut plan in plans {
+        if best_plan.is_some() && search_deadline_reached() {
+            break;
+        }
         plan.utility = evaluate_plan_with_replies(game, &plan, perspective, config);
         let replace = best_plan
             .as_ref()
@@ -1669,7 +1704,7 @@ fn generate_opportunity_plans(
             continue;
         };
         expansions += 1;
-        if expansions > expansion_cap {
+        if expansions > expansion_cap || search_deadline_reached() {
             budget_exhausted = true;
             break;
         }
@@ -1765,7 +1800,7 @@ fn generate_opportunity_plans(
                     continue;
                 };
                 expansions += 1;
-                if expansions > expansion_cap {
+                if expansions > expansion_cap || search_deadline_reached() {
                     terminal.push(node.clone());
                     budget_exhausted = true;
                     stop_expansion = true;
@@ -2137,7 +2172,7 @@ fn build_macro_from_head_opportunity(
         let current_utility = evaluate_state_utility(&current, root, perspective, config);
         let risky_temporary_state = current_oracle.opportunity.delta.drainer_safety < 0
             || current_utility.drainer_safety < 0
-            || own_drainer_safety_score(&current.board, perspective) < 0;
+            || own_drainer_safety_score(&current, perspective) < 0;
         let mut best_followup: Option<(
             i32,
             TurnOpportunity,
@@ -2358,7 +2393,7 @@ fn generate_macro_plans(
     let mut seen = HashMap::<(u64, u64), i64>::new();
     for opportunity in opportunities {
         expansions += 1;
-        if expansions > expansion_cap {
+        if expansions > expansion_cap || search_deadline_reached() {
             budget_exhausted = true;
             break;
         }
@@ -2441,7 +2476,7 @@ fn generate_macro_plans(
             let mut node_expanded = false;
             for opportunity in opportunities {
                 expansions += 1;
-                if expansions > expansion_cap {
+                if expansions > expansion_cap || search_deadline_reached() {
                     terminal.push(node.clone());
                     budget_exhausted = true;
                     stop_expansion = true;
@@ -2572,7 +2607,7 @@ fn generate_turn_plans(
             continue;
         };
         expansions += 1;
-        if expansions > expansion_cap {
+        if expansions > expansion_cap || search_deadline_reached() {
             return Err(PlanBuildStatus::BudgetExceeded);
         }
         let order = quick_order_score(game, &after, perspective, seed.family, 1, config);
@@ -2646,7 +2681,7 @@ fn generate_turn_plans(
                     continue;
                 };
                 expansions += 1;
-                if expansions > expansion_cap {
+                if expansions > expansion_cap || search_deadline_reached() {
                     return Err(PlanBuildStatus::BudgetExceeded);
                 }
                 let mut actions = node.actions.clone();
@@ -2930,21 +2965,18 @@ fn evaluate_state_utility_uncached(
         .unwrap_or(0);
     let immediate_bonus = strategic.immediate_window.best_score.saturating_mul(110)
         + strategic.immediate_window.multi_pressure.saturating_mul(18);
-    let safe_supermana_bonus =
-        if own_drainer_carries_safe_mana(&game.board, perspective, Mana::Supermana) {
-            380
-        } else {
-            0
-        };
-    let safe_opponent_mana_bonus = if own_drainer_carries_safe_mana(
-        &game.board,
-        perspective,
-        Mana::Regular(perspective.other()),
-    ) {
-        300
+    let safe_supermana_bonus = if own_drainer_carries_safe_mana(game, perspective, Mana::Supermana)
+    {
+        380
     } else {
         0
     };
+    let safe_opponent_mana_bonus =
+        if own_drainer_carries_safe_mana(game, perspective, Mana::Regular(perspective.other())) {
+            300
+        } else {
+            0
+        };
     let opponent = perspective.other();
     let opponent_window_before = active_turn_score_window(start, opponent);
     let opponent_window_after = if game.active_color == opponent {
@@ -2953,16 +2985,20 @@ fn evaluate_state_utility_uncached(
         oracle.opponent_immediate_window
     };
     let deny_gain = opponent_window_before.saturating_sub(opponent_window_after);
-    let drainer_safety = own_drainer_safety_score(&game.board, perspective);
+    let drainer_safety = own_drainer_safety_score(game, perspective);
     let unsafe_progress_penalty = if drainer_safety < 0 {
         drainer_safety.saturating_abs().saturating_mul(900)
     } else {
         0
     };
-    let opponent_needed_before =
-        Config::TARGET_SCORE.saturating_sub(score_for_color(start, opponent));
-    let opponent_needed_after =
-        Config::TARGET_SCORE.saturating_sub(score_for_color(game, opponent));
+    let opponent_needed_before = start
+        .rules()
+        .target_score
+        .saturating_sub(score_for_color(start, opponent));
+    let opponent_needed_after = game
+        .rules()
+        .target_score
+        .saturating_sub(score_for_color(game, opponent));
     let denied_immediate_window = opponent_needed_before > 0
         && opponent_window_before >= opponent_needed_before
         && (opponent_needed_after <= 0 || opponent_window_after < opponent_needed_after);
@@ -3153,8 +3189,8 @@ fn macro_opportunity_delta(
             .saturating_sub(end_oracle.opponent_immediate_window)
             .max(0),
         drainer_attack: end_oracle.opportunity.delta.drainer_attack_available,
-        drainer_safety_delta: own_drainer_safety_score(&end_game.board, perspective)
-            .saturating_sub(own_drainer_safety_score(&game.board, perspective)),
+        drainer_safety_delta: own_drainer_safety_score(end_game, perspective)
+            .saturating_sub(own_drainer_safety_score(game, perspective)),
         supermana_progress_gain: progress_step_gain(
             start_oracle.opportunity.delta.safe_supermana_progress_steps,
             end_oracle.opportunity.delta.safe_supermana_progress_steps,
@@ -3686,7 +3722,7 @@ fn deny_window_seeds(game: &MonsGame, perspective: Color) -> Vec<ActionSeed> {
     );
     if let Some(drainer) = find_awake_drainer_location(&game.board, perspective) {
         for &next in drainer.nearby_locations_ref() {
-            let before_safety = own_drainer_safety_score(&game.board, perspective);
+            let before_safety = own_drainer_safety_score(game, perspective);
             let before_dist = distance_to_nearest_pool(drainer, perspective);
             let after_dist = distance_to_nearest_pool(next, perspective);
             if after_dist > before_dist.saturating_add(1) && before_safety >= 0 {
@@ -3917,7 +3953,7 @@ fn safe_progress_seeds(
     let mut seeds = Vec::new();
     let before_state_hash = MonsGameModel::search_state_hash(game);
     let before_exact = safe_progress_exact_snapshot(game, perspective, wanted, before_state_hash);
-    let before_safety = own_drainer_safety_score(&game.board, perspective);
+    let before_safety = own_drainer_safety_score(game, perspective);
     if let Some(path) = exact_secure_specific_mana_path_from(game, perspective, drainer, wanted) {
         if let Some(step) = path.first().copied() {
             seeds.push(ActionSeed {
@@ -3960,7 +3996,7 @@ fn safe_progress_seeds(
                     wanted,
                     MonsGameModel::search_state_hash(&after),
                 );
-                let after_safety = own_drainer_safety_score(&after.board, perspective);
+                let after_safety = own_drainer_safety_score(&after, perspective);
                 let after_exact_steps =
                     after_exact.progress_steps.unwrap_or(Config::BOARD_SIZE * 3);
                 let after_score_path = after_exact
@@ -4037,7 +4073,7 @@ fn safety_recovery_seeds(game: &MonsGame, perspective: Color) -> Vec<ActionSeed>
     let Some(drainer) = find_awake_drainer_location(&game.board, perspective) else {
         return Vec::new();
     };
-    let before_safety = own_drainer_safety_score(&game.board, perspective);
+    let before_safety = own_drainer_safety_score(game, perspective);
 
     let mut seeds = Vec::new();
     for &next in drainer.nearby_locations_ref() {
@@ -4047,7 +4083,7 @@ fn safety_recovery_seeds(game: &MonsGame, perspective: Color) -> Vec<ActionSeed>
         ) else {
             continue;
         };
-        let safety_after = own_drainer_safety_score(&after.board, perspective);
+        let safety_after = own_drainer_safety_score(&after, perspective);
         if safety_after <= before_safety {
             continue;
         }
@@ -4074,7 +4110,7 @@ fn fallback_walk_seeds(game: &MonsGame, perspective: Color) -> Vec<ActionSeed> {
     }
 
     let mut seeds = Vec::new();
-    let before_safety = own_drainer_safety_score(&game.board, perspective);
+    let before_safety = own_drainer_safety_score(game, perspective);
     if let Some(drainer) = find_awake_drainer_location(&game.board, perspective) {
         let before_pool_dist = distance_to_nearest_pool(drainer, perspective);
         for &next in drainer.nearby_locations_ref() {
@@ -4090,7 +4126,7 @@ fn fallback_walk_seeds(game: &MonsGame, perspective: Color) -> Vec<ActionSeed> {
             if opponent_can_win_immediately(&after, perspective) {
                 continue;
             }
-            let after_safety = own_drainer_safety_score(&after.board, perspective);
+            let after_safety = own_drainer_safety_score(&after, perspective);
             if after_safety < before_safety {
                 continue;
             }
@@ -4155,7 +4191,7 @@ fn fallback_walk_seeds(game: &MonsGame, perspective: Color) -> Vec<ActionSeed> {
 
 fn best_follow_up_safety_recovery_priority(game: &MonsGame, perspective: Color) -> Option<i32> {
     let drainer = find_awake_drainer_location(&game.board, perspective)?;
-    let before_safety = own_drainer_safety_score(&game.board, perspective);
+    let before_safety = own_drainer_safety_score(game, perspective);
     let mut best_priority = None;
     for &next in drainer.nearby_locations_ref() {
         if !walk_destination_plausible(&game.board, drainer, next) {
@@ -4167,7 +4203,7 @@ fn best_follow_up_safety_recovery_priority(game: &MonsGame, perspective: Color)
         ) else {
             continue;
         };
-        let safety_after = own_drainer_safety_score(&after.board, perspective);
+        let safety_after = own_drainer_safety_score(&after, perspective);
         if safety_after <= before_safety {
             continue;
         }
@@ -4195,7 +4231,7 @@ fn risky_recovery_setup_seeds(
     let Some(drainer) = find_awake_drainer_location(&game.board, perspective) else {
         return Vec::new();
     };
-    let before_safety = own_drainer_safety_score(&game.board, perspective);
+    let before_safety = own_drainer_safety_score(game, perspective);
     let before_pool_dist = distance_to_nearest_pool(drainer, perspective);
     let mut seeds = Vec::new();
 
@@ -4212,7 +4248,7 @@ fn risky_recovery_setup_seeds(
         if opponent_can_win_immediately(&after, perspective) {
             continue;
         }
-        let after_safety = own_drainer_safety_score(&after.board, perspective);
+        let after_safety = own_drainer_safety_score(&after, perspective);
         if after_safety >= before_safety {
             continue;
         }
@@ -4289,7 +4325,7 @@ fn oracle_walk_seeds_with_projection_mode(
         (0, 0)
     };
     let before_safety = if allow_supermana || allow_safety {
-        own_drainer_safety_score(&game.board, perspective)
+        own_drainer_safety_score(game, perspective)
     } else {
         0
     };
@@ -4383,7 +4419,7 @@ fn oracle_walk_seeds_with_projection_mode(
             };
             let after_safety =
                 if actor_capabilities.can_emit_supermana || actor_capabilities.can_emit_safety {
-                    own_drainer_safety_score(&after.board, perspective)
+                    own_drainer_safety_score(&after, perspective)
                 } else {
                     before_safety
                 };
@@ -4585,7 +4621,7 @@ fn spirit_impact_seeds(
         MonsGameModel::search_state_hash(game),
         tactical_flags,
     );
-    let before_safety = own_drainer_safety_score(&game.board, perspective);
+    let before_safety = own_drainer_safety_score(game, perspective);
     for (spirit_location, item) in game.board.occupied() {
         let Some(mon) = item.mon().copied() else {
             continue;
@@ -4665,7 +4701,7 @@ fn spirit_impact_seeds(
                 if after_turn.spirit_assisted_denial {
                     priority += 820 + after_turn.spirit_assisted_denial_value.saturating_mul(140);
                 }
-                let after_safety = own_drainer_safety_score(&after.board, perspective);
+                let after_safety = own_drainer_safety_score(&after, perspective);
                 if after_safety > before_safety {
                     priority += after_safety
                         .saturating_sub(before_safety)
@@ -5125,8 +5161,8 @@ fn transition_matches_action(
         }
         TurnAction::SafetyRetreat { actor, to } => {
             moved_actor_to(events, actor, to)
-                && own_drainer_safety_score(&after.board, perspective)
-                    > own_drainer_safety_score(&before.board, perspective)
+                && own_drainer_safety_score(after, perspective)
+                    > own_drainer_safety_score(before, perspective)
         }
     }
 }
@@ -5141,7 +5177,7 @@ fn transition_score(
     let mut score = score_for_color(after, perspective)
         .saturating_sub(score_for_color(before, perspective))
         * 500;
-    score += own_drainer_safety_score(&after.board, perspective).saturating_mul(180);
+    score += own_drainer_safety_score(after, perspective).saturating_mul(180);
     if !opponent_can_win_immediately(before, perspective)
         && opponent_can_win_immediately(after, perspective)
     {
@@ -5187,7 +5223,7 @@ fn transition_score(
             score += wanted.score(perspective).saturating_mul(200);
         }
         TurnAction::SafetyRetreat { .. } => {
-            score += own_drainer_safety_score(&after.board, perspective).saturating_mul(260);
+            score += own_drainer_safety_score(after, perspective).saturating_mul(260);
         }
     }
     score
@@ -5643,7 +5679,10 @@ fn opponent_can_win_immediately(game: &MonsGame, perspective: Color) -> bool {
         return false;
     }
     let opponent = perspective.other();
-    let needed = Config::TARGET_SCORE.saturating_sub(score_for_color(game, opponent));
+    let needed = game
+        .rules()
+        .target_score
+        .saturating_sub(score_for_color(game, opponent));
     if needed <= 0 {
         return true;
     }
@@ -5660,9 +5699,9 @@ fn score_for_color(game: &MonsGame, color: Color) -> i32 {
 
 fn remaining_moves_for_color(game: &MonsGame, color: Color) -> i32 {
     if game.active_color == color {
-        (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0)
+        (game.mons_moves_limit() - game.mons_moves_count).max(0)
     } else {
-        Config::MONS_MOVES_PER_TURN
+        game.rules().mons_moves_per_turn
     }
 }
 
@@ -5687,11 +5726,12 @@ fn find_awake_drainer_location(board: &Board, color: Color) -> Option<Location>
     })
 }
 
-fn own_drainer_safety_score(board: &Board, color: Color) -> i32 {
+fn own_drainer_safety_score(game: &MonsGame, color: Color) -> i32 {
     crate::models::automove_exact::exact_own_drainer_safety_score_with_hash(
-        board,
-        crate::models::automove_exact::exact_board_hash(board),
+        &game.board,
+        crate::models::automove_exact::exact_board_hash(&game.board),
         color,
+        game.rules(),
     )
 }
 
@@ -5701,13 +5741,13 @@ fn opponent_drainer_kill_is_high_value(
     target_drainer: Location,
 ) -> bool {
     let opponent = perspective.other();
-    if own_drainer_safety_score(&game.board, perspective) < 0 {
+    if own_drainer_safety_score(game, perspective) < 0 {
         return true;
     }
     if active_turn_score_window(game, opponent) > 0 {
         return true;
     }
-    if score_for_color(game, opponent) >= Config::TARGET_SCORE - 2 {
+    if score_for_color(game, opponent) >= game.rules().target_score - 2 {
         return true;
     }
     if matches!(
@@ -5719,14 +5759,19 @@ fn opponent_drainer_kill_is_high_value(
     distance_to_nearest_pool(target_drainer, opponent) <= 3
 }
 
-fn own_drainer_carries_safe_mana(board: &Board, color: Color, wanted: Mana) -> bool {
-    let Some(drainer_location) = find_awake_drainer_location(board, color) else {
+fn own_drainer_carries_safe_mana(game: &MonsGame, color: Color, wanted: Mana) -> bool {
+    let Some(drainer_location) = find_awake_drainer_location(&game.board, color) else {
         return false;
     };
     matches!(
-        board.item(drainer_location),
+        game.board.item(drainer_location),
         Some(Item::MonWithMana { mana, .. }) if *mana == wanted
-    ) && is_drainer_exactly_safe_next_turn_on_board(board, color, drainer_location)
+    ) && is_drainer_exactly_safe_next_turn_on_board(
+        &game.board,
+        color,
+        drainer_location,
+        game.rules(),
+    )
 }
 
 #[cfg(test)]
@@ -6135,7 +6180,7 @@ mod tests {
                     Color::White,
                     2,
                 );
-                let before_safety = own_drainer_safety_score(&game.board, Color::White);
+                let before_safety = own_drainer_safety_score(&game, Color::White);
                 if before_safety >= 0 {
                     continue;
                 }
@@ -6148,8 +6193,7 @@ mod tests {
                 .any(|transition| {
                     transition.events.iter().any(
                         |event| matches!(event, Event::MonMove { from, .. } if *from == drainer),
-                    ) && own_drainer_safety_score(&transition.game.board, Color::White)
-                        > before_safety
+                    ) && own_drainer_safety_score(&transition.game, Color::White) > before_safety
                 });
                 if has_improving_move {
                     return game;
@@ -6306,7 +6350,7 @@ mod tests {
     #[test]
     fn turn_engine_finds_drainer_safety_recovery_plan() {
         let game = safety_recovery_fixture();
-        let before_safety = own_drainer_safety_score(&game.board, Color::White);
+        let before_safety = own_drainer_safety_score(&game, Color::White);
         let plan = turn_engine_best_plan_for_test(&game, Color::White, engine_config())
             .expect("safety recovery plan");
         assert!(
@@ -6318,7 +6362,7 @@ mod tests {
             plan.head_family
         );
         let state = assert_plan_roundtrip(&game, &plan);
-        assert!(own_drainer_safety_score(&state.board, Color::White) > before_safety);
+        assert!(own_drainer_safety_score(&state, Color::White) > before_safety);
     }
 
     #[test]
@@ -2,6 +2,7 @@ use crate::*;
 
 #[wasm_bindgen]
 #[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AvailableMoveKind {
     MonMove,
     ManaMove,
@@ -0,0 +1,559 @@
+use crate::models::location::BOARD_CELLS;
+use crate::*;
+
+/// Leading byte of every `to_bytes` payload.
+pub const BINARY_FORMAT_VERSION: u8 = 1;
+
+const OCCUPANCY_BYTES: usize = BOARD_CELLS.div_ceil(8);
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Item tags live in the high nibble of an item's first byte, the attached mana
+// or consumable code in the low nibble. Items holding a mon are followed by a
+// second byte with the mon kind, color and cooldown.
+const ITEM_TAG_MON: u8 = 0;
+const ITEM_TAG_MANA: u8 = 1;
+const ITEM_TAG_MON_WITH_MANA: u8 = 2;
+const ITEM_TAG_MON_WITH_CONSUMABLE: u8 = 3;
+const ITEM_TAG_CONSUMABLE: u8 = 4;
+
+// Mark the rules, handicap and progress rules blocks after the board;
+// termination bytes start with 0, 1 or 2.
+const RULES_TAG: u8 = 3;
+const HANDICAP_TAG: u8 = 4;
+const PROGRESS_RULES_TAG: u8 = 5;
+
+impl Board {
+    /// Compact binary form: version, variant id, a 121-bit occupancy mask
+    /// and one or two bytes per occupied cell.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![BINARY_FORMAT_VERSION];
+        write_board(self, &mut bytes);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Board> {
+        let mut reader = BinaryReader::new(bytes);
+        if reader.byte()? != BINARY_FORMAT_VERSION {
+            return None;
+        }
+        let board = read_board(&mut reader)?;
+        reader.is_finished().then_some(board)
+    }
+}
+
+impl MonsGame {
+    /// Compact binary form of the position: the same state `fen` describes,
+    /// so `from_bytes(to_bytes())` and `from_fen(fen())` are interchangeable.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![BINARY_FORMAT_VERSION];
+        for value in [
+            self.white_score,
+            self.black_score,
+            self.actions_used_count,
+            self.mana_moves_count,
+            self.mons_moves_count,
+            self.white_potions_count,
+            self.black_potions_count,
+            self.turn_number,
+        ] {
+            write_varint(&mut bytes, value);
+        }
+        bytes.push(color_code(self.active_color));
+        write_board(&self.board, &mut bytes);
+        if self.rules != self.variant_rules() {
+            bytes.push(RULES_TAG);
+            for value in [
+                self.rules.target_score,
+                self.rules.mons_moves_per_turn,
+                self.rules.mana_moves_per_turn,
+                self.rules.actions_per_turn,
+                self.rules.faint_cooldown,
+            ] {
+                write_varint(&mut bytes, value);
+            }
+        }
+        if !self.handicap.is_empty() {
+            let code = self.handicap.code();
+            bytes.push(HANDICAP_TAG);
+            write_varint(&mut bytes, code.len() as i32);
+            bytes.extend_from_slice(code.as_bytes());
+        }
+        // Unset progress limits are written as 0; limits past `i32::MAX` are
+        // never reached anyway.
+        if let Some(progress_rules) = self.progress_rules {
+            bytes.push(PROGRESS_RULES_TAG);
+            for limit in [
+                progress_rules.max_repetitions,
+                progress_rules.max_turns_without_progress,
+            ] {
+                let limit = limit.map_or(0, |limit| i32::try_from(limit).unwrap_or(i32::MAX));
+                write_varint(&mut bytes, limit);
+            }
+        }
+        // Games ended by anything other than score carry two trailing bytes.
+        if let Some(termination) = self.termination {
+            bytes.push(match termination.result {
+                GameResult::Win(color) => color_code(color),
+                GameResult::Draw => 2,
+            });
+            bytes.push(termination_reason_code(termination.reason));
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8], with_verbose_tracking: bool) -> Option<MonsGame> {
+        let mut reader = BinaryReader::new(bytes);
+        if reader.byte()? != BINARY_FORMAT_VERSION {
+            return None;
+        }
+        let mut counters = [0; 8];
+        for counter in counters.iter_mut() {
+            *counter = reader.varint()?;
+        }
+        let active_color = color_from_code(reader.byte()?)?;
+        let board = read_board(&mut reader)?;
+        let rules = if reader.peek() == Some(RULES_TAG) {
+            reader.byte()?;
+            let rules = RuleSet {
+                target_score: reader.varint()?,
+                mons_moves_per_turn: reader.varint()?,
+                mana_moves_per_turn: reader.varint()?,
+                actions_per_turn: reader.varint()?,
+                faint_cooldown: reader.varint()?,
+            };
+            if !rules.is_valid() {
+                return None;
+            }
+            Some(rules)
+        } else {
+            None
+        };
+        let handicap = if reader.peek() == Some(HANDICAP_TAG) {
+            reader.byte()?;
+            let length = usize::try_from(reader.varint()?).ok()?;
+            let code = std::str::from_utf8(reader.take(length)?).ok()?;
+            Some(Handicap::from_code(code)?)
+        } else {
+            None
+        };
+        let progress_rules = if reader.peek() == Some(PROGRESS_RULES_TAG) {
+            reader.byte()?;
+            let mut limits = [None; 2];
+            for limit in limits.iter_mut() {
+                let value = u32::try_from(reader.varint()?).ok()?;
+                *limit = (value > 0).then_some(value);
+            }
+            let progress_rules = ProgressRules {
+                max_repetitions: limits[0],
+                max_turns_without_progress: limits[1],
+            };
+            if progress_rules.is_empty() {
+                return None;
+            }
+            Some(progress_rules)
+        } else {
+            None
+        };
+        let termination = if reader.is_finished() {
+            None
+        } else {
+            let result = match reader.byte()? {
+                2 => GameResult::Draw,
+                code => GameResult::Win(color_from_code(code)?),
+            };
+            let reason = termination_reason_from_code(reader.byte()?)?;
+            if !reader.is_finished() || reason == TerminationReason::TargetScore {
+                return None;
+            }
+            Some(Termination { result, reason })
+        };
+
+        let mut game = MonsGame::new_with_layout(with_verbose_tracking, board.layout());
+        game.board = board;
+        if let Some(rules) = rules {
+            game.rules = rules;
+        }
+        if let Some(handicap) = handicap {
+            if !handicap.fits_rules(&game.rules) {
+                return None;
+            }
+            game.handicap = handicap;
+        }
+        game.set_progress_rules(progress_rules);
+        game.white_score = counters[0];
+        game.black_score = counters[1];
+        game.actions_used_count = counters[2];
+        game.mana_moves_count = counters[3];
+        game.mons_moves_count = counters[4];
+        game.white_potions_count = counters[5];
+        game.black_potions_count = counters[6];
+        game.turn_number = counters[7];
+        game.active_color = active_color;
+        game.termination = termination;
+        game.takeback_fens.clear();
+        game.is_moves_verified = false;
+        game.verbose_tracking_entities.clear();
+        game.invalidate_process_input_cache();
+        Some(game)
+    }
+
+    /// `to_bytes` as unpadded URL-safe base64.
+    pub fn to_base64(&self) -> String {
+        encode_base64(&self.to_bytes())
+    }
+
+    pub fn from_base64(encoded: &str, with_verbose_tracking: bool) -> Option<MonsGame> {
+        MonsGame::from_bytes(&decode_base64(encoded)?, with_verbose_tracking)
+    }
+}
+
+fn write_board(board: &Board, bytes: &mut Vec<u8>) {
+    bytes.push(board.variant().id() as u8);
+    // Registered variants follow their id with a length-prefixed name.
+    if board.variant() == GameVariant::Custom {
+        let name = board.layout().spec().name.as_bytes();
+        bytes.push(name.len() as u8);
+        bytes.extend_from_slice(name);
+    }
+    let mut occupancy = [0u8; OCCUPANCY_BYTES];
+    for (index, item) in board.items.iter().enumerate() {
+        if item.is_some() {
+            occupancy[index / 8] |= 1 << (index % 8);
+        }
+    }
+    bytes.extend_from_slice(&occupancy);
+    for item in board.items.iter().flatten() {
+        write_item(*item, bytes);
+    }
+}
+
+fn read_board(reader: &mut BinaryReader) -> Option<Board> {
+    let layout = match GameVariant::from_id(reader.byte()? as i32)? {
+        GameVariant::Custom => {
+            let length = reader.byte()? as usize;
+            let name = std::str::from_utf8(reader.take(length)?).ok()?;
+            VariantLayout::registered(name)?
+        }
+        variant => VariantLayout::builtin(variant)?,
+    };
+    let occupancy = reader.take(OCCUPANCY_BYTES)?;
+    if occupancy[OCCUPANCY_BYTES - 1] >> (BOARD_CELLS % 8) != 0 {
+        return None;
+    }
+    let mut items = [None; BOARD_CELLS];
+    for (index, slot) in items.iter_mut().enumerate() {
+        if occupancy[index / 8] & (1 << (index % 8)) != 0 {
+            *slot = Some(read_item(reader)?);
+        }
+    }
+    Some(Board::from_items_array(items, layout))
+}
+
+fn write_item(item: Item, bytes: &mut Vec<u8>) {
+    let (tag, attachment, mon) = match item {
+        Item::Mon { mon } => (ITEM_TAG_MON, 0, Some(mon)),
+        Item::Mana { mana } => (ITEM_TAG_MANA, mana_code(mana), None),
+        Item::MonWithMana { mon, mana } => (ITEM_TAG_MON_WITH_MANA, mana_code(mana), Some(mon)),
+        Item::MonWithConsumable { mon, consumable } => (
+            ITEM_TAG_MON_WITH_CONSUMABLE,
+            consumable_code(consumable),
+            Some(mon),
+        ),
+        Item::Consumable { consumable } => (ITEM_TAG_CONSUMABLE, consumable_code(consumable), None),
+    };
+    bytes.push(tag << 4 | attachment);
+    if let Some(mon) = mon {
+        // Matches FEN, which only keeps the last digit of the cooldown.
+        let cooldown = mon.cooldown.rem_euclid(10) as u8;
+        bytes.push(mon_kind_code(mon.kind) | color_code(mon.color) << 3 | cooldown << 4);
+    }
+}
+
+fn read_item(reader: &mut BinaryReader) -> Option<Item> {
+    let header = reader.byte()?;
+    let (tag, attachment) = (header >> 4, header & 0x0f);
+    let mut read_mon = || -> Option<Mon> {
+        let code = reader.byte()?;
+        let cooldown = (code >> 4) as i32;
+        if cooldown > 9 {
+            return None;
+        }
+        Some(Mon {
+            kind: mon_kind_from_code(code & 0x07)?,
+            color: color_from_code((code >> 3) & 0x01)?,
+            cooldown,
+        })
+    };
+    match tag {
+        ITEM_TAG_MON if attachment == 0 => Some(Item::Mon { mon: read_mon()? }),
+        ITEM_TAG_MANA => Some(Item::Mana {
+            mana: mana_from_code(attachment)?,
+        }),
+        ITEM_TAG_MON_WITH_MANA => {
+            let mana = mana_from_code(attachment)?;
+            Some(Item::MonWithMana {
+                mon: read_mon()?,
+                mana,
+            })
+        }
+        ITEM_TAG_MON_WITH_CONSUMABLE => {
+            let consumable = consumable_from_code(attachment)?;
+            Some(Item::MonWithConsumable {
+                mon: read_mon()?,
+                consumable,
+            })
+        }
+        ITEM_TAG_CONSUMABLE => Some(Item::Consumable {
+            consumable: consumable_from_code(attachment)?,
+        }),
+        _ => None,
+    }
+}
+
+fn termination_reason_code(reason: TerminationReason) -> u8 {
+    match reason {
+        TerminationReason::TargetScore => 0,
+        TerminationReason::Resignation => 1,
+        TerminationReason::Timeout => 2,
+        TerminationReason::Abandonment => 3,
+        TerminationReason::Adjudication => 4,
+        TerminationReason::Agreement => 5,
+    }
+}
+
+fn termination_reason_from_code(code: u8) -> Option<TerminationReason> {
+    match code {
+        0 => Some(TerminationReason::TargetScore),
+        1 => Some(TerminationReason::Resignation),
+        2 => Some(TerminationReason::Timeout),
+        3 => Some(TerminationReason::Abandonment),
+        4 => Some(TerminationReason::Adjudication),
+        5 => Some(TerminationReason::Agreement),
+        _ => None,
+    }
+}
+
+fn color_code(color: Color) -> u8 {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+fn color_from_code(code: u8) -> Option<Color> {
+    match code {
+        0 => Some(Color::White),
+        1 => Some(Color::Black),
+        _ => None,
+    }
+}
+
+fn mon_kind_code(kind: MonKind) -> u8 {
+    match kind {
+        MonKind::Demon => 0,
+        MonKind::Drainer => 1,
+        MonKind::Angel => 2,
+        MonKind::Spirit => 3,
+        MonKind::Mystic => 4,
+    }
+}
+
+fn mon_kind_from_code(code: u8) -> Option<MonKind> {
+    match code {
+        0 => Some(MonKind::Demon),
+        1 => Some(MonKind::Drainer),
+        2 => Some(MonKind::Angel),
+        3 => Some(MonKind::Spirit),
+        4 => Some(MonKind::Mystic),
+        _ => None,
+    }
+}
+
+fn mana_code(mana: Mana) -> u8 {
+    match mana {
+        Mana::Regular(Color::White) => 0,
+        Mana::Regular(Color::Black) => 1,
+        Mana::Supermana => 2,
+    }
+}
+
+fn mana_from_code(code: u8) -> Option<Mana> {
+    match code {
+        0 => Some(Mana::Regular(Color::White)),
+        1 => Some(Mana::Regular(Color::Black)),
+        2 => Some(Mana::Supermana),
+        _ => None,
+    }
+}
+
+fn consumable_code(consumable: Consumable) -> u8 {
+    match consumable {
+        Consumable::Potion => 0,
+        Consumable::Bomb => 1,
+        Consumable::BombOrPotion => 2,
+    }
+}
+
+fn consumable_from_code(code: u8) -> Option<Consumable> {
+    match code {
+        0 => Some(Consumable::Potion),
+        1 => Some(Consumable::Bomb),
+        2 => Some(Consumable::BombOrPotion),
+        _ => None,
+    }
+}
+
+/// Zigzag LEB128, so small negative counters stay one byte long.
+fn write_varint(bytes: &mut Vec<u8>, value: i32) {
+    let mut zigzag = ((value << 1) ^ (value >> 31)) as u32;
+    loop {
+        let byte = (zigzag & 0x7f) as u8;
+        zigzag >>= 7;
+        if zigzag == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+struct BinaryReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.position)?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.position..self.position + len)?;
+        self.position += len;
+        Some(slice)
+    }
+
+    fn varint(&mut self) -> Option<i32> {
+        let mut zigzag: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            zigzag |= ((byte & 0x7f) as u32).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some((zigzag >> 1) as i32 ^ -((zigzag & 1) as i32));
+            }
+        }
+        None
+    }
+
+    fn is_finished(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk.iter().enumerate().fold(0u32, |buffer, (i, byte)| {
+            buffer | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64_ALPHABET[(buffer >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+fn decode_base64(encoded: &str) -> Option<Vec<u8>> {
+    let encoded = encoded.as_bytes();
+    if encoded.len() % 4 == 1 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.chunks(4) {
+        let mut buffer = 0u32;
+        for (i, symbol) in chunk.iter().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|c| c == symbol)? as u32;
+            buffer |= value << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            bytes.push((buffer >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_and_fen_round_trips_yield_identical_states() {
+        for variant in [GameVariant::Classic, GameVariant::OffsetArcManaRows] {
+            let mut game = MonsGame::new(false, variant);
+            for ply in 0..80 {
+                let bytes = game.to_bytes();
+                let from_bytes = MonsGame::from_bytes(&bytes, false).unwrap();
+                let from_fen = MonsGame::from_fen(&game.fen(), false).unwrap();
+                assert_eq!(from_bytes.fen(), game.fen());
+                assert_eq!(from_bytes.zobrist(), from_fen.zobrist());
+                assert_eq!(from_bytes.board, from_fen.board);
+                assert_eq!(from_fen.to_bytes(), bytes);
+                assert_eq!(
+                    MonsGame::from_base64(&game.to_base64(), false)
+                        .unwrap()
+                        .to_bytes(),
+                    bytes
+                );
+                assert_eq!(
+                    Board::from_bytes(&game.board.to_bytes()),
+                    Some(game.board.clone())
+                );
+                assert!(bytes.len() < game.fen().len() / 2);
+
+                let legal_moves = game.legal_move_inputs(true);
+                if legal_moves.is_empty() {
+                    break;
+                }
+                let inputs = legal_moves[(ply * 13) % legal_moves.len()].clone();
+                game.process_input(inputs, false, false);
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_payloads_are_rejected() {
+        let bytes = MonsGame::new(false, GameVariant::Classic).to_bytes();
+        assert!(MonsGame::from_bytes(&bytes[..bytes.len() - 1], false).is_none());
+        assert!(MonsGame::from_bytes(&[bytes.as_slice(), &[0]].concat(), false).is_none());
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = BINARY_FORMAT_VERSION + 1;
+        assert!(MonsGame::from_bytes(&wrong_version, false).is_none());
+        assert!(MonsGame::from_base64("not base64!", false).is_none());
+    }
+
+    #[test]
+    fn varints_and_base64_round_trip() {
+        for value in [0, 1, -1, 63, -64, 64, 300, -300, i32::MAX, i32::MIN] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, value);
+            let mut reader = BinaryReader::new(&bytes);
+            assert_eq!(reader.varint(), Some(value));
+            assert!(reader.is_finished());
+        }
+        for len in 0..8 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 97 + 13) as u8).collect();
+            assert_eq!(decode_base64(&encode_base64(&bytes)), Some(bytes));
+        }
+    }
+}
@@ -3,8 +3,10 @@ use crate::*;
 
 #[derive(Clone)]
 pub struct Board {
+    /// Prefer `put` / `remove_item` for writes; they keep `zobrist` in sync.
     pub items: [Option<Item>; BOARD_CELLS],
-    variant: GameVariant,
+    layout: &'static VariantLayout,
+    zobrist: u64,
 }
 
 impl std::fmt::Debug for Board {
@@ -16,7 +18,7 @@ impl std::fmt::Debug for Board {
             .filter_map(|(idx, opt)| opt.as_ref().map(|item| (Location::from_index(idx), item)))
             .collect();
         f.debug_struct("Board")
-            .field("variant", &self.variant)
+            .field("variant", &self.layout.spec().name)
             .field("items", &occupied)
             .finish()
     }
@@ -24,7 +26,7 @@ impl std::fmt::Debug for Board {
 
 impl PartialEq for Board {
     fn eq(&self, other: &Self) -> bool {
-        self.items == other.items && self.variant == other.variant
+        self.items == other.items && self.layout == other.layout
     }
 }
 
@@ -41,8 +43,13 @@ impl Board {
         Self::new_with_variant(GameVariant::DEFAULT)
     }
 
+    /// Panics for `Custom`; use `new_with_layout` for registered variants.
     pub fn new_with_variant(variant: GameVariant) -> Self {
-        Self::from_items_array(Config::initial_items_array_for_variant(variant), variant)
+        Self::new_with_layout(VariantLayout::expect_builtin(variant))
+    }
+
+    pub fn new_with_layout(layout: &'static VariantLayout) -> Self {
+        Self::from_items_array(*layout.initial_items(), layout)
     }
 
     fn items_array_from_iter<I>(items: I) -> [Option<Item>; BOARD_CELLS]
@@ -65,28 +72,55 @@ impl Board {
         Self::new_with_items_and_variant(items, GameVariant::DEFAULT)
     }
 
+    /// Panics for `Custom`; use `new_with_items_and_layout` for registered
+    /// variants.
     pub fn new_with_items_and_variant<I>(items: I, variant: GameVariant) -> Self
     where
         I: IntoIterator<Item = (Location, Item)>,
     {
-        Self::from_items_array(Self::items_array_from_iter(items), variant)
+        Self::new_with_items_and_layout(items, VariantLayout::expect_builtin(variant))
+    }
+
+    pub fn new_with_items_and_layout<I>(items: I, layout: &'static VariantLayout) -> Self
+    where
+        I: IntoIterator<Item = (Location, Item)>,
+    {
+        Self::from_items_array(Self::items_array_from_iter(items), layout)
     }
 
     pub(crate) fn from_items_array(
         items: [Option<Item>; BOARD_CELLS],
-        variant: GameVariant,
+        layout: &'static VariantLayout,
     ) -> Self {
-        Self { items, variant }
+        Self {
+            zobrist: zobrist_board_key(&items),
+            items,
+            layout,
+        }
     }
 
     #[inline]
     pub fn remove_item(&mut self, location: Location) {
-        self.items[location.index()] = None;
+        let index = location.index();
+        if let Some(item) = self.items[index].take() {
+            self.zobrist ^= zobrist_item_key(index, item);
+        }
     }
 
     #[inline]
     pub fn put(&mut self, item: Item, location: Location) {
-        self.items[location.index()] = Some(item);
+        let index = location.index();
+        if let Some(previous) = self.items[index] {
+            self.zobrist ^= zobrist_item_key(index, previous);
+        }
+        self.zobrist ^= zobrist_item_key(index, item);
+        self.items[index] = Some(item);
+    }
+
+    /// Zobrist key of the items on the board, maintained by `put` / `remove_item`.
+    #[inline]
+    pub fn zobrist(&self) -> u64 {
+        self.zobrist
     }
 
     #[inline]
@@ -102,21 +136,31 @@ impl Board {
         if !location.is_valid() {
             return Square::Regular;
         }
-        Config::square_at_for_variant(location, self.variant)
+        self.layout.square(location)
     }
 
     #[inline]
     pub fn variant(&self) -> GameVariant {
-        self.variant
+        self.layout.variant()
+    }
+
+    #[inline]
+    pub fn layout(&self) -> &'static VariantLayout {
+        self.layout
     }
 
     pub fn all_mons_bases(&self) -> Vec<Location> {
-        Config::MONS_BASE_LOCATIONS.to_vec()
+        self.layout
+            .spec()
+            .mon_bases
+            .iter()
+            .map(|base| base.location)
+            .collect()
     }
 
     #[inline]
     pub fn supermana_base(&self) -> Location {
-        Config::SUPERMANA_BASE
+        self.layout.supermana_base()
     }
 
     pub fn all_mons_locations(&self, color: Color) -> Vec<Location> {
@@ -150,7 +194,7 @@ impl Board {
     }
 
     pub fn base(&self, mon: Mon) -> Location {
-        Config::mon_base(mon.kind, mon.color)
+        self.layout.mon_base(mon.kind, mon.color)
     }
 
     pub fn fainted_mons_locations(&self, color: Color) -> Vec<Location> {
@@ -5,6 +5,7 @@ use std::sync::LazyLock;
 
 #[wasm_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameVariant {
     #[default]
     Classic = 0,
@@ -19,11 +20,30 @@ pub enum GameVariant {
     SplitFlankManaRows = 9,
     ForwardBridgeManaRows = 10,
     CornerChainManaRows = 11,
+    /// A layout registered at runtime from a `VariantSpec`; boards carry the
+    /// layout itself.
+    Custom = 12,
 }
 
 impl GameVariant {
     pub const DEFAULT: Self = Self::Classic;
 
+    /// Every variant with a layout of its own, in id order.
+    pub const BUILTIN: [Self; 12] = [
+        Self::Classic,
+        Self::SwappedManaRows,
+        Self::OffsetArcManaRows,
+        Self::CenterSpokeManaRows,
+        Self::AlternatingManaRows,
+        Self::InnerWedgeManaRows,
+        Self::OuterWedgeManaRows,
+        Self::BentCenterManaRows,
+        Self::OuterEdgeManaRows,
+        Self::SplitFlankManaRows,
+        Self::ForwardBridgeManaRows,
+        Self::CornerChainManaRows,
+    ];
+
     pub const fn id(self) -> i32 {
         match self {
             Self::Classic => 0,
@@ -38,6 +58,7 @@ impl GameVariant {
             Self::SplitFlankManaRows => 9,
             Self::ForwardBridgeManaRows => 10,
             Self::CornerChainManaRows => 11,
+            Self::Custom => 12,
         }
     }
 
@@ -55,6 +76,7 @@ impl GameVariant {
             9 => Some(Self::SplitFlankManaRows),
             10 => Some(Self::ForwardBridgeManaRows),
             11 => Some(Self::CornerChainManaRows),
+            12 => Some(Self::Custom),
             _ => None,
         }
     }
@@ -62,143 +84,27 @@ impl GameVariant {
     pub fn from_fen(fen: &str) -> Option<Self> {
         fen.parse::<i32>().ok().and_then(Self::from_id)
     }
-}
 
-static CLASSIC_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::Classic));
-static CLASSIC_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in CLASSIC_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
-    }
-    arr
-});
-static CLASSIC_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::Classic));
-static SWAPPED_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::SwappedManaRows));
-static SWAPPED_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in SWAPPED_MANA_ROWS_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
-    }
-    arr
-});
-static SWAPPED_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::SwappedManaRows));
-static OFFSET_ARC_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::OffsetArcManaRows));
-static OFFSET_ARC_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in OFFSET_ARC_MANA_ROWS_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
-    }
-    arr
-});
-static OFFSET_ARC_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::OffsetArcManaRows));
-static CENTER_SPOKE_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::CenterSpokeManaRows));
-static CENTER_SPOKE_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> =
-    LazyLock::new(|| {
-        let mut arr = [Square::Regular; BOARD_CELLS];
-        for (&loc, &sq) in CENTER_SPOKE_MANA_ROWS_SQUARES_MAP.iter() {
-            arr[loc.index()] = sq;
+    /// Name of the built-in `VariantSpec`.
+    pub const fn builtin_name(self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::SwappedManaRows => "swapped-mana-rows",
+            Self::OffsetArcManaRows => "offset-arc-mana-rows",
+            Self::CenterSpokeManaRows => "center-spoke-mana-rows",
+            Self::AlternatingManaRows => "alternating-mana-rows",
+            Self::InnerWedgeManaRows => "inner-wedge-mana-rows",
+            Self::OuterWedgeManaRows => "outer-wedge-mana-rows",
+            Self::BentCenterManaRows => "bent-center-mana-rows",
+            Self::OuterEdgeManaRows => "outer-edge-mana-rows",
+            Self::SplitFlankManaRows => "split-flank-mana-rows",
+            Self::ForwardBridgeManaRows => "forward-bridge-mana-rows",
+            Self::CornerChainManaRows => "corner-chain-mana-rows",
+            Self::Custom => "custom",
         }
-        arr
-    });
-static CENTER_SPOKE_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::CenterSpokeManaRows));
-static ALTERNATING_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::AlternatingManaRows));
-static ALTERNATING_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in ALTERNATING_MANA_ROWS_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
-    }
-    arr
-});
-static ALTERNATING_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::AlternatingManaRows));
-static INNER_WEDGE_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::InnerWedgeManaRows));
-static INNER_WEDGE_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in INNER_WEDGE_MANA_ROWS_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
-    }
-    arr
-});
-static INNER_WEDGE_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::InnerWedgeManaRows));
-static OUTER_WEDGE_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::OuterWedgeManaRows));
-static OUTER_WEDGE_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in OUTER_WEDGE_MANA_ROWS_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
     }
-    arr
-});
-static OUTER_WEDGE_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::OuterWedgeManaRows));
-static BENT_CENTER_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::BentCenterManaRows));
-static BENT_CENTER_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in BENT_CENTER_MANA_ROWS_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
-    }
-    arr
-});
-static BENT_CENTER_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::BentCenterManaRows));
-static OUTER_EDGE_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::OuterEdgeManaRows));
-static OUTER_EDGE_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in OUTER_EDGE_MANA_ROWS_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
-    }
-    arr
-});
-static OUTER_EDGE_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::OuterEdgeManaRows));
-static SPLIT_FLANK_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::SplitFlankManaRows));
-static SPLIT_FLANK_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> = LazyLock::new(|| {
-    let mut arr = [Square::Regular; BOARD_CELLS];
-    for (&loc, &sq) in SPLIT_FLANK_MANA_ROWS_SQUARES_MAP.iter() {
-        arr[loc.index()] = sq;
-    }
-    arr
-});
-static SPLIT_FLANK_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::SplitFlankManaRows));
-static FORWARD_BRIDGE_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::ForwardBridgeManaRows));
-static FORWARD_BRIDGE_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> =
-    LazyLock::new(|| {
-        let mut arr = [Square::Regular; BOARD_CELLS];
-        for (&loc, &sq) in FORWARD_BRIDGE_MANA_ROWS_SQUARES_MAP.iter() {
-            arr[loc.index()] = sq;
-        }
-        arr
-    });
-static FORWARD_BRIDGE_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::ForwardBridgeManaRows));
-static CORNER_CHAIN_MANA_ROWS_SQUARES_MAP: LazyLock<HashMap<Location, Square>> =
-    LazyLock::new(|| Config::build_squares(GameVariant::CornerChainManaRows));
-static CORNER_CHAIN_MANA_ROWS_SQUARES_ARRAY: LazyLock<[Square; BOARD_CELLS]> =
-    LazyLock::new(|| {
-        let mut arr = [Square::Regular; BOARD_CELLS];
-        for (&loc, &sq) in CORNER_CHAIN_MANA_ROWS_SQUARES_MAP.iter() {
-            arr[loc.index()] = sq;
-        }
-        arr
-    });
-static CORNER_CHAIN_MANA_ROWS_INITIAL_ITEMS_ARRAY: LazyLock<[Option<Item>; BOARD_CELLS]> =
-    LazyLock::new(|| Config::build_initial_items_array(GameVariant::CornerChainManaRows));
+}
+
 static MONS_BASES_SET: LazyLock<HashSet<Location>> =
     LazyLock::new(|| Config::MONS_BASE_LOCATIONS.iter().copied().collect());
 static IS_MON_BASE: LazyLock<[bool; BOARD_CELLS]> = LazyLock::new(|| {
@@ -411,132 +317,13 @@ impl Config {
         Location { i: 7, j: 5 },
     ];
 
-    fn build_squares(variant: GameVariant) -> HashMap<Location, Square> {
-        use Square::*;
-        let mut squares = HashMap::new();
-        squares.insert(
-            Location::new(0, 0),
-            ManaPool {
-                color: Color::Black,
-            },
-        );
-        squares.insert(
-            Location::new(0, 10),
-            ManaPool {
-                color: Color::Black,
-            },
-        );
-        squares.insert(
-            Location::new(10, 0),
-            ManaPool {
-                color: Color::White,
-            },
-        );
-        squares.insert(
-            Location::new(10, 10),
-            ManaPool {
-                color: Color::White,
-            },
-        );
-
-        squares.insert(
-            Location::new(0, 3),
-            MonBase {
-                kind: MonKind::Mystic,
-                color: Color::Black,
-            },
-        );
-        squares.insert(
-            Location::new(0, 4),
-            MonBase {
-                kind: MonKind::Spirit,
-                color: Color::Black,
-            },
-        );
-        squares.insert(
-            Location::new(0, 5),
-            MonBase {
-                kind: MonKind::Drainer,
-                color: Color::Black,
-            },
-        );
-        squares.insert(
-            Location::new(0, 6),
-            MonBase {
-                kind: MonKind::Angel,
-                color: Color::Black,
-            },
-        );
-        squares.insert(
-            Location::new(0, 7),
-            MonBase {
-                kind: MonKind::Demon,
-                color: Color::Black,
-            },
-        );
-
-        squares.insert(
-            Location::new(10, 3),
-            MonBase {
-                kind: MonKind::Demon,
-                color: Color::White,
-            },
-        );
-        squares.insert(
-            Location::new(10, 4),
-            MonBase {
-                kind: MonKind::Angel,
-                color: Color::White,
-            },
-        );
-        squares.insert(
-            Location::new(10, 5),
-            MonBase {
-                kind: MonKind::Drainer,
-                color: Color::White,
-            },
-        );
-        squares.insert(
-            Location::new(10, 6),
-            MonBase {
-                kind: MonKind::Spirit,
-                color: Color::White,
-            },
-        );
-        squares.insert(
-            Location::new(10, 7),
-            MonBase {
-                kind: MonKind::Mystic,
-                color: Color::White,
-            },
-        );
-
-        for &location in Self::mana_base_locations(variant, Color::Black) {
-            squares.insert(
-                location,
-                ManaBase {
-                    color: Color::Black,
-                },
-            );
-        }
-        for &location in Self::mana_base_locations(variant, Color::White) {
-            squares.insert(
-                location,
-                ManaBase {
-                    color: Color::White,
-                },
-            );
-        }
-
-        squares.insert(Location::new(5, 0), ConsumableBase);
-        squares.insert(Location::new(5, 10), ConsumableBase);
-        squares.insert(Location::new(5, 5), SupermanaBase);
-
-        squares
-    }
-
-    fn mana_base_locations(variant: GameVariant, color: Color) -> &'static [Location; 5] {
-        match (variant, color) {
+    /// `None` for `Custom`, whose mana bases come from a registered spec.
+    pub(crate) fn mana_base_locations(
+        variant: GameVariant,
+        color: Color,
+    ) -> Option<&'static [Location; 5]> {
+        Some(match (variant, color) {
+            (GameVariant::Custom, _) => return None,
             (GameVariant::Classic, Color::Black) => &Self::CLASSIC_BLACK_MANA_BASE_LOCATIONS,
             (GameVariant::Classic, Color::White) => &Self::CLASSIC_WHITE_MANA_BASE_LOCATIONS,
             (GameVariant::SwappedManaRows, Color::Black) => {
@@ -605,10 +392,10 @@ impl Config {
             (GameVariant::CornerChainManaRows, Color::White) => {
                 &Self::CORNER_CHAIN_WHITE_MANA_BASE_LOCATIONS
             }
-        }
+        })
     }
 
-    fn initial_item_for_square(square: Square) -> Option<Item> {
+    pub(crate) fn initial_item_for_square(square: Square) -> Option<Item> {
         match square {
             Square::MonBase { kind, color } => Some(Item::Mon {
                 mon: Mon::new(kind, color, 0),
@@ -626,29 +413,9 @@ impl Config {
         }
     }
 
-    fn build_initial_items_array(variant: GameVariant) -> [Option<Item>; BOARD_CELLS] {
-        let mut arr: [Option<Item>; BOARD_CELLS] = [None; BOARD_CELLS];
-        for (&location, &square) in Self::squares_ref_for_variant(variant).iter() {
-            arr[location.index()] = Self::initial_item_for_square(square);
-        }
-        arr
-    }
-
+    /// Panics for `Custom`, like every `_for_variant` lookup here.
     pub fn squares_ref_for_variant(variant: GameVariant) -> &'static HashMap<Location, Square> {
-        match variant {
-            GameVariant::Classic => &CLASSIC_SQUARES_MAP,
-            GameVariant::SwappedManaRows => &SWAPPED_MANA_ROWS_SQUARES_MAP,
-            GameVariant::OffsetArcManaRows => &OFFSET_ARC_MANA_ROWS_SQUARES_MAP,
-            GameVariant::CenterSpokeManaRows => &CENTER_SPOKE_MANA_ROWS_SQUARES_MAP,
-            GameVariant::AlternatingManaRows => &ALTERNATING_MANA_ROWS_SQUARES_MAP,
-            GameVariant::InnerWedgeManaRows => &INNER_WEDGE_MANA_ROWS_SQUARES_MAP,
-            GameVariant::OuterWedgeManaRows => &OUTER_WEDGE_MANA_ROWS_SQUARES_MAP,
-            GameVariant::BentCenterManaRows => &BENT_CENTER_MANA_ROWS_SQUARES_MAP,
-            GameVariant::OuterEdgeManaRows => &OUTER_EDGE_MANA_ROWS_SQUARES_MAP,
-            GameVariant::SplitFlankManaRows => &SPLIT_FLANK_MANA_ROWS_SQUARES_MAP,
-            GameVariant::ForwardBridgeManaRows => &FORWARD_BRIDGE_MANA_ROWS_SQUARES_MAP,
-            GameVariant::CornerChainManaRows => &CORNER_CHAIN_MANA_ROWS_SQUARES_MAP,
-        }
+        VariantLayout::expect_builtin(variant).squares_map()
     }
 
     pub fn squares_ref() -> &'static HashMap<Location, Square> {
@@ -665,36 +432,7 @@ impl Config {
 
     #[inline]
     pub fn square_at_for_variant(location: Location, variant: GameVariant) -> Square {
-        match variant {
-            GameVariant::Classic => CLASSIC_SQUARES_ARRAY[location.index()],
-            GameVariant::SwappedManaRows => SWAPPED_MANA_ROWS_SQUARES_ARRAY[location.index()],
-            GameVariant::OffsetArcManaRows => OFFSET_ARC_MANA_ROWS_SQUARES_ARRAY[location.index()],
-            GameVariant::CenterSpokeManaRows => {
-                CENTER_SPOKE_MANA_ROWS_SQUARES_ARRAY[location.index()]
-            }
-            GameVariant::AlternatingManaRows => {
-                ALTERNATING_MANA_ROWS_SQUARES_ARRAY[location.index()]
-            }
-            GameVariant::InnerWedgeManaRows => {
-                INNER_WEDGE_MANA_ROWS_SQUARES_ARRAY[location.index()]
-            }
-            GameVariant::OuterWedgeManaRows => {
-                OUTER_WEDGE_MANA_ROWS_SQUARES_ARRAY[location.index()]
-            }
-            GameVariant::BentCenterManaRows => {
-                BENT_CENTER_MANA_ROWS_SQUARES_ARRAY[location.index()]
-            }
-            GameVariant::OuterEdgeManaRows => OUTER_EDGE_MANA_ROWS_SQUARES_ARRAY[location.index()],
-            GameVariant::SplitFlankManaRows => {
-                SPLIT_FLANK_MANA_ROWS_SQUARES_ARRAY[location.index()]
-            }
-            GameVariant::ForwardBridgeManaRows => {
-                FORWARD_BRIDGE_MANA_ROWS_SQUARES_ARRAY[location.index()]
-            }
-            GameVariant::CornerChainManaRows => {
-                CORNER_CHAIN_MANA_ROWS_SQUARES_ARRAY[location.index()]
-            }
-        }
+        VariantLayout::expect_builtin(variant).square(location)
     }
 
     #[inline]
@@ -703,20 +441,7 @@ impl Config {
     }
 
     pub fn squares_array_for_variant(variant: GameVariant) -> &'static [Square; BOARD_CELLS] {
-        match variant {
-            GameVariant::Classic => &CLASSIC_SQUARES_ARRAY,
-            GameVariant::SwappedManaRows => &SWAPPED_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::OffsetArcManaRows => &OFFSET_ARC_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::CenterSpokeManaRows => &CENTER_SPOKE_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::AlternatingManaRows => &ALTERNATING_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::InnerWedgeManaRows => &INNER_WEDGE_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::OuterWedgeManaRows => &OUTER_WEDGE_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::BentCenterManaRows => &BENT_CENTER_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::OuterEdgeManaRows => &OUTER_EDGE_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::SplitFlankManaRows => &SPLIT_FLANK_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::ForwardBridgeManaRows => &FORWARD_BRIDGE_MANA_ROWS_SQUARES_ARRAY,
-            GameVariant::CornerChainManaRows => &CORNER_CHAIN_MANA_ROWS_SQUARES_ARRAY,
-        }
+        VariantLayout::expect_builtin(variant).squares()
     }
 
     pub fn squares_array() -> &'static [Square; BOARD_CELLS] {
@@ -737,20 +462,7 @@ impl Config {
     }
 
     pub fn initial_items_array_for_variant(variant: GameVariant) -> [Option<Item>; BOARD_CELLS] {
-        match variant {
-            GameVariant::Classic => *CLASSIC_INITIAL_ITEMS_ARRAY,
-            GameVariant::SwappedManaRows => *SWAPPED_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::OffsetArcManaRows => *OFFSET_ARC_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::CenterSpokeManaRows => *CENTER_SPOKE_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::AlternatingManaRows => *ALTERNATING_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::InnerWedgeManaRows => *INNER_WEDGE_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::OuterWedgeManaRows => *OUTER_WEDGE_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::BentCenterManaRows => *BENT_CENTER_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::OuterEdgeManaRows => *OUTER_EDGE_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::SplitFlankManaRows => *SPLIT_FLANK_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::ForwardBridgeManaRows => *FORWARD_BRIDGE_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-            GameVariant::CornerChainManaRows => *CORNER_CHAIN_MANA_ROWS_INITIAL_ITEMS_ARRAY,
-        }
+        *VariantLayout::expect_builtin(variant).initial_items()
     }
 
     pub fn initial_items_array() -> [Option<Item>; BOARD_CELLS] {
@@ -762,6 +474,9 @@ impl Config {
 
     pub const SUPERMANA_BASE: Location = Location { i: 5, j: 5 };
 
+    pub const CONSUMABLE_BASE_LOCATIONS: [Location; 2] =
+        [Location { i: 5, j: 0 }, Location { i: 5, j: 10 }];
+
     pub const MONS_BASE_LOCATIONS: [Location; 10] = [
         Location { i: 0, j: 3 },
         Location { i: 0, j: 4 },
@@ -2,6 +2,7 @@ use crate::*;
 
 #[wasm_bindgen]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Consumable {
     Potion,
     Bomb,
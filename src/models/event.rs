@@ -1,6 +1,7 @@
 use crate::*;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     MonMove {
         item: Item,
@@ -85,5 +86,9 @@ pub enum Event {
     GameOver {
         winner: Color,
     },
+    GameTerminated {
+        result: GameResult,
+        reason: TerminationReason,
+    },
     Takeback,
 }
@@ -18,8 +18,21 @@ impl FenRepresentable for MonsGame {
             self.turn_number.to_string(),
             self.board.fen(),
         ];
-        if self.variant() != GameVariant::DEFAULT {
-            fields.push(self.variant().id().to_string());
+        if let Some(variant) = self.variant_layout().fen() {
+            fields.push(variant);
+        }
+        let rules = self.rules.code(&self.variant_rules());
+        if !rules.is_empty() {
+            fields.push(format!("~{}", rules));
+        }
+        if !self.handicap.is_empty() {
+            fields.push(format!("+{}", self.handicap.code()));
+        }
+        if let Some(progress_rules) = self.progress_rules {
+            fields.push(format!("%{}", progress_rules.code()));
+        }
+        if let Some(termination) = self.termination {
+            fields.push(format!("#{}", termination.code()));
         }
         fields.join(" ")
     }
@@ -27,14 +40,61 @@ impl FenRepresentable for MonsGame {
 
 impl MonsGame {
     pub fn from_fen(fen: &str, with_verbose_tracking: bool) -> Option<Self> {
-        let fields: Vec<&str> = fen.split_whitespace().collect();
-        let variant = match fields.len() {
-            10 => GameVariant::DEFAULT,
-            11 => GameVariant::from_fen(fields[10])?,
+        let mut fields: Vec<&str> = fen.split_whitespace().collect();
+        // Trailing `#<result>:<reason>` records a termination other than score.
+        let termination = match fields.last() {
+            Some(field) if field.starts_with('#') => {
+                let termination = Termination::from_code(&field[1..])?;
+                if termination.reason == TerminationReason::TargetScore {
+                    return None;
+                }
+                fields.pop();
+                Some(termination)
+            }
+            _ => None,
+        };
+        // `%key=value,...` records the progress rules the game is played under.
+        let progress_rules = match fields.last() {
+            Some(field) if field.starts_with('%') => {
+                let progress_rules = fields.pop()?;
+                Some(ProgressRules::from_code(&progress_rules[1..])?)
+            }
+            _ => None,
+        };
+        // `+<side>:<entry>,...` records the handicap the game started with.
+        let handicap = match fields.last() {
+            Some(field) if field.starts_with('+') => {
+                let handicap = fields.pop()?;
+                Some(Handicap::from_code(&handicap[1..])?)
+            }
+            _ => None,
+        };
+        // `~key=value,...` records rules that differ from the variant's.
+        let rules = match fields.last() {
+            Some(field) if field.starts_with('~') => {
+                let rules = fields.pop()?;
+                Some(&rules[1..])
+            }
+            _ => None,
+        };
+        let layout = match fields.len() {
+            10 => VariantLayout::expect_builtin(GameVariant::DEFAULT),
+            11 => VariantLayout::from_fen(fields[10])?,
             _ => return None,
         };
-        let mut game = Self::new(with_verbose_tracking, variant);
-        game.board = Board::from_fen(fields[9], variant)?;
+        let mut game = Self::new_with_layout(with_verbose_tracking, layout);
+        if let Some(rules) = rules {
+            game.rules = RuleSet::from_code(rules, &game.rules)?;
+        }
+        if let Some(handicap) = handicap {
+            if !handicap.fits_rules(&game.rules) {
+                return None;
+            }
+            game.handicap = handicap;
+        }
+        game.set_progress_rules(progress_rules);
+        game.termination = termination;
+        game.board = Board::from_fen_with_layout(fields[9], layout)?;
         game.white_score = fields[0].parse().ok()?;
         game.black_score = fields[1].parse().ok()?;
         game.active_color = Color::from_fen(fields[2])?;
@@ -130,7 +190,13 @@ impl FenRepresentable for Board {
 }
 
 impl Board {
+    /// `None` for `Custom` as well; use `from_fen_with_layout` for
+    /// registered variants.
     pub fn from_fen(fen: &str, variant: GameVariant) -> Option<Self> {
+        Self::from_fen_with_layout(fen, VariantLayout::builtin(variant)?)
+    }
+
+    pub fn from_fen_with_layout(fen: &str, layout: &'static VariantLayout) -> Option<Self> {
         let lines: Vec<&str> = fen.split('/').collect();
         if lines.len() != Config::BOARD_SIZE as usize {
             return None;
@@ -159,7 +225,7 @@ impl Board {
                 }
             }
         }
-        Some(Self::from_items_array(items, variant))
+        Some(Self::from_items_array(items, layout))
     }
 }
 
@@ -240,7 +306,7 @@ impl FenRepresentable for Color {
 }
 
 impl Color {
-    fn from_fen(fen: &str) -> Option<Self> {
+    pub fn from_fen(fen: &str) -> Option<Self> {
         match fen {
             "w" => Some(Color::White),
             "b" => Some(Color::Black),
@@ -313,6 +379,9 @@ impl FenRepresentable for Event {
             Event::BombExplosion { at } => format!("be {}", at.fen()),
             Event::NextTurn { color } => format!("nt {}", color.fen()),
             Event::GameOver { winner } => format!("go {}", winner.fen()),
+            Event::GameTerminated { result, reason } => {
+                format!("gt {} {}", result.code(), reason.code())
+            }
             Event::Takeback => "z".to_string(),
             Event::UsePotion { from, to } => format!("up {} {}", from.fen(), to.fen()),
         }
@@ -402,6 +471,10 @@ impl Event {
             ["go", winner_fen] => Some(Event::GameOver {
                 winner: Color::from_fen(winner_fen)?,
             }),
+            ["gt", result_code, reason_code] => Some(Event::GameTerminated {
+                result: GameResult::from_code(result_code)?,
+                reason: TerminationReason::from_code(reason_code)?,
+            }),
             ["z"] => Some(Event::Takeback),
             ["up", from_fen, to_fen] => Some(Event::UsePotion {
                 from: Location::from_fen(from_fen)?,
@@ -0,0 +1,373 @@
+use crate::*;
+use std::fmt;
+
+/// Time budget rules for a `GameClock`. All durations are in milliseconds.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TimeControl {
+    /// A shared bank per player, topped up by `increment_ms` after each turn.
+    Fischer { initial_ms: u64, increment_ms: u64 },
+    /// A shared bank per player; the first `delay_ms` of each turn are free.
+    Bronstein { initial_ms: u64, delay_ms: u64 },
+    /// Every turn gets `turn_ms`, unused time is not carried over.
+    FixedPerTurn { turn_ms: u64 },
+}
+
+impl TimeControl {
+    fn initial_ms(&self) -> u64 {
+        match *self {
+            TimeControl::Fischer { initial_ms, .. } | TimeControl::Bronstein { initial_ms, .. } => {
+                initial_ms
+            }
+            TimeControl::FixedPerTurn { turn_ms } => turn_ms,
+        }
+    }
+}
+
+/// Formats as `fischer:300000+2000`, `bronstein:300000+2000` or
+/// `fixed:30000`, the same form `from_str` accepts.
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeControl::Fischer {
+                initial_ms,
+                increment_ms,
+            } => write!(f, "fischer:{}+{}", initial_ms, increment_ms),
+            TimeControl::Bronstein {
+                initial_ms,
+                delay_ms,
+            } => write!(f, "bronstein:{}+{}", initial_ms, delay_ms),
+            TimeControl::FixedPerTurn { turn_ms } => write!(f, "fixed:{}", turn_ms),
+        }
+    }
+}
+
+impl std::str::FromStr for TimeControl {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid time control \"{}\"", value);
+        let (kind, budget) = value.trim().split_once(':').ok_or_else(invalid)?;
+        let parse = |text: &str| text.trim().parse::<u64>().map_err(|_| invalid());
+        match kind.trim() {
+            "fischer" | "bronstein" => {
+                let (initial, extra) = budget.split_once('+').ok_or_else(invalid)?;
+                let (initial_ms, extra_ms) = (parse(initial)?, parse(extra)?);
+                if kind.trim() == "fischer" {
+                    Ok(TimeControl::Fischer {
+                        initial_ms,
+                        increment_ms: extra_ms,
+                    })
+                } else {
+                    Ok(TimeControl::Bronstein {
+                        initial_ms,
+                        delay_ms: extra_ms,
+                    })
+                }
+            }
+            "fixed" => Ok(TimeControl::FixedPerTurn {
+                turn_ms: parse(budget)?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Chess-style clock driven by caller supplied timestamps, so it behaves the
+/// same in wasm, on servers and in replays. It switches sides on
+/// `Event::NextTurn` and stops on `Event::GameOver` / `Event::GameTerminated`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameClock {
+    control: TimeControl,
+    white_remaining_ms: u64,
+    black_remaining_ms: u64,
+    running: Option<(Color, u64)>,
+    flagged: Option<Color>,
+}
+
+impl GameClock {
+    pub fn new(control: TimeControl) -> Self {
+        Self {
+            control,
+            white_remaining_ms: control.initial_ms(),
+            black_remaining_ms: control.initial_ms(),
+            running: None,
+            flagged: None,
+        }
+    }
+
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Starts the turn of `color` at `now_ms`.
+    pub fn start(&mut self, color: Color, now_ms: u64) {
+        if self.flagged.is_none() {
+            self.running = Some((color, now_ms));
+        }
+    }
+
+    pub fn running_color(&self) -> Option<Color> {
+        self.running.map(|(color, _)| color)
+    }
+
+    /// Player whose time ran out, if any.
+    pub fn flagged(&self) -> Option<Color> {
+        self.flagged
+    }
+
+    /// Time `color` has left at `now_ms`. For fixed per-turn controls this is
+    /// what remains of the current turn.
+    pub fn remaining_ms(&self, color: Color, now_ms: u64) -> u64 {
+        if self.flagged == Some(color) {
+            return 0;
+        }
+        match self.running {
+            Some((running, started_at_ms)) if running == color => self
+                .turn_budget_ms(color)
+                .saturating_sub(now_ms.saturating_sub(started_at_ms)),
+            _ => self.bank_ms(color),
+        }
+    }
+
+    /// Marks the running player as flagged once their budget is exhausted
+    /// at `now_ms`, and returns them.
+    pub fn check_flag(&mut self, now_ms: u64) -> Option<Color> {
+        if let Some((color, started_at_ms)) = self.running {
+            if now_ms.saturating_sub(started_at_ms) >= self.turn_budget_ms(color) {
+                self.flagged = Some(color);
+                self.running = None;
+                self.set_bank_ms(color, 0);
+            }
+        }
+        self.flagged
+    }
+
+    /// Charges the running player for the turn ending at `now_ms` and starts
+    /// the clock of `next`. Returns the flagged player if the turn overran.
+    pub fn end_turn(&mut self, next: Color, now_ms: u64) -> Option<Color> {
+        if self.stop(now_ms).is_some() {
+            return self.flagged;
+        }
+        self.start(next, now_ms);
+        None
+    }
+
+    /// Stops the clock at `now_ms`, charging the running player.
+    pub fn stop(&mut self, now_ms: u64) -> Option<Color> {
+        if self.check_flag(now_ms).is_some() {
+            return self.flagged;
+        }
+        if let Some((color, started_at_ms)) = self.running.take() {
+            let elapsed_ms = now_ms.saturating_sub(started_at_ms);
+            let bank_ms = self.bank_ms(color);
+            let remaining_ms = match self.control {
+                TimeControl::Fischer { increment_ms, .. } => bank_ms - elapsed_ms + increment_ms,
+                TimeControl::Bronstein { delay_ms, .. } => {
+                    bank_ms - elapsed_ms.saturating_sub(delay_ms)
+                }
+                TimeControl::FixedPerTurn { turn_ms } => turn_ms,
+            };
+            self.set_bank_ms(color, remaining_ms);
+        }
+        None
+    }
+
+    /// Advances the clock through events returned by `process_input`.
+    pub fn apply_events(&mut self, events: &[Event], now_ms: u64) -> Option<Color> {
+        for event in events {
+            let flagged = match event {
+                Event::NextTurn { color } => self.end_turn(*color, now_ms),
+                Event::GameOver { .. } | Event::GameTerminated { .. } => self.stop(now_ms),
+                _ => None,
+            };
+            if flagged.is_some() {
+                return flagged;
+            }
+        }
+        self.check_flag(now_ms)
+    }
+
+    fn turn_budget_ms(&self, color: Color) -> u64 {
+        match self.control {
+            TimeControl::Bronstein { delay_ms, .. } => self.bank_ms(color) + delay_ms,
+            TimeControl::Fischer { .. } => self.bank_ms(color),
+            TimeControl::FixedPerTurn { turn_ms } => turn_ms,
+        }
+    }
+
+    fn bank_ms(&self, color: Color) -> u64 {
+        match color {
+            Color::White => self.white_remaining_ms,
+            Color::Black => self.black_remaining_ms,
+        }
+    }
+
+    fn set_bank_ms(&mut self, color: Color, remaining_ms: u64) {
+        match color {
+            Color::White => self.white_remaining_ms = remaining_ms,
+            Color::Black => self.black_remaining_ms = remaining_ms,
+        }
+    }
+}
+
+impl MonsGame {
+    /// `process_input` for a timed game. A flag-fall detected at `now_ms`
+    /// ends the game before the input is considered; otherwise the clock
+    /// advances on the produced events.
+    pub fn process_input_timed(
+        &mut self,
+        input: Vec<Input>,
+        clock: &mut GameClock,
+        now_ms: u64,
+    ) -> Output {
+        if let Some(event) = self.check_flag_fall(clock, now_ms) {
+            return Output::Events(vec![event]);
+        }
+        let output = self.process_input(input, false, false);
+        if let Output::Events(events) = &output {
+            if let Some(flagged) = clock.apply_events(events, now_ms) {
+                self.flag_fall(flagged);
+            }
+        }
+        output
+    }
+
+    /// Ends the game if the running player of `clock` is out of time at
+    /// `now_ms`, returning the `GameTerminated` event.
+    pub fn check_flag_fall(&mut self, clock: &mut GameClock, now_ms: u64) -> Option<Event> {
+        let flagged = clock.check_flag(now_ms)?;
+        self.flag_fall(flagged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first_turn_inputs(game: &MonsGame) -> Vec<Vec<Input>> {
+        let turn = game.legal_turns_up_to(1).remove(0);
+        turn.moves
+    }
+
+    #[test]
+    fn clocks_charge_turns_by_control() {
+        let mut fischer = GameClock::new(TimeControl::Fischer {
+            initial_ms: 10_000,
+            increment_ms: 1_000,
+        });
+        fischer.start(Color::White, 0);
+        assert_eq!(fischer.remaining_ms(Color::White, 4_000), 6_000);
+        assert_eq!(fischer.end_turn(Color::Black, 4_000), None);
+        assert_eq!(fischer.remaining_ms(Color::White, 5_000), 7_000);
+        assert_eq!(fischer.remaining_ms(Color::Black, 5_000), 9_000);
+
+        let mut bronstein = GameClock::new(TimeControl::Bronstein {
+            initial_ms: 10_000,
+            delay_ms: 3_000,
+        });
+        bronstein.start(Color::White, 0);
+        assert_eq!(bronstein.end_turn(Color::Black, 2_000), None);
+        assert_eq!(bronstein.remaining_ms(Color::White, 2_000), 10_000);
+        assert_eq!(bronstein.end_turn(Color::White, 7_000), None);
+        assert_eq!(bronstein.remaining_ms(Color::Black, 7_000), 8_000);
+        assert_eq!(bronstein.check_flag(19_999), None);
+        assert_eq!(bronstein.check_flag(20_000), Some(Color::White));
+
+        let mut fixed = GameClock::new(TimeControl::FixedPerTurn { turn_ms: 5_000 });
+        fixed.start(Color::White, 0);
+        assert_eq!(fixed.end_turn(Color::Black, 4_999), None);
+        assert_eq!(fixed.remaining_ms(Color::White, 4_999), 5_000);
+        assert_eq!(fixed.end_turn(Color::White, 10_000), Some(Color::Black));
+        assert_eq!(fixed.remaining_ms(Color::Black, 10_000), 0);
+    }
+
+    #[test]
+    fn time_control_round_trips_through_text() {
+        for control in [
+            TimeControl::Fischer {
+                initial_ms: 300_000,
+                increment_ms: 2_000,
+            },
+            TimeControl::Bronstein {
+                initial_ms: 60_000,
+                delay_ms: 5_000,
+            },
+            TimeControl::FixedPerTurn { turn_ms: 30_000 },
+        ] {
+            assert_eq!(control.to_string().parse(), Ok(control));
+        }
+        assert!("fischer:300000".parse::<TimeControl>().is_err());
+    }
+
+    #[test]
+    fn flag_fall_during_timed_play_ends_game() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let mut clock = GameClock::new(TimeControl::Fischer {
+            initial_ms: 10_000,
+            increment_ms: 0,
+        });
+        clock.start(Color::White, 0);
+
+        for inputs in first_turn_inputs(&game) {
+            assert!(matches!(
+                game.process_input_timed(inputs, &mut clock, 1_000),
+                Output::Events(_)
+            ));
+        }
+        assert_eq!(game.active_color, Color::Black);
+        assert_eq!(clock.running_color(), Some(Color::Black));
+        assert_eq!(clock.remaining_ms(Color::White, 1_000), 9_000);
+
+        let black_inputs = first_turn_inputs(&game).remove(0);
+        assert_eq!(
+            game.process_input_timed(black_inputs, &mut clock, 11_000),
+            Output::Events(vec![Event::GameTerminated {
+                result: GameResult::Win(Color::White),
+                reason: TerminationReason::Timeout,
+            }])
+        );
+        assert_eq!(
+            game.termination().map(|termination| termination.code()),
+            Some("w:timeout".to_string())
+        );
+    }
+
+    #[test]
+    fn timed_replay_verifies_moves_and_keeps_flag_fall() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let mut clock = GameClock::new(TimeControl::Fischer {
+            initial_ms: 10_000,
+            increment_ms: 0,
+        });
+        clock.start(Color::White, 0);
+        let (mut moves_w, mut timestamps_w) = (Vec::new(), Vec::new());
+        for (index, inputs) in first_turn_inputs(&game).into_iter().enumerate() {
+            moves_w.push(Input::fen_from_array(&inputs));
+            timestamps_w.push((1_000 * (index + 1)).to_string());
+            game.process_input_timed(inputs, &mut clock, 1_000 * (index as u64 + 1));
+        }
+        let black_inputs = first_turn_inputs(&game).remove(0);
+        game.process_input_timed(black_inputs.clone(), &mut clock, 60_000);
+        assert_eq!(game.winner_color(), Some(Color::White));
+
+        let moves_b = Input::fen_from_array(&black_inputs);
+        let mut model = MonsGameModel::from_fen(&game.fen()).unwrap();
+        assert!(!model.verify_moves_with_timestamps(
+            &moves_w.join("-"),
+            &moves_b,
+            &timestamps_w.join("-"),
+            "",
+            "fischer:10000+0",
+        ));
+        assert!(model.verify_moves_with_timestamps(
+            &moves_w.join("-"),
+            &moves_b,
+            &timestamps_w.join("-"),
+            "60000",
+            "fischer:10000+0",
+        ));
+        assert_eq!(model.winner_color(), Some(Color::White));
+    }
+}
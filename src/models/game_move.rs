@@ -0,0 +1,320 @@
+use crate::*;
+
+/// Consumable a mon takes when it lands on a bomb-or-potion pickup.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum PickupChoice {
+    Bomb,
+    Potion,
+}
+
+impl PickupChoice {
+    pub fn modifier(self) -> Modifier {
+        match self {
+            PickupChoice::Bomb => Modifier::SelectBomb,
+            PickupChoice::Potion => Modifier::SelectPotion,
+        }
+    }
+
+    pub fn from_modifier(modifier: Modifier) -> Option<Self> {
+        match modifier {
+            Modifier::SelectBomb => Some(PickupChoice::Bomb),
+            Modifier::SelectPotion => Some(PickupChoice::Potion),
+            Modifier::Cancel => None,
+        }
+    }
+}
+
+/// A single complete input chain, described by what it does.
+///
+/// Potions are never spent on their own: an action taken once the per-turn
+/// actions are used up consumes one, which `with_potion` records.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Move {
+    MonMove {
+        from: Location,
+        to: Location,
+        pickup: Option<PickupChoice>,
+    },
+    ManaMove {
+        from: Location,
+        to: Location,
+    },
+    MysticAction {
+        from: Location,
+        target: Location,
+        with_potion: bool,
+    },
+    DemonAction {
+        from: Location,
+        target: Location,
+        /// Square the demon steps to when it cannot stay on `target`.
+        step: Option<Location>,
+        pickup: Option<PickupChoice>,
+        with_potion: bool,
+    },
+    SpiritAction {
+        from: Location,
+        target: Location,
+        destination: Location,
+        pickup: Option<PickupChoice>,
+        with_potion: bool,
+    },
+    BombAttack {
+        from: Location,
+        target: Location,
+    },
+}
+
+impl Move {
+    pub fn to_inputs(&self) -> Vec<Input> {
+        let (locations, pickup) = match *self {
+            Move::MonMove { from, to, pickup } => (vec![from, to], pickup),
+            Move::ManaMove { from, to } => (vec![from, to], None),
+            Move::MysticAction { from, target, .. } => (vec![from, target], None),
+            Move::DemonAction {
+                from,
+                target,
+                step,
+                pickup,
+                ..
+            } => (
+                [Some(from), Some(target), step]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                pickup,
+            ),
+            Move::SpiritAction {
+                from,
+                target,
+                destination,
+                pickup,
+                ..
+            } => (vec![from, target, destination], pickup),
+            Move::BombAttack { from, target } => (vec![from, target], None),
+        };
+        locations
+            .into_iter()
+            .map(Input::Location)
+            .chain(pickup.map(|pickup| Input::Modifier(pickup.modifier())))
+            .collect()
+    }
+
+    /// Move made by playing `inputs` in `game`, or `None` if they do not form
+    /// a complete legal move there.
+    pub fn from_inputs(game: &MonsGame, inputs: &[Input]) -> Option<Move> {
+        let mut after = game.clone_for_simulation();
+        match after.process_input_slice(inputs, false, false) {
+            Output::Events(events) => Self::from_applied(inputs, &events),
+            _ => None,
+        }
+    }
+
+    /// Move described by a complete input chain and the events applying it
+    /// produced.
+    pub(crate) fn from_applied(inputs: &[Input], events: &[Event]) -> Option<Move> {
+        let pickup = match inputs.last() {
+            Some(Input::Modifier(modifier)) => PickupChoice::from_modifier(*modifier),
+            _ => None,
+        };
+        let with_potion = events
+            .iter()
+            .any(|event| matches!(event, Event::UsePotion { .. }));
+        let game_move = match *events.first()? {
+            Event::MonMove { from, to, .. } => Move::MonMove { from, to, pickup },
+            Event::ManaMove { from, to, .. } => Move::ManaMove { from, to },
+            Event::MysticAction { from, to, .. } => Move::MysticAction {
+                from,
+                target: to,
+                with_potion,
+            },
+            Event::DemonAction { from, to, .. } => Move::DemonAction {
+                from,
+                target: to,
+                step: events.iter().find_map(|event| match event {
+                    Event::DemonAdditionalStep { to, .. } => Some(*to),
+                    _ => None,
+                }),
+                pickup,
+                with_potion,
+            },
+            Event::SpiritTargetMove { from, to, by, .. } => Move::SpiritAction {
+                from: by,
+                target: from,
+                destination: to,
+                pickup,
+                with_potion,
+            },
+            Event::BombAttack { from, to, .. } => Move::BombAttack { from, target: to },
+            _ => return None,
+        };
+        Some(game_move)
+    }
+}
+
+/// Moves one player made in a turn, the last of which may end it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Turn {
+    pub number: i32,
+    pub color: Color,
+    pub moves: Vec<Move>,
+}
+
+impl Turn {
+    pub fn to_inputs(&self) -> Vec<Vec<Input>> {
+        self.moves.iter().map(Move::to_inputs).collect()
+    }
+
+    /// Turn made by playing `chains` in `game`, or `None` if a chain is not a
+    /// complete legal move or an earlier one already ended the turn.
+    pub fn from_inputs(game: &MonsGame, chains: &[Vec<Input>]) -> Option<Turn> {
+        let mut after = game.clone_for_simulation();
+        let mut turn = Turn {
+            number: game.turn_number,
+            color: game.active_color,
+            moves: Vec::with_capacity(chains.len()),
+        };
+        for inputs in chains {
+            if after.turn_number != turn.number || after.is_game_over() {
+                return None;
+            }
+            let Output::Events(events) = after.process_input_slice(inputs, false, false) else {
+                return None;
+            };
+            turn.moves.push(Move::from_applied(inputs, &events)?);
+        }
+        Some(turn)
+    }
+}
+
+impl MonsGame {
+    /// Plays `game_move`, returning the resulting events. Rejects moves that
+    /// are not legal here, including ones whose `with_potion` or `pickup`
+    /// does not match what the inputs would actually do.
+    pub fn apply_move(&mut self, game_move: &Move) -> Result<Vec<Event>, InputError> {
+        let inputs = game_move.to_inputs();
+        let last = inputs.len() - 1;
+        let illegal = InputError::IllegalInput {
+            index: last,
+            input: inputs[last],
+        };
+        if Move::from_inputs(self, &inputs).as_ref() != Some(game_move) {
+            self.clone_for_simulation()
+                .process_input_checked(inputs, true, false)?;
+            return Err(illegal);
+        }
+        match self.process_input_checked(inputs, false, false)? {
+            Output::Events(events) => Ok(events),
+            _ => Err(illegal),
+        }
+    }
+
+    /// Plays every move of `turn` for the active player, stopping at the
+    /// first rejected one.
+    pub fn apply_turn(&mut self, turn: &Turn) -> Result<Vec<Event>, InputError> {
+        let mut events = Vec::new();
+        for game_move in &turn.moves {
+            events.extend(self.apply_move(game_move)?);
+        }
+        Ok(events)
+    }
+
+    /// `legal_moves`, described as `Move`s.
+    pub fn legal_typed_moves(&self) -> Vec<Move> {
+        self.legal_moves()
+            .iter()
+            .filter_map(|legal_move| Move::from_applied(&legal_move.inputs, &legal_move.events))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legal_moves_round_trip_through_move() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let mut seen_action = false;
+        for ply in 0..40 {
+            let legal_moves = game.legal_moves();
+            if legal_moves.is_empty() {
+                break;
+            }
+            for legal_move in &legal_moves {
+                let game_move = Move::from_inputs(&game, &legal_move.inputs).unwrap();
+                assert_eq!(game_move.to_inputs(), legal_move.inputs);
+                seen_action |= matches!(
+                    game_move,
+                    Move::MysticAction { .. }
+                        | Move::DemonAction { .. }
+                        | Move::SpiritAction { .. }
+                );
+            }
+            let game_move = game.legal_typed_moves()[(ply * 7) % legal_moves.len()];
+            let mut expected = game.clone_for_simulation();
+            let expected_events = match expected.process_input(game_move.to_inputs(), false, false)
+            {
+                Output::Events(events) => events,
+                output => panic!("unexpected output {:?}", output),
+            };
+            assert_eq!(game.apply_move(&game_move), Ok(expected_events));
+            assert_eq!(game.fen(), expected.fen());
+        }
+        assert!(seen_action);
+    }
+
+    #[test]
+    fn apply_move_rejects_mismatched_descriptions() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let Move::MonMove { from, to, .. } = game.legal_typed_moves()[0] else {
+            panic!("expected a mon move to open the game");
+        };
+        let fen = game.fen();
+        assert!(game.apply_move(&Move::ManaMove { from, to }).is_err());
+        assert!(game
+            .apply_move(&Move::MonMove {
+                from,
+                to,
+                pickup: Some(PickupChoice::Bomb),
+            })
+            .is_err());
+        assert_eq!(game.fen(), fen);
+    }
+
+    #[test]
+    fn turn_collects_moves_until_next_turn() {
+        let game = MonsGame::new(false, GameVariant::Classic);
+        let legal_turn = game.legal_turns_up_to(1).remove(0);
+        let turn = Turn::from_inputs(&game, &legal_turn.moves).unwrap();
+        assert_eq!(turn.color, Color::White);
+        assert_eq!(turn.to_inputs(), legal_turn.moves);
+
+        let mut played = game.clone_for_simulation();
+        played.apply_turn(&turn).unwrap();
+        assert_eq!(played.zobrist(), legal_turn.game.zobrist());
+
+        let mut too_long = legal_turn.moves.clone();
+        too_long.push(legal_turn.game.legal_move_inputs(true).remove(0));
+        assert_eq!(Turn::from_inputs(&game, &too_long), None);
+    }
+
+    #[test]
+    fn automove_output_carries_its_move() {
+        let mut model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        let before = MonsGame::from_fen(model.fen().as_str(), false).unwrap();
+        let output = model.automove();
+        let game_move = output.game_move().unwrap();
+        assert_eq!(
+            Input::fen_from_array(&game_move.to_inputs()),
+            output.input_fen()
+        );
+        assert_eq!(
+            Move::from_inputs(&before, &game_move.to_inputs()),
+            Some(game_move)
+        );
+    }
+}
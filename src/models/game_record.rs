@@ -0,0 +1,695 @@
+use crate::*;
+use std::fmt;
+
+/// One input chain of a recorded turn.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordMove {
+    pub inputs: Vec<Input>,
+    /// Move quality suffix such as `!`, `?`, `!?`.
+    pub annotation: Option<String>,
+    pub comment: Option<String>,
+    /// Engine or author evaluation, written as `[%eval N]` inside the comment.
+    pub evaluation: Option<i32>,
+    /// Alternative lines that replace this move, each starting from the
+    /// position the move was played in.
+    pub variations: Vec<Vec<RecordTurn>>,
+}
+
+/// All input chains played by `color` during turn `number`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordTurn {
+    pub number: i32,
+    pub color: Color,
+    pub moves: Vec<RecordMove>,
+}
+
+/// PGN-like game archive: metadata headers followed by a per-turn move list.
+///
+/// ```text
+/// [Variant "0"]
+/// [White "alice"]
+///
+/// 1. w l10,3;l9,3 l10,4;l9,4! {solid}
+/// 2. b l0,4;l1,4 {[%eval -3]} (2. b l0,5;l1,5 l0,6;l1,6)
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    pub variant: GameVariant,
+    pub white: Option<String>,
+    pub black: Option<String>,
+    pub date: Option<String>,
+    pub result: Option<String>,
+    pub termination: Option<String>,
+    pub clock: Option<String>,
+    /// Rules the game was played under when they are not the standard ones,
+    /// written as a `RuleSet` code.
+    pub rules: Option<RuleSet>,
+    /// Odds the game started with, written as a `Handicap` code. Applied to
+    /// the variant's initial setup when there is no start fen.
+    pub handicap: Option<Handicap>,
+    /// Repetition and no-progress limits, written as a `ProgressRules` code.
+    pub progress_rules: Option<ProgressRules>,
+    /// Start position when the game did not begin from the variant's initial setup.
+    pub start_fen: Option<String>,
+    pub extra_headers: Vec<(String, String)>,
+    pub turns: Vec<RecordTurn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordError {
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    InvalidStartFen,
+    VariantMismatch,
+    InvalidRules,
+    HandicapMismatch,
+    WrongTurn {
+        turn: usize,
+    },
+    IllegalMove {
+        turn: usize,
+        index: usize,
+        error: InputError,
+    },
+    IncompleteMove {
+        turn: usize,
+        index: usize,
+    },
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            RecordError::InvalidStartFen => write!(f, "invalid start fen"),
+            RecordError::VariantMismatch => write!(f, "start fen does not match the variant"),
+            RecordError::InvalidRules => write!(f, "invalid rules"),
+            RecordError::HandicapMismatch => {
+                write!(f, "handicap score reaches the target score of the rules")
+            }
+            RecordError::WrongTurn { turn } => {
+                write!(f, "turn {} does not match the game state", turn + 1)
+            }
+            RecordError::IllegalMove { turn, index, error } => write!(
+                f,
+                "turn {} move {} is illegal: {}",
+                turn + 1,
+                index + 1,
+                error
+            ),
+            RecordError::IncompleteMove { turn, index } => {
+                write!(f, "turn {} move {} is incomplete", turn + 1, index + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+impl GameRecord {
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let mut push_header = |key: &str, value: &str| {
+            text.push_str(format!("[{} \"{}\"]\n", key, escape(value, '"')).as_str());
+        };
+        push_header("Variant", self.variant.id().to_string().as_str());
+        let optional_headers = [
+            ("White", &self.white),
+            ("Black", &self.black),
+            ("Date", &self.date),
+            ("Result", &self.result),
+            ("Termination", &self.termination),
+            ("Clock", &self.clock),
+            ("FEN", &self.start_fen),
+        ];
+        for (key, value) in optional_headers {
+            if let Some(value) = value {
+                push_header(key, value);
+            }
+        }
+        if let Some(rules) = &self.rules {
+            push_header("Rules", rules.code(&RuleSet::STANDARD).as_str());
+        }
+        if let Some(handicap) = &self.handicap {
+            push_header("Handicap", handicap.code().as_str());
+        }
+        if let Some(progress_rules) = &self.progress_rules {
+            push_header("Progress", progress_rules.code().as_str());
+        }
+        for (key, value) in &self.extra_headers {
+            push_header(key, value);
+        }
+
+        text.push('\n');
+        for turn in &self.turns {
+            push_turn(&mut text, turn);
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, RecordError> {
+        let mut record = GameRecord::default();
+        let mut lines = text.lines().enumerate().peekable();
+
+        while let Some((index, line)) = lines.peek().copied() {
+            let line = line.trim();
+            if !line.starts_with('[') {
+                break;
+            }
+            lines.next();
+            let (key, value) = parse_header(line).ok_or_else(|| RecordError::Syntax {
+                line: index + 1,
+                message: "malformed header".to_string(),
+            })?;
+            match key.as_str() {
+                "Variant" => {
+                    record.variant = GameVariant::from_fen(value.as_str()).ok_or_else(|| {
+                        RecordError::Syntax {
+                            line: index + 1,
+                            message: format!("unknown variant `{}`", value),
+                        }
+                    })?;
+                }
+                "White" => record.white = Some(value),
+                "Black" => record.black = Some(value),
+                "Date" => record.date = Some(value),
+                "Result" => record.result = Some(value),
+                "Termination" => record.termination = Some(value),
+                "Clock" => record.clock = Some(value),
+                "FEN" => record.start_fen = Some(value),
+                "Rules" => {
+                    let rules = RuleSet::from_code(value.as_str(), &RuleSet::STANDARD).ok_or_else(
+                        || RecordError::Syntax {
+                            line: index + 1,
+                            message: format!("invalid rules `{}`", value),
+                        },
+                    )?;
+                    record.rules = Some(rules);
+                }
+                "Handicap" => {
+                    let handicap =
+                        Handicap::from_code(value.as_str()).ok_or_else(|| RecordError::Syntax {
+                            line: index + 1,
+                            message: format!("invalid handicap `{}`", value),
+                        })?;
+                    record.handicap = Some(handicap);
+                }
+                "Progress" => {
+                    let progress_rules =
+                        ProgressRules::from_code(value.as_str()).ok_or_else(|| {
+                            RecordError::Syntax {
+                                line: index + 1,
+                                message: format!("invalid progress rules `{}`", value),
+                            }
+                        })?;
+                    record.progress_rules = Some(progress_rules);
+                }
+                _ => record.extra_headers.push((key, value)),
+            }
+        }
+
+        // Open variations, innermost last; moves go to the innermost one.
+        let mut variations: Vec<Vec<RecordTurn>> = vec![];
+        let mut last_line = 0;
+        for (index, line) in lines {
+            last_line = index + 1;
+            let syntax_error = |message: &str| RecordError::Syntax {
+                line: index + 1,
+                message: message.to_string(),
+            };
+            let mut rest = line.trim_start();
+            while !rest.is_empty() {
+                let turns = variations.last_mut().unwrap_or(&mut record.turns);
+                if let Some(comment) = rest.strip_prefix('{') {
+                    let (comment, after) = take_comment(comment)
+                        .ok_or_else(|| syntax_error("unterminated comment"))?;
+                    let record_move = turns
+                        .last_mut()
+                        .and_then(|turn| turn.moves.last_mut())
+                        .ok_or_else(|| syntax_error("comment without a move"))?;
+                    let (evaluation, comment) = split_evaluation(comment.as_str())
+                        .ok_or_else(|| syntax_error("invalid evaluation"))?;
+                    record_move.evaluation = evaluation;
+                    record_move.comment = comment;
+                    rest = after.trim_start();
+                    continue;
+                }
+                if let Some(after) = rest.strip_prefix('(') {
+                    if turns.last().is_none_or(|turn| turn.moves.is_empty()) {
+                        return Err(syntax_error("variation without a move"));
+                    }
+                    variations.push(vec![]);
+                    rest = after.trim_start();
+                    continue;
+                }
+                if let Some(after) = rest.strip_prefix(')') {
+                    let variation = variations
+                        .pop()
+                        .filter(|variation| !variation.is_empty())
+                        .ok_or_else(|| syntax_error("unmatched or empty variation"))?;
+                    let turns = variations.last_mut().unwrap_or(&mut record.turns);
+                    if let Some(record_move) =
+                        turns.last_mut().and_then(|turn| turn.moves.last_mut())
+                    {
+                        record_move.variations.push(variation);
+                    }
+                    rest = after.trim_start();
+                    continue;
+                }
+
+                let end = rest.find(is_token_end).unwrap_or(rest.len());
+                let token = &rest[..end];
+                rest = rest[end..].trim_start();
+
+                if let Some(number) = token.strip_suffix('.') {
+                    let number = number
+                        .parse::<i32>()
+                        .map_err(|_| syntax_error("invalid turn number"))?;
+                    let color_end = rest.find(is_token_end).unwrap_or(rest.len());
+                    let color = Color::from_fen(&rest[..color_end])
+                        .ok_or_else(|| syntax_error("missing turn color"))?;
+                    rest = rest[color_end..].trim_start();
+                    turns.push(RecordTurn {
+                        number,
+                        color,
+                        moves: vec![],
+                    });
+                    continue;
+                }
+
+                let annotation_start = token.find(['!', '?']).unwrap_or(token.len());
+                let (inputs_fen, annotation) = token.split_at(annotation_start);
+                let inputs = inputs_fen
+                    .split(';')
+                    .map(Input::from_fen)
+                    .collect::<Option<Vec<_>>>()
+                    .filter(|inputs| !inputs.is_empty())
+                    .ok_or_else(|| syntax_error("invalid input chain"))?;
+                if !annotation.chars().all(|c| c == '!' || c == '?') {
+                    return Err(syntax_error("invalid annotation"));
+                }
+                let turn = turns
+                    .last_mut()
+                    .ok_or_else(|| syntax_error("move before turn number"))?;
+                turn.moves.push(RecordMove {
+                    inputs,
+                    annotation: (!annotation.is_empty()).then(|| annotation.to_string()),
+                    ..RecordMove::default()
+                });
+            }
+        }
+        if !variations.is_empty() {
+            return Err(RecordError::Syntax {
+                line: last_line,
+                message: "unterminated variation".to_string(),
+            });
+        }
+
+        Ok(record)
+    }
+}
+
+impl GameRecord {
+    /// Position the record's first turn is played from: the start fen when
+    /// there is one, otherwise the variant's initial setup with the record's
+    /// rules, handicap and progress rules applied.
+    pub(crate) fn start_game(&self, with_verbose_tracking: bool) -> Result<MonsGame, RecordError> {
+        let mut game = match &self.start_fen {
+            Some(fen) => MonsGame::from_fen(fen.as_str(), with_verbose_tracking)
+                .ok_or(RecordError::InvalidStartFen)?,
+            None => VariantLayout::builtin(self.variant)
+                .map(|layout| MonsGame::new_with_layout(with_verbose_tracking, layout))
+                .ok_or(RecordError::InvalidStartFen)?,
+        };
+        if game.variant() != self.variant {
+            return Err(RecordError::VariantMismatch);
+        }
+        if let Some(rules) = self.rules {
+            if !rules.is_valid() {
+                return Err(RecordError::InvalidRules);
+            }
+            if !game.set_rules(rules) {
+                return Err(RecordError::HandicapMismatch);
+            }
+        }
+        if let (None, Some(handicap)) = (&self.start_fen, &self.handicap) {
+            if !handicap.fits_rules(game.rules()) {
+                return Err(RecordError::HandicapMismatch);
+            }
+            handicap.apply(&mut game);
+        }
+        if self.progress_rules.is_some() {
+            game.set_progress_rules(self.progress_rules);
+        }
+        Ok(game)
+    }
+}
+
+/// Plays `turns` on `game`, rejecting any move that does not apply cleanly.
+/// `on_move` sees each move with its events and, for moves that carry
+/// variations, the position the move was played in.
+pub(crate) fn replay_record_turns(
+    game: &mut MonsGame,
+    turns: &[RecordTurn],
+    mut on_move: impl FnMut(&RecordMove, Vec<Event>, Option<MonsGame>) -> Result<(), RecordError>,
+) -> Result<(), RecordError> {
+    for (turn_index, turn) in turns.iter().enumerate() {
+        if turn.color != game.active_color || turn.number != game.turn_number {
+            return Err(RecordError::WrongTurn { turn: turn_index });
+        }
+        for (index, record_move) in turn.moves.iter().enumerate() {
+            let before = (!record_move.variations.is_empty()).then(|| game.clone_for_simulation());
+            let events = match game.process_input_checked(record_move.inputs.clone(), false, false)
+            {
+                Ok(Output::Events(events)) => events,
+                Ok(_) => {
+                    return Err(RecordError::IncompleteMove {
+                        turn: turn_index,
+                        index,
+                    })
+                }
+                Err(error) => {
+                    return Err(RecordError::IllegalMove {
+                        turn: turn_index,
+                        index,
+                        error,
+                    })
+                }
+            };
+            on_move(record_move, events, before)?;
+        }
+    }
+    Ok(())
+}
+
+impl MonsGame {
+    /// Replays `record` from its start position, rejecting any move that
+    /// does not apply cleanly.
+    pub fn from_record(
+        record: &GameRecord,
+        with_verbose_tracking: bool,
+    ) -> Result<MonsGame, RecordError> {
+        let mut game = record.start_game(with_verbose_tracking)?;
+        replay_record_turns(&mut game, &record.turns, |_, _, _| Ok(()))?;
+
+        let result = record.result.as_deref().and_then(GameResult::from_code);
+        let reason = record
+            .termination
+            .as_deref()
+            .and_then(TerminationReason::from_code);
+        if let (Some(result), Some(reason)) = (result, reason) {
+            game.adjudicate(result, reason);
+        }
+        Ok(game)
+    }
+
+    /// Record of `input_history`, grouped into turns. Player, date and clock
+    /// headers are left for the caller to fill in.
+    pub fn to_record(&self) -> GameRecord {
+        let variant = self.variant();
+        let start_fen = self
+            .input_history_start_fen()
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                let mut start = self.clone_for_simulation();
+                start.termination = None;
+                start.fen()
+            });
+        let termination = self.termination();
+        let mut record = GameRecord {
+            variant,
+            result: termination.map(|termination| termination.result.code()),
+            termination: termination.map(|termination| termination.reason.code().to_string()),
+            rules: (self.rules != RuleSet::STANDARD).then_some(self.rules),
+            handicap: (!self.handicap.is_empty()).then(|| self.handicap.clone()),
+            progress_rules: self.progress_rules,
+            ..GameRecord::default()
+        };
+        // Custom layouts have no initial setup to leave out, so their start
+        // fen always names them.
+        let initial = VariantLayout::builtin(variant).map(|layout| {
+            let mut initial = MonsGame::new_with_layout(false, layout);
+            initial.rules = self.rules;
+            self.handicap.apply(&mut initial);
+            initial.progress_rules = self.progress_rules;
+            initial.fen()
+        });
+        if initial.as_ref() != Some(&start_fen) {
+            record.start_fen = Some(start_fen.clone());
+        }
+
+        let Some(start) = MonsGame::from_fen(start_fen.as_str(), false) else {
+            return record;
+        };
+        let mut replay = start.clone_for_simulation();
+        for inputs in self.input_history() {
+            let number = replay.turn_number;
+            if record.turns.last().map(|turn| turn.number) != Some(number) {
+                record.turns.push(RecordTurn {
+                    number,
+                    color: replay.active_color,
+                    moves: vec![],
+                });
+            }
+            if let Some(turn) = record.turns.last_mut() {
+                turn.moves.push(RecordMove {
+                    inputs: inputs.clone(),
+                    ..RecordMove::default()
+                });
+            }
+            _ = replay.process_input_slice(inputs.as_slice(), false, false);
+        }
+        record
+    }
+}
+
+fn push_turn(text: &mut String, turn: &RecordTurn) {
+    text.push_str(format!("{}. {}", turn.number, turn.color.fen()).as_str());
+    for record_move in &turn.moves {
+        text.push(' ');
+        text.push_str(Input::fen_from_array(&record_move.inputs).as_str());
+        if let Some(annotation) = &record_move.annotation {
+            text.push_str(annotation);
+        }
+        let comment = match (record_move.evaluation, &record_move.comment) {
+            (Some(evaluation), Some(comment)) => {
+                Some(format!("[%eval {}] {}", evaluation, comment))
+            }
+            (Some(evaluation), None) => Some(format!("[%eval {}]", evaluation)),
+            (None, comment) => comment.clone(),
+        };
+        if let Some(comment) = comment {
+            text.push_str(format!(" {{{}}}", escape(comment.as_str(), '}')).as_str());
+        }
+        for variation in &record_move.variations {
+            text.push_str(" (");
+            for (index, turn) in variation.iter().enumerate() {
+                if index > 0 {
+                    text.push(' ');
+                }
+                push_turn(text, turn);
+            }
+            text.push(')');
+        }
+    }
+}
+
+fn is_token_end(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '(' | ')' | '{')
+}
+
+/// Splits a leading `[%eval N]` off a comment. `None` if it is malformed.
+fn split_evaluation(comment: &str) -> Option<(Option<i32>, Option<String>)> {
+    let Some(evaluation) = comment.strip_prefix("[%eval ") else {
+        return Some((None, Some(comment.to_string())));
+    };
+    let (evaluation, comment) = evaluation.split_once(']')?;
+    let comment = comment.trim_start();
+    Some((
+        Some(evaluation.trim().parse().ok()?),
+        (!comment.is_empty()).then(|| comment.to_string()),
+    ))
+}
+
+fn escape(value: &str, delimiter: char) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            // The parser reads line by line, so line breaks must not reach the text.
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ if c == '\\' || c == delimiter => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn parse_header(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (key, value) = inner.split_once(' ')?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((key.to_string(), unescape(value)))
+}
+
+fn take_comment(text: &str) -> Option<(String, &str)> {
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '}' => return Some((unescape(&text[..index]), &text[index + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some(next) => unescaped.push(next),
+                None => {}
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play_moves(game: &mut MonsGame, count: usize) {
+        for ply in 0..count {
+            let legal_moves = game.legal_move_inputs(true);
+            let Some(inputs) = legal_moves.get((ply * 3) % legal_moves.len().max(1)) else {
+                return;
+            };
+            assert!(matches!(
+                game.process_input(inputs.clone(), false, false),
+                Output::Events(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn record_round_trips_through_text_and_replay() {
+        let mut game = MonsGame::new(false, GameVariant::SwappedManaRows);
+        play_moves(&mut game, 12);
+        let history_len = game.input_history().len();
+        if game.can_takeback(game.active_color) {
+            assert!(matches!(
+                game.process_input(vec![Input::Takeback], false, false),
+                Output::Events(_)
+            ));
+            assert_eq!(game.input_history().len(), history_len - 1);
+        }
+
+        let mut record = game.to_record();
+        assert!(record.start_fen.is_none());
+        assert!(record.turns.len() > 1);
+        record.white = Some("alice \"the bold\"".to_string());
+        record.date = Some("2026.01.02".to_string());
+        record
+            .extra_headers
+            .push(("Event".to_string(), "casual".to_string()));
+        record.turns[0].moves[0].annotation = Some("!?".to_string());
+        record.turns[1].moves[0].comment = Some("solid {really}".to_string());
+
+        let parsed = GameRecord::from_text(record.to_text().as_str()).unwrap();
+        assert_eq!(parsed, record);
+
+        let replayed = MonsGame::from_record(&parsed, false).unwrap();
+        assert_eq!(replayed.fen(), game.fen());
+        assert_eq!(replayed.to_record().turns.len(), record.turns.len());
+    }
+
+    #[test]
+    fn record_round_trips_multi_line_header_and_comment() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        play_moves(&mut game, 2);
+
+        let mut record = game.to_record();
+        record.white = Some("Alice\nSmith".to_string());
+        record
+            .extra_headers
+            .push(("Site".to_string(), "a\r\nb \\n".to_string()));
+        record.turns[0].moves[0].comment = Some("line one\nline two\\".to_string());
+
+        let text = record.to_text();
+        assert_eq!(GameRecord::from_text(text.as_str()).unwrap(), record);
+    }
+
+    #[test]
+    fn record_keeps_custom_start_position_and_rejects_illegal_moves() {
+        let mut opening = MonsGame::new(false, GameVariant::Classic);
+        play_moves(&mut opening, 4);
+        let mut game = MonsGame::from_fen(opening.fen().as_str(), false).unwrap();
+        play_moves(&mut game, 4);
+
+        let mut record = game.to_record();
+        assert_eq!(record.start_fen, Some(opening.fen()));
+        assert_eq!(
+            MonsGame::from_record(&record, false).unwrap().fen(),
+            game.fen()
+        );
+
+        record.turns[0].moves[0].inputs = vec![Input::Location(Location::new(5, 5))];
+        assert!(matches!(
+            MonsGame::from_record(&record, false),
+            Err(RecordError::IllegalMove {
+                turn: 0,
+                index: 0,
+                ..
+            })
+        ));
+        assert!(matches!(
+            GameRecord::from_text("1. w l10,3;l9,3 {unterminated"),
+            Err(RecordError::Syntax { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn record_keeps_non_score_termination() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        play_moves(&mut game, 6);
+        game.resign(game.active_color);
+
+        let record = game.to_record();
+        assert_eq!(record.termination.as_deref(), Some("resign"));
+        let parsed = GameRecord::from_text(record.to_text().as_str()).unwrap();
+        let replayed = MonsGame::from_record(&parsed, false).unwrap();
+        assert_eq!(replayed.termination(), game.termination());
+        assert_eq!(replayed.fen(), game.fen());
+    }
+
+    #[test]
+    fn record_rejects_invalid_rules() {
+        let mut record = MonsGame::new(false, GameVariant::Classic).to_record();
+        record.rules = Some(RuleSet {
+            faint_cooldown: 0,
+            ..RuleSet::STANDARD
+        });
+        assert_eq!(
+            MonsGame::from_record(&record, false).map(|game| game.fen()),
+            Err(RecordError::InvalidRules)
+        );
+    }
+}
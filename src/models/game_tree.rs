@@ -0,0 +1,494 @@
+use crate::*;
+
+/// One applied input chain in a `GameTree`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GameTreeNode {
+    pub inputs: Vec<Input>,
+    pub events: Vec<Event>,
+    /// Move quality suffix such as `!`, `?`, `!?`.
+    pub annotation: Option<String>,
+    pub comment: Option<String>,
+    pub evaluation: Option<i32>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+impl GameTreeNode {
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    /// Continuations of this node. The first one is the main line, the rest
+    /// are variations.
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+}
+
+/// Branching move tree for analysis boards and post-game review. Node
+/// `GameTree::ROOT` is the start position and holds no inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameTree {
+    start_fen: String,
+    nodes: Vec<GameTreeNode>,
+}
+
+impl GameTree {
+    pub const ROOT: usize = 0;
+
+    /// Empty tree rooted at the current position of `game`.
+    pub fn new(game: &MonsGame) -> Self {
+        let mut start = game.clone_for_simulation();
+        start.termination = None;
+        Self {
+            start_fen: start.fen(),
+            nodes: vec![GameTreeNode::default()],
+        }
+    }
+
+    /// Tree whose main line is the `input_history` of `game`.
+    pub fn from_game(game: &MonsGame) -> Self {
+        let mut start = game.clone_for_simulation();
+        start.termination = None;
+        let start_fen = game
+            .input_history_start_fen()
+            .map(str::to_string)
+            .unwrap_or_else(|| start.fen());
+        let mut tree = Self {
+            start_fen,
+            nodes: vec![GameTreeNode::default()],
+        };
+        let mut parent = Self::ROOT;
+        for inputs in game.input_history() {
+            match tree.add_move(parent, inputs.clone()) {
+                Some(node) => parent = node,
+                None => break,
+            }
+        }
+        tree
+    }
+
+    pub fn start_fen(&self) -> &str {
+        self.start_fen.as_str()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.len() <= 1
+    }
+
+    pub fn node(&self, id: usize) -> Option<&GameTreeNode> {
+        self.nodes.get(id)
+    }
+
+    /// Plays `inputs` after `parent` and returns the new node, or the
+    /// existing child if that chain was already added. `None` if `parent`
+    /// does not exist or `inputs` is not a complete legal move there.
+    pub fn add_move(&mut self, parent: usize, inputs: Vec<Input>) -> Option<usize> {
+        if let Some(existing) = self.child_with_inputs(self.nodes.get(parent)?, &inputs) {
+            return Some(existing);
+        }
+        let mut game = self.game_at(parent, false)?;
+        let Ok(Output::Events(events)) = game.process_input_checked(inputs.clone(), false, false)
+        else {
+            return None;
+        };
+        Some(self.push_node(parent, inputs, events))
+    }
+
+    /// Makes the line through `id` the main line at every branching point
+    /// above it. Returns whether anything changed.
+    pub fn promote_variation(&mut self, id: usize) -> bool {
+        let mut promoted = false;
+        for node in self.path_to(id) {
+            let Some(parent) = self.nodes[node].parent else {
+                continue;
+            };
+            let children = &mut self.nodes[parent].children;
+            if let Some(position) = children.iter().position(|&child| child == node) {
+                if position > 0 {
+                    children.remove(position);
+                    children.insert(0, node);
+                    promoted = true;
+                }
+            }
+        }
+        promoted
+    }
+
+    /// Nodes from the root along first children, not including the root.
+    pub fn mainline(&self) -> Vec<usize> {
+        let mut line = vec![];
+        let mut node = Self::ROOT;
+        while let Some(&child) = self.nodes[node].children.first() {
+            line.push(child);
+            node = child;
+        }
+        line
+    }
+
+    /// Nodes from the root down to `id`, not including the root. Empty if
+    /// `id` does not exist.
+    pub fn path_to(&self, id: usize) -> Vec<usize> {
+        let mut path = vec![];
+        let mut node = id;
+        while let Some(parent) = self.nodes.get(node).and_then(|node| node.parent) {
+            path.push(node);
+            node = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Position after the move at `id`, replayed from the start position.
+    pub fn game_at(&self, id: usize, with_verbose_tracking: bool) -> Option<MonsGame> {
+        if id >= self.nodes.len() {
+            return None;
+        }
+        let mut game = MonsGame::from_fen(self.start_fen.as_str(), with_verbose_tracking)?;
+        for node in self.path_to(id) {
+            let inputs = self.nodes[node].inputs.clone();
+            if !matches!(
+                game.process_input_checked(inputs, false, false),
+                Ok(Output::Events(_))
+            ) {
+                return None;
+            }
+        }
+        Some(game)
+    }
+
+    pub fn set_comment(&mut self, id: usize, comment: Option<String>) -> bool {
+        match self.nodes.get_mut(id) {
+            Some(node) if id != Self::ROOT => {
+                node.comment = comment;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn set_evaluation(&mut self, id: usize, evaluation: Option<i32>) -> bool {
+        match self.nodes.get_mut(id) {
+            Some(node) if id != Self::ROOT => {
+                node.evaluation = evaluation;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Rebuilds the tree from `record`, replaying the main line and every
+    /// nested variation.
+    pub fn from_record(record: &GameRecord) -> Result<Self, RecordError> {
+        let start = record.start_game(false)?;
+        let mut tree = Self::new(&start);
+        tree.add_record_line(Self::ROOT, start, &record.turns)?;
+        Ok(tree)
+    }
+
+    /// Record of the whole tree with variations nested under the moves they
+    /// replace. Headers other than the variant and start position are left
+    /// for the caller to fill in.
+    pub fn to_record(&self) -> GameRecord {
+        let mut record = GameRecord {
+            variant: self.variant(),
+            ..GameRecord::default()
+        };
+        let initial = VariantLayout::builtin(record.variant)
+            .map(|layout| MonsGame::new_with_layout(false, layout).fen());
+        if initial.as_ref() != Some(&self.start_fen) {
+            record.start_fen = Some(self.start_fen.clone());
+        }
+        if let Some(start) = MonsGame::from_fen(self.start_fen.as_str(), false) {
+            record.turns = self.record_line(Self::ROOT, 0, start);
+        }
+        record
+    }
+
+    fn variant(&self) -> GameVariant {
+        MonsGame::from_fen(self.start_fen.as_str(), false)
+            .map(|game| game.variant())
+            .unwrap_or_default()
+    }
+
+    fn child_with_inputs(&self, parent: &GameTreeNode, inputs: &[Input]) -> Option<usize> {
+        parent
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].inputs == inputs)
+    }
+
+    fn push_node(&mut self, parent: usize, inputs: Vec<Input>, events: Vec<Event>) -> usize {
+        let id = self.nodes.len();
+        self.nodes.push(GameTreeNode {
+            inputs,
+            events,
+            parent: Some(parent),
+            ..GameTreeNode::default()
+        });
+        self.nodes[parent].children.push(id);
+        id
+    }
+
+    fn add_record_line(
+        &mut self,
+        mut parent: usize,
+        mut game: MonsGame,
+        turns: &[RecordTurn],
+    ) -> Result<(), RecordError> {
+        replay_record_turns(&mut game, turns, |record_move, events, before| {
+            // A move the tree already has keeps its own notes and only takes
+            // the ones it is missing.
+            let node = match self.child_with_inputs(&self.nodes[parent], &record_move.inputs) {
+                Some(existing) => existing,
+                None => self.push_node(parent, record_move.inputs.clone(), events),
+            };
+            let tree_node = &mut self.nodes[node];
+            if tree_node.annotation.is_none() {
+                tree_node.annotation = record_move.annotation.clone();
+            }
+            if tree_node.comment.is_none() {
+                tree_node.comment = record_move.comment.clone();
+            }
+            if tree_node.evaluation.is_none() {
+                tree_node.evaluation = record_move.evaluation;
+            }
+            if let Some(before) = before {
+                for variation in &record_move.variations {
+                    self.add_record_line(parent, before.clone_for_simulation(), variation)?;
+                }
+            }
+            parent = node;
+            Ok(())
+        })
+    }
+
+    /// Turns along the first children below `parent`, with the remaining
+    /// children of each node written as variations of its main move.
+    /// `child_index` picks which child of `parent` starts the line.
+    fn record_line(
+        &self,
+        parent: usize,
+        child_index: usize,
+        mut game: MonsGame,
+    ) -> Vec<RecordTurn> {
+        let mut turns: Vec<RecordTurn> = vec![];
+        let mut parent = parent;
+        let mut next = self.nodes[parent].children.get(child_index).copied();
+        while let Some(node) = next {
+            let before = game.clone_for_simulation();
+            if turns.last().map(|turn| turn.number) != Some(game.turn_number) {
+                turns.push(RecordTurn {
+                    number: game.turn_number,
+                    color: game.active_color,
+                    moves: vec![],
+                });
+            }
+            let tree_node = &self.nodes[node];
+            _ = game.process_input_slice(tree_node.inputs.as_slice(), false, false);
+            let variations = if node == self.nodes[parent].children[0] {
+                (1..self.nodes[parent].children.len())
+                    .map(|index| self.record_line(parent, index, before.clone_for_simulation()))
+                    .collect()
+            } else {
+                vec![]
+            };
+            if let Some(turn) = turns.last_mut() {
+                turn.moves.push(RecordMove {
+                    inputs: tree_node.inputs.clone(),
+                    annotation: tree_node.annotation.clone(),
+                    comment: tree_node.comment.clone(),
+                    evaluation: tree_node.evaluation,
+                    variations,
+                });
+            }
+            parent = node;
+            next = tree_node.children.first().copied();
+        }
+        turns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first_moves(game: &MonsGame, count: usize) -> Vec<Vec<Input>> {
+        let mut game = game.clone_for_simulation();
+        let mut moves = vec![];
+        for _ in 0..count {
+            let inputs = game.legal_move_inputs(true).remove(0);
+            game.process_input(inputs.clone(), false, false);
+            moves.push(inputs);
+        }
+        moves
+    }
+
+    #[test]
+    fn variations_can_be_added_promoted_and_walked() {
+        let game = MonsGame::new(false, GameVariant::Classic);
+        let mut tree = GameTree::new(&game);
+        let mut node = GameTree::ROOT;
+        for inputs in first_moves(&game, 3) {
+            node = tree.add_move(node, inputs).unwrap();
+        }
+        let mainline = tree.mainline();
+        assert_eq!(mainline.len(), 3);
+        assert!(!tree.node(mainline[2]).unwrap().events.is_empty());
+
+        let alternative = game.legal_move_inputs(true).pop().unwrap();
+        let variation = tree.add_move(GameTree::ROOT, alternative.clone()).unwrap();
+        assert_eq!(tree.add_move(GameTree::ROOT, alternative), Some(variation));
+        assert_eq!(tree.add_move(GameTree::ROOT, vec![]), None);
+        assert_eq!(tree.node(GameTree::ROOT).unwrap().children()[1], variation);
+        assert_eq!(tree.mainline(), mainline);
+
+        assert!(tree.promote_variation(variation));
+        assert!(!tree.promote_variation(variation));
+        assert_eq!(tree.mainline(), vec![variation]);
+        assert_eq!(tree.path_to(mainline[2]), mainline);
+        assert_eq!(
+            tree.game_at(variation, false)
+                .unwrap()
+                .input_history()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn tree_round_trips_through_record_text() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        for inputs in game.legal_turns_up_to(1).remove(0).moves {
+            game.process_input(inputs, false, false);
+        }
+        for inputs in first_moves(&game, 2) {
+            game.process_input(inputs, false, false);
+        }
+        let mut tree = GameTree::from_game(&game);
+        let mainline = tree.mainline();
+        assert_eq!(mainline.len(), game.input_history().len());
+
+        let branch_point = mainline[mainline.len() - 3];
+        let branch_game = tree.game_at(branch_point, false).unwrap();
+        let mut node = branch_point;
+        for inputs in branch_game
+            .legal_move_inputs(true)
+            .into_iter()
+            .rev()
+            .take(1)
+        {
+            node = tree.add_move(node, inputs).unwrap();
+        }
+        for inputs in first_moves(&tree.game_at(node, false).unwrap(), 2) {
+            node = tree.add_move(node, inputs).unwrap();
+        }
+        let nested = tree
+            .add_move(
+                tree.node(node).unwrap().parent().unwrap(),
+                tree.game_at(tree.node(node).unwrap().parent().unwrap(), false)
+                    .unwrap()
+                    .legal_move_inputs(true)
+                    .pop()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(tree.set_comment(nested, Some("sharp (really)".to_string())));
+        assert!(tree.set_evaluation(nested, Some(-4)));
+        assert!(tree.set_evaluation(mainline[0], Some(2)));
+        assert!(!tree.set_comment(GameTree::ROOT, None));
+
+        let text = tree.to_record().to_text();
+        assert!(text.contains('('));
+        assert!(text.contains("[%eval -4] sharp (really)"));
+        let parsed = GameTree::from_record(&GameRecord::from_text(text.as_str()).unwrap()).unwrap();
+        assert_eq!(parsed.to_record().to_text(), text);
+        assert_eq!(parsed.len(), tree.len());
+        assert_eq!(parsed.mainline().len(), mainline.len());
+
+        let record = GameRecord::from_text(text.as_str()).unwrap();
+        assert_eq!(
+            MonsGame::from_record(&record, false).unwrap().fen(),
+            game.fen()
+        );
+        assert!(matches!(
+            GameRecord::from_text("1. w l10,3;l9,3 (1. w l10,4;l9,4"),
+            Err(RecordError::Syntax { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn tree_from_record_merges_repeated_moves_into_one_node() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        for inputs in first_moves(&game, 2) {
+            game.process_input(inputs, false, false);
+        }
+        let mut record = game.to_record();
+        let alternative = MonsGame::new(false, GameVariant::Classic)
+            .legal_move_inputs(true)
+            .pop()
+            .unwrap();
+        let first_turn = &record.turns[0];
+        let repeated_mainline = RecordTurn {
+            moves: vec![RecordMove {
+                comment: Some("same move".to_string()),
+                annotation: Some("!".to_string()),
+                ..first_turn.moves[0].clone()
+            }],
+            ..first_turn.clone()
+        };
+        let alternative_line = RecordTurn {
+            moves: vec![RecordMove {
+                inputs: alternative,
+                ..RecordMove::default()
+            }],
+            ..first_turn.clone()
+        };
+        record.turns[0].moves[0].comment = Some("main".to_string());
+        record.turns[0].moves[0].variations = vec![
+            vec![repeated_mainline],
+            vec![alternative_line.clone()],
+            vec![alternative_line],
+        ];
+
+        let tree = GameTree::from_record(&record).unwrap();
+        assert_eq!(tree.len(), 4);
+        let root_children = tree.node(GameTree::ROOT).unwrap().children();
+        assert_eq!(root_children.len(), 2);
+        let main = tree.node(root_children[0]).unwrap();
+        assert_eq!(main.comment.as_deref(), Some("main"));
+        assert_eq!(main.annotation.as_deref(), Some("!"));
+        assert_eq!(tree.to_record().turns[0].moves[0].variations.len(), 1);
+    }
+
+    #[test]
+    fn tree_from_record_starts_under_the_record_rules_and_handicap() {
+        let handicap = Handicap::from_code("b:score=2").unwrap();
+        let mut game = MonsGame::new_with_handicap(false, GameVariant::Classic, &handicap).unwrap();
+        assert!(game.set_rules(RuleSet::from_code("mons=3", &RuleSet::STANDARD).unwrap()));
+        for inputs in game.legal_turns_up_to(1).remove(0).moves {
+            game.process_input(inputs, false, false);
+        }
+        for inputs in first_moves(&game, 2) {
+            game.process_input(inputs, false, false);
+        }
+
+        let record = game.to_record();
+        assert!(record.start_fen.is_none());
+        assert!(record.rules.is_some() && record.handicap.is_some());
+        let tree = GameTree::from_record(&record).unwrap();
+        let mainline = tree.mainline();
+        assert_eq!(mainline.len(), game.input_history().len());
+        assert_eq!(
+            tree.game_at(*mainline.last().unwrap(), false)
+                .unwrap()
+                .fen(),
+            game.fen()
+        );
+    }
+}
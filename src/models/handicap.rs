@@ -0,0 +1,310 @@
+use crate::*;
+
+/// Odds one side starts a game with.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct SideHandicap {
+    /// Mons taken off their bases before the first move.
+    pub missing_mons: Vec<MonKind>,
+    /// Potions the side starts with.
+    pub extra_potions: i32,
+    /// Points the side starts with.
+    pub score_offset: i32,
+    /// Mon moves allowed on the side's first turn, capped by the rules.
+    pub first_turn_mon_moves: Option<i32>,
+}
+
+impl SideHandicap {
+    pub fn is_empty(&self) -> bool {
+        *self == SideHandicap::default()
+    }
+
+    fn is_valid(&self) -> bool {
+        let mut seen = Vec::new();
+        let unique_mons = self.missing_mons.iter().all(|kind| {
+            let is_new = !seen.contains(kind);
+            seen.push(*kind);
+            is_new
+        });
+        unique_mons
+            && self.extra_potions >= 0
+            && self.score_offset >= 0
+            && self.first_turn_mon_moves.is_none_or(|moves| moves >= 1)
+    }
+
+    fn code_entries(&self) -> Vec<String> {
+        let mut entries: Vec<String> = self
+            .missing_mons
+            .iter()
+            .map(|kind| format!("-{}", mon_kind_name(*kind)))
+            .collect();
+        if self.extra_potions != 0 {
+            entries.push(format!("potions={}", self.extra_potions));
+        }
+        if self.score_offset != 0 {
+            entries.push(format!("score={}", self.score_offset));
+        }
+        if let Some(moves) = self.first_turn_mon_moves {
+            entries.push(format!("first={}", moves));
+        }
+        entries
+    }
+
+    fn apply_entry(&mut self, entry: &str) -> Option<()> {
+        if let Some(name) = entry.strip_prefix('-') {
+            let kind = MON_KINDS
+                .into_iter()
+                .find(|kind| mon_kind_name(*kind) == name)?;
+            self.missing_mons.push(kind);
+            return Some(());
+        }
+        let (key, value) = entry.split_once('=')?;
+        let value = value.parse().ok()?;
+        match key {
+            "potions" => self.extra_potions = value,
+            "score" => self.score_offset = value,
+            "first" => self.first_turn_mon_moves = Some(value),
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+/// Material and tempo odds applied to the initial setup, for coaching and
+/// rating-balanced games.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Handicap {
+    pub white: SideHandicap,
+    pub black: SideHandicap,
+}
+
+impl Handicap {
+    pub fn side(&self, color: Color) -> &SideHandicap {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+
+    pub fn side_mut(&mut self, color: Color) -> &mut SideHandicap {
+        match color {
+            Color::White => &mut self.white,
+            Color::Black => &mut self.black,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.white.is_empty() && self.black.is_empty()
+    }
+
+    /// Whether no mon is listed twice and every number is in range: no
+    /// negative potions or score, and at least one move on a limited turn.
+    pub fn is_valid(&self) -> bool {
+        self.white.is_valid() && self.black.is_valid()
+    }
+
+    /// Whether both sides start short of `rules.target_score`, so the game
+    /// is not over before its first move.
+    pub fn fits_rules(&self, rules: &RuleSet) -> bool {
+        self.white.score_offset < rules.target_score && self.black.score_offset < rules.target_score
+    }
+
+    /// Comma separated entries prefixed with the side they apply to, such as
+    /// `w:-drainer,b:potions=1,b:first=1`. Empty when there is no handicap.
+    pub fn code(&self) -> String {
+        [Color::White, Color::Black]
+            .into_iter()
+            .flat_map(|color| {
+                self.side(color)
+                    .code_entries()
+                    .into_iter()
+                    .map(move |entry| format!("{}:{}", color.fen(), entry))
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    pub fn from_code(code: &str) -> Option<Handicap> {
+        let mut handicap = Handicap::default();
+        for entry in code.split(',').filter(|entry| !entry.is_empty()) {
+            let (color, entry) = entry.split_once(':')?;
+            handicap
+                .side_mut(Color::from_fen(color)?)
+                .apply_entry(entry)?;
+        }
+        handicap.is_valid().then_some(handicap)
+    }
+
+    /// Takes the missing mons off their bases and hands out the starting
+    /// potions and points. Meant for a game still in its initial setup.
+    pub(crate) fn apply(&self, game: &mut MonsGame) {
+        for color in [Color::White, Color::Black] {
+            let side = self.side(color);
+            for kind in &side.missing_mons {
+                let base = game.variant_layout().mon_base(*kind, color);
+                let is_on_base = game
+                    .board
+                    .item(base)
+                    .and_then(|item| item.mon())
+                    .is_some_and(|mon| mon.kind == *kind && mon.color == color);
+                if is_on_base {
+                    game.board.remove_item(base);
+                }
+            }
+            match color {
+                Color::White => {
+                    game.white_potions_count += side.extra_potions;
+                    game.white_score += side.score_offset;
+                }
+                Color::Black => {
+                    game.black_potions_count += side.extra_potions;
+                    game.black_score += side.score_offset;
+                }
+            }
+        }
+        game.handicap = self.clone();
+        game.invalidate_process_input_cache();
+    }
+}
+
+impl MonsGame {
+    /// Initial setup of `variant` with `handicap` applied. `None` for
+    /// `Custom`, or when the handicap is not valid or does not fit the
+    /// variant's rules.
+    pub fn new_with_handicap(
+        with_verbose_tracking: bool,
+        variant: GameVariant,
+        handicap: &Handicap,
+    ) -> Option<Self> {
+        let layout = VariantLayout::builtin(variant)?;
+        let mut game = MonsGame::new_with_layout(with_verbose_tracking, layout);
+        if !handicap.is_valid() || !handicap.fits_rules(game.rules()) {
+            return None;
+        }
+        handicap.apply(&mut game);
+        Some(game)
+    }
+
+    pub fn handicap(&self) -> &Handicap {
+        &self.handicap
+    }
+
+    /// Mon moves the active player gets this turn.
+    pub(crate) fn mons_moves_limit(&self) -> i32 {
+        let first_turn_number = match self.active_color {
+            Color::White => 1,
+            Color::Black => 2,
+        };
+        match self.handicap.side(self.active_color).first_turn_mon_moves {
+            Some(moves) if self.turn_number == first_turn_number => {
+                moves.min(self.rules.mons_moves_per_turn)
+            }
+            _ => self.rules.mons_moves_per_turn,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn odds() -> Handicap {
+        Handicap {
+            white: SideHandicap {
+                missing_mons: vec![MonKind::Drainer],
+                first_turn_mon_moves: Some(2),
+                ..SideHandicap::default()
+            },
+            black: SideHandicap {
+                extra_potions: 1,
+                score_offset: 2,
+                ..SideHandicap::default()
+            },
+        }
+    }
+
+    #[test]
+    fn handicap_codes_round_trip() {
+        let handicap = odds();
+        let code = handicap.code();
+        assert_eq!(code, "w:-drainer,w:first=2,b:potions=1,b:score=2");
+        assert_eq!(Handicap::from_code(code.as_str()), Some(handicap.clone()));
+        assert_eq!(Handicap::from_code(""), Some(Handicap::default()));
+        assert_eq!(Handicap::from_code("w:-drainer,w:-drainer"), None);
+        assert_eq!(Handicap::from_code("b:first=0"), None);
+        assert_eq!(Handicap::from_code("x:score=1"), None);
+        assert_eq!(Handicap::from_code("w:-dragon"), None);
+
+        let game = MonsGame::new_with_handicap(false, GameVariant::Classic, &handicap).unwrap();
+        let fen = game.fen();
+        assert!(fen.ends_with(format!(" +{}", code).as_str()));
+        let loaded = MonsGame::from_fen(fen.as_str(), false).unwrap();
+        assert_eq!(loaded.handicap(), &handicap);
+        assert_eq!(loaded.fen(), fen);
+        assert_eq!(
+            MonsGame::from_bytes(&game.to_bytes(), false).map(|game| game.fen()),
+            Some(fen)
+        );
+
+        let record = game.to_record();
+        assert_eq!(record.handicap, Some(handicap.clone()));
+        assert_eq!(record.start_fen, None);
+        let parsed = GameRecord::from_text(record.to_text().as_str()).unwrap();
+        assert_eq!(
+            MonsGame::from_record(&parsed, false).map(|game| game.fen()),
+            Ok(game.fen())
+        );
+    }
+
+    #[test]
+    fn handicap_shapes_the_initial_setup_and_first_turns() {
+        let game = MonsGame::new_with_handicap(false, GameVariant::Classic, &odds()).unwrap();
+        let drainer_base = Config::mon_base(MonKind::Drainer, Color::White);
+        assert_eq!(game.board.item(drainer_base), None);
+        assert_eq!(game.black_potions_count, 1);
+        assert_eq!(game.black_score, 2);
+        assert_eq!(game.validate_position(), vec![]);
+        assert_eq!(game.available_move_kinds()[&AvailableMoveKind::MonMove], 2);
+
+        let mut game = game;
+        for _ in 0..2 {
+            let inputs = game.legal_move_inputs(true).remove(0);
+            game.process_input(inputs, false, false);
+        }
+        assert_eq!(game.active_color, Color::Black);
+        assert_eq!(
+            game.available_move_kinds()[&AvailableMoveKind::MonMove],
+            Config::MONS_MOVES_PER_TURN
+        );
+        let fresh = MonsGame::new_with_handicap(false, GameVariant::Classic, &odds()).unwrap();
+        assert_eq!(game.initial_game(false).fen(), fresh.fen());
+    }
+
+    #[test]
+    fn handicap_score_stays_below_the_target_score() {
+        let head_start = Handicap::from_code("b:score=4").unwrap();
+        let mut game =
+            MonsGame::new_with_handicap(false, GameVariant::Classic, &head_start).unwrap();
+        let short = RuleSet::from_code("target=4", &RuleSet::STANDARD).unwrap();
+        assert!(!game.set_rules(short));
+        assert_eq!(game.rules(), &RuleSet::STANDARD);
+
+        let reaching = Handicap::from_code("b:score=5").unwrap();
+        assert!(!reaching.fits_rules(&RuleSet::STANDARD));
+        assert!(MonsGame::new_with_handicap(false, GameVariant::Classic, &reaching).is_none());
+
+        let fen = game.fen().replace("+b:score=4", "~target=4 +b:score=4");
+        assert!(MonsGame::from_fen(fen.as_str(), false).is_none());
+
+        let mut record = game.to_record();
+        assert!(MonsGame::from_record(&record, false).is_ok());
+        record.rules = Some(short);
+        assert_eq!(
+            MonsGame::from_record(&record, false).map(|game| game.fen()),
+            Err(RecordError::HandicapMismatch)
+        );
+        game.set_rules(RuleSet::from_code("target=6", &RuleSet::STANDARD).unwrap());
+        assert!(MonsGame::from_bytes(&game.to_bytes(), false).is_some());
+    }
+}
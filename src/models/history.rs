@@ -0,0 +1,191 @@
+use crate::*;
+
+/// Full line of input chains a game can be scrubbed along. The game's own
+/// `input_history` is always a prefix of it; the rest can be redone.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HistoryLine {
+    inputs: Vec<Vec<Input>>,
+    /// Termination at the end of the line that replaying cannot reproduce,
+    /// such as a resignation.
+    termination: Option<Termination>,
+}
+
+impl HistoryLine {
+    /// Brings the line in sync with `game`, starting a new line from its
+    /// `input_history` when the game is at the end or left the old line.
+    pub(crate) fn sync(&mut self, game: &MonsGame) {
+        if !self.contains_inside(game) {
+            self.inputs = game.input_history().to_vec();
+            self.termination = game
+                .termination
+                .filter(|termination| termination.reason != TerminationReason::TargetScore);
+        }
+    }
+
+    /// Length of the line `sync` would leave for `game`.
+    pub(crate) fn len(&self, game: &MonsGame) -> usize {
+        if self.contains_inside(game) {
+            self.inputs.len()
+        } else {
+            game.input_history().len()
+        }
+    }
+
+    fn contains_inside(&self, game: &MonsGame) -> bool {
+        let played = game.input_history();
+        played.len() < self.inputs.len()
+            && self.inputs.starts_with(played)
+            && game.termination.is_none()
+    }
+
+    /// Position after the first `ply` input chains of the line, or `None` if
+    /// the line is shorter or a chain no longer applies.
+    pub(crate) fn game_at(&self, game: &MonsGame, ply: usize) -> Option<MonsGame> {
+        let mut replay = game.replay_from_history_start(self.inputs.get(..ply)?)?;
+        if ply == self.inputs.len() {
+            if let Some(termination) = self.termination {
+                replay.adjudicate(termination.result, termination.reason);
+            }
+        }
+        Some(replay)
+    }
+
+    /// Ply at which the turn containing `ply` started, or the previous turn
+    /// if `ply` is already a turn start.
+    pub(crate) fn previous_turn_start(&self, game: &MonsGame, ply: usize) -> Option<usize> {
+        let mut replay = game.history_start(false)?;
+        replay.set_takeback_history_tracking(false);
+        let mut turn_start = 0;
+        for (index, inputs) in self.inputs.iter().take(ply.saturating_sub(1)).enumerate() {
+            let turn_number = replay.turn_number;
+            if !matches!(
+                replay.process_input_slice(inputs, false, false),
+                Output::Events(_)
+            ) {
+                return None;
+            }
+            if replay.turn_number != turn_number {
+                turn_start = index + 1;
+            }
+        }
+        (ply > 0).then_some(turn_start)
+    }
+}
+
+impl MonsGame {
+    /// Replays `inputs` from the position `input_history` started at, keeping
+    /// this game's tracking settings and progress rules.
+    pub(crate) fn replay_from_history_start(&self, inputs: &[Vec<Input>]) -> Option<MonsGame> {
+        let mut replay = self.history_start(self.with_verbose_tracking)?;
+        replay.is_moves_verified = self.is_moves_verified;
+        replay.progress_rules = self.progress_rules;
+        for chain in inputs {
+            match replay.process_input_checked(chain.clone(), false, false) {
+                Ok(Output::Events(_)) => {}
+                _ => return None,
+            }
+        }
+        Some(replay)
+    }
+
+    fn history_start(&self, with_verbose_tracking: bool) -> Option<MonsGame> {
+        let start_fen = match self.input_history_start_fen() {
+            Some(fen) => fen.to_string(),
+            None => {
+                let mut start = self.clone_for_simulation();
+                start.termination = None;
+                start.fen()
+            }
+        };
+        MonsGame::from_fen(start_fen.as_str(), with_verbose_tracking)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_of(model: &MonsGameModel) -> MonsGame {
+        MonsGame::from_fen(model.fen().as_str(), false).unwrap()
+    }
+
+    fn play(model: &mut MonsGameModel, inputs: &[Input]) {
+        let output = model.process_input_fen(Input::fen_from_array(inputs).as_str());
+        assert_eq!(output.kind, OutputModelKind::Events);
+    }
+
+    fn model_after_turns(turns: usize) -> MonsGameModel {
+        let mut model = MonsGameModel::new(GameVariant::Classic).unwrap();
+        for _ in 0..turns {
+            for inputs in game_of(&model).legal_turns_up_to(1).remove(0).moves {
+                play(&mut model, &inputs);
+            }
+        }
+        model
+    }
+
+    #[test]
+    fn undo_redo_and_goto_ply_scrub_the_line() {
+        let mut model = model_after_turns(3);
+        let end_fen = model.fen();
+        let end_tracking = model.verbose_tracking_entities().len();
+        let len = model.history_len();
+        assert!(len > 3);
+        assert_eq!(model.history_ply(), len);
+        assert!(!model.redo_move());
+
+        assert!(model.undo_move());
+        assert_eq!(model.history_ply(), len - 1);
+        assert_eq!(model.history_len(), len);
+        assert!(model.undo_turn());
+        assert!(model.undo_turn());
+        let turn_start_ply = model.history_ply();
+        let turn_start = game_of(&model);
+        assert_eq!(turn_start.turn_number, 2);
+        assert_eq!(turn_start.actions_used_count, 0);
+        assert_eq!(turn_start.mons_moves_count, 0);
+
+        assert!(model.goto_ply(0));
+        assert_eq!(
+            model.fen(),
+            MonsGame::new(false, GameVariant::Classic).fen()
+        );
+        assert!(!model.undo_move());
+        assert!(!model.undo_turn());
+        assert!(!model.goto_ply(len + 1));
+
+        assert!(model.goto_ply(turn_start_ply));
+        assert!(model.goto_ply(len));
+        assert_eq!(model.fen(), end_fen);
+        assert_eq!(model.verbose_tracking_entities().len(), end_tracking);
+    }
+
+    #[test]
+    fn new_move_replaces_redo_line_and_termination_is_restored() {
+        let mut model = model_after_turns(2);
+        let len = model.history_len();
+        model.resign(Color::White);
+        assert!(model.undo_move());
+        assert_eq!(model.winner_color(), None);
+        assert!(model.redo_move());
+        assert_eq!(model.winner_color(), Some(Color::Black));
+
+        assert!(model.undo_move());
+        assert!(model.undo_move());
+        assert!(model.redo_move());
+        let redo_fen = model.fen();
+        assert!(model.undo_move());
+        let inputs = game_of(&model)
+            .legal_move_inputs(true)
+            .into_iter()
+            .find(|inputs| {
+                let mut game = game_of(&model);
+                game.process_input(inputs.clone(), false, false);
+                game.fen() != redo_fen
+            })
+            .unwrap();
+        play(&mut model, &inputs);
+        assert_eq!(model.history_len(), len - 1);
+        assert!(!model.redo_move());
+    }
+}
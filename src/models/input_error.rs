@@ -0,0 +1,81 @@
+use crate::*;
+use std::fmt;
+
+/// Reason a `process_input_checked` call was rejected.
+///
+/// `index` always refers to the position of the offending element within the
+/// submitted input chain.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputError {
+    GameOver,
+    NoAvailableStart,
+    TakebackNotAllowed,
+    ExpectedLocation { index: usize, input: Input },
+    EmptyStartSquare { location: Location },
+    NoAvailableOptions { index: usize, location: Location },
+    IllegalInput { index: usize, input: Input },
+    UnexpectedModifier { index: usize, modifier: Modifier },
+}
+
+impl InputError {
+    /// Index of the rejected input within the chain, if the error is tied to one.
+    pub fn input_index(&self) -> Option<usize> {
+        match self {
+            InputError::GameOver | InputError::NoAvailableStart => None,
+            InputError::TakebackNotAllowed | InputError::EmptyStartSquare { .. } => Some(0),
+            InputError::ExpectedLocation { index, .. }
+            | InputError::NoAvailableOptions { index, .. }
+            | InputError::IllegalInput { index, .. }
+            | InputError::UnexpectedModifier { index, .. } => Some(*index),
+        }
+    }
+
+    /// Board location the rejection refers to, if any.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            InputError::EmptyStartSquare { location }
+            | InputError::NoAvailableOptions { location, .. } => Some(*location),
+            InputError::ExpectedLocation { input, .. } | InputError::IllegalInput { input, .. } => {
+                match input {
+                    Input::Location(location) => Some(*location),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::GameOver => write!(f, "game is already over"),
+            InputError::NoAvailableStart => write!(f, "no available start locations"),
+            InputError::TakebackNotAllowed => write!(f, "takeback is not allowed"),
+            InputError::ExpectedLocation { index, input } => {
+                write!(f, "input {} ({}) must be a location", index, input.fen())
+            }
+            InputError::EmptyStartSquare { location } => {
+                write!(f, "start square {} is empty", location.fen())
+            }
+            InputError::NoAvailableOptions { index, location } => write!(
+                f,
+                "input {} ({}) has no available follow-up",
+                index,
+                location.fen()
+            ),
+            InputError::IllegalInput { index, input } => {
+                write!(f, "input {} ({}) is not a legal option", index, input.fen())
+            }
+            InputError::UnexpectedModifier { index, modifier } => write!(
+                f,
+                "input {} ({}) is not an applicable modifier",
+                index,
+                modifier.fen()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
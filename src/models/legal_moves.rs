@@ -0,0 +1,228 @@
+use crate::*;
+use std::collections::HashSet;
+
+/// A complete input chain that is legal in some position, together with the
+/// events it produces once applied (including `NextTurn` / `GameOver`).
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegalMove {
+    pub inputs: Vec<Input>,
+    pub events: Vec<Event>,
+}
+
+/// A distinct position reachable by the active player by the end of their turn.
+#[derive(Debug, Clone)]
+pub struct LegalTurn {
+    pub moves: Vec<Vec<Input>>,
+    pub game: MonsGame,
+}
+
+impl MonsGame {
+    /// Every complete input chain legal in the current position.
+    ///
+    /// Unlike the start suggestions returned for an empty input, this includes
+    /// mana starts even while mons can still move.
+    pub fn legal_moves(&self) -> Vec<LegalMove> {
+        self.legal_move_inputs(true)
+            .into_iter()
+            .filter_map(|inputs| {
+                let mut after = self.clone_for_simulation();
+                match after.process_input_slice(inputs.as_slice(), false, false) {
+                    Output::Events(events) => Some(LegalMove { inputs, events }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Distinct end-of-turn positions for the active player, deduplicated by
+    /// `zobrist()` key. Each entry keeps one move sequence that reaches it.
+    pub fn legal_turns(&self) -> Vec<LegalTurn> {
+        self.legal_turns_up_to(usize::MAX)
+    }
+
+    /// Same as `legal_turns`, stopping once `max_turns` positions were found.
+    pub fn legal_turns_up_to(&self, max_turns: usize) -> Vec<LegalTurn> {
+        let mut turns = Vec::new();
+        if self.winner_color().is_some() || max_turns == 0 {
+            return turns;
+        }
+
+        let mut visited = HashSet::new();
+        let mut finished = HashSet::new();
+        let mut moves = Vec::new();
+        let game = self.clone_for_simulation();
+        visited.insert(game.zobrist());
+        Self::collect_legal_turns(
+            &game,
+            game.active_color,
+            &mut moves,
+            &mut visited,
+            &mut finished,
+            &mut turns,
+            max_turns,
+        );
+        turns
+    }
+
+    pub(crate) fn legal_move_inputs(&self, use_input_cache: bool) -> Vec<Vec<Input>> {
+        self.clone_for_simulation()
+            .legal_move_inputs_in_place(use_input_cache)
+    }
+
+    /// Only the process input cache is mutated; the position stays as is.
+    pub(crate) fn legal_move_inputs_in_place(&mut self, use_input_cache: bool) -> Vec<Vec<Input>> {
+        let mut chains = Vec::new();
+        if self.winner_color().is_some() {
+            return chains;
+        }
+
+        let mut starts = self.board.all_mons_locations(self.active_color);
+        starts.extend(
+            self.board
+                .all_free_regular_mana_locations(self.active_color),
+        );
+        starts.sort();
+
+        let mut inputs = Vec::new();
+        for start in starts {
+            inputs.push(Input::Location(start));
+            Self::collect_legal_move_inputs(self, &mut inputs, &mut chains, use_input_cache);
+            inputs.pop();
+        }
+        chains
+    }
+
+    fn collect_legal_move_inputs(
+        game: &mut MonsGame,
+        inputs: &mut Vec<Input>,
+        chains: &mut Vec<Vec<Input>>,
+        use_input_cache: bool,
+    ) {
+        if !use_input_cache {
+            game.invalidate_process_input_cache();
+        }
+        match game.process_input_slice(inputs.as_slice(), true, false) {
+            Output::Events(_) => chains.push(inputs.clone()),
+            Output::NextInputOptions(options) => {
+                for option in options {
+                    inputs.push(option.input);
+                    Self::collect_legal_move_inputs(game, inputs, chains, use_input_cache);
+                    inputs.pop();
+                }
+            }
+            Output::InvalidInput | Output::LocationsToStartFrom(_) => {}
+        }
+    }
+
+    fn collect_legal_turns(
+        game: &MonsGame,
+        color: Color,
+        moves: &mut Vec<Vec<Input>>,
+        visited: &mut HashSet<u64>,
+        finished: &mut HashSet<u64>,
+        turns: &mut Vec<LegalTurn>,
+        max_turns: usize,
+    ) {
+        let legal_moves = game.legal_moves();
+        if legal_moves.is_empty() && !moves.is_empty() {
+            Self::push_legal_turn(game, moves, finished, turns);
+            return;
+        }
+
+        for legal_move in legal_moves {
+            if turns.len() >= max_turns {
+                return;
+            }
+            let mut after = game.clone_for_simulation();
+            if !matches!(
+                after.process_input_slice(legal_move.inputs.as_slice(), false, false),
+                Output::Events(_)
+            ) {
+                continue;
+            }
+            moves.push(legal_move.inputs);
+            if after.active_color != color || after.winner_color().is_some() {
+                Self::push_legal_turn(&after, moves, finished, turns);
+            } else if visited.insert(after.zobrist()) {
+                Self::collect_legal_turns(
+                    &after, color, moves, visited, finished, turns, max_turns,
+                );
+            }
+            moves.pop();
+        }
+    }
+
+    fn push_legal_turn(
+        game: &MonsGame,
+        moves: &[Vec<Input>],
+        finished: &mut HashSet<u64>,
+        turns: &mut Vec<LegalTurn>,
+    ) {
+        if finished.insert(game.zobrist()) {
+            turns.push(LegalTurn {
+                moves: moves.to_vec(),
+                game: game.clone_for_simulation(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legal_moves_replay_to_their_events() {
+        let game = MonsGame::new(false, GameVariant::Classic);
+        let moves = game.legal_moves();
+        assert!(!moves.is_empty());
+
+        for legal_move in moves {
+            let mut replay = game.clone_for_simulation();
+            assert_eq!(
+                replay.process_input(legal_move.inputs.clone(), false, false),
+                Output::Events(legal_move.events)
+            );
+        }
+    }
+
+    #[test]
+    fn legal_moves_cover_suggested_starts() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let starts = match game.process_input(vec![], true, false) {
+            Output::LocationsToStartFrom(starts) => starts,
+            output => panic!("expected start locations, got {:?}", output),
+        };
+        let moves = game.legal_moves();
+        for start in starts {
+            assert!(moves
+                .iter()
+                .any(|legal_move| legal_move.inputs[0] == Input::Location(start)));
+        }
+    }
+
+    #[test]
+    fn legal_turns_end_with_opponent_to_move_and_are_distinct() {
+        let game = MonsGame::new(false, GameVariant::Classic);
+        let turns = game.legal_turns_up_to(64);
+        assert_eq!(turns.len(), 64);
+
+        let mut hashes = HashSet::new();
+        for turn in turns {
+            assert!(
+                turn.game.active_color != game.active_color || turn.game.winner_color().is_some()
+            );
+            assert!(hashes.insert(turn.game.zobrist()));
+
+            let mut replay = game.clone_for_simulation();
+            for inputs in turn.moves {
+                assert!(matches!(
+                    replay.process_input(inputs, false, false),
+                    Output::Events(_)
+                ));
+            }
+            assert_eq!(replay.fen(), turn.game.fen());
+        }
+    }
+}
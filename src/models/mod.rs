@@ -1,42 +1,81 @@
 pub(crate) mod automove_exact;
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) mod automove_turn_engine;
 pub mod available_move_kind;
+pub mod binary_encoding;
 pub mod board;
 pub mod color;
 pub mod config;
 pub mod consumable;
 pub mod event;
 pub mod fen_representable;
+pub mod game_clock;
+pub mod game_move;
+pub mod game_record;
+pub mod game_tree;
+pub mod handicap;
+pub(crate) mod history;
 pub mod input;
+pub mod input_error;
 pub mod item;
+pub mod legal_moves;
 pub mod location;
 pub mod mana;
 pub mod mon;
 pub mod mons_game;
 pub mod mons_game_model;
 pub mod next_input;
+pub mod notation;
 pub mod output;
+pub mod perft;
+pub mod position_builder;
+pub mod position_validator;
+pub mod progress;
+pub mod rule_set;
 pub mod scoring;
+#[cfg(feature = "serde")]
+pub mod serde_support;
 pub mod square;
+pub mod termination;
+pub mod undo_token;
+pub mod variant_spec;
+pub(crate) mod zobrist;
 pub(crate) use automove_exact::*;
-#[cfg(any(target_arch = "wasm32", test))]
 pub(crate) use automove_turn_engine::*;
 pub use available_move_kind::*;
+pub use binary_encoding::*;
 pub use board::*;
 pub use color::*;
 pub use config::*;
 pub use consumable::*;
 pub use event::*;
 pub use fen_representable::*;
+pub use game_clock::*;
+pub use game_move::*;
+pub use game_record::*;
+pub use game_tree::*;
+pub use handicap::*;
+pub(crate) use history::*;
 pub use input::*;
+pub use input_error::*;
 pub use item::*;
+pub use legal_moves::*;
 pub use location::*;
 pub use mana::*;
 pub use mon::*;
 pub use mons_game::*;
 pub use mons_game_model::*;
 pub use next_input::*;
+pub use notation::*;
 pub use output::*;
+pub use position_builder::*;
+pub use position_validator::*;
+pub use progress::*;
+pub use rule_set::*;
+#[cfg(feature = "serde")]
+pub use serde_support::*;
 pub use square::*;
+pub use termination::*;
+pub use undo_token::*;
+pub use variant_spec::*;
 pub use wasm_bindgen::prelude::*;
+pub(crate) use zobrist::*;
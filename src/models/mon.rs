@@ -2,6 +2,7 @@ use crate::*;
 
 #[wasm_bindgen]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MonKind {
     Demon,
     Drainer,
@@ -12,6 +13,7 @@ pub enum MonKind {
 
 #[wasm_bindgen]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mon {
     pub kind: MonKind,
     pub color: Color,
@@ -33,7 +35,14 @@ impl Mon {
     }
 
     pub fn faint(&mut self) {
-        self.cooldown = 2;
+        self.cooldown = Self::FAINT_COOLDOWN;
+    }
+
+    /// Faints the mon for `cooldown` of its owner's turns instead of the
+    /// standard two.
+    #[wasm_bindgen(js_name = faintFor)]
+    pub fn faint_for(&mut self, cooldown: i32) {
+        self.cooldown = cooldown;
     }
 
     pub fn decrease_cooldown(&mut self) {
@@ -42,3 +51,7 @@ impl Mon {
         }
     }
 }
+
+impl Mon {
+    pub const FAINT_COOLDOWN: i32 = 2;
+}
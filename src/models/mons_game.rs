@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use std::hash::Hash;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VerboseTrackingEntity {
     pub fen: String,
     pub color: Color,
@@ -68,6 +69,13 @@ pub struct MonsGame {
     pub with_verbose_tracking: bool,
     pub verbose_tracking_entities: Vec<VerboseTrackingEntity>,
     track_takeback_history: bool,
+    input_history: Vec<Vec<Input>>,
+    input_history_start_fen: Option<String>,
+    pub(crate) termination: Option<Termination>,
+    pub(crate) position_history: Vec<PositionRecord>,
+    pub(crate) progress_rules: Option<ProgressRules>,
+    pub(crate) rules: RuleSet,
+    pub(crate) handicap: Handicap,
     process_input_cache: ProcessInputCache,
 }
 
@@ -102,15 +110,32 @@ impl Clone for MonsGame {
             with_verbose_tracking: self.with_verbose_tracking,
             verbose_tracking_entities: self.verbose_tracking_entities.clone(),
             track_takeback_history: self.track_takeback_history,
+            input_history: self.input_history.clone(),
+            input_history_start_fen: self.input_history_start_fen.clone(),
+            termination: self.termination,
+            position_history: self.position_history.clone(),
+            progress_rules: self.progress_rules,
+            rules: self.rules,
+            handicap: self.handicap.clone(),
             process_input_cache: ProcessInputCache::default(),
         }
     }
 }
 
 impl MonsGame {
+    /// Initial setup of a built-in variant. Panics for `Custom`; use
+    /// `new_with_layout` for registered variants.
     pub fn new(with_verbose_tracking: bool, variant: GameVariant) -> Self {
-        Self {
-            board: Board::new_with_variant(variant),
+        Self::new_with_layout(
+            with_verbose_tracking,
+            VariantLayout::expect_builtin(variant),
+        )
+    }
+
+    /// Initial setup of a built-in or registered variant layout.
+    pub fn new_with_layout(with_verbose_tracking: bool, layout: &'static VariantLayout) -> Self {
+        let mut game = Self {
+            board: Board::new_with_layout(layout),
             white_score: 0,
             black_score: 0,
             active_color: Color::White,
@@ -125,8 +150,17 @@ impl MonsGame {
             with_verbose_tracking,
             verbose_tracking_entities: vec![],
             track_takeback_history: true,
+            input_history: vec![],
+            input_history_start_fen: None,
+            termination: None,
+            position_history: vec![],
+            progress_rules: None,
+            rules: RuleSet::STANDARD,
+            handicap: Handicap::default(),
             process_input_cache: ProcessInputCache::default(),
-        }
+        };
+        game.rules = RuleSet::for_variant(layout.spec());
+        game
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -158,6 +192,13 @@ impl MonsGame {
             with_verbose_tracking: false,
             verbose_tracking_entities: vec![],
             track_takeback_history: false,
+            input_history: vec![],
+            input_history_start_fen: None,
+            termination: None,
+            position_history: vec![],
+            progress_rules: None,
+            rules: RuleSet::STANDARD,
+            handicap: Handicap::default(),
             process_input_cache: ProcessInputCache::default(),
         }
     }
@@ -176,6 +217,9 @@ impl MonsGame {
             self.turn_number,
         );
         simulation.is_moves_verified = self.is_moves_verified;
+        simulation.termination = self.termination;
+        simulation.rules = self.rules;
+        simulation.handicap = self.handicap.clone();
         simulation
     }
 
@@ -184,13 +228,30 @@ impl MonsGame {
         self.board.variant()
     }
 
+    #[inline]
+    pub fn variant_layout(&self) -> &'static VariantLayout {
+        self.board.layout()
+    }
+
+    /// 64-bit Zobrist key of the full position: board items and cooldowns,
+    /// scores, active color, per-turn counters, potions, turn number and variant.
+    /// The board part is maintained incrementally by `Board::put` / `remove_item`;
+    /// the counter part is a constant-time fold over the public counter fields.
+    #[inline]
+    pub fn zobrist(&self) -> u64 {
+        self.board.zobrist() ^ zobrist_state_key(self)
+    }
+
     pub fn replace_board_items<I>(&mut self, items: I)
     where
         I: IntoIterator<Item = (Location, Item)>,
     {
-        self.board = Board::new_with_items_and_variant(items, self.variant());
+        self.board = Board::new_with_items_and_layout(items, self.variant_layout());
         self.takeback_fens.clear();
         self.verbose_tracking_entities.clear();
+        self.input_history.clear();
+        self.input_history_start_fen = None;
+        self.position_history.clear();
         self.is_moves_verified = false;
         self.invalidate_process_input_cache();
     }
@@ -203,6 +264,30 @@ impl MonsGame {
         self.invalidate_process_input_cache();
     }
 
+    pub(crate) fn is_tracking_takeback_history(&self) -> bool {
+        self.track_takeback_history
+    }
+
+    /// Input chains applied through `process_input` since the game was created
+    /// or loaded, with takebacks removed. Only tracked for non-simulation games.
+    pub fn input_history(&self) -> &[Vec<Input>] {
+        &self.input_history
+    }
+
+    /// Position the first entry of `input_history` was applied to.
+    pub fn input_history_start_fen(&self) -> Option<&str> {
+        self.input_history_start_fen.as_deref()
+    }
+
+    pub(crate) fn set_input_history(
+        &mut self,
+        start_fen: Option<String>,
+        input_history: Vec<Vec<Input>>,
+    ) {
+        self.input_history_start_fen = start_fen;
+        self.input_history = input_history;
+    }
+
     pub(crate) fn invalidate_process_input_cache(&mut self) {
         self.process_input_cache = ProcessInputCache::default();
     }
@@ -305,6 +390,23 @@ impl MonsGame {
             one_option_enough,
             suggested_start_options.unwrap_or_default(),
         )
+        .unwrap_or(Output::InvalidInput)
+    }
+
+    /// Same as `process_input`, but reports why an input was rejected
+    /// instead of collapsing every rejection into `Output::InvalidInput`.
+    pub fn process_input_checked(
+        &mut self,
+        input: Vec<Input>,
+        do_not_apply_events: bool,
+        one_option_enough: bool,
+    ) -> Result<Output, InputError> {
+        self.process_input_internal(
+            input.as_slice(),
+            do_not_apply_events,
+            one_option_enough,
+            SuggestedStartInputOptions::default(),
+        )
     }
 
     fn process_input_internal(
@@ -313,9 +415,9 @@ impl MonsGame {
         do_not_apply_events: bool,
         one_option_enough: bool,
         suggested_start_options: SuggestedStartInputOptions,
-    ) -> Output {
-        if self.winner_color().is_some() {
-            return Output::InvalidInput;
+    ) -> Result<Output, InputError> {
+        if self.is_game_over() {
+            return Err(InputError::GameOver);
         }
         if input.is_empty() {
             if let Some(cached) = self
@@ -323,7 +425,7 @@ impl MonsGame {
                 .start_suggestions
                 .get(&suggested_start_options)
             {
-                return cached.clone();
+                return Self::start_suggestions_result(cached.clone());
             }
             let output = self.suggested_input_to_start_with(suggested_start_options);
             Self::bounded_cache_insert(
@@ -332,13 +434,15 @@ impl MonsGame {
                 output.clone(),
                 START_SUGGESTIONS_CACHE_CAPACITY,
             );
-            return output;
+            return Self::start_suggestions_result(output);
         }
 
         if input.len() == 1 && input[0] == Input::Takeback {
             if self.can_takeback(self.active_color) {
                 self.takeback_fens.pop();
                 self.verbose_tracking_entities.pop();
+                self.input_history.pop();
+                self.pop_position_record();
                 let fen = self.takeback_fens.last().cloned();
                 if let Some(fen) = fen {
                     let fen_game = MonsGame::from_fen(fen.as_str(), false);
@@ -346,22 +450,32 @@ impl MonsGame {
                         self.update_with(&fen_game);
                     }
                 } else {
-                    return Output::InvalidInput;
+                    return Err(InputError::TakebackNotAllowed);
                 }
                 self.invalidate_process_input_cache();
-                return Output::Events(vec![Event::Takeback]);
+                return Ok(Output::Events(vec![Event::Takeback]));
             } else {
-                return Output::InvalidInput;
+                return Err(InputError::TakebackNotAllowed);
             }
         }
 
         let start_location = match input.first() {
             Some(Input::Location(location)) => *location,
-            _ => return Output::InvalidInput,
+            Some(input) => {
+                return Err(InputError::ExpectedLocation {
+                    index: 0,
+                    input: *input,
+                })
+            }
+            None => return Err(InputError::NoAvailableStart),
         };
         let start_item = match self.board.item(start_location) {
             Some(item) => *item,
-            None => return Output::InvalidInput,
+            None => {
+                return Err(InputError::EmptyStartSquare {
+                    location: start_location,
+                })
+            }
         };
         let specific_second_input = input.get(1).copied();
         let second_input_options = self.second_input_options(
@@ -374,21 +488,29 @@ impl MonsGame {
         let second_input = if let Some(second_input) = specific_second_input {
             second_input
         } else if second_input_options.is_empty() {
-            return Output::InvalidInput;
+            return Err(InputError::NoAvailableOptions {
+                index: 0,
+                location: start_location,
+            });
         } else {
-            return Output::NextInputOptions(second_input_options);
+            return Ok(Output::NextInputOptions(second_input_options));
         };
 
         let target_location = match second_input {
             Input::Location(location) => location,
-            _ => return Output::InvalidInput,
+            input => return Err(InputError::ExpectedLocation { index: 1, input }),
         };
         let second_input_kind = match second_input_options
             .iter()
             .find(|option| option.input == second_input)
         {
             Some(option) => option.kind,
-            None => return Output::InvalidInput,
+            None => {
+                return Err(InputError::IllegalInput {
+                    index: 1,
+                    input: second_input,
+                })
+            }
         };
 
         let specific_third_input = input.get(2).copied();
@@ -405,15 +527,18 @@ impl MonsGame {
 
         if specific_third_input.is_none() {
             if !third_input_options.is_empty() {
-                return Output::NextInputOptions(third_input_options);
+                return Ok(Output::NextInputOptions(third_input_options));
             } else if !events.is_empty() {
-                return Output::Events(if do_not_apply_events {
+                return Ok(Output::Events(if do_not_apply_events {
                     events
                 } else {
-                    self.apply_and_add_resulting_events(events)
-                });
+                    self.apply_input_events(&input[..2], events)
+                }));
             } else {
-                return Output::InvalidInput;
+                return Err(InputError::NoAvailableOptions {
+                    index: 1,
+                    location: target_location,
+                });
             }
         }
 
@@ -424,7 +549,12 @@ impl MonsGame {
             .find(|option| option.input == specific_third_input)
         {
             Some(option) => option,
-            None => return Output::InvalidInput,
+            None => {
+                return Err(InputError::IllegalInput {
+                    index: 2,
+                    input: specific_third_input,
+                })
+            }
         };
 
         let specific_forth_input = input.get(3).copied();
@@ -441,15 +571,21 @@ impl MonsGame {
 
         if specific_forth_input.is_none() {
             if !forth_input_options.is_empty() {
-                return Output::NextInputOptions(forth_input_options);
+                return Ok(Output::NextInputOptions(forth_input_options));
             } else if !events.is_empty() {
-                return Output::Events(if do_not_apply_events {
+                return Ok(Output::Events(if do_not_apply_events {
                     events
                 } else {
-                    self.apply_and_add_resulting_events(events)
-                });
+                    self.apply_input_events(&input[..3], events)
+                }));
             } else {
-                return Output::InvalidInput;
+                // A modifier picks something up at the target, so it is the
+                // square left without options.
+                let location = match specific_third_input {
+                    Input::Location(location) => location,
+                    _ => target_location,
+                };
+                return Err(InputError::NoAvailableOptions { index: 2, location });
             }
         }
 
@@ -459,14 +595,14 @@ impl MonsGame {
             Input::Modifier(modifier) => {
                 let destination_location = match third_input.input {
                     Input::Location(location) => location,
-                    _ => return Output::InvalidInput,
+                    _ => return Err(InputError::UnexpectedModifier { index: 3, modifier }),
                 };
                 let forth_input = match forth_input_options
                     .iter()
                     .find(|option| option.input == specific_forth_input)
                 {
                     Some(option) => option,
-                    None => return Output::InvalidInput,
+                    None => return Err(InputError::UnexpectedModifier { index: 3, modifier }),
                 };
                 if let Some(actor_mon_item) = forth_input.actor_mon_item {
                     if let Some(actor_mon) = actor_mon_item.mon() {
@@ -479,18 +615,42 @@ impl MonsGame {
                                 by: actor_mon_item,
                                 at: destination_location,
                             }),
-                            Modifier::Cancel => return Output::InvalidInput,
+                            Modifier::Cancel => {
+                                return Err(InputError::UnexpectedModifier { index: 3, modifier })
+                            }
                         }
-                        return Output::Events(if do_not_apply_events {
+                        return Ok(Output::Events(if do_not_apply_events {
                             events
                         } else {
-                            self.apply_and_add_resulting_events(events)
-                        });
+                            self.apply_input_events(&input[..4], events)
+                        }));
                     }
                 }
-                Output::InvalidInput
+                Err(InputError::UnexpectedModifier { index: 3, modifier })
+            }
+            input => Err(InputError::IllegalInput { index: 3, input }),
+        }
+    }
+
+    fn apply_input_events(&mut self, input: &[Input], events: Vec<Event>) -> Vec<Event> {
+        if self.track_takeback_history {
+            if self.input_history.is_empty() {
+                self.input_history_start_fen = Some(self.fen());
             }
-            _ => Output::InvalidInput,
+            self.input_history.push(input.to_vec());
+            self.record_start_position();
+        }
+        let mut events = self.apply_and_add_resulting_events(events);
+        if self.track_takeback_history {
+            events.extend(self.record_position(&events));
+        }
+        events
+    }
+
+    fn start_suggestions_result(output: Output) -> Result<Output, InputError> {
+        match output {
+            Output::InvalidInput => Err(InputError::NoAvailableStart),
+            output => Ok(output),
         }
     }
 
@@ -507,7 +667,7 @@ impl MonsGame {
             let start_input = [Input::Location(location)];
             let output =
                 self.process_input_internal(&start_input, true, true, suggested_start_options);
-            if matches!(output, Output::NextInputOptions(options) if !options.is_empty()) {
+            if matches!(output, Ok(Output::NextInputOptions(options)) if !options.is_empty()) {
                 let index = location.index();
                 if !seen_locations[index] {
                     seen_locations[index] = true;
@@ -521,7 +681,7 @@ impl MonsGame {
                 || suggested_locations.is_empty()
                 || (suggested_start_options.include_mana_starts_with_potion_action
                     && !self.player_can_move_mon()
-                    && self.actions_used_count >= Config::ACTIONS_PER_TURN
+                    && self.actions_used_count >= self.rules.actions_per_turn
                     && self.player_potions_count() > 0));
 
         if should_add_regular_mana_starts {
@@ -532,7 +692,7 @@ impl MonsGame {
                 let start_input = [Input::Location(location)];
                 let output =
                     self.process_input_internal(&start_input, true, true, suggested_start_options);
-                if matches!(output, Output::NextInputOptions(options) if !options.is_empty()) {
+                if matches!(output, Ok(Output::NextInputOptions(options)) if !options.is_empty()) {
                     let index = location.index();
                     if !seen_locations[index] {
                         seen_locations[index] = true;
@@ -1664,7 +1824,7 @@ impl MonsGame {
                     from,
                     to,
                 } => {
-                    if self.actions_used_count >= Config::ACTIONS_PER_TURN {
+                    if self.actions_used_count >= self.rules.actions_per_turn {
                         if self.active_color == Color::White {
                             self.white_potions_count -= 1;
                         } else {
@@ -1691,7 +1851,7 @@ impl MonsGame {
                         self.board.remove_item(*to);
                     }
 
-                    if self.actions_used_count >= Config::ACTIONS_PER_TURN {
+                    if self.actions_used_count >= self.rules.actions_per_turn {
                         if self.active_color == Color::White {
                             self.white_potions_count -= 1;
                         } else {
@@ -1710,7 +1870,7 @@ impl MonsGame {
                     self.board.put(Item::Mon { mon: *demon }, *to);
                 }
                 Event::SpiritTargetMove { item, from, to, by } => {
-                    if self.actions_used_count >= Config::ACTIONS_PER_TURN {
+                    if self.actions_used_count >= self.rules.actions_per_turn {
                         if self.active_color == Color::White {
                             self.white_potions_count -= 1;
                         } else {
@@ -1756,7 +1916,7 @@ impl MonsGame {
                 }
                 Event::MonFainted { mon, from: _, to } => {
                     let mut fainted_mon = *mon;
-                    fainted_mon.faint();
+                    fainted_mon.faint_for(self.rules.faint_cooldown);
                     self.board.put(Item::Mon { mon: fainted_mon }, *to);
                 }
                 Event::ManaDropped { mana, at } => {
@@ -1787,7 +1947,10 @@ impl MonsGame {
                 Event::BombExplosion { at } => {
                     self.board.remove_item(*at);
                 }
-                Event::MonAwake { .. } | Event::GameOver { .. } | Event::NextTurn { .. } => {}
+                Event::MonAwake { .. }
+                | Event::GameOver { .. }
+                | Event::GameTerminated { .. }
+                | Event::NextTurn { .. } => {}
                 Event::Takeback => {}
                 Event::UsePotion { .. } => {}
             }
@@ -1928,7 +2091,7 @@ impl MonsGame {
         let mut moves = HashMap::new();
         moves.insert(
             AvailableMoveKind::MonMove,
-            Config::MONS_MOVES_PER_TURN - self.mons_moves_count,
+            self.mons_moves_limit() - self.mons_moves_count,
         );
         moves.insert(AvailableMoveKind::Action, 0);
         moves.insert(AvailableMoveKind::Potion, 0);
@@ -1940,21 +2103,28 @@ impl MonsGame {
 
         moves.insert(
             AvailableMoveKind::Action,
-            Config::ACTIONS_PER_TURN - self.actions_used_count,
+            self.rules.actions_per_turn - self.actions_used_count,
         );
         moves.insert(AvailableMoveKind::Potion, self.player_potions_count());
         moves.insert(
             AvailableMoveKind::ManaMove,
-            Config::MANA_MOVES_PER_TURN - self.mana_moves_count,
+            self.rules.mana_moves_per_turn - self.mana_moves_count,
         );
 
         moves
     }
 
     pub fn winner_color(&self) -> Option<Color> {
-        if self.white_score >= Config::TARGET_SCORE {
+        if let Some(termination) = self.termination {
+            return termination.winner();
+        }
+        self.score_winner()
+    }
+
+    pub(crate) fn score_winner(&self) -> Option<Color> {
+        if self.white_score >= self.rules.target_score {
             Some(Color::White)
-        } else if self.black_score >= Config::TARGET_SCORE {
+        } else if self.black_score >= self.rules.target_score {
             Some(Color::Black)
         } else {
             None
@@ -1962,7 +2132,7 @@ impl MonsGame {
     }
 
     pub fn is_later_than(&self, game: &MonsGame) -> bool {
-        if self.variant() != game.variant() {
+        if self.variant_layout() != game.variant_layout() {
             false
         } else if self.turn_number > game.turn_number {
             true
@@ -1996,17 +2166,17 @@ impl MonsGame {
     }
 
     pub fn player_can_move_mon(&self) -> bool {
-        self.mons_moves_count < Config::MONS_MOVES_PER_TURN
+        self.mons_moves_count < self.mons_moves_limit()
     }
 
     pub fn player_can_move_mana(&self) -> bool {
-        !self.is_first_turn() && self.mana_moves_count < Config::MANA_MOVES_PER_TURN
+        !self.is_first_turn() && self.mana_moves_count < self.rules.mana_moves_per_turn
     }
 
     pub fn player_can_use_action(&self) -> bool {
         !self.is_first_turn()
             && (self.player_potions_count() > 0
-                || self.actions_used_count < Config::ACTIONS_PER_TURN)
+                || self.actions_used_count < self.rules.actions_per_turn)
     }
 
     pub fn protected_by_opponents_angel(&self) -> std::collections::HashSet<Location> {
@@ -2140,6 +2310,13 @@ mod tests {
             with_verbose_tracking: false,
             verbose_tracking_entities: vec![],
             track_takeback_history: true,
+            input_history: vec![],
+            input_history_start_fen: None,
+            termination: None,
+            position_history: vec![],
+            progress_rules: None,
+            rules: RuleSet::STANDARD,
+            handicap: Handicap::default(),
             process_input_cache: ProcessInputCache::default(),
         }
     }
@@ -2338,6 +2515,70 @@ mod tests {
         assert!(!game.is_moves_verified);
     }
 
+    #[test]
+    fn process_input_checked_reports_rejection_reasons() {
+        let game = MonsGame::new(true, GameVariant::Classic);
+        let start = match game
+            .clone_for_simulation()
+            .process_input(vec![], true, false)
+        {
+            Output::LocationsToStartFrom(starts) => starts[0],
+            output => panic!("expected start locations, got {:?}", output),
+        };
+        let empty = Location::new(1, 1);
+        assert!(game.board.item(empty).is_none());
+
+        let cases = vec![
+            (vec![Input::Takeback], InputError::TakebackNotAllowed),
+            (
+                vec![Input::Modifier(Modifier::Cancel)],
+                InputError::ExpectedLocation {
+                    index: 0,
+                    input: Input::Modifier(Modifier::Cancel),
+                },
+            ),
+            (
+                vec![Input::Location(empty)],
+                InputError::EmptyStartSquare { location: empty },
+            ),
+            (
+                vec![Input::Location(start), Input::Location(start)],
+                InputError::IllegalInput {
+                    index: 1,
+                    input: Input::Location(start),
+                },
+            ),
+        ];
+        for (input, expected) in cases {
+            let mut checked = game.clone_for_simulation();
+            assert_eq!(
+                checked.process_input_checked(input.clone(), false, false),
+                Err(expected)
+            );
+            let mut regular = game.clone_for_simulation();
+            assert_eq!(
+                regular.process_input(input, false, false),
+                Output::InvalidInput
+            );
+        }
+
+        let chain = first_chain_from_state(&game).expect("expected legal input chain");
+        let mut checked = game.clone_for_simulation();
+        let mut regular = game.clone_for_simulation();
+        assert_eq!(
+            checked.process_input_checked(chain.clone(), false, false),
+            Ok(regular.process_input(chain, false, false))
+        );
+        assert_eq!(checked.fen(), regular.fen());
+
+        let mut finished = game.clone_for_simulation();
+        finished.white_score = Config::TARGET_SCORE;
+        assert_eq!(
+            finished.process_input_checked(vec![], false, false),
+            Err(InputError::GameOver)
+        );
+    }
+
     #[test]
     fn is_later_than_rejects_cross_variant_games() {
         let mut classic = MonsGame::new(false, GameVariant::Classic);
@@ -1,4 +1,3 @@
-#[cfg(any(target_arch = "wasm32", test))]
 use crate::models::scoring::{
     evaluate_preferability_with_context, evaluate_preferability_with_weights_and_exact_policy,
     ScoringEvalContext, ScoringWeights, BALANCED_DISTANCE_SCORING_WEIGHTS, DEFAULT_SCORING_WEIGHTS,
@@ -11,17 +10,33 @@ use crate::models::scoring::{
 };
 use crate::*;
 
-#[cfg(any(target_arch = "wasm32", test))]
+#[path = "automove_analysis.rs"]
+pub(crate) mod automove_analysis;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+#[path = "automove_parallel.rs"]
+pub(crate) mod automove_parallel;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+pub use automove_parallel::ParallelSearchOptions;
+#[path = "automove_ponder.rs"]
+pub(crate) mod automove_ponder;
+pub use automove_ponder::Ponder;
 #[path = "automove_runtime_variants.rs"]
 pub(crate) mod automove_runtime_variants;
+pub use automove_analysis::AnalysisLine;
+#[path = "automove_search_handle.rs"]
+pub(crate) mod automove_search_handle;
+pub use automove_search_handle::SearchHandle;
+#[path = "automove_time_budget.rs"]
+pub(crate) mod automove_time_budget;
+use automove_time_budget::search_deadline_reached;
 
 #[wasm_bindgen]
 #[derive(Debug)]
 pub struct MonsGameModel {
     game: MonsGame,
+    history_line: HistoryLine,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TurnEngineRootInjectionAcceptance {
     Accepted,
@@ -31,7 +46,6 @@ enum TurnEngineRootInjectionAcceptance {
     RejectedHeuristicGap,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TurnEngineEmergencyRootInjectionAcceptance {
     Accepted,
@@ -42,14 +56,12 @@ enum TurnEngineEmergencyRootInjectionAcceptance {
     RejectedDrainerUnsafe,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TurnEngineRerankOverrideAcceptance {
     Accepted,
     Rejected(TurnEngineRerankOverrideRejectReason),
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TurnEngineRootInjectionAttemptOutcome {
     Accepted,
@@ -65,7 +77,6 @@ enum TurnEngineRootInjectionAttemptOutcome {
     RejectedHeuristicGap,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TurnEngineRerankOverrideAttemptOutcome {
     NoPlan,
@@ -73,7 +84,6 @@ enum TurnEngineRerankOverrideAttemptOutcome {
     RejectedAcceptance,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TurnEngineRerankOverrideRejectReason {
     NotInRoot,
@@ -86,7 +96,6 @@ enum TurnEngineRerankOverrideRejectReason {
     SafetyProgressGate,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Debug, Clone, Copy, Default)]
 struct TurnEngineRootSelectionDiagnostics {
     injected_root_attempts: usize,
@@ -117,7 +126,6 @@ struct TurnEngineRootSelectionDiagnostics {
     rerank_override_reject_safety_progress_gate: usize,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 thread_local! {
     static TURN_ENGINE_ROOT_SELECTION_DIAGNOSTICS: std::cell::RefCell<TurnEngineRootSelectionDiagnostics> =
         std::cell::RefCell::new(TurnEngineRootSelectionDiagnostics::default());
@@ -135,7 +143,6 @@ fn turn_engine_root_selection_diagnostics_snapshot() -> TurnEngineRootSelectionD
     TURN_ENGINE_ROOT_SELECTION_DIAGNOSTICS.with(|diagnostics| *diagnostics.borrow())
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[inline]
 fn update_turn_engine_root_selection_diagnostics(
     update: impl FnOnce(&mut TurnEngineRootSelectionDiagnostics),
@@ -144,7 +151,6 @@ fn update_turn_engine_root_selection_diagnostics(
         .with(|diagnostics| update(&mut diagnostics.borrow_mut()));
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn record_turn_engine_root_injection_attempt(outcome: TurnEngineRootInjectionAttemptOutcome) {
     update_turn_engine_root_selection_diagnostics(|diagnostics| {
         diagnostics.injected_root_attempts = diagnostics.injected_root_attempts.saturating_add(1);
@@ -217,7 +223,6 @@ fn record_turn_engine_root_injection_attempt(outcome: TurnEngineRootInjectionAtt
     });
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn record_turn_engine_root_injection_candidates(count: usize) {
     update_turn_engine_root_selection_diagnostics(|diagnostics| {
         diagnostics.injected_root_candidates_seen = diagnostics
@@ -226,7 +231,6 @@ fn record_turn_engine_root_injection_candidates(count: usize) {
     });
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn record_turn_engine_root_injection_duplicate() {
     update_turn_engine_root_selection_diagnostics(|diagnostics| {
         diagnostics.injected_root_duplicates =
@@ -234,7 +238,6 @@ fn record_turn_engine_root_injection_duplicate() {
     });
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn record_turn_engine_rerank_override_attempt(outcome: TurnEngineRerankOverrideAttemptOutcome) {
     update_turn_engine_root_selection_diagnostics(|diagnostics| match outcome {
         TurnEngineRerankOverrideAttemptOutcome::NoPlan => {
@@ -256,7 +259,6 @@ fn record_turn_engine_rerank_override_attempt(outcome: TurnEngineRerankOverrideA
     });
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn record_turn_engine_rerank_override_reject_reason(reason: TurnEngineRerankOverrideRejectReason) {
     update_turn_engine_root_selection_diagnostics(|diagnostics| match reason {
         TurnEngineRerankOverrideRejectReason::NotInRoot => {
@@ -302,18 +304,19 @@ fn record_turn_engine_rerank_override_reject_reason(reason: TurnEngineRerankOver
     });
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn turn_engine_root_injection_emergency_state(game: &MonsGame, perspective: Color) -> bool {
     if turn_engine_opponent_can_win_immediately(game, perspective) {
         return true;
     }
 
-    if turn_engine_own_drainer_safety_score(&game.board, perspective) <= -2 {
+    if turn_engine_own_drainer_safety_score(game, perspective) <= -2 {
         return true;
     }
 
-    let needed =
-        Config::TARGET_SCORE.saturating_sub(MonsGameModel::score_for_color(game, perspective));
+    let needed = game
+        .rules()
+        .target_score
+        .saturating_sub(MonsGameModel::score_for_color(game, perspective));
     if needed > 0 {
         let same_turn = exact_turn_summary(game, perspective).same_turn_score_window_value;
         if same_turn >= needed {
@@ -324,15 +327,16 @@ fn turn_engine_root_injection_emergency_state(game: &MonsGame, perspective: Colo
     false
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn turn_engine_opponent_can_win_immediately(game: &MonsGame, perspective: Color) -> bool {
     if game.winner_color().is_some() || game.active_color != perspective.other() {
         return false;
     }
 
     let opponent = perspective.other();
-    let needed =
-        Config::TARGET_SCORE.saturating_sub(MonsGameModel::score_for_color(game, opponent));
+    let needed = game
+        .rules()
+        .target_score
+        .saturating_sub(MonsGameModel::score_for_color(game, opponent));
     if needed <= 0 {
         return true;
     }
@@ -340,184 +344,109 @@ fn turn_engine_opponent_can_win_immediately(game: &MonsGame, perspective: Color)
     exact_turn_summary(game, opponent).same_turn_score_window_value >= needed
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
-fn turn_engine_own_drainer_safety_score(board: &Board, color: Color) -> i32 {
+fn turn_engine_own_drainer_safety_score(game: &MonsGame, color: Color) -> i32 {
     crate::models::automove_exact::exact_own_drainer_safety_score_with_hash(
-        board,
-        crate::models::automove_exact::exact_board_hash(board),
+        &game.board,
+        crate::models::automove_exact::exact_board_hash(&game.board),
         color,
+        game.rules(),
     )
 }
 
 impl Clone for MonsGameModel {
     fn clone(&self) -> Self {
-        Self::with_game(self.game.clone())
+        Self {
+            game: self.game.clone(),
+            history_line: self.history_line.clone(),
+        }
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 const MIN_SMART_SEARCH_DEPTH: usize = 1;
-#[cfg(any(target_arch = "wasm32", test))]
 const MAX_SMART_SEARCH_DEPTH: usize = 5;
-#[cfg(any(target_arch = "wasm32", test))]
 const MIN_SMART_MAX_VISITED_NODES: usize = 32;
-#[cfg(any(target_arch = "wasm32", test))]
 const MAX_SMART_MAX_VISITED_NODES: usize = 180_000;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TERMINAL_SCORE: i32 = i32::MAX / 8;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_MAX_INPUT_CHAIN: usize = 8;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TRANSPOSITION_TABLE_MAX_ENTRIES: usize = 12_000;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NO_EFFECT_ROOT_PENALTY: i32 = 120;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NO_EFFECT_CHILD_PENALTY: i32 = 0;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_LOW_IMPACT_ROOT_PENALTY: i32 = 40;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_LOW_IMPACT_CHILD_PENALTY: i32 = 0;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_EFFICIENCY_SCORE_MARGIN: i32 = 2_500;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_BACKTRACK_PENALTY: i32 = 140;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_ASPIRATION_WINDOW: i32 = 1_600;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TT_BEST_CHILD_BONUS: i32 = 2_400;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_KILLER_MOVE_BONUS: i32 = 1_200;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_HISTORY_BONUS_CAP: i32 = 800;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TWO_PASS_ROOT_SCOUT_DEPTH: usize = 2;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TWO_PASS_ROOT_SCOUT_MIN_NODES: usize = 96;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TWO_PASS_ROOT_FOCUS_SCORE_MARGIN: i32 = 2_000;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TWO_PASS_ROOT_VOLATILITY_KEEP: usize = 2;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TWO_PASS_ROOT_VOLATILITY_MARGIN: i32 = 600;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_MANA_HANDOFF_PENALTY: i32 = 220;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_DRAINER_SAFETY_SCORE_MARGIN: i32 = 2_200;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_SHORTLIST_MAX: usize = 4;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_SCORE_MARGIN: i32 = 3_000;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_REPLY_LIMIT_MIN: usize = 12;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_REPLY_LIMIT_MAX: usize = 36;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_SCORE_RACE_TRIGGER: i32 = 3;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_DEEP_FLOOR_SCORE_RACE_TRIGGER: i32 = 3;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_DEEP_FLOOR_SCORE_MARGIN: i32 = 2_400;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_DEEP_FLOOR_MAX_CANDIDATES: usize = 3;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_DEEP_FLOOR_REPLY_LIMIT_MIN: usize = 8;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_NORMAL_ROOT_SAFETY_DEEP_FLOOR_REPLY_LIMIT_MAX: usize = 16;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_REPLY_RISK_SCORE_MARGIN: i32 = 140;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_REPLY_RISK_SHORTLIST_FAST: usize = 3;
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const SMART_ROOT_REPLY_RISK_SHORTLIST_NORMAL: usize = 5;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_REPLY_RISK_REPLY_LIMIT_FAST: usize = 8;
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const SMART_ROOT_REPLY_RISK_REPLY_LIMIT_NORMAL: usize = 12;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_REPLY_RISK_NODE_SHARE_BP_FAST: i32 = 600;
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const SMART_ROOT_REPLY_RISK_NODE_SHARE_BP_NORMAL: i32 = 1_000;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_REPLY_RISK_WINNER_SPREAD_SKIP: i32 = SMART_TWO_PASS_ROOT_NARROW_SPREAD_FALLBACK;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_TWO_PASS_ROOT_NARROW_SPREAD_FALLBACK: i32 = 700;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_MOVE_CLASS_ROOT_SCORE_MARGIN: i32 = 120;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_MOVE_CLASS_CHILD_SCORE_MARGIN: i32 = 110;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_ANTI_HELP_SCORE_MARGIN: i32 = 180;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_ANTI_HELP_REPLY_LIMIT_FAST: usize = 6;
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const SMART_ROOT_ANTI_HELP_REPLY_LIMIT_NORMAL: usize = 8;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_SELECTIVE_EXTENSION_NODE_SHARE_BP_NORMAL: i32 = 1_200;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_SPIRIT_DEVELOPMENT_SCORE_MARGIN: i32 = 700;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_INTERVIEW_SOFT_PRIORITY_SCORE_MARGIN: i32 = 120;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_INTERVIEW_SOFT_SUPERMANA_PROGRESS_BONUS: i32 = 240;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_INTERVIEW_SOFT_SUPERMANA_SCORE_BONUS: i32 = 420;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_INTERVIEW_SOFT_OPPONENT_MANA_PROGRESS_BONUS: i32 = 210;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_INTERVIEW_SOFT_OPPONENT_MANA_SCORE_BONUS: i32 = 360;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_INTERVIEW_SOFT_MANA_HANDOFF_PENALTY: i32 = 220;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_INTERVIEW_SOFT_ROUNDTRIP_PENALTY: i32 = 140;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_POTION_SPEND_NO_COMPENSATION_PENALTY_FAST: i32 = 340;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_POTION_SPEND_NO_COMPENSATION_PENALTY_NORMAL: i32 = 260;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_ROOT_POTION_HOLD_SCORE_MARGIN: i32 = 180;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_SPIRIT_DEPLOY_EFFICIENCY_BONUS: i32 = 90;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_SPIRIT_ACTION_TARGET_DELTA_WEIGHT: i32 = 22;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_FORCED_DRAINER_ATTACK_FALLBACK_FAST_CANDIDATES: usize = 4;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_FORCED_DRAINER_ATTACK_FALLBACK_NORMAL_CANDIDATES: usize = 6;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_FORCED_DRAINER_ATTACK_FALLBACK_NODE_BUDGET_FAST: usize = 600;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_FORCED_DRAINER_ATTACK_FALLBACK_NODE_BUDGET_NORMAL: usize = 1_800;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_FORCED_DRAINER_ATTACK_FALLBACK_ENUM_LIMIT_FAST: usize = 220;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_FORCED_DRAINER_ATTACK_FALLBACK_ENUM_LIMIT_NORMAL: usize = 280;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_AUTOMOVE_FAST_DEPTH: i32 = 2;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_AUTOMOVE_FAST_MAX_VISITED_NODES: i32 = 480;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_AUTOMOVE_NORMAL_DEPTH: i32 = 3;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_AUTOMOVE_NORMAL_MAX_VISITED_NODES: i32 = 3800;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_AUTOMOVE_PRO_DEPTH: i32 = 4;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_AUTOMOVE_PRO_MAX_VISITED_NODES: i32 =
     SMART_AUTOMOVE_NORMAL_MAX_VISITED_NODES * 369 / 100;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_MOVE_EFFICIENCY_SNAPSHOT_CACHE_MAX_ENTRIES: usize = 16_384;
-#[cfg(any(target_arch = "wasm32", test))]
 const SMART_SEARCH_PREFERABILITY_CACHE_MAX_ENTRIES: usize = 32_768;
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct IdentityU64Hasher(u64);
 
-#[cfg(any(target_arch = "wasm32", test))]
 impl std::hash::Hasher for IdentityU64Hasher {
     fn finish(&self) -> u64 {
         self.0
@@ -537,18 +466,13 @@ impl std::hash::Hasher for IdentityU64Hasher {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 type U64BuildHasher = std::hash::BuildHasherDefault<IdentityU64Hasher>;
 
-#[cfg(any(target_arch = "wasm32", test))]
 type U64HashMap<V> = std::collections::HashMap<u64, V, U64BuildHasher>;
 
-#[cfg(any(target_arch = "wasm32", test))]
 type U64HashSet = std::collections::HashSet<u64, U64BuildHasher>;
 
-#[cfg(any(target_arch = "wasm32", test))]
 type FastHashMap<K, V> = std::collections::HashMap<K, V, U64BuildHasher>;
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         use_legacy_formula: false,
@@ -570,7 +494,6 @@ const RUNTIME_NORMAL_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeigh
         spirit_action_utility: 86,
         ..BALANCED_DISTANCE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         use_legacy_formula: false,
@@ -592,7 +515,6 @@ const RUNTIME_NORMAL_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeigh
         spirit_action_utility: 90,
         ..TACTICAL_BALANCED_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         use_legacy_formula: false,
@@ -614,7 +536,6 @@ const RUNTIME_NORMAL_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS: S
         spirit_action_utility: 94,
         ..TACTICAL_BALANCED_AGGRESSIVE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         use_legacy_formula: false,
@@ -635,7 +556,6 @@ const RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS: Scoring
         spirit_action_utility: 88,
         ..FINISHER_BALANCED_SOFT_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         use_legacy_formula: false,
@@ -657,35 +577,30 @@ const RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGH
         ..FINISHER_BALANCED_SOFT_AGGRESSIVE_SCORING_WEIGHTS
     };
 
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         drainer_danger_boolean: -1200,
         mana_carrier_danger_boolean: -800,
         ..RUNTIME_NORMAL_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         drainer_danger_boolean: -1200,
         mana_carrier_danger_boolean: -800,
         ..RUNTIME_NORMAL_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     drainer_danger_boolean: -1200,
     mana_carrier_danger_boolean: -800,
     ..RUNTIME_NORMAL_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     drainer_danger_boolean: -1200,
     mana_carrier_danger_boolean: -800,
     ..RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     drainer_danger_boolean: -1200,
@@ -693,7 +608,6 @@ const RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BA
     ..RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_STRONG_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
@@ -701,7 +615,6 @@ const RUNTIME_NORMAL_STRONG_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHT
         mana_carrier_danger_boolean: -1200,
         ..RUNTIME_NORMAL_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_STRONG_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
@@ -709,7 +622,6 @@ const RUNTIME_NORMAL_STRONG_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHT
         mana_carrier_danger_boolean: -1200,
         ..RUNTIME_NORMAL_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_STRONG_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -717,7 +629,6 @@ const RUNTIME_NORMAL_STRONG_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCO
     mana_carrier_danger_boolean: -1200,
     ..RUNTIME_NORMAL_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_STRONG_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -725,7 +636,6 @@ const RUNTIME_NORMAL_STRONG_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_W
     mana_carrier_danger_boolean: -1200,
     ..RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_STRONG_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -734,69 +644,58 @@ const RUNTIME_NORMAL_STRONG_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BAS
     ..RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACK_BONUS_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         opponent_drainer_attack_bonus: 400,
         ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACK_BONUS_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
         opponent_drainer_attack_bonus: 400,
         ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACK_BONUS_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     opponent_drainer_attack_bonus: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACK_BONUS_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     opponent_drainer_attack_bonus: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACK_BONUS_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     opponent_drainer_attack_bonus: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACK_BONUS_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     opponent_drainer_attack_bonus: 800,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACK_BONUS_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     opponent_drainer_attack_bonus: 800,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACK_BONUS_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     opponent_drainer_attack_bonus: 800,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACK_BONUS_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     opponent_drainer_attack_bonus: 800,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACK_BONUS_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     opponent_drainer_attack_bonus: 800,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
@@ -804,7 +703,6 @@ const RUNTIME_NORMAL_WALK_THREAT_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS:
         mana_carrier_walk_threat_boolean: -400,
         ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
@@ -812,7 +710,6 @@ const RUNTIME_NORMAL_WALK_THREAT_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS:
         mana_carrier_walk_threat_boolean: -400,
         ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -820,7 +717,6 @@ const RUNTIME_NORMAL_WALK_THREAT_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORIN
     mana_carrier_walk_threat_boolean: -400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -828,7 +724,6 @@ const RUNTIME_NORMAL_WALK_THREAT_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIG
     mana_carrier_walk_threat_boolean: -400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -837,7 +732,6 @@ const RUNTIME_NORMAL_WALK_THREAT_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_S
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_LIGHT_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -845,7 +739,6 @@ const RUNTIME_NORMAL_WALK_THREAT_LIGHT_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEI
     mana_carrier_walk_threat_boolean: -100,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_LIGHT_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -853,7 +746,6 @@ const RUNTIME_NORMAL_WALK_THREAT_LIGHT_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEI
     mana_carrier_walk_threat_boolean: -100,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_LIGHT_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -861,7 +753,6 @@ const RUNTIME_NORMAL_WALK_THREAT_LIGHT_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_
     mana_carrier_walk_threat_boolean: -100,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_LIGHT_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -869,7 +760,6 @@ const RUNTIME_NORMAL_WALK_THREAT_LIGHT_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORIN
     mana_carrier_walk_threat_boolean: -100,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_LIGHT_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -878,7 +768,6 @@ const RUNTIME_NORMAL_WALK_THREAT_LIGHT_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -886,7 +775,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WE
     mana_carrier_walk_threat_boolean: -150,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -894,7 +782,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WE
     mana_carrier_walk_threat_boolean: -150,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -902,7 +789,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE
     mana_carrier_walk_threat_boolean: -150,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -910,7 +796,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORI
     mana_carrier_walk_threat_boolean: -150,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -919,7 +804,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MEDIUM_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MODERATE_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -927,7 +811,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MODERATE_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_
     mana_carrier_walk_threat_boolean: -200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MODERATE_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -935,7 +818,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MODERATE_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_
     mana_carrier_walk_threat_boolean: -200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MODERATE_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -943,7 +825,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MODERATE_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BA
     mana_carrier_walk_threat_boolean: -200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MODERATE_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -951,7 +832,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MODERATE_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCO
     mana_carrier_walk_threat_boolean: -200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_WALK_THREAT_MODERATE_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -960,7 +840,6 @@ const RUNTIME_NORMAL_WALK_THREAT_MODERATE_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIR
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_DRAINER_SHIELD_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
@@ -974,7 +853,6 @@ const RUNTIME_NORMAL_DRAINER_SHIELD_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHT
         mana_carrier_walk_threat_boolean: -400,
         ..RUNTIME_NORMAL_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_DRAINER_SHIELD_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS: ScoringWeights =
     ScoringWeights {
@@ -988,7 +866,6 @@ const RUNTIME_NORMAL_DRAINER_SHIELD_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHT
         mana_carrier_walk_threat_boolean: -400,
         ..RUNTIME_NORMAL_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
     };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_DRAINER_SHIELD_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -1002,7 +879,6 @@ const RUNTIME_NORMAL_DRAINER_SHIELD_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCO
     mana_carrier_walk_threat_boolean: -400,
     ..RUNTIME_NORMAL_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_DRAINER_SHIELD_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -1016,7 +892,6 @@ const RUNTIME_NORMAL_DRAINER_SHIELD_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_W
     mana_carrier_walk_threat_boolean: -400,
     ..RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 #[allow(dead_code)]
 const RUNTIME_NORMAL_DRAINER_SHIELD_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
@@ -1031,97 +906,82 @@ const RUNTIME_NORMAL_DRAINER_SHIELD_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BAS
     ..RUNTIME_NORMAL_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACKER_PROXIMITY_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACKER_PROXIMITY_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACKER_PROXIMITY_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACKER_PROXIMITY_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_ATTACKER_PROXIMITY_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACKER_PROXIMITY_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACKER_PROXIMITY_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACKER_PROXIMITY_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACKER_PROXIMITY_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_STRONG_ATTACKER_PROXIMITY_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_COMBO_PROXIMITY_ATTACK_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     opponent_drainer_attack_bonus: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_BALANCED_DISTANCE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_COMBO_PROXIMITY_ATTACK_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     opponent_drainer_attack_bonus: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_COMBO_PROXIMITY_ATTACK_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     opponent_drainer_attack_bonus: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_TACTICAL_BALANCED_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_COMBO_PROXIMITY_ATTACK_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
     opponent_drainer_attack_bonus: 400,
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_SPIRIT_BASE_SCORING_WEIGHTS
 };
-#[cfg(any(target_arch = "wasm32", test))]
 const RUNTIME_NORMAL_COMBO_PROXIMITY_ATTACK_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS:
     ScoringWeights = ScoringWeights {
     attacker_close_to_opponent_drainer: 200,
@@ -1129,17 +989,48 @@ const RUNTIME_NORMAL_COMBO_PROXIMITY_ATTACK_FINISHER_BALANCED_SOFT_AGGRESSIVE_SP
     ..RUNTIME_NORMAL_BOOLEAN_DRAINER_FINISHER_BALANCED_SOFT_AGGRESSIVE_SPIRIT_BASE_SCORING_WEIGHTS
 };
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum SmartAutomovePreference {
+pub enum SmartAutomovePreference {
     Fast,
     Normal,
     Pro,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutomoveError {
+    InvalidPreference(String),
+    GameOver,
+    NoLegalMoves,
+}
+
+impl std::fmt::Display for AutomoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPreference(_) => write!(
+                f,
+                "invalid smart automove mode; expected '{}', '{}', or '{}'",
+                SmartAutomovePreference::Fast.as_api_value(),
+                SmartAutomovePreference::Normal.as_api_value(),
+                SmartAutomovePreference::Pro.as_api_value(),
+            ),
+            Self::GameOver => write!(f, "game is already over"),
+            Self::NoLegalMoves => write!(f, "no legal moves available"),
+        }
+    }
+}
+
+impl std::error::Error for AutomoveError {}
+
+impl std::str::FromStr for SmartAutomovePreference {
+    type Err = AutomoveError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::from_api_value(value).ok_or_else(|| AutomoveError::InvalidPreference(value.into()))
+    }
+}
+
 impl SmartAutomovePreference {
-    fn from_api_value(value: &str) -> Option<Self> {
+    pub fn from_api_value(value: &str) -> Option<Self> {
         let normalized = value.trim();
         if normalized.eq_ignore_ascii_case("fast") {
             Some(Self::Fast)
@@ -1152,7 +1043,7 @@ impl SmartAutomovePreference {
         }
     }
 
-    fn as_api_value(self) -> &'static str {
+    pub fn as_api_value(self) -> &'static str {
         match self {
             Self::Fast => "fast",
             Self::Normal => "normal",
@@ -1178,7 +1069,6 @@ impl SmartAutomovePreference {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy)]
 pub(crate) struct AutomoveSearchConfig {
     depth: usize,
@@ -1318,7 +1208,6 @@ pub(crate) struct AutomoveSearchConfig {
     futility_margin: i32,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 impl AutomoveSearchConfig {
     fn from_preference(preference: SmartAutomovePreference) -> Self {
         let (depth, max_visited_nodes) = preference.depth_and_max_nodes();
@@ -1671,7 +1560,6 @@ impl AutomoveSearchConfig {
         }
     }
 
-    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
     fn for_runtime(self) -> Self {
         let mut tuned = self;
 
@@ -1713,7 +1601,6 @@ impl AutomoveSearchConfig {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone)]
 struct ScoredRootMove {
     root_rank: usize,
@@ -1746,7 +1633,6 @@ struct ScoredRootMove {
     classes: MoveClassFlags,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone)]
 pub(crate) struct RootEvaluation {
     root_rank: usize,
@@ -1779,7 +1665,6 @@ pub(crate) struct RootEvaluation {
     classes: MoveClassFlags,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone)]
 struct TurnEngineRootProjection {
     plan: TurnPlan,
@@ -1817,7 +1702,6 @@ pub(crate) struct TurnEngineSelectorDiagnostics {
     pub top_level_last_return_stage: &'static str,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum ProV2RootAdvisorReasonCode {
     RankedRoot,
@@ -1834,7 +1718,6 @@ pub(crate) enum ProV2RootAdvisorReasonCode {
     ApprovedFamilyCompetition,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct ProV2RootAdvisorEntry {
     pub inputs: Vec<Input>,
@@ -1843,7 +1726,6 @@ pub(crate) struct ProV2RootAdvisorEntry {
     pub reason: ProV2RootAdvisorReasonCode,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct ProV2InjectedRootAdvisorDecision {
     pub inputs: Vec<Input>,
@@ -1852,7 +1734,6 @@ pub(crate) struct ProV2InjectedRootAdvisorDecision {
     pub reason: ProV2RootAdvisorReasonCode,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) struct ProV2RootAdvisorDecision {
     pub ordered_shortlist: Vec<ProV2RootAdvisorEntry>,
@@ -1886,21 +1767,18 @@ thread_local! {
         const { std::cell::Cell::new(0) };
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct TurnEngineSelectorFollowupFloorCacheKey {
     state_hash: u64,
     perspective: Color,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct TurnEngineSelectedOverrideCacheKey {
     state_hash: u64,
     family: TurnPlanFamily,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct MoveEfficiencySnapshotCacheKey {
     state_hash: u64,
@@ -1909,7 +1787,6 @@ struct MoveEfficiencySnapshotCacheKey {
     include_strategic_exact: bool,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct SearchPreferabilityCacheKey {
     state_hash: u64,
@@ -1922,7 +1799,6 @@ struct SearchPreferabilityCacheKey {
     enable_scoring_drainer_attack_reach_target_narrowing: bool,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 thread_local! {
     static TURN_ENGINE_SELECTOR_FOLLOWUP_FLOOR_CACHE: std::cell::RefCell<
         std::collections::HashMap<TurnEngineSelectorFollowupFloorCacheKey, i32>
@@ -1938,7 +1814,6 @@ thread_local! {
     > = std::cell::RefCell::new(FastHashMap::default());
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 fn clear_turn_engine_selector_followup_floor_cache() {
     TURN_ENGINE_SELECTOR_FOLLOWUP_FLOOR_CACHE.with(|cache| cache.borrow_mut().clear());
     TURN_ENGINE_SELECTED_OVERRIDE_UTILITY_CACHE.with(|cache| cache.borrow_mut().clear());
@@ -2075,11 +1950,10 @@ fn set_turn_engine_root_selection_snapshot(
     });
 }
 
-#[cfg(all(not(test), target_arch = "wasm32"))]
+#[cfg(not(test))]
 #[inline]
 fn set_pro_v2_root_advisor_decision(_: Option<ProV2RootAdvisorDecision>) {}
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct MoveEfficiencySnapshot {
     my_best_carrier_steps: i32,
@@ -2106,14 +1980,12 @@ struct MoveEfficiencySnapshot {
     opponent_safe_opponent_mana_progress_steps: i32,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy)]
 struct CarrierProgressSnapshot {
     carrier_count: i32,
     best_carrier_steps: i32,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy)]
 struct NormalRootSafetySnapshot {
     allows_immediate_opponent_win: bool,
@@ -2123,13 +1995,11 @@ struct NormalRootSafetySnapshot {
     worst_reply_score: i32,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Default)]
 struct TargetedExactTurnSummaryMemo {
     entries: FastHashMap<u64, ExactTurnSummary>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 impl TargetedExactTurnSummaryMemo {
     fn get_or_compute(&mut self, game: &MonsGame, color: Color) -> ExactTurnSummary {
         if game.active_color != color {
@@ -2149,14 +2019,12 @@ impl TargetedExactTurnSummaryMemo {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone)]
 struct SameTurnScoreWindowCandidate {
     transition: LegalInputTransition,
     exact_turn: ExactTurnSummary,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[allow(dead_code)]
 enum MoveClass {
@@ -2168,7 +2036,6 @@ enum MoveClass {
     Quiet,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy, Debug, Default)]
 struct MoveClassFlags {
     immediate_score: bool,
@@ -2179,7 +2046,6 @@ struct MoveClassFlags {
     quiet: bool,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 impl MoveClassFlags {
     fn has(self, class: MoveClass) -> bool {
         match class {
@@ -2197,7 +2063,6 @@ impl MoveClassFlags {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy)]
 struct RootReplyRiskSnapshot {
     allows_immediate_opponent_win: bool,
@@ -2205,7 +2070,6 @@ struct RootReplyRiskSnapshot {
     worst_reply_score: i32,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy)]
 enum TranspositionBound {
     Exact,
@@ -2213,13 +2077,10 @@ enum TranspositionBound {
     UpperBound,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 type KillerTable = [[u64; 2]; MAX_SMART_SEARCH_DEPTH + 2];
 
-#[cfg(any(target_arch = "wasm32", test))]
 type HistoryTable = U64HashMap<i32>;
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy)]
 struct TranspositionEntry {
     depth: usize,
@@ -2228,18 +2089,17 @@ struct TranspositionEntry {
     best_child_hash: u64,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone)]
 struct RankedChildState {
     game: MonsGame,
     hash: u64,
+    ordering_hash: u64,
     ordering_efficiency: i32,
     tactical_extension_trigger: bool,
     quiet_reduction_candidate: bool,
     classes: MoveClassFlags,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone, Copy)]
 struct ChildEvalBundle {
     child_hash: u64,
@@ -2254,23 +2114,20 @@ struct ChildEvalBundle {
     classes: MoveClassFlags,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone)]
 struct CheapChildOrderingEntry {
     transition: LegalInputTransition,
-    child_hash: u64,
+    ordering_hash: u64,
     heuristic: i32,
     force_full: bool,
     eventful_reserve: bool,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 struct ChildOrderingScratch {
     before_efficiency_snapshot: Option<MoveEfficiencySnapshot>,
     own_drainer_vulnerable_before: bool,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 #[derive(Clone)]
 pub(crate) struct LegalInputTransition {
     pub inputs: Vec<Input>,
@@ -2278,13 +2135,10 @@ pub(crate) struct LegalInputTransition {
     pub events: Vec<Event>,
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 type RootMoveRepresentativeSpec = (ProV2RootAdvisorReasonCode, fn(&ScoredRootMove) -> bool);
 
-#[cfg(any(target_arch = "wasm32", test))]
 type RootEvaluationRepresentativeSpec = (ProV2RootAdvisorReasonCode, fn(&RootEvaluation) -> bool);
 
-#[cfg(test)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum AsyncSmartSearchPhase {
     PendingRootRanking,
@@ -2292,48 +2146,332 @@ enum AsyncSmartSearchPhase {
     Scoring,
 }
 
-#[cfg(test)]
-#[allow(clippy::large_enum_variant)]
-enum AsyncSmartSearchStart {
-    Immediate(OutputModel),
-    Pending(Box<AsyncSmartSearchState>),
-}
-
-#[cfg(test)]
 struct AsyncSmartSearchState {
     game: MonsGame,
     perspective: Color,
     config: AutomoveSearchConfig,
     phase: AsyncSmartSearchPhase,
     root_moves: Vec<ScoredRootMove>,
-    pending_output: Option<OutputModel>,
     next_index: usize,
     visited_nodes: usize,
     alpha: i32,
     extension_nodes_used: usize,
     extension_node_budget: usize,
+    transposition_table: U64HashMap<TranspositionEntry>,
+    killer_table: KillerTable,
+    history_table: HistoryTable,
+    quiescence_nodes_used: usize,
+}
+
+/// Where a smart search is, between the stages it can pause at.
+enum SmartSearchStage {
+    /// Search with this config from the start, as the guards fell back to it.
+    Start(Box<AutomoveSearchConfig>),
+    Ranked(Box<RankedSmartSearch>),
+    Planned(Box<PlannedSmartSearch>),
+    Advised(Box<AdvisedSmartSearch>),
+    Scoring(Box<SmartRootSearch>),
+    Finished(Vec<Input>),
+}
+
+struct RankedSmartSearch {
+    perspective: Color,
+    config: AutomoveSearchConfig,
+    use_transposition_table: bool,
+    root_moves: Vec<ScoredRootMove>,
+    prechecked_cached_inputs: Option<Vec<Input>>,
+    live_turn_engine_config: TurnEngineConfig,
+}
+
+struct PlannedSmartSearch {
+    perspective: Color,
+    config: AutomoveSearchConfig,
+    use_transposition_table: bool,
+    root_moves: Vec<ScoredRootMove>,
+    turn_engine: Option<SmartRootSearchTurnEngine>,
+}
+
+struct AdvisedSmartSearch {
+    perspective: Color,
+    config: AutomoveSearchConfig,
+    use_transposition_table: bool,
+    root_moves: Vec<ScoredRootMove>,
+    advisor_priority_inputs: Vec<Vec<Input>>,
+    turn_engine: Option<SmartRootSearchTurnEngine>,
+}
+
+/// Turn planner state a search with the turn engine selector settles its
+/// choice against.
+struct SmartRootSearchTurnEngine {
+    mode: TurnEngineMode,
+    config: TurnEngineConfig,
+    head_plan: Option<TurnPlan>,
+    cached_inputs: Option<Vec<Input>>,
+}
+
+/// Root scoring of a smart search, a root move at a time: the shallow
+/// iterative deepening pass first when the config asks for it, then the full
+/// depth search of each root move until the node budget runs out.
+struct SmartRootSearch {
+    perspective: Color,
+    config: AutomoveSearchConfig,
+    use_transposition_table: bool,
+    turn_engine: Option<SmartRootSearchTurnEngine>,
+    /// Root moves left to score, in the order they get scored.
+    root_moves: Vec<ScoredRootMove>,
+    /// Scores of the shallow pass so far, while it runs.
+    preliminary_scores: Option<Vec<i32>>,
+    preliminary_alpha: i32,
+    visited_nodes: usize,
+    alpha: i32,
     scored_roots: Vec<RootEvaluation>,
     transposition_table: U64HashMap<TranspositionEntry>,
+    extension_nodes_used: usize,
+    extension_node_budget: usize,
     killer_table: KillerTable,
     history_table: HistoryTable,
     quiescence_nodes_used: usize,
 }
 
+impl SmartRootSearch {
+    fn new(
+        perspective: Color,
+        config: AutomoveSearchConfig,
+        use_transposition_table: bool,
+        root_moves: Vec<ScoredRootMove>,
+        scout_visited_nodes: usize,
+        transposition_table: U64HashMap<TranspositionEntry>,
+        turn_engine: Option<SmartRootSearchTurnEngine>,
+    ) -> Self {
+        let extension_node_budget =
+            if config.enable_selective_extensions && config.selective_extension_node_share_bp > 0 {
+                ((config.max_visited_nodes * config.selective_extension_node_share_bp as usize)
+                    / 10_000)
+                    .max(1)
+            } else {
+                0
+            };
+        Self {
+            perspective,
+            config,
+            use_transposition_table,
+            turn_engine,
+            preliminary_scores: (config.enable_iterative_deepening && config.depth >= 3)
+                .then(|| Vec::with_capacity(root_moves.len())),
+            root_moves,
+            preliminary_alpha: i32::MIN,
+            visited_nodes: scout_visited_nodes,
+            alpha: i32::MIN,
+            scored_roots: Vec::new(),
+            transposition_table,
+            extension_nodes_used: 0,
+            extension_node_budget,
+            killer_table: [[0u64; 2]; MAX_SMART_SEARCH_DEPTH + 2],
+            history_table: HistoryTable::default(),
+            quiescence_nodes_used: 0,
+        }
+    }
+
+    fn without_preliminary_pass(mut self) -> Self {
+        self.preliminary_scores = None;
+        self
+    }
+
+    /// Searches the next root move. False once none is left to score or the
+    /// node budget ran out.
+    fn score_next_root(&mut self) -> bool {
+        if let Some(preliminary_scores) = self.preliminary_scores.as_mut() {
+            let Some(candidate) = self.root_moves.get(preliminary_scores.len()) else {
+                self.finish_preliminary_pass();
+                return true;
+            };
+            let preliminary_score = if self.visited_nodes >= self.config.max_visited_nodes {
+                i32::MIN
+            } else {
+                self.visited_nodes += 1;
+                MonsGameModel::search_score(
+                    &candidate.game,
+                    self.perspective,
+                    self.config
+                        .depth
+                        .saturating_sub(self.config.iterative_deepening_depth_offset)
+                        .max(1),
+                    self.preliminary_alpha,
+                    i32::MAX,
+                    &mut self.visited_nodes,
+                    self.config,
+                    &mut self.transposition_table,
+                    0,
+                    &mut self.extension_nodes_used,
+                    0,
+                    self.use_transposition_table,
+                    &mut self.killer_table,
+                    &mut self.history_table,
+                    &mut self.quiescence_nodes_used,
+                )
+            };
+            preliminary_scores.push(preliminary_score);
+            self.preliminary_alpha = self.preliminary_alpha.max(preliminary_score);
+            return true;
+        }
+
+        if self.root_moves.is_empty() || self.visited_nodes >= self.config.max_visited_nodes {
+            return false;
+        }
+        let candidate = self.root_moves.remove(0);
+        self.visited_nodes += 1;
+        let candidate_score = MonsGameModel::evaluate_root_candidate_score(
+            &candidate,
+            self.perspective,
+            self.alpha,
+            &mut self.visited_nodes,
+            self.config,
+            &mut self.transposition_table,
+            &mut self.extension_nodes_used,
+            self.extension_node_budget,
+            self.use_transposition_table,
+            &mut self.killer_table,
+            &mut self.history_table,
+            &mut self.quiescence_nodes_used,
+        );
+        if candidate_score > self.alpha {
+            self.alpha = candidate_score;
+        }
+        self.scored_roots
+            .push(automove_runtime_variants::root_evaluation_from_scored_root(
+                candidate,
+                candidate_score,
+            ));
+        true
+    }
+
+    /// Orders the root moves by their shallow pass scores, and starts alpha
+    /// from the best of them when the config sets a margin.
+    fn finish_preliminary_pass(&mut self) {
+        let Some(preliminary_scores) = self.preliminary_scores.take() else {
+            return;
+        };
+        let best_preliminary_score = preliminary_scores.iter().copied().max().unwrap_or(i32::MIN);
+        self.root_moves = MonsGameModel::sort_root_moves_by_ranked_scores(
+            std::mem::take(&mut self.root_moves),
+            preliminary_scores.as_slice(),
+        );
+        if self.config.iterative_deepening_alpha_margin > 0 && best_preliminary_score != i32::MIN {
+            self.alpha =
+                best_preliminary_score.saturating_sub(self.config.iterative_deepening_alpha_margin);
+        }
+    }
+
+    /// Scores the root moves left across threads, once the shallow pass ran.
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    fn score_roots_in_parallel(&mut self, options: ParallelSearchOptions) {
+        while self.preliminary_scores.is_some() {
+            self.score_next_root();
+        }
+        self.scored_roots
+            .extend(MonsGameModel::score_root_moves_in_parallel(
+                options,
+                std::mem::take(&mut self.root_moves),
+                automove_parallel::RootSplit {
+                    perspective: self.perspective,
+                    alpha: self.alpha,
+                    visited_nodes: self.visited_nodes,
+                    config: self.config,
+                    transposition_table: &self.transposition_table,
+                    extension_node_budget: self.extension_node_budget,
+                    use_transposition_table: self.use_transposition_table,
+                    killer_table: &self.killer_table,
+                    history_table: &self.history_table,
+                },
+            ));
+    }
+
+    /// Move the search would pick among the root moves scored so far.
+    fn best_inputs_so_far(&self, game: &MonsGame) -> Option<Vec<Input>> {
+        (!self.scored_roots.is_empty()).then(|| {
+            MonsGameModel::pick_root_move_with_exploration(
+                game,
+                &self.scored_roots,
+                self.perspective,
+                self.config,
+            )
+        })
+    }
+}
+
 #[wasm_bindgen]
 impl MonsGameModel {
     fn with_game(game: MonsGame) -> Self {
-        Self { game }
+        Self {
+            game,
+            history_line: HistoryLine::default(),
+        }
     }
 
-    pub fn new(variant: GameVariant) -> MonsGameModel {
-        Self::with_game(MonsGame::new(true, variant))
+    /// Initial setup of a built-in variant. Nothing for `Custom`; use
+    /// `newWithVariantName` for registered variants.
+    pub fn new(variant: GameVariant) -> Option<MonsGameModel> {
+        let layout = VariantLayout::builtin(variant)?;
+        Some(Self::with_game(MonsGame::new_with_layout(true, layout)))
     }
 
     #[wasm_bindgen(js_name = newForSimulation)]
-    pub fn new_for_simulation(variant: GameVariant) -> MonsGameModel {
-        let mut game = MonsGame::new(false, variant);
+    pub fn new_for_simulation(variant: GameVariant) -> Option<MonsGameModel> {
+        let mut game = MonsGame::new_with_layout(false, VariantLayout::builtin(variant)?);
         game.set_takeback_history_tracking(false);
-        Self::with_game(game)
+        Some(Self::with_game(game))
+    }
+
+    /// Initial setup of a variant previously added with `registerVariant`.
+    #[wasm_bindgen(js_name = newWithVariantName)]
+    pub fn new_with_variant_name(name: &str) -> Option<MonsGameModel> {
+        VariantLayout::registered(name)
+            .map(|layout| Self::with_game(MonsGame::new_with_layout(true, layout)))
+    }
+
+    /// Initial setup of `variant` under the standard rules with the
+    /// parameters in `rules` replaced, as in `target=3,mons=4`.
+    #[wasm_bindgen(js_name = newWithRules)]
+    pub fn new_with_rules(variant: GameVariant, rules: &str) -> Option<MonsGameModel> {
+        let mut game = MonsGame::new_with_layout(true, VariantLayout::builtin(variant)?);
+        game.set_rules(RuleSet::from_code(rules, game.rules())?);
+        Some(Self::with_game(game))
+    }
+
+    /// Parameters of the game's rules that differ from the standard ones.
+    #[wasm_bindgen(js_name = rulesCode)]
+    pub fn rules_code(&self) -> String {
+        self.game.rules().code(&RuleSet::STANDARD)
+    }
+
+    /// Initial setup of `variant` with the odds in `handicap` applied, as in
+    /// `w:-drainer,b:potions=1`. Nothing when a side would start at the
+    /// target score.
+    #[wasm_bindgen(js_name = newWithHandicap)]
+    pub fn new_with_handicap(variant: GameVariant, handicap: &str) -> Option<MonsGameModel> {
+        let handicap = Handicap::from_code(handicap)?;
+        MonsGame::new_with_handicap(true, variant, &handicap).map(Self::with_game)
+    }
+
+    /// Odds the game started with, empty for an even game.
+    #[wasm_bindgen(js_name = handicapCode)]
+    pub fn handicap_code(&self) -> String {
+        self.game.handicap().code()
+    }
+
+    /// Registers a variant from its text definition. Returns why the
+    /// definition was rejected, or nothing once it is registered.
+    #[wasm_bindgen(js_name = registerVariant)]
+    pub fn register_variant(definition: &str) -> Option<String> {
+        VariantSpec::from_text(definition)
+            .and_then(VariantSpec::register)
+            .err()
+            .map(|error| error.to_string())
+    }
+
+    #[wasm_bindgen(js_name = variantDefinition)]
+    pub fn variant_definition(&self) -> String {
+        self.game.variant_layout().spec().to_text()
     }
 
     pub fn from_fen(fen: &str) -> Option<MonsGameModel> {
@@ -2377,22 +2515,117 @@ impl MonsGameModel {
         self.game.fen()
     }
 
+    #[wasm_bindgen(js_name = fromRecord)]
+    pub fn from_record(record: &str) -> Option<MonsGameModel> {
+        let record = GameRecord::from_text(record).ok()?;
+        MonsGame::from_record(&record, true)
+            .ok()
+            .map(Self::with_game)
+    }
+
+    #[wasm_bindgen(js_name = toRecord)]
+    pub fn to_record(&self) -> String {
+        self.game.to_record().to_text()
+    }
+
+    /// Analysis tree whose main line is this game's input history.
+    #[wasm_bindgen(js_name = gameTree)]
+    pub fn game_tree(&self) -> GameTreeModel {
+        GameTreeModel {
+            tree: GameTree::from_game(&self.game),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromBase64)]
+    pub fn from_base64(encoded: &str) -> Option<MonsGameModel> {
+        MonsGame::from_base64(encoded, true).map(Self::with_game)
+    }
+
+    #[wasm_bindgen(js_name = toBase64)]
+    pub fn to_base64(&self) -> String {
+        self.game.to_base64()
+    }
+
     #[cfg(target_arch = "wasm32")]
     #[wasm_bindgen(js_name = smartAutomove)]
     pub fn smart_automove(&self, preference: &str) -> Result<OutputModel, JsValue> {
-        let Some(preference) = SmartAutomovePreference::from_api_value(preference) else {
-            let message = format!(
-                "invalid smart automove mode; expected '{}', '{}', or '{}'",
-                SmartAutomovePreference::Fast.as_api_value(),
-                SmartAutomovePreference::Normal.as_api_value(),
-                SmartAutomovePreference::Pro.as_api_value(),
-            );
-            return Err(JsValue::from_str(message.as_str()));
-        };
+        let preference = preference
+            .parse::<SmartAutomovePreference>()
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))?;
 
         Ok(self.smart_automove_output(preference))
     }
 
+    /// Best move found within `budget_ms`, searching deeper while time is
+    /// left. `clock` returns the current time in milliseconds, such as
+    /// `() => performance.now()`.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = smartAutomoveWithin)]
+    pub fn smart_automove_with_js_clock(
+        &self,
+        budget_ms: f64,
+        clock: js_sys::Function,
+    ) -> Result<OutputModel, JsValue> {
+        self.smart_automove_within(budget_ms, move || {
+            clock
+                .call0(&JsValue::NULL)
+                .ok()
+                .and_then(|now| now.as_f64())
+                .unwrap_or(f64::INFINITY)
+        })
+        .map_err(|error| JsValue::from_str(error.to_string().as_str()))
+    }
+
+    /// Top `k` candidate moves for `preference` with their scores and
+    /// principal variations, best first.
+    #[cfg(target_arch = "wasm32")]
+    pub fn analyze(&self, preference: &str, k: usize) -> Result<Vec<AnalysisLine>, JsValue> {
+        let preference = preference
+            .parse::<SmartAutomovePreference>()
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))?;
+        self.analyze_native(preference, k)
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))
+    }
+
+    /// Starts a smart automove search that runs in slices; see `SearchHandle`.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = beginSearch)]
+    pub fn begin_search(&self, preference: &str) -> Result<SearchHandle, JsValue> {
+        let preference = preference
+            .parse::<SmartAutomovePreference>()
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))?;
+        self.begin_search_native(preference)
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))
+    }
+
+    /// Starts pondering the reply to the opponent's predicted turn while
+    /// the opponent, who is to move, thinks; see `Ponder`.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = beginPonder)]
+    pub fn begin_ponder(&self, preference: &str) -> Result<Ponder, JsValue> {
+        let preference = preference
+            .parse::<SmartAutomovePreference>()
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))?;
+        self.begin_ponder_native(preference)
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))
+    }
+
+    /// `smartAutomove` that reuses `ponder`'s search when the opponent
+    /// played the predicted turn.
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen(js_name = smartAutomovePondered)]
+    pub fn smart_automove_pondered(
+        &self,
+        preference: &str,
+        ponder: Ponder,
+    ) -> Result<OutputModel, JsValue> {
+        let preference = preference
+            .parse::<SmartAutomovePreference>()
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))?;
+        self.smart_automove_pondered_native(preference, ponder)
+            .map_err(|error| JsValue::from_str(error.to_string().as_str()))
+    }
+
     pub fn automove(&mut self) -> OutputModel {
         Self::automove_game(&mut self.game)
     }
@@ -2437,8 +2670,7 @@ impl MonsGameModel {
                     );
                 }
                 Output::Events(events) => {
-                    let input_fen = Input::fen_from_array(&inputs);
-                    return OutputModel::new(Output::Events(events), input_fen.as_str());
+                    return OutputModel::new_with_move(Output::Events(events), &inputs);
                 }
             }
         }
@@ -2472,6 +2704,60 @@ impl MonsGameModel {
         self.game.clear_tracking();
     }
 
+    /// Number of input chains in the current history line, including ones
+    /// that were undone and can be redone.
+    #[wasm_bindgen(js_name = historyLen)]
+    pub fn history_len(&self) -> usize {
+        self.history_line.len(&self.game)
+    }
+
+    /// Index of the current position in the history line.
+    #[wasm_bindgen(js_name = historyPly)]
+    pub fn history_ply(&self) -> usize {
+        self.game.input_history().len()
+    }
+
+    /// Replays the history line up to its first `ply` input chains, keeping
+    /// later chains available for redo.
+    #[wasm_bindgen(js_name = gotoPly)]
+    pub fn goto_ply(&mut self, ply: usize) -> bool {
+        self.history_line.sync(&self.game);
+        match self.history_line.game_at(&self.game, ply) {
+            Some(game) => {
+                self.game = game;
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[wasm_bindgen(js_name = undoMove)]
+    pub fn undo_move(&mut self) -> bool {
+        match self.history_ply().checked_sub(1) {
+            Some(ply) => self.goto_ply(ply),
+            None => false,
+        }
+    }
+
+    #[wasm_bindgen(js_name = redoMove)]
+    pub fn redo_move(&mut self) -> bool {
+        self.goto_ply(self.history_ply() + 1)
+    }
+
+    /// Goes back to the start of the current turn, or of the previous turn
+    /// if no move has been made in the current one yet.
+    #[wasm_bindgen(js_name = undoTurn)]
+    pub fn undo_turn(&mut self) -> bool {
+        self.history_line.sync(&self.game);
+        match self
+            .history_line
+            .previous_turn_start(&self.game, self.history_ply())
+        {
+            Some(ply) => self.goto_ply(ply),
+            None => false,
+        }
+    }
+
     pub fn takeback(&mut self) -> OutputModel {
         let inputs: Vec<Input> = vec![Input::Takeback];
         let input_fen = Input::fen_from_array(&inputs);
@@ -2512,6 +2798,49 @@ impl MonsGameModel {
     }
 
     pub fn verify_moves(&mut self, flat_moves_string_w: &str, flat_moves_string_b: &str) -> bool {
+        self.verify_moves_internal(flat_moves_string_w, flat_moves_string_b, None)
+    }
+
+    /// `verify_moves` for a timed game. `flat_timestamps_w` / `flat_timestamps_b`
+    /// hold one `-` separated timestamp per move, in milliseconds since the
+    /// game started, and `time_control` uses the `TimeControl` text form.
+    /// A flag-fall found during the replay is kept as the game's termination.
+    #[wasm_bindgen(js_name = verifyMovesWithTimestamps)]
+    pub fn verify_moves_with_timestamps(
+        &mut self,
+        flat_moves_string_w: &str,
+        flat_moves_string_b: &str,
+        flat_timestamps_w: &str,
+        flat_timestamps_b: &str,
+        time_control: &str,
+    ) -> bool {
+        let parse_timestamps = |flat: &str| -> Option<Vec<u64>> {
+            if flat.is_empty() {
+                Some(Vec::new())
+            } else {
+                flat.split("-").map(|value| value.parse().ok()).collect()
+            }
+        };
+        let (Some(timestamps_w), Some(timestamps_b), Ok(time_control)) = (
+            parse_timestamps(flat_timestamps_w),
+            parse_timestamps(flat_timestamps_b),
+            time_control.parse::<TimeControl>(),
+        ) else {
+            return false;
+        };
+        self.verify_moves_internal(
+            flat_moves_string_w,
+            flat_moves_string_b,
+            Some((GameClock::new(time_control), timestamps_w, timestamps_b)),
+        )
+    }
+
+    fn verify_moves_internal(
+        &mut self,
+        flat_moves_string_w: &str,
+        flat_moves_string_b: &str,
+        mut timing: Option<(GameClock, Vec<u64>, Vec<u64>)>,
+    ) -> bool {
         let moves_w: Vec<&str> = if flat_moves_string_w.is_empty() {
             Vec::new()
         } else {
@@ -2523,8 +2852,17 @@ impl MonsGameModel {
             flat_moves_string_b.split("-").collect()
         };
 
+        if let Some((_, timestamps_w, timestamps_b)) = &timing {
+            if timestamps_w.len() != moves_w.len() || timestamps_b.len() != moves_b.len() {
+                return false;
+            }
+        }
+
         let with_verbose_tracking = self.game.with_verbose_tracking;
-        let mut fresh_verification_game = MonsGame::new(with_verbose_tracking, self.game.variant());
+        let mut fresh_verification_game = self.game.initial_game(with_verbose_tracking);
+        if let Some((clock, _, _)) = &mut timing {
+            clock.start(Color::White, 0);
+        }
 
         let mut w_index = 0;
         let mut b_index = 0;
@@ -2535,20 +2873,47 @@ impl MonsGameModel {
                     return false;
                 }
                 let inputs = Input::array_from_fen(moves_w[w_index]);
-                _ = fresh_verification_game.process_input(inputs, false, false);
+                match &mut timing {
+                    Some((clock, timestamps_w, _)) => {
+                        _ = fresh_verification_game.process_input_timed(
+                            inputs,
+                            clock,
+                            timestamps_w[w_index],
+                        )
+                    }
+                    None => _ = fresh_verification_game.process_input(inputs, false, false),
+                }
                 w_index += 1;
             } else {
                 if b_index >= moves_b.len() {
                     return false;
                 }
                 let inputs = Input::array_from_fen(moves_b[b_index]);
-                _ = fresh_verification_game.process_input(inputs, false, false);
+                match &mut timing {
+                    Some((clock, _, timestamps_b)) => {
+                        _ = fresh_verification_game.process_input_timed(
+                            inputs,
+                            clock,
+                            timestamps_b[b_index],
+                        )
+                    }
+                    None => _ = fresh_verification_game.process_input(inputs, false, false),
+                }
                 b_index += 1;
             }
         }
 
         if fresh_verification_game.fen() == self.game.fen() {
+            self.game.set_input_history(
+                fresh_verification_game
+                    .input_history_start_fen()
+                    .map(str::to_string),
+                fresh_verification_game.input_history().to_vec(),
+            );
             self.game.takeback_fens = fresh_verification_game.takeback_fens;
+            if timing.is_some() {
+                self.game.termination = fresh_verification_game.termination;
+            }
             if with_verbose_tracking {
                 self.game.verbose_tracking_entities =
                     fresh_verification_game.verbose_tracking_entities;
@@ -2571,6 +2936,38 @@ impl MonsGameModel {
         self.game.winner_color()
     }
 
+    #[wasm_bindgen(js_name = terminationReason)]
+    pub fn termination_reason(&self) -> Option<TerminationReason> {
+        self.game
+            .termination()
+            .map(|termination| termination.reason)
+    }
+
+    pub fn resign(&mut self, color: Color) -> bool {
+        self.game.resign(color).is_some()
+    }
+
+    /// Descriptions of everything `PositionValidator` objects to in the
+    /// current position, empty for a sane one.
+    #[wasm_bindgen(js_name = positionViolations)]
+    pub fn position_violations(&self) -> Vec<String> {
+        self.game
+            .validate_position()
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect()
+    }
+
+    #[wasm_bindgen(js_name = repetitionCount)]
+    pub fn repetition_count(&self) -> usize {
+        self.game.repetition_count()
+    }
+
+    #[wasm_bindgen(js_name = turnsSinceProgress)]
+    pub fn turns_since_progress(&self) -> u32 {
+        self.game.turns_since_progress()
+    }
+
     pub fn black_score(&self) -> i32 {
         self.game.black_score
     }
@@ -2631,8 +3028,101 @@ impl MonsGameModel {
     }
 }
 
-#[cfg(any(target_arch = "wasm32", test))]
 impl MonsGameModel {
+    /// Native counterpart of `smartAutomove`: same move selection, typed errors.
+    /// The returned output is not applied to this model.
+    pub fn smart_automove_native(
+        &self,
+        preference: SmartAutomovePreference,
+    ) -> Result<OutputModel, AutomoveError> {
+        if self.game.is_game_over() {
+            return Err(AutomoveError::GameOver);
+        }
+        let output = self.smart_automove_output(preference);
+        if output.kind == OutputModelKind::InvalidInput {
+            return Err(AutomoveError::NoLegalMoves);
+        }
+        Ok(output)
+    }
+
+    /// Native counterpart of `analyze`. Scores come from the alpha-beta
+    /// search of `preference`; the move `smart_automove_native` plays can
+    /// still differ, as Pro layers a turn planner on top of that search.
+    pub fn analyze_native(
+        &self,
+        preference: SmartAutomovePreference,
+        k: usize,
+    ) -> Result<Vec<AnalysisLine>, AutomoveError> {
+        if self.game.is_game_over() {
+            return Err(AutomoveError::GameOver);
+        }
+        let config = self.shipping_search_config_for_preference(preference);
+        let lines = Self::analysis_lines(&self.game, config, k);
+        if lines.is_empty() && k > 0 {
+            return Err(AutomoveError::NoLegalMoves);
+        }
+        Ok(lines)
+    }
+
+    /// Native counterpart of `beginSearch`.
+    pub fn begin_search_native(
+        &self,
+        preference: SmartAutomovePreference,
+    ) -> Result<SearchHandle, AutomoveError> {
+        if self.game.is_game_over() {
+            return Err(AutomoveError::GameOver);
+        }
+        Ok(self.begin_search_handle(preference))
+    }
+
+    /// Native counterpart of `beginPonder`.
+    pub fn begin_ponder_native(
+        &self,
+        preference: SmartAutomovePreference,
+    ) -> Result<Ponder, AutomoveError> {
+        if self.game.is_game_over() {
+            return Err(AutomoveError::GameOver);
+        }
+        Ok(self.begin_ponder_handle(preference))
+    }
+
+    /// Native counterpart of `smartAutomovePondered`. The returned output
+    /// is not applied to this model.
+    pub fn smart_automove_pondered_native(
+        &self,
+        preference: SmartAutomovePreference,
+        ponder: Ponder,
+    ) -> Result<OutputModel, AutomoveError> {
+        if self.game.is_game_over() {
+            return Err(AutomoveError::GameOver);
+        }
+        let output = self.smart_automove_pondered_output(preference, ponder);
+        if output.kind == OutputModelKind::InvalidInput {
+            return Err(AutomoveError::NoLegalMoves);
+        }
+        Ok(output)
+    }
+
+    /// Wall-clock counterpart of `smart_automove_native`: searches the Fast,
+    /// Normal and Pro levels in turn, then deeper Pro searches, and returns
+    /// the deepest one that finished within `budget_ms` of `clock`, which
+    /// reads milliseconds. The returned output is not applied to this model.
+    pub fn smart_automove_within(
+        &self,
+        budget_ms: f64,
+        clock: impl Fn() -> f64 + 'static,
+    ) -> Result<OutputModel, AutomoveError> {
+        if self.game.is_game_over() {
+            return Err(AutomoveError::GameOver);
+        }
+        let result = self.timed_search_inputs(budget_ms, std::rc::Rc::new(clock));
+        let output = self.output_model_from_runtime_inputs(result.inputs);
+        if output.kind == OutputModelKind::InvalidInput {
+            return Err(AutomoveError::NoLegalMoves);
+        }
+        Ok(output)
+    }
+
     fn public_runtime_inputs(
         &self,
         preference: SmartAutomovePreference,
@@ -2649,15 +3139,16 @@ impl MonsGameModel {
     }
 
     fn output_model_from_runtime_inputs(&self, inputs: Vec<Input>) -> OutputModel {
+        Self::runtime_output_for_game(&self.game, inputs)
+    }
+
+    fn runtime_output_for_game(game: &MonsGame, inputs: Vec<Input>) -> OutputModel {
+        let mut game = game.clone_for_simulation();
         if inputs.is_empty() {
-            let mut game = self.game.clone_for_simulation();
             return Self::automove_game(&mut game);
         }
-
-        let mut game = self.game.clone_for_simulation();
-        let input_fen = Input::fen_from_array(&inputs);
-        let output = game.process_input(inputs, false, false);
-        OutputModel::new(output, input_fen.as_str())
+        let output = game.process_input_slice(&inputs, false, false);
+        OutputModel::new_with_move(output, &inputs)
     }
 
     fn smart_automove_output(&self, preference: SmartAutomovePreference) -> OutputModel {
@@ -2666,7 +3157,6 @@ impl MonsGameModel {
         self.output_model_from_runtime_inputs(inputs)
     }
 
-    #[cfg(test)]
     fn new_async_smart_search_state(
         game: MonsGame,
         perspective: Color,
@@ -2678,13 +3168,11 @@ impl MonsGameModel {
             config,
             phase: AsyncSmartSearchPhase::PendingRootRanking,
             root_moves: Vec::new(),
-            pending_output: None,
             next_index: 0,
             visited_nodes: 0,
             alpha: i32::MIN,
             extension_nodes_used: 0,
             extension_node_budget: 0,
-            scored_roots: Vec::new(),
             transposition_table: U64HashMap::default(),
             killer_table: [[0u64; 2]; MAX_SMART_SEARCH_DEPTH + 2],
             history_table: HistoryTable::default(),
@@ -2692,26 +3180,6 @@ impl MonsGameModel {
         }
     }
 
-    #[cfg(test)]
-    fn begin_async_smart_search(
-        &self,
-        preference: SmartAutomovePreference,
-    ) -> AsyncSmartSearchStart {
-        clear_exact_state_analysis_cache();
-        let config = self.shipping_search_config_for_preference(preference);
-        if matches!(preference, SmartAutomovePreference::Pro) {
-            let inputs = self.public_runtime_inputs(preference, config);
-            return AsyncSmartSearchStart::Immediate(self.output_model_from_runtime_inputs(inputs));
-        }
-        let perspective = self.game.active_color;
-        let game = self.game.clone_for_simulation();
-        AsyncSmartSearchStart::Pending(Box::new(Self::new_async_smart_search_state(
-            game,
-            perspective,
-            config,
-        )))
-    }
-
     fn shipping_search_config_for_preference(
         &self,
         preference: SmartAutomovePreference,
@@ -2724,14 +3192,12 @@ impl MonsGameModel {
         &self,
         preference: SmartAutomovePreference,
     ) -> OutputModel {
-        let mut state = match self.begin_async_smart_search(preference) {
-            AsyncSmartSearchStart::Immediate(output) => return output,
-            AsyncSmartSearchStart::Pending(state) => *state,
-        };
-
+        let mut handle = self.begin_search_handle(preference);
         for _ in 0..1_000_000usize {
-            if Self::advance_async_search(&mut state) {
-                return Self::finalize_async_search(&mut state);
+            if handle.step(1) {
+                return handle
+                    .best_so_far()
+                    .expect("finished search handle has an output");
             }
         }
 
@@ -2901,7 +3367,6 @@ impl MonsGameModel {
         config
     }
 
-    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
     fn with_runtime_scoring_weights(
         game: &MonsGame,
         mut config: AutomoveSearchConfig,
@@ -2932,8 +3397,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -2963,8 +3428,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -2994,8 +3459,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3025,8 +3490,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3056,8 +3521,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3101,8 +3566,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3132,8 +3597,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3163,8 +3628,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3194,8 +3659,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3225,8 +3690,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3256,8 +3721,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3287,8 +3752,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3318,8 +3783,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         let score_gap = my_score - opponent_score;
 
         if my_distance_to_win <= 1 {
@@ -3492,7 +3957,7 @@ impl MonsGameModel {
             && simulated_game.active_color == perspective
             && same_turn_score_window_value > 0;
         let spirit_own_mana_setup_now =
-            Self::events_spirit_scoring_mana_setup(&events, &simulated_game.board, perspective);
+            Self::events_spirit_scoring_mana_setup(&events, &simulated_game, perspective);
         let spirit_setup_gain = if config.enable_static_exact_evaluation {
             exact_strategic_analysis(&simulated_game)
                 .color_summary(perspective)
@@ -3508,26 +3973,26 @@ impl MonsGameModel {
         };
         let safe_supermana_pickup_now = Self::events_pickup_supermana(&events)
             && Self::own_drainer_carries_specific_mana_safely(
-                &simulated_game.board,
+                &simulated_game,
                 perspective,
                 Mana::Supermana,
             );
         let safe_opponent_mana_pickup_now = Self::events_pickup_opponent_mana(&events, perspective)
             && Self::own_drainer_carries_specific_mana_safely(
-                &simulated_game.board,
+                &simulated_game,
                 perspective,
                 Mana::Regular(perspective.other()),
             );
         let supermana_progress = scores_supermana_this_turn
             || Self::events_pickup_supermana(&events)
             || Self::events_move_supermana_toward_color(&events, perspective)
-            || Self::events_spirit_supermana_setup(&events, &simulated_game.board, perspective)
+            || Self::events_spirit_supermana_setup(&events, &simulated_game, perspective)
             || exact_turn.safe_supermana_progress
             || exact_turn.spirit_assisted_supermana_progress;
         let opponent_mana_progress = scores_opponent_mana_this_turn
             || Self::events_pickup_opponent_mana(&events, perspective)
             || Self::events_move_opponent_mana_toward_color(&events, perspective)
-            || Self::events_spirit_opponent_mana_setup(&events, &simulated_game.board, perspective)
+            || Self::events_spirit_opponent_mana_setup(&events, &simulated_game, perspective)
             || exact_turn.safe_opponent_mana_progress
             || exact_turn.spirit_assisted_opponent_mana_progress
             || exact_turn.spirit_assisted_denial;
@@ -3611,6 +4076,7 @@ impl MonsGameModel {
             own_drainer_vulnerable,
             mana_handoff_to_opponent,
             has_roundtrip,
+            game.rules().mons_moves_per_turn,
         );
 
         let drainer_exposure_penalty = if own_drainer_vulnerable
@@ -3867,7 +4333,7 @@ impl MonsGameModel {
 
     fn events_spirit_move_opponent_mana_onto_safe_drainer(
         events: &[Event],
-        board_after: &Board,
+        game_after: &MonsGame,
         perspective: Color,
     ) -> bool {
         events.iter().any(|event| {
@@ -3881,7 +4347,7 @@ impl MonsGameModel {
                 } if *owner == perspective.other()
             )
         }) && Self::own_drainer_carries_specific_mana_safely(
-            board_after,
+            game_after,
             perspective,
             Mana::Regular(perspective.other()),
         )
@@ -3905,7 +4371,7 @@ impl MonsGameModel {
 
     fn events_spirit_move_supermana_onto_safe_drainer(
         events: &[Event],
-        board_after: &Board,
+        game_after: &MonsGame,
         perspective: Color,
     ) -> bool {
         events.iter().any(|event| {
@@ -3919,7 +4385,7 @@ impl MonsGameModel {
                 }
             )
         }) && Self::own_drainer_carries_specific_mana_safely(
-            board_after,
+            game_after,
             perspective,
             Mana::Supermana,
         )
@@ -3927,38 +4393,34 @@ impl MonsGameModel {
 
     fn events_spirit_supermana_setup(
         events: &[Event],
-        board_after: &Board,
+        game_after: &MonsGame,
         perspective: Color,
     ) -> bool {
         Self::events_spirit_move_supermana_toward_color(events, perspective)
-            || Self::events_spirit_move_supermana_onto_safe_drainer(
-                events,
-                board_after,
-                perspective,
-            )
+            || Self::events_spirit_move_supermana_onto_safe_drainer(events, game_after, perspective)
     }
 
     fn events_spirit_opponent_mana_setup(
         events: &[Event],
-        board_after: &Board,
+        game_after: &MonsGame,
         perspective: Color,
     ) -> bool {
         Self::events_spirit_move_opponent_mana_toward_color(events, perspective)
             || Self::events_spirit_move_opponent_mana_onto_safe_drainer(
                 events,
-                board_after,
+                game_after,
                 perspective,
             )
     }
 
     fn events_spirit_scoring_mana_setup(
         events: &[Event],
-        board_after: &Board,
+        game_after: &MonsGame,
         perspective: Color,
     ) -> bool {
         Self::events_spirit_move_own_mana_toward_color(events, perspective)
-            || Self::events_spirit_supermana_setup(events, board_after, perspective)
-            || Self::events_spirit_opponent_mana_setup(events, board_after, perspective)
+            || Self::events_spirit_supermana_setup(events, game_after, perspective)
+            || Self::events_spirit_opponent_mana_setup(events, game_after, perspective)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -3973,6 +4435,7 @@ impl MonsGameModel {
         own_drainer_vulnerable: bool,
         mana_handoff_to_opponent: bool,
         has_roundtrip: bool,
+        mons_moves_per_turn: i32,
     ) -> i32 {
         if !config.enable_interview_soft_root_priors {
             return 0;
@@ -3987,6 +4450,7 @@ impl MonsGameModel {
                 .saturating_add(Self::root_progress_step_soft_bonus(
                     safe_supermana_progress_steps,
                     8,
+                    mons_moves_per_turn,
                 ));
         }
 
@@ -3998,6 +4462,7 @@ impl MonsGameModel {
                 .saturating_add(Self::root_progress_step_soft_bonus(
                     safe_opponent_mana_progress_steps,
                     6,
+                    mons_moves_per_turn,
                 ));
         }
 
@@ -4011,13 +4476,18 @@ impl MonsGameModel {
         score
     }
 
-    fn root_progress_step_soft_bonus(steps: i32, per_step_bonus: i32) -> i32 {
+    fn root_progress_step_soft_bonus(
+        steps: i32,
+        per_step_bonus: i32,
+        mons_moves_per_turn: i32,
+    ) -> i32 {
         let unknown_steps = Config::BOARD_SIZE + 4;
         if steps >= unknown_steps || per_step_bonus <= 0 {
             return 0;
         }
-        let clamped_steps = steps.clamp(0, Config::MONS_MOVES_PER_TURN);
-        (Config::MONS_MOVES_PER_TURN - clamped_steps) * per_step_bonus
+        let mons_moves_per_turn = mons_moves_per_turn.max(0);
+        let clamped_steps = steps.clamp(0, mons_moves_per_turn);
+        (mons_moves_per_turn - clamped_steps) * per_step_bonus
     }
 
     fn root_progress_steps_better(candidate_steps: i32, incumbent_steps: i32) -> bool {
@@ -4208,7 +4678,7 @@ impl MonsGameModel {
             return attackers;
         };
 
-        let remaining_mon_moves = (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+        let remaining_mon_moves = (game.mons_moves_limit() - game.mons_moves_count).max(0);
         if remaining_mon_moves <= 0 {
             return attackers;
         }
@@ -4372,7 +4842,7 @@ impl MonsGameModel {
             perspective.other(),
             perspective,
             own_drainer_location,
-            Config::MONS_MOVES_PER_TURN,
+            game.rules().mons_moves_per_turn,
             !game.is_first_turn(),
         )
     }
@@ -4454,7 +4924,7 @@ impl MonsGameModel {
                 }
                 if !Self::events_spirit_scoring_mana_setup(
                     &transition.events,
-                    &transition.game.board,
+                    &transition.game,
                     perspective,
                 ) {
                     continue;
@@ -4801,7 +5271,7 @@ impl MonsGameModel {
         if matches!(wanted_mana, Mana::Supermana) {
             if Self::events_score_supermana(&transition.events)
                 || Self::own_drainer_carries_specific_mana_safely(
-                    &transition.game.board,
+                    &transition.game,
                     perspective,
                     Mana::Supermana,
                 )
@@ -4819,7 +5289,7 @@ impl MonsGameModel {
         if wanted_mana == Mana::Regular(perspective.other()) {
             if Self::events_score_opponent_mana(&transition.events, perspective)
                 || Self::own_drainer_carries_specific_mana_safely(
-                    &transition.game.board,
+                    &transition.game,
                     perspective,
                     Mana::Regular(perspective.other()),
                 )
@@ -5016,7 +5486,7 @@ impl MonsGameModel {
             };
             if picked_wanted_mana
                 && (Self::own_drainer_carries_specific_mana_safely(
-                    &after_game.board,
+                    &after_game,
                     perspective,
                     wanted_mana,
                 ) || match wanted_mana {
@@ -5373,7 +5843,7 @@ impl MonsGameModel {
             && !root_transitions.iter().any(|transition| {
                 Self::events_pickup_supermana(&transition.events)
                     && Self::own_drainer_carries_specific_mana_safely(
-                        &transition.game.board,
+                        &transition.game,
                         perspective,
                         Mana::Supermana,
                     )
@@ -5402,7 +5872,7 @@ impl MonsGameModel {
             && !root_transitions.iter().any(|transition| {
                 Self::events_pickup_opponent_mana(&transition.events, perspective)
                     && Self::own_drainer_carries_specific_mana_safely(
-                        &transition.game.board,
+                        &transition.game,
                         perspective,
                         Mana::Regular(perspective.other()),
                     )
@@ -5513,7 +5983,7 @@ impl MonsGameModel {
             && !root_transitions.iter().any(|transition| {
                 Self::events_spirit_scoring_mana_setup(
                     &transition.events,
-                    &transition.game.board,
+                    &transition.game,
                     perspective,
                 )
             })
@@ -5541,6 +6011,10 @@ impl MonsGameModel {
         let mut remaining_exact_lite_root_calls = config.exact_lite_root_call_budget;
         let mut remaining_exact_lite_static_calls = config.exact_lite_static_call_budget;
         for transition in root_transitions {
+            // Past the deadline the roots scored so far are all there is time for.
+            if !candidates.is_empty() && search_deadline_reached() {
+                break;
+            }
             let transition_config = Self::with_exact_lite_budgeted_transition_config(
                 config,
                 perspective,
@@ -5883,7 +6357,7 @@ impl MonsGameModel {
                 Self::child_class_priority_score(b.1.classes)
                     .cmp(&Self::child_class_priority_score(a.1.classes))
             })
-            .then_with(|| b.1.hash.cmp(&a.1.hash))
+            .then_with(|| b.1.ordering_hash.cmp(&a.1.ordering_hash))
     }
 
     fn is_child_search_priority_class(classes: MoveClassFlags) -> bool {
@@ -6063,6 +6537,7 @@ impl MonsGameModel {
                 .saturating_add(Self::root_progress_step_soft_bonus(
                     candidate.safe_supermana_progress_steps,
                     48,
+                    candidate.game.rules().mons_moves_per_turn,
                 ));
         }
 
@@ -6077,6 +6552,7 @@ impl MonsGameModel {
                 .saturating_add(Self::root_progress_step_soft_bonus(
                     candidate.safe_opponent_mana_progress_steps,
                     40,
+                    candidate.game.rules().mons_moves_per_turn,
                 ));
         }
 
@@ -6531,7 +7007,7 @@ impl MonsGameModel {
         } else {
             game.white_score
         };
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         if opponent_distance_to_win <= 1 {
             if let Some(index) = Self::best_tactical_root_index(root_moves, |candidate| {
                 candidate.classes.immediate_score
@@ -6899,22 +7375,18 @@ impl MonsGameModel {
         scored_states.swap(swap_index, replacement_index);
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn smart_search_best_inputs(game: &MonsGame, config: AutomoveSearchConfig) -> Vec<Input> {
         Self::smart_search_best_inputs_internal(game, config, true)
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_config_from_search_config(config: AutomoveSearchConfig) -> TurnEngineConfig {
         automove_runtime_variants::turn_engine_config_from_search_config(config)
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_mode_uses_macro_plans(mode: TurnEngineMode) -> bool {
         matches!(mode, TurnEngineMode::ProV2)
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_config_for_game(
         game: &MonsGame,
         config: AutomoveSearchConfig,
@@ -6938,7 +7410,6 @@ impl MonsGameModel {
         engine
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_rerank_config(config: AutomoveSearchConfig) -> TurnEngineConfig {
         let mut engine = Self::turn_engine_config_from_search_config(config);
         let pro_v2 = Self::turn_engine_mode_uses_macro_plans(config.turn_engine_mode);
@@ -6958,7 +7429,6 @@ impl MonsGameModel {
         engine
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_projection_config_for_game(
         game: &MonsGame,
         config: AutomoveSearchConfig,
@@ -6981,7 +7451,6 @@ impl MonsGameModel {
         engine
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_injected_root_config_for_game(
         game: &MonsGame,
         config: AutomoveSearchConfig,
@@ -7009,7 +7478,6 @@ impl MonsGameModel {
         engine
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn accept_turn_engine_cached_step(
         root_moves: &[ScoredRootMove],
         cached_inputs: &[Input],
@@ -7070,7 +7538,6 @@ impl MonsGameModel {
             || (!candidate_unsafe && top_unsafe && index <= 10 && heuristic_gap <= 256)
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn seed_turn_engine_followup_cache_if_safe(
         game: &MonsGame,
         perspective: Color,
@@ -7111,7 +7578,6 @@ impl MonsGameModel {
         );
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn should_resume_turn_engine_cached_step(
         root_moves: &[ScoredRootMove],
         cached_inputs: &[Input],
@@ -7127,7 +7593,6 @@ impl MonsGameModel {
                 .unwrap_or(false)
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_move_family(root: &ScoredRootMove) -> TurnPlanFamily {
         if root.wins_immediately
             || root.scores_supermana_this_turn
@@ -7154,7 +7619,6 @@ impl MonsGameModel {
         }
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_evaluation_family(root: &RootEvaluation) -> TurnPlanFamily {
         if root.wins_immediately
             || root.scores_supermana_this_turn
@@ -7181,7 +7645,6 @@ impl MonsGameModel {
         }
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_move_has_progress_surface(root: &ScoredRootMove) -> bool {
         root.safe_supermana_pickup_now
             || root.safe_opponent_mana_pickup_now
@@ -7189,7 +7652,6 @@ impl MonsGameModel {
             || root.opponent_mana_progress
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_evaluation_has_progress_surface(root: &RootEvaluation) -> bool {
         root.safe_supermana_pickup_now
             || root.safe_opponent_mana_pickup_now
@@ -7197,7 +7659,6 @@ impl MonsGameModel {
             || root.opponent_mana_progress
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_move_has_concrete_score_surface(root: &ScoredRootMove) -> bool {
         root.wins_immediately
             || root.scores_supermana_this_turn
@@ -7206,7 +7667,6 @@ impl MonsGameModel {
             || root.safe_opponent_mana_pickup_now
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_evaluation_has_concrete_score_surface(root: &RootEvaluation) -> bool {
         root.wins_immediately
             || root.scores_supermana_this_turn
@@ -7215,7 +7675,6 @@ impl MonsGameModel {
             || root.safe_opponent_mana_pickup_now
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_move_is_unsafe(root: &ScoredRootMove) -> bool {
         root.mana_handoff_to_opponent
             || (root.own_drainer_vulnerable
@@ -7224,7 +7683,6 @@ impl MonsGameModel {
                 && !Self::turn_engine_root_move_has_concrete_score_surface(root))
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_evaluation_is_unsafe(root: &RootEvaluation) -> bool {
         root.mana_handoff_to_opponent
             || (root.own_drainer_vulnerable
@@ -7233,7 +7691,6 @@ impl MonsGameModel {
                 && !Self::turn_engine_root_evaluation_has_concrete_score_surface(root))
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_scored_root_utility(
         root: &MonsGame,
         candidate: &ScoredRootMove,
@@ -7269,7 +7726,6 @@ impl MonsGameModel {
         plan.utility
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     pub(crate) fn focused_candidate_rank_for_runtime_inputs(
         game: &MonsGame,
         perspective: Color,
@@ -7311,7 +7767,6 @@ impl MonsGameModel {
             .position(|root| root.inputs.as_slice() == selected_inputs)
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_plan_utility(
         root: &MonsGame,
         selected: &RootEvaluation,
@@ -7329,7 +7784,6 @@ impl MonsGameModel {
         )
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_plan_utility_with_engine_config(
         root: &MonsGame,
         selected: &RootEvaluation,
@@ -7368,7 +7822,6 @@ impl MonsGameModel {
         selected_plan.utility
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_selected_override_utility(
         root: &MonsGame,
         selected: &RootEvaluation,
@@ -7452,7 +7905,6 @@ impl MonsGameModel {
         result
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn inject_turn_engine_root_candidate(
         game: &MonsGame,
         perspective: Color,
@@ -7895,7 +8347,6 @@ impl MonsGameModel {
             && !setup.opponent_mana_progress
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn accept_turn_engine_head_after_search(
         game: &MonsGame,
         perspective: Color,
@@ -9725,7 +10176,6 @@ impl MonsGameModel {
         }
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn classify_turn_engine_rerank_override(
         root_moves: &[ScoredRootMove],
         override_inputs: &[Input],
@@ -9878,7 +10328,6 @@ impl MonsGameModel {
         )
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn pro_v2_root_advisor_conflicts_with_choice(
         game: &MonsGame,
         perspective: Color,
@@ -9901,7 +10350,6 @@ impl MonsGameModel {
             .is_some_and(|approved| approved.inputs.as_slice() != inputs)
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_allowed_rerank_override_candidate(
         root_moves: &[ScoredRootMove],
         inputs: &[Input],
@@ -9924,7 +10372,6 @@ impl MonsGameModel {
             || candidate.spirit_same_turn_score_setup_now
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn classify_turn_engine_root_injection_candidate(
         top: &ScoredRootMove,
         candidate: &ScoredRootMove,
@@ -9992,7 +10439,6 @@ impl MonsGameModel {
         TurnEngineRootInjectionAcceptance::RejectedHeuristicGap
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     #[cfg(test)]
     fn accept_turn_engine_root_injection_candidate(
         top: &ScoredRootMove,
@@ -10011,7 +10457,6 @@ impl MonsGameModel {
         )
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn turn_engine_root_injection_limit_for_game(
         game: &MonsGame,
         perspective: Color,
@@ -10035,7 +10480,6 @@ impl MonsGameModel {
         config.turn_engine_root_injection_limit
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn classify_turn_engine_emergency_root_injection_candidate(
         top: &ScoredRootMove,
         candidate: &ScoredRootMove,
@@ -10093,7 +10537,6 @@ impl MonsGameModel {
         )
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn inject_turn_engine_root_candidates(
         game: &MonsGame,
         perspective: Color,
@@ -10135,8 +10578,10 @@ impl MonsGameModel {
         let mut injected_any = false;
         let top_opponent_can_win_immediately_after = {
             let opponent = perspective.other();
-            let needed =
-                Config::TARGET_SCORE.saturating_sub(Self::score_for_color(&top.game, opponent));
+            let needed = game
+                .rules()
+                .target_score
+                .saturating_sub(Self::score_for_color(&top.game, opponent));
             needed <= 0
                 || (top.game.active_color == opponent
                     && exact_turn_summary(&top.game, opponent).same_turn_score_window_value
@@ -10164,7 +10609,9 @@ impl MonsGameModel {
             };
             let opponent_can_win_immediately_after = {
                 let opponent = perspective.other();
-                let needed = Config::TARGET_SCORE
+                let needed = game
+                    .rules()
+                    .target_score
                     .saturating_sub(Self::score_for_color(&candidate.game, opponent));
                 needed <= 0
                     || (candidate.game.active_color == opponent
@@ -10248,7 +10695,6 @@ impl MonsGameModel {
         }
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn should_invoke_turn_head_rerank(root_moves: &[ScoredRootMove]) -> bool {
         let Some(top) = root_moves.first() else {
             return false;
@@ -10284,7 +10730,6 @@ impl MonsGameModel {
         })
     }
 
-    #[cfg(any(target_arch = "wasm32", test))]
     fn smart_search_best_inputs_internal(
         game: &MonsGame,
         config: AutomoveSearchConfig,
@@ -10292,6 +10737,61 @@ impl MonsGameModel {
     ) -> Vec<Input> {
         #[cfg(test)]
         let _turn_engine_selector_runtime_depth = TurnEngineSelectorRuntimeDepthGuard::enter();
+        let mut stage = Self::begin_smart_search(game, config, use_transposition_table);
+        loop {
+            stage = match stage {
+                SmartSearchStage::Start(config) => {
+                    return Self::smart_search_best_inputs_internal(
+                        game,
+                        *config,
+                        use_transposition_table,
+                    );
+                }
+                SmartSearchStage::Ranked(ranked) => Self::plan_root_search(game, *ranked),
+                SmartSearchStage::Planned(planned) => Self::advise_root_search(game, *planned),
+                SmartSearchStage::Advised(advised) => Self::scout_root_search(game, *advised),
+                SmartSearchStage::Scoring(mut search) => {
+                    // Only the turn engine branch splits root scoring across threads.
+                    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+                    if search.turn_engine.is_some() {
+                        if let Some(options) = automove_parallel::active_parallel_search() {
+                            search.score_roots_in_parallel(options);
+                        }
+                    }
+                    while search.score_next_root() {}
+                    SmartSearchStage::Finished(Self::finish_root_search(game, *search))
+                }
+                SmartSearchStage::Finished(inputs) => return inputs,
+            };
+        }
+    }
+
+    /// Runs the next stage of a stepped `smart_search_best_inputs`: one root
+    /// move per call while scoring.
+    fn advance_smart_search(game: &MonsGame, stage: SmartSearchStage) -> SmartSearchStage {
+        match stage {
+            SmartSearchStage::Start(config) => Self::begin_smart_search(game, *config, true),
+            SmartSearchStage::Ranked(ranked) => Self::plan_root_search(game, *ranked),
+            SmartSearchStage::Planned(planned) => Self::advise_root_search(game, *planned),
+            SmartSearchStage::Advised(advised) => Self::scout_root_search(game, *advised),
+            SmartSearchStage::Scoring(mut search) => {
+                if search.score_next_root() {
+                    SmartSearchStage::Scoring(search)
+                } else {
+                    SmartSearchStage::Finished(Self::finish_root_search(game, *search))
+                }
+            }
+            SmartSearchStage::Finished(inputs) => SmartSearchStage::Finished(inputs),
+        }
+    }
+
+    /// Guards and config clamps of `smart_search_best_inputs`, then the
+    /// root move ranking.
+    fn begin_smart_search(
+        game: &MonsGame,
+        config: AutomoveSearchConfig,
+        use_transposition_table: bool,
+    ) -> SmartSearchStage {
         clear_exact_state_analysis_cache();
         clear_turn_engine_selector_followup_floor_cache();
         set_pro_v2_root_advisor_decision(None);
@@ -10408,17 +10908,36 @@ impl MonsGameModel {
             update_top_level_turn_engine_selector_last_return_stage(
                 "engine_disabled_prov1_fallback",
             );
-            return Self::smart_search_best_inputs_internal(
-                game,
-                legacy_fallback,
-                use_transposition_table,
-            );
+            return SmartSearchStage::Start(Box::new(legacy_fallback));
         }
-        if config.enable_turn_engine_selector {
-            let mut root_moves = Self::ranked_root_moves(game, perspective, config);
-            if root_moves.is_empty() {
-                return Vec::new();
-            }
+        let root_moves = Self::ranked_root_moves(game, perspective, config);
+        if root_moves.is_empty() {
+            return SmartSearchStage::Finished(Vec::new());
+        }
+        SmartSearchStage::Ranked(Box::new(RankedSmartSearch {
+            perspective,
+            config,
+            use_transposition_table,
+            root_moves,
+            prechecked_cached_inputs,
+            live_turn_engine_config,
+        }))
+    }
+
+    /// Turn planner and advisor passes over the ranked root moves, then the
+    /// scout that narrows them down to the ones root scoring searches.
+    /// Builds the turn planner's head plan when the turn engine selector is
+    /// on.
+    fn plan_root_search(game: &MonsGame, ranked: RankedSmartSearch) -> SmartSearchStage {
+        let RankedSmartSearch {
+            perspective,
+            config,
+            use_transposition_table,
+            root_moves,
+            prechecked_cached_inputs,
+            live_turn_engine_config,
+        } = ranked;
+        let turn_engine = config.enable_turn_engine_selector.then(|| {
             let engine_mode = config.turn_engine_mode;
             let cached_inputs = prechecked_cached_inputs
                 .or_else(|| turn_engine_cached_step(game, live_turn_engine_config))
@@ -10454,6 +10973,39 @@ impl MonsGameModel {
                     diagnostics.head_plan_hits += 1
                 });
             }
+            SmartRootSearchTurnEngine {
+                mode: engine_mode,
+                config: engine_config,
+                head_plan: engine_head_plan,
+                cached_inputs,
+            }
+        });
+        SmartSearchStage::Planned(Box::new(PlannedSmartSearch {
+            perspective,
+            config,
+            use_transposition_table,
+            root_moves,
+            turn_engine,
+        }))
+    }
+
+    /// Settles the searches the turn planner or the tactical prepasses
+    /// decide, then runs the root advisor.
+    fn advise_root_search(game: &MonsGame, planned: PlannedSmartSearch) -> SmartSearchStage {
+        let PlannedSmartSearch {
+            perspective,
+            config,
+            use_transposition_table,
+            mut root_moves,
+            turn_engine,
+        } = planned;
+        if let Some(turn_engine) = turn_engine {
+            let SmartRootSearchTurnEngine {
+                mode: engine_mode,
+                config: engine_config,
+                head_plan: engine_head_plan,
+                cached_inputs,
+            } = turn_engine;
             if matches!(engine_mode, TurnEngineMode::ProV2) {
                 if let Some(cached_inputs) = cached_inputs.as_ref() {
                     if Self::should_resume_turn_engine_cached_step(
@@ -10465,7 +11017,7 @@ impl MonsGameModel {
                         update_turn_engine_selector_diagnostics(|diagnostics| {
                             diagnostics.last_return_stage = "engine_cached_resume";
                         });
-                        return cached_inputs.clone();
+                        return SmartSearchStage::Finished(cached_inputs.clone());
                     }
                 }
                 if let (Some(cached_inputs), Some(plan)) =
@@ -10491,7 +11043,7 @@ impl MonsGameModel {
                             plan,
                             engine_config,
                         );
-                        return cached_inputs.clone();
+                        return SmartSearchStage::Finished(cached_inputs.clone());
                     }
                 }
             }
@@ -10584,7 +11136,7 @@ impl MonsGameModel {
                                         &plan,
                                         engine_config,
                                     );
-                                    return inputs;
+                                    return SmartSearchStage::Finished(inputs);
                                 }
                             }
                             TurnEngineRerankOverrideAcceptance::Rejected(reason) => {
@@ -10632,7 +11184,7 @@ impl MonsGameModel {
                         );
                     }
                 }
-                return forced_inputs;
+                return SmartSearchStage::Finished(forced_inputs);
             }
             if let Some(plan) = engine_head_plan.as_ref() {
                 if let Some(forced_inputs) = Self::forced_low_budget_turn_engine_prepass_choice(
@@ -10658,7 +11210,7 @@ impl MonsGameModel {
                         plan,
                         engine_config,
                     );
-                    return forced_inputs;
+                    return SmartSearchStage::Finished(forced_inputs);
                 }
             }
             let root_advisor_decision = Self::pro_v2_root_advisor_presearch(
@@ -10674,200 +11226,19 @@ impl MonsGameModel {
                 .as_ref()
                 .map(Self::pro_v2_root_advisor_priority_inputs)
                 .unwrap_or_default();
-
-            let (mut root_moves, scout_visited_nodes) =
-                Self::focused_root_candidates_with_priority_inputs(
-                    game,
-                    perspective,
-                    root_moves,
-                    config,
-                    use_transposition_table,
-                    (!advisor_priority_inputs.is_empty())
-                        .then_some(advisor_priority_inputs.as_slice()),
-                    None,
-                );
-            if root_moves.is_empty() {
-                return Vec::new();
-            }
-
-            let mut visited_nodes = scout_visited_nodes;
-            let mut alpha = i32::MIN;
-            let mut scored_roots = Vec::with_capacity(root_moves.len());
-            let mut transposition_table = U64HashMap::default();
-            let extension_node_budget = if config.enable_selective_extensions
-                && config.selective_extension_node_share_bp > 0
-            {
-                ((config.max_visited_nodes * config.selective_extension_node_share_bp as usize)
-                    / 10_000)
-                    .max(1)
-            } else {
-                0
-            };
-            let mut extension_nodes_used = 0usize;
-            let mut killer_table: KillerTable = [[0u64; 2]; MAX_SMART_SEARCH_DEPTH + 2];
-            let mut history_table: HistoryTable = HistoryTable::default();
-            let mut quiescence_nodes_used = 0usize;
-
-            if config.enable_iterative_deepening && config.depth >= 3 {
-                let preliminary_depth = config
-                    .depth
-                    .saturating_sub(config.iterative_deepening_depth_offset)
-                    .max(1);
-                let mut prelim_alpha = i32::MIN;
-                let mut prelim_scores: Vec<i32> = Vec::with_capacity(root_moves.len());
-                for candidate in root_moves.iter() {
-                    if visited_nodes >= config.max_visited_nodes {
-                        prelim_scores.push(i32::MIN);
-                        continue;
-                    }
-                    visited_nodes += 1;
-                    let prelim_score = Self::search_score(
-                        &candidate.game,
-                        perspective,
-                        preliminary_depth,
-                        prelim_alpha,
-                        i32::MAX,
-                        &mut visited_nodes,
-                        config,
-                        &mut transposition_table,
-                        0,
-                        &mut extension_nodes_used,
-                        0,
-                        use_transposition_table,
-                        &mut killer_table,
-                        &mut history_table,
-                        &mut quiescence_nodes_used,
-                    );
-                    prelim_scores.push(prelim_score);
-                    if prelim_score > prelim_alpha {
-                        prelim_alpha = prelim_score;
-                    }
-                }
-                let best_prelim_score = prelim_scores.iter().copied().max().unwrap_or(i32::MIN);
-                root_moves =
-                    Self::sort_root_moves_by_ranked_scores(root_moves, prelim_scores.as_slice());
-                if config.iterative_deepening_alpha_margin > 0 && best_prelim_score != i32::MIN {
-                    alpha =
-                        best_prelim_score.saturating_sub(config.iterative_deepening_alpha_margin);
-                }
-            }
-
-            for candidate in root_moves.into_iter() {
-                if visited_nodes >= config.max_visited_nodes {
-                    break;
-                }
-
-                visited_nodes += 1;
-                let candidate_score = Self::evaluate_root_candidate_score(
-                    &candidate,
-                    perspective,
-                    alpha,
-                    &mut visited_nodes,
-                    config,
-                    &mut transposition_table,
-                    &mut extension_nodes_used,
-                    extension_node_budget,
-                    use_transposition_table,
-                    &mut killer_table,
-                    &mut history_table,
-                    &mut quiescence_nodes_used,
-                );
-
-                scored_roots.push(RootEvaluation {
-                    root_rank: candidate.root_rank,
-                    score: candidate_score,
-                    efficiency: candidate.efficiency,
-                    inputs: candidate.inputs,
-                    game: candidate.game,
-                    wins_immediately: candidate.wins_immediately,
-                    attacks_opponent_drainer: candidate.attacks_opponent_drainer,
-                    own_drainer_vulnerable: candidate.own_drainer_vulnerable,
-                    own_drainer_walk_vulnerable: candidate.own_drainer_walk_vulnerable,
-                    spirit_development: candidate.spirit_development,
-                    keeps_awake_spirit_on_base: candidate.keeps_awake_spirit_on_base,
-                    mana_handoff_to_opponent: candidate.mana_handoff_to_opponent,
-                    has_roundtrip: candidate.has_roundtrip,
-                    scores_supermana_this_turn: candidate.scores_supermana_this_turn,
-                    scores_opponent_mana_this_turn: candidate.scores_opponent_mana_this_turn,
-                    safe_supermana_pickup_now: candidate.safe_supermana_pickup_now,
-                    safe_opponent_mana_pickup_now: candidate.safe_opponent_mana_pickup_now,
-                    safe_supermana_progress_steps: candidate.safe_supermana_progress_steps,
-                    safe_opponent_mana_progress_steps: candidate.safe_opponent_mana_progress_steps,
-                    score_path_best_steps: candidate.score_path_best_steps,
-                    same_turn_score_window_value: candidate.same_turn_score_window_value,
-                    spirit_setup_gain: candidate.spirit_setup_gain,
-                    spirit_same_turn_score_setup_now: candidate.spirit_same_turn_score_setup_now,
-                    spirit_own_mana_setup_now: candidate.spirit_own_mana_setup_now,
-                    supermana_progress: candidate.supermana_progress,
-                    opponent_mana_progress: candidate.opponent_mana_progress,
-                    interview_soft_priority: candidate.interview_soft_priority,
-                    classes: candidate.classes,
-                });
-
-                if candidate_score > alpha {
-                    alpha = candidate_score;
-                }
-            }
-
-            if scored_roots.is_empty() {
-                return Vec::new();
-            }
-            let mut selected_inputs =
-                Self::pick_root_move_with_exploration(game, &scored_roots, perspective, config);
-            if let Some(plan) = engine_head_plan.as_ref() {
-                let accepted = Self::accept_turn_engine_head_after_search(
-                    game,
-                    perspective,
-                    config,
-                    scored_roots.as_slice(),
-                    selected_inputs.as_slice(),
-                    plan,
-                );
-                if accepted {
-                    if let Some(chunk) = plan.compiled_chunks.first() {
-                        selected_inputs = chunk.clone();
-                    }
-                }
-            }
-            #[cfg(test)]
-            set_turn_engine_root_selection_snapshot(
+            return SmartSearchStage::Advised(Box::new(AdvisedSmartSearch {
+                perspective,
                 config,
-                scored_roots.as_slice(),
-                selected_inputs.as_slice(),
-            );
-            if cached_inputs.as_ref() == Some(&selected_inputs) {
-                #[cfg(test)]
-                update_turn_engine_selector_diagnostics(|diagnostics| {
-                    diagnostics.last_return_stage = "engine_cached_selected";
-                });
-                #[cfg(test)]
-                update_top_level_turn_engine_selector_last_return_stage("engine_cached_selected");
-                return selected_inputs;
-            }
-            if let Some(plan) = engine_head_plan.as_ref() {
-                if plan.compiled_chunks.first() == Some(&selected_inputs) {
-                    turn_engine_commit_plan(game, perspective, engine_mode, plan, engine_config);
-                    Self::seed_turn_engine_followup_cache_if_safe(
-                        game,
-                        perspective,
-                        config,
-                        engine_mode,
-                        plan,
-                        engine_config,
-                    );
-                }
-            }
-            #[cfg(test)]
-            update_turn_engine_selector_diagnostics(|diagnostics| {
-                diagnostics.last_return_stage = "engine_post_search";
-            });
-            #[cfg(test)]
-            update_top_level_turn_engine_selector_last_return_stage("engine_post_search");
-            return selected_inputs;
-        }
-        let mut root_moves = Self::ranked_root_moves(game, perspective, config);
-        if root_moves.is_empty() {
-            return Vec::new();
+                use_transposition_table,
+                root_moves,
+                advisor_priority_inputs,
+                turn_engine: Some(SmartRootSearchTurnEngine {
+                    mode: engine_mode,
+                    config: engine_config,
+                    head_plan: engine_head_plan,
+                    cached_inputs,
+                }),
+            }));
         }
         if config.enable_turn_engine_root_injection {
             Self::inject_turn_engine_root_candidates(game, perspective, config, &mut root_moves);
@@ -10926,7 +11297,7 @@ impl MonsGameModel {
                             update_top_level_turn_engine_selector_last_return_stage(
                                 "search_only_engine_allowed_head",
                             );
-                            return inputs;
+                            return SmartSearchStage::Finished(inputs);
                         }
                     }
                     TurnEngineRerankOverrideAcceptance::Rejected(reason) => {
@@ -10951,7 +11322,7 @@ impl MonsGameModel {
             });
             #[cfg(test)]
             update_top_level_turn_engine_selector_last_return_stage("search_only_forced_prepass");
-            return forced_inputs;
+            return SmartSearchStage::Finished(forced_inputs);
         }
 
         let root_advisor_decision =
@@ -10962,143 +11333,70 @@ impl MonsGameModel {
             .as_ref()
             .map(Self::pro_v2_root_advisor_priority_inputs)
             .unwrap_or_default();
+        SmartSearchStage::Advised(Box::new(AdvisedSmartSearch {
+            perspective,
+            config,
+            use_transposition_table,
+            root_moves,
+            advisor_priority_inputs,
+            turn_engine: None,
+        }))
+    }
 
-        let (mut root_moves, scout_visited_nodes) =
-            Self::focused_root_candidates_with_priority_inputs(
-                game,
-                perspective,
-                root_moves,
-                config,
-                use_transposition_table,
-                (!advisor_priority_inputs.is_empty()).then_some(advisor_priority_inputs.as_slice()),
-                None,
-            );
+    /// Scouts the root moves to focus root scoring on.
+    fn scout_root_search(game: &MonsGame, advised: AdvisedSmartSearch) -> SmartSearchStage {
+        let AdvisedSmartSearch {
+            perspective,
+            config,
+            use_transposition_table,
+            root_moves,
+            advisor_priority_inputs,
+            turn_engine,
+        } = advised;
+        let (root_moves, scout_visited_nodes) = Self::focused_root_candidates_with_priority_inputs(
+            game,
+            perspective,
+            root_moves,
+            config,
+            use_transposition_table,
+            (!advisor_priority_inputs.is_empty()).then_some(advisor_priority_inputs.as_slice()),
+            None,
+        );
         if root_moves.is_empty() {
-            return Vec::new();
-        }
-
-        let mut visited_nodes = scout_visited_nodes;
-        let mut alpha = i32::MIN;
-        let mut scored_roots = Vec::with_capacity(root_moves.len());
-        let mut transposition_table = U64HashMap::default();
-        let extension_node_budget =
-            if config.enable_selective_extensions && config.selective_extension_node_share_bp > 0 {
-                ((config.max_visited_nodes * config.selective_extension_node_share_bp as usize)
-                    / 10_000)
-                    .max(1)
-            } else {
-                0
-            };
-        let mut extension_nodes_used = 0usize;
-        let mut killer_table: KillerTable = [[0u64; 2]; MAX_SMART_SEARCH_DEPTH + 2];
-        let mut history_table: HistoryTable = HistoryTable::default();
-        let mut quiescence_nodes_used = 0usize;
-
-        // Iterative deepening: run a shallow pass to pre-populate TT and reorder roots
-        if config.enable_iterative_deepening && config.depth >= 3 {
-            let preliminary_depth = config
-                .depth
-                .saturating_sub(config.iterative_deepening_depth_offset)
-                .max(1);
-            let mut prelim_alpha = i32::MIN;
-            let mut prelim_scores: Vec<i32> = Vec::with_capacity(root_moves.len());
-            for candidate in root_moves.iter() {
-                if visited_nodes >= config.max_visited_nodes {
-                    prelim_scores.push(i32::MIN);
-                    continue;
-                }
-                visited_nodes += 1;
-                let prelim_score = Self::search_score(
-                    &candidate.game,
-                    perspective,
-                    preliminary_depth,
-                    prelim_alpha,
-                    i32::MAX,
-                    &mut visited_nodes,
-                    config,
-                    &mut transposition_table,
-                    0,
-                    &mut extension_nodes_used,
-                    0,
-                    use_transposition_table,
-                    &mut killer_table,
-                    &mut history_table,
-                    &mut quiescence_nodes_used,
-                );
-                prelim_scores.push(prelim_score);
-                if prelim_score > prelim_alpha {
-                    prelim_alpha = prelim_score;
-                }
-            }
-            let best_prelim_score = prelim_scores.iter().copied().max().unwrap_or(i32::MIN);
-            root_moves =
-                Self::sort_root_moves_by_ranked_scores(root_moves, prelim_scores.as_slice());
-            // Optionally initialize alpha from preliminary best score
-            if config.iterative_deepening_alpha_margin > 0 && best_prelim_score != i32::MIN {
-                alpha = best_prelim_score.saturating_sub(config.iterative_deepening_alpha_margin);
-            }
-        }
-
-        for candidate in root_moves.into_iter() {
-            if visited_nodes >= config.max_visited_nodes {
-                break;
-            }
-
-            visited_nodes += 1;
-            let candidate_score = Self::evaluate_root_candidate_score(
-                &candidate,
-                perspective,
-                alpha,
-                &mut visited_nodes,
-                config,
-                &mut transposition_table,
-                &mut extension_nodes_used,
-                extension_node_budget,
-                use_transposition_table,
-                &mut killer_table,
-                &mut history_table,
-                &mut quiescence_nodes_used,
-            );
-
-            scored_roots.push(RootEvaluation {
-                root_rank: candidate.root_rank,
-                score: candidate_score,
-                efficiency: candidate.efficiency,
-                inputs: candidate.inputs,
-                game: candidate.game,
-                wins_immediately: candidate.wins_immediately,
-                attacks_opponent_drainer: candidate.attacks_opponent_drainer,
-                own_drainer_vulnerable: candidate.own_drainer_vulnerable,
-                own_drainer_walk_vulnerable: candidate.own_drainer_walk_vulnerable,
-                spirit_development: candidate.spirit_development,
-                keeps_awake_spirit_on_base: candidate.keeps_awake_spirit_on_base,
-                mana_handoff_to_opponent: candidate.mana_handoff_to_opponent,
-                has_roundtrip: candidate.has_roundtrip,
-                scores_supermana_this_turn: candidate.scores_supermana_this_turn,
-                scores_opponent_mana_this_turn: candidate.scores_opponent_mana_this_turn,
-                safe_supermana_pickup_now: candidate.safe_supermana_pickup_now,
-                safe_opponent_mana_pickup_now: candidate.safe_opponent_mana_pickup_now,
-                safe_supermana_progress_steps: candidate.safe_supermana_progress_steps,
-                safe_opponent_mana_progress_steps: candidate.safe_opponent_mana_progress_steps,
-                score_path_best_steps: candidate.score_path_best_steps,
-                same_turn_score_window_value: candidate.same_turn_score_window_value,
-                spirit_setup_gain: candidate.spirit_setup_gain,
-                spirit_same_turn_score_setup_now: candidate.spirit_same_turn_score_setup_now,
-                spirit_own_mana_setup_now: candidate.spirit_own_mana_setup_now,
-                supermana_progress: candidate.supermana_progress,
-                opponent_mana_progress: candidate.opponent_mana_progress,
-                interview_soft_priority: candidate.interview_soft_priority,
-                classes: candidate.classes,
-            });
-
-            if candidate_score > alpha {
-                alpha = candidate_score;
-            }
+            return SmartSearchStage::Finished(Vec::new());
         }
+        SmartSearchStage::Scoring(Box::new(SmartRootSearch::new(
+            perspective,
+            config,
+            use_transposition_table,
+            root_moves,
+            scout_visited_nodes,
+            automove_ponder::take_pondered_transposition_table(game, perspective)
+                .unwrap_or_default(),
+            turn_engine,
+        )))
+    }
 
+    /// Picks the move among the scored root moves, then settles it against
+    /// the turn planner's head plan.
+    fn finish_root_search(game: &MonsGame, search: SmartRootSearch) -> Vec<Input> {
+        let SmartRootSearch {
+            perspective,
+            config,
+            turn_engine,
+            scored_roots,
+            ..
+        } = search;
         if scored_roots.is_empty() {
-            Vec::new()
-        } else {
+            return Vec::new();
+        }
+        let Some(SmartRootSearchTurnEngine {
+            mode: engine_mode,
+            config: engine_config,
+            head_plan: engine_head_plan,
+            cached_inputs,
+        }) = turn_engine
+        else {
             let selected_inputs =
                 Self::pick_root_move_with_exploration(game, &scored_roots, perspective, config);
             #[cfg(test)]
@@ -11107,8 +11405,60 @@ impl MonsGameModel {
                 scored_roots.as_slice(),
                 selected_inputs.as_slice(),
             );
-            selected_inputs
+            return selected_inputs;
+        };
+        let mut selected_inputs =
+            Self::pick_root_move_with_exploration(game, &scored_roots, perspective, config);
+        if let Some(plan) = engine_head_plan.as_ref() {
+            let accepted = Self::accept_turn_engine_head_after_search(
+                game,
+                perspective,
+                config,
+                scored_roots.as_slice(),
+                selected_inputs.as_slice(),
+                plan,
+            );
+            if accepted {
+                if let Some(chunk) = plan.compiled_chunks.first() {
+                    selected_inputs = chunk.clone();
+                }
+            }
+        }
+        #[cfg(test)]
+        set_turn_engine_root_selection_snapshot(
+            config,
+            scored_roots.as_slice(),
+            selected_inputs.as_slice(),
+        );
+        if cached_inputs.as_ref() == Some(&selected_inputs) {
+            #[cfg(test)]
+            update_turn_engine_selector_diagnostics(|diagnostics| {
+                diagnostics.last_return_stage = "engine_cached_selected";
+            });
+            #[cfg(test)]
+            update_top_level_turn_engine_selector_last_return_stage("engine_cached_selected");
+            return selected_inputs;
+        }
+        if let Some(plan) = engine_head_plan.as_ref() {
+            if plan.compiled_chunks.first() == Some(&selected_inputs) {
+                turn_engine_commit_plan(game, perspective, engine_mode, plan, engine_config);
+                Self::seed_turn_engine_followup_cache_if_safe(
+                    game,
+                    perspective,
+                    config,
+                    engine_mode,
+                    plan,
+                    engine_config,
+                );
+            }
         }
+        #[cfg(test)]
+        update_turn_engine_selector_diagnostics(|diagnostics| {
+            diagnostics.last_return_stage = "engine_post_search";
+        });
+        #[cfg(test)]
+        update_top_level_turn_engine_selector_last_return_stage("engine_post_search");
+        selected_inputs
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -11183,7 +11533,6 @@ impl MonsGameModel {
         score
     }
 
-    #[cfg(test)]
     fn focused_root_candidates(
         game: &MonsGame,
         perspective: Color,
@@ -11566,7 +11915,7 @@ impl MonsGameModel {
         if let Some(terminal_score) = Self::terminal_score(game, perspective, depth, config.depth) {
             return terminal_score;
         }
-        if *visited_nodes >= config.max_visited_nodes {
+        if *visited_nodes >= config.max_visited_nodes || search_deadline_reached() {
             return Self::evaluate_search_preferability(game, perspective, config);
         }
         if depth == 0 {
@@ -11712,7 +12061,10 @@ impl MonsGameModel {
         let mut preferred_child_hash = None;
 
         if use_transposition_table {
-            if let Some(entry) = transposition_table.get(&state_key).copied() {
+            let entry = transposition_table.get(&state_key).copied();
+            #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+            let entry = entry.or_else(|| automove_parallel::shared_transposition_probe(state_key));
+            if let Some(entry) = entry {
                 if config.enable_tt_best_child_ordering && entry.best_child_hash != 0 {
                     preferred_child_hash = Some(entry.best_child_hash);
                 }
@@ -12057,17 +12409,17 @@ impl MonsGameModel {
                     transposition_table.clear();
                 }
             }
+            let entry = TranspositionEntry {
+                depth,
+                score: value,
+                bound,
+                best_child_hash,
+            };
             if !skip_tt_write {
-                transposition_table.insert(
-                    state_key,
-                    TranspositionEntry {
-                        depth,
-                        score: value,
-                        bound,
-                        best_child_hash,
-                    },
-                );
+                transposition_table.insert(state_key, entry);
             }
+            #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+            automove_parallel::shared_transposition_store(state_key, entry);
         }
 
         value
@@ -12335,7 +12687,7 @@ impl MonsGameModel {
         } else {
             a.heuristic.cmp(&b.heuristic)
         };
-        heuristic_cmp.then_with(|| b.child_hash.cmp(&a.child_hash))
+        heuristic_cmp.then_with(|| b.ordering_hash.cmp(&a.ordering_hash))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -12577,9 +12929,10 @@ impl MonsGameModel {
                         retain_opponent_mana_progress,
                     );
 
+                    let ordering_hash = Self::search_ordering_hash(&transition.game);
                     CheapChildOrderingEntry {
                         transition,
-                        child_hash,
+                        ordering_hash,
                         heuristic,
                         force_full,
                         eventful_reserve,
@@ -12625,6 +12978,7 @@ impl MonsGameModel {
                     RankedChildState {
                         game: transition.game,
                         hash: bundle.child_hash,
+                        ordering_hash: entry.ordering_hash,
                         ordering_efficiency: bundle.ordering_efficiency,
                         tactical_extension_trigger: bundle.tactical_extension_trigger,
                         quiet_reduction_candidate: bundle.quiet_reduction_candidate,
@@ -12780,11 +13134,13 @@ impl MonsGameModel {
                     diagnostics.child_ordering_shortlist_children += 1;
                 });
 
+                let ordering_hash = Self::search_ordering_hash(&simulated_game);
                 scored_states.push((
                     bundle.heuristic,
                     RankedChildState {
                         game: simulated_game,
                         hash: bundle.child_hash,
+                        ordering_hash,
                         ordering_efficiency: bundle.ordering_efficiency,
                         tactical_extension_trigger: bundle.tactical_extension_trigger,
                         quiet_reduction_candidate: bundle.quiet_reduction_candidate,
@@ -12817,7 +13173,7 @@ impl MonsGameModel {
     }
 
     fn should_probe_exact_child_progress(game: &MonsGame, color: Color) -> bool {
-        let remaining_moves = (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+        let remaining_moves = (game.mons_moves_limit() - game.mons_moves_count).max(0);
         if remaining_moves <= 0 {
             return false;
         }
@@ -13192,6 +13548,7 @@ impl MonsGameModel {
                 | Event::BombExplosion { .. }
                 | Event::NextTurn { .. }
                 | Event::GameOver { .. }
+                | Event::GameTerminated { .. }
                 | Event::Takeback => {}
             }
         }
@@ -13246,6 +13603,7 @@ impl MonsGameModel {
             | Event::MonAwake { .. }
             | Event::NextTurn { .. }
             | Event::GameOver { .. }
+            | Event::GameTerminated { .. }
             | Event::Takeback => false,
         })
     }
@@ -13346,7 +13704,7 @@ impl MonsGameModel {
     ) -> ExactTurnSummary {
         let strategic =
             allow_exact_strategic.then(|| exact_strategic_analysis(game).color_summary(color));
-        let remaining_moves = (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+        let remaining_moves = (game.mons_moves_limit() - game.mons_moves_count).max(0);
         let safe_supermana_progress_steps =
             Self::approximate_specific_mana_progress_steps(game, color, Mana::Supermana);
         let safe_opponent_mana_progress_steps = Self::approximate_specific_mana_progress_steps(
@@ -14054,7 +14412,7 @@ impl MonsGameModel {
         if game.active_color != color {
             return 0;
         }
-        let remaining_moves = (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+        let remaining_moves = (game.mons_moves_limit() - game.mons_moves_count).max(0);
         game.board
             .occupied()
             .filter_map(|(location, item)| match item {
@@ -14179,6 +14537,13 @@ impl MonsGameModel {
     }
 
     pub(crate) fn search_state_hash(game: &MonsGame) -> u64 {
+        Self::search_mix_u64(0x6a09e667f3bcc909u64 ^ game.zobrist())
+    }
+
+    /// Cell-scan hash that breaks ties between children with equal ordering
+    /// scores. It predates `zobrist()` and is kept so the order children are
+    /// searched in, and with it the moves the engine plays, stays unchanged.
+    fn search_ordering_hash(game: &MonsGame) -> u64 {
         let mut state = 0x6a09e667f3bcc909u64;
         for (idx, item) in game.board.items.iter().enumerate() {
             let Some(item) = item else { continue };
@@ -14271,7 +14636,6 @@ impl MonsGameModel {
         value ^ (value >> 31)
     }
 
-    #[cfg(test)]
     fn advance_async_search(state: &mut AsyncSmartSearchState) -> bool {
         match state.phase {
             AsyncSmartSearchPhase::PendingRootRanking => {
@@ -14281,16 +14645,14 @@ impl MonsGameModel {
                 false
             }
             AsyncSmartSearchPhase::PendingFocusedCandidates => {
-                if let Some(forced_inputs) = Self::forced_tactical_prepass_choice(
+                if Self::forced_tactical_prepass_choice(
                     &state.game,
                     state.perspective,
                     state.root_moves.as_slice(),
                     state.config,
-                ) {
-                    let mut forced_game = state.game.clone_for_simulation();
-                    let input_fen = Input::fen_from_array(&forced_inputs);
-                    let output = forced_game.process_input(forced_inputs, false, false);
-                    state.pending_output = Some(OutputModel::new(output, input_fen.as_str()));
+                )
+                .is_some()
+                {
                     return true;
                 }
 
@@ -14342,37 +14704,6 @@ impl MonsGameModel {
                     &mut state.quiescence_nodes_used,
                 );
 
-                state.scored_roots.push(RootEvaluation {
-                    root_rank: candidate.root_rank,
-                    score: candidate_score,
-                    efficiency: candidate.efficiency,
-                    inputs: candidate.inputs.clone(),
-                    game: candidate.game.clone(),
-                    wins_immediately: candidate.wins_immediately,
-                    attacks_opponent_drainer: candidate.attacks_opponent_drainer,
-                    own_drainer_vulnerable: candidate.own_drainer_vulnerable,
-                    own_drainer_walk_vulnerable: candidate.own_drainer_walk_vulnerable,
-                    spirit_development: candidate.spirit_development,
-                    keeps_awake_spirit_on_base: candidate.keeps_awake_spirit_on_base,
-                    mana_handoff_to_opponent: candidate.mana_handoff_to_opponent,
-                    has_roundtrip: candidate.has_roundtrip,
-                    scores_supermana_this_turn: candidate.scores_supermana_this_turn,
-                    scores_opponent_mana_this_turn: candidate.scores_opponent_mana_this_turn,
-                    safe_supermana_pickup_now: candidate.safe_supermana_pickup_now,
-                    safe_opponent_mana_pickup_now: candidate.safe_opponent_mana_pickup_now,
-                    safe_supermana_progress_steps: candidate.safe_supermana_progress_steps,
-                    safe_opponent_mana_progress_steps: candidate.safe_opponent_mana_progress_steps,
-                    score_path_best_steps: candidate.score_path_best_steps,
-                    same_turn_score_window_value: candidate.same_turn_score_window_value,
-                    spirit_setup_gain: candidate.spirit_setup_gain,
-                    spirit_same_turn_score_setup_now: candidate.spirit_same_turn_score_setup_now,
-                    spirit_own_mana_setup_now: candidate.spirit_own_mana_setup_now,
-                    supermana_progress: candidate.supermana_progress,
-                    opponent_mana_progress: candidate.opponent_mana_progress,
-                    interview_soft_priority: candidate.interview_soft_priority,
-                    classes: candidate.classes,
-                });
-
                 if candidate_score > state.alpha {
                     state.alpha = candidate_score;
                 }
@@ -14384,26 +14715,6 @@ impl MonsGameModel {
         }
     }
 
-    #[cfg(test)]
-    fn finalize_async_search(state: &mut AsyncSmartSearchState) -> OutputModel {
-        if let Some(output) = state.pending_output.take() {
-            return output;
-        }
-        if state.scored_roots.is_empty() {
-            return Self::automove_game(&mut state.game);
-        }
-
-        let best_inputs = Self::pick_root_move_with_exploration(
-            &state.game,
-            &state.scored_roots,
-            state.perspective,
-            state.config,
-        );
-        let input_fen = Input::fen_from_array(&best_inputs);
-        let output = state.game.process_input(best_inputs, false, false);
-        OutputModel::new(output, input_fen.as_str())
-    }
-
     fn events_include_opponent_drainer_fainted(events: &[Event], perspective: Color) -> bool {
         events.iter().any(|event| {
             matches!(
@@ -14454,11 +14765,11 @@ impl MonsGameModel {
     }
 
     fn own_drainer_carries_specific_mana_safely(
-        board: &Board,
+        game: &MonsGame,
         perspective: Color,
         wanted_mana: Mana,
     ) -> bool {
-        board.occupied().any(|(location, item)| {
+        game.board.occupied().any(|(location, item)| {
             matches!(
                 item,
                 Item::MonWithMana { mon, mana }
@@ -14467,9 +14778,10 @@ impl MonsGameModel {
                         && !mon.is_fainted()
                         && *mana == wanted_mana
                         && crate::models::automove_exact::is_drainer_exactly_safe_next_turn_on_board(
-                            board,
+                            &game.board,
                             perspective,
                             location,
+                            game.rules(),
                         )
             )
         })
@@ -14547,7 +14859,7 @@ impl MonsGameModel {
             perspective,
             game.active_color == perspective.other(),
             if game.active_color == perspective.other() {
-                (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0)
+                (game.mons_moves_limit() - game.mons_moves_count).max(0)
             } else {
                 0
             },
@@ -14565,7 +14877,7 @@ impl MonsGameModel {
             &game.board,
             perspective,
             true,
-            Config::MONS_MOVES_PER_TURN,
+            game.rules().mons_moves_per_turn,
             !game.is_first_turn(),
             enhanced,
         )
@@ -14581,7 +14893,7 @@ impl MonsGameModel {
             &game.board,
             perspective,
             true,
-            Config::MONS_MOVES_PER_TURN,
+            game.rules().mons_moves_per_turn,
             !game.is_first_turn(),
             enhanced,
             context,
@@ -14599,7 +14911,7 @@ impl MonsGameModel {
             &game.board,
             perspective,
             true,
-            Config::MONS_MOVES_PER_TURN,
+            game.rules().mons_moves_per_turn,
             opponent_can_use_action,
             enhanced,
         ) {
@@ -14651,7 +14963,7 @@ impl MonsGameModel {
         game.active_color == perspective
             && !game.is_first_turn()
             && !game.player_can_move_mon()
-            && game.actions_used_count >= Config::ACTIONS_PER_TURN
+            && game.actions_used_count >= game.rules().actions_per_turn
             && game.player_can_move_mana()
             && Self::potions_for_color(game, perspective) > 0
     }
@@ -23831,7 +24143,7 @@ impl MonsGameModel {
             } else {
                 after_reply.white_score
             };
-            if Config::TARGET_SCORE - opponent_score_after <= 1 {
+            if after_reply.rules().target_score - opponent_score_after <= 1 {
                 opponent_reaches_match_point = true;
             }
 
@@ -26168,8 +26480,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         my_distance_to_win <= SMART_NORMAL_ROOT_SAFETY_SCORE_RACE_TRIGGER
             || opponent_distance_to_win <= SMART_NORMAL_ROOT_SAFETY_SCORE_RACE_TRIGGER
     }
@@ -26307,7 +26619,7 @@ impl MonsGameModel {
                 NormalRootSafetySnapshot {
                     allows_immediate_opponent_win: true,
                     opponent_reaches_match_point: true,
-                    opponent_max_score_gain: Config::TARGET_SCORE,
+                    opponent_max_score_gain: state_after_move.rules().target_score,
                     my_score_gain,
                     worst_reply_score: -SMART_TERMINAL_SCORE / 2,
                 }
@@ -26362,7 +26674,7 @@ impl MonsGameModel {
             };
             let score_gain = (opponent_score_after - opponent_score_before).max(0);
             opponent_max_score_gain = opponent_max_score_gain.max(score_gain);
-            if Config::TARGET_SCORE - opponent_score_after <= 1 {
+            if after_reply.rules().target_score - opponent_score_after <= 1 {
                 opponent_reaches_match_point = true;
             }
 
@@ -26402,8 +26714,8 @@ impl MonsGameModel {
         } else {
             (game.black_score, game.white_score)
         };
-        let my_distance_to_win = Config::TARGET_SCORE - my_score;
-        let opponent_distance_to_win = Config::TARGET_SCORE - opponent_score;
+        let my_distance_to_win = game.rules().target_score - my_score;
+        let opponent_distance_to_win = game.rules().target_score - opponent_score;
         my_distance_to_win <= SMART_NORMAL_ROOT_SAFETY_DEEP_FLOOR_SCORE_RACE_TRIGGER
             || opponent_distance_to_win <= SMART_NORMAL_ROOT_SAFETY_DEEP_FLOOR_SCORE_RACE_TRIGGER
     }
@@ -27670,9 +27982,10 @@ mod evaluation_cache_tests {
                 retain_opponent_mana_progress,
             );
 
+            let ordering_hash = MonsGameModel::search_ordering_hash(&transition.game);
             CheapChildOrderingEntry {
                 transition,
-                child_hash,
+                ordering_hash,
                 heuristic,
                 force_full,
                 eventful_reserve,
@@ -27682,7 +27995,7 @@ mod evaluation_cache_tests {
     }
 
     fn cheap_child_entry_for_selection_test(
-        child_hash: u64,
+        ordering_hash: u64,
         heuristic: i32,
         force_full: bool,
         eventful_reserve: bool,
@@ -27693,7 +28006,7 @@ mod evaluation_cache_tests {
                 game: game_with_items(vec![], Color::White),
                 events: Vec::new(),
             },
-            child_hash,
+            ordering_hash,
             heuristic,
             force_full,
             eventful_reserve,
@@ -27925,7 +28238,8 @@ mod evaluation_cache_tests {
             .min_by_key(|(_, entry)| entry.heuristic)
             .map(|(index, _)| index)
             .expect("expected at least one child");
-        let target_hash = plain_entries[target_index].child_hash;
+        let target_hash =
+            MonsGameModel::search_state_hash(&plain_entries[target_index].transition.game);
         let plain_selected =
             MonsGameModel::selected_two_stage_child_ordering_indices(&plain_entries, true, config);
         assert!(
@@ -28386,7 +28700,7 @@ mod smart_automove_tests {
         preference: SmartAutomovePreference,
         max_plies: usize,
     ) -> Color {
-        let mut model = MonsGameModel::new_for_simulation(GameVariant::Classic);
+        let mut model = MonsGameModel::new_for_simulation(GameVariant::Classic).unwrap();
         let mut previous_fen = model.fen();
 
         for ply in 0..max_plies {
@@ -28523,6 +28837,7 @@ mod smart_automove_tests {
                             board,
                             color,
                             location,
+                            &RuleSet::STANDARD,
                         )
             )
         })
@@ -28887,6 +29202,78 @@ mod smart_automove_tests {
         assert_eq!(output.input_fen(), expected_input_fen.as_str());
     }
 
+    #[test]
+    fn smart_automove_plays_by_non_standard_rules() {
+        let rules = RuleSet::from_code("target=3,mons=2", &RuleSet::STANDARD).unwrap();
+        let mut game = immediate_score_runtime_fixture();
+        assert!(game.set_rules(rules));
+        game.white_score = rules.target_score - 2;
+        for preference in [
+            SmartAutomovePreference::Fast,
+            SmartAutomovePreference::Normal,
+            SmartAutomovePreference::Pro,
+        ] {
+            let output = MonsGameModel::with_game(game.clone_for_simulation())
+                .smart_automove_output(preference);
+            assert_eq!(output.kind, OutputModelKind::Events);
+            let mut after = game.clone_for_simulation();
+            let inputs = Input::array_from_fen(output.input_fen().as_str());
+            assert!(matches!(
+                after.process_input(inputs, false, false),
+                Output::Events(_)
+            ));
+            assert_eq!(after.winner_color(), Some(Color::White), "{preference:?}");
+        }
+
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        assert!(game.set_rules(rules));
+        while game.turn_number < 4 {
+            let output = MonsGameModel::with_game(game.clone_for_simulation())
+                .smart_automove_output(SmartAutomovePreference::Normal);
+            assert_eq!(output.kind, OutputModelKind::Events);
+            let inputs = Input::array_from_fen(output.input_fen().as_str());
+            assert!(matches!(
+                game.process_input(inputs, false, false),
+                Output::Events(_)
+            ));
+            assert!(game.mons_moves_count <= rules.mons_moves_per_turn);
+        }
+    }
+
+    #[test]
+    fn smart_automove_native_matches_runtime_output_and_reports_errors() {
+        let game = immediate_score_runtime_fixture();
+        let model = MonsGameModel::with_game(game.clone_for_simulation());
+        for preference in [SmartAutomovePreference::Fast, SmartAutomovePreference::Pro] {
+            let expected = model.smart_automove_output(preference);
+            let native = model
+                .smart_automove_native(preference)
+                .expect("native smart automove should find a move");
+            assert_eq!(native.kind, OutputModelKind::Events);
+            assert_eq!(native.input_fen(), expected.input_fen());
+        }
+        assert_eq!(model.fen(), game.fen());
+
+        assert_eq!(
+            " Normal ".parse::<SmartAutomovePreference>(),
+            Ok(SmartAutomovePreference::Normal)
+        );
+        assert_eq!(
+            "blitz".parse::<SmartAutomovePreference>(),
+            Err(AutomoveError::InvalidPreference("blitz".to_string()))
+        );
+
+        let mut finished = game.clone_for_simulation();
+        finished.white_score = Config::TARGET_SCORE;
+        let finished_model = MonsGameModel::with_game(finished);
+        assert_eq!(
+            finished_model
+                .smart_automove_native(SmartAutomovePreference::Fast)
+                .map(|output| output.kind),
+            Err(AutomoveError::GameOver)
+        );
+    }
+
     #[test]
     #[ignore = "release-only mixed-position latency and cpu-ordering guard for publish flow"]
     fn smart_automove_release_mixed_runtime_speed_gate() {
@@ -32396,6 +32783,7 @@ mod smart_automove_tests {
         RankedChildState {
             game: game.clone_for_simulation(),
             hash,
+            ordering_hash: hash,
             ordering_efficiency,
             tactical_extension_trigger: false,
             quiet_reduction_candidate: false,
@@ -33390,7 +33778,7 @@ mod smart_automove_tests {
             children.iter().any(|candidate| {
                 let game = &candidate.game;
                 MonsGameModel::own_drainer_carries_specific_mana_safely(
-                    &game.board,
+                    game,
                     Color::White,
                     Mana::Supermana,
                 ) || (game.active_color == Color::White && {
@@ -33537,7 +33925,7 @@ mod smart_automove_tests {
             children.iter().any(|candidate| {
                 let game = &candidate.game;
                 MonsGameModel::own_drainer_carries_specific_mana_safely(
-                    &game.board,
+                    game,
                     Color::White,
                     Mana::Regular(Color::Black),
                 ) || (game.active_color == Color::White
@@ -33610,6 +33998,7 @@ mod smart_automove_tests {
             false,
             false,
             false,
+            Config::MONS_MOVES_PER_TURN,
         );
 
         assert!(short.supermana_progress);
@@ -33627,6 +34016,7 @@ mod smart_automove_tests {
                 false,
                 false,
                 false,
+                Config::MONS_MOVES_PER_TURN,
             )
         );
         assert!(short.interview_soft_priority > long.interview_soft_priority);
@@ -33702,6 +34092,7 @@ mod smart_automove_tests {
             false,
             false,
             false,
+            Config::MONS_MOVES_PER_TURN,
         );
 
         assert!(short.opponent_mana_progress);
@@ -33719,6 +34110,7 @@ mod smart_automove_tests {
                 false,
                 false,
                 false,
+                Config::MONS_MOVES_PER_TURN,
             )
         );
         assert!(short.interview_soft_priority > long.interview_soft_priority);
@@ -37182,7 +37574,7 @@ mod smart_automove_tests {
 
     #[test]
     fn model_remove_item_invalidates_cached_start_suggestions() {
-        let mut model = MonsGameModel::new_for_simulation(GameVariant::Classic);
+        let mut model = MonsGameModel::new_for_simulation(GameVariant::Classic).unwrap();
         let initial_suggestions = match model.game.process_input(vec![], true, false) {
             Output::LocationsToStartFrom(locations) => locations,
             output => panic!("expected start locations, got {:?}", output),
@@ -37226,6 +37618,7 @@ pub struct OutputModel {
     next_inputs: Vec<NextInputModel>,
     events: Vec<EventModel>,
     input_fen: String,
+    game_move: Option<Move>,
 }
 
 #[wasm_bindgen]
@@ -37248,6 +37641,22 @@ impl OutputModel {
 }
 
 impl OutputModel {
+    /// Move an automove output stands for.
+    pub fn game_move(&self) -> Option<Move> {
+        self.game_move
+    }
+
+    /// Same as `new`, also describing the applied chain as a `Move`.
+    fn new_with_move(output: Output, inputs: &[Input]) -> Self {
+        let game_move = match &output {
+            Output::Events(events) => Move::from_applied(inputs, events),
+            _ => None,
+        };
+        let mut model = Self::new(output, Input::fen_from_array(inputs).as_str());
+        model.game_move = game_move;
+        model
+    }
+
     fn new(output: Output, input_fen: &str) -> Self {
         match output {
             Output::InvalidInput => Self {
@@ -37256,6 +37665,7 @@ impl OutputModel {
                 next_inputs: vec![],
                 events: vec![],
                 input_fen: input_fen.to_string(),
+                game_move: None,
             },
             Output::LocationsToStartFrom(locations) => Self {
                 kind: OutputModelKind::LocationsToStartFrom,
@@ -37263,6 +37673,7 @@ impl OutputModel {
                 next_inputs: vec![],
                 events: vec![],
                 input_fen: input_fen.to_string(),
+                game_move: None,
             },
             Output::NextInputOptions(next_inputs) => Self {
                 kind: OutputModelKind::NextInputOptions,
@@ -37273,6 +37684,7 @@ impl OutputModel {
                     .collect(),
                 events: vec![],
                 input_fen: input_fen.to_string(),
+                game_move: None,
             },
             Output::Events(events) => Self {
                 kind: OutputModelKind::Events,
@@ -37283,6 +37695,7 @@ impl OutputModel {
                     .map(|event| EventModel::new(&event))
                     .collect(),
                 input_fen: input_fen.to_string(),
+                game_move: None,
             },
         }
     }
@@ -37471,6 +37884,7 @@ pub enum EventModelKind {
     GameOver,
     Takeback,
     UsePotion,
+    GameTerminated,
 }
 
 #[wasm_bindgen]
@@ -37483,6 +37897,7 @@ pub struct EventModel {
     pub loc1: Option<Location>,
     pub loc2: Option<Location>,
     pub color: Option<Color>,
+    pub reason: Option<TerminationReason>,
 }
 
 impl EventModel {
@@ -37496,6 +37911,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::ManaMove { mana, from, to } => EventModel {
                 kind: EventModelKind::ManaMove,
@@ -37505,6 +37921,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::ManaScored { mana, at } => EventModel {
                 kind: EventModelKind::ManaScored,
@@ -37514,6 +37931,7 @@ impl EventModel {
                 loc1: Some(*at),
                 loc2: None,
                 color: None,
+                reason: None,
             },
             Event::MysticAction { mystic, from, to } => EventModel {
                 kind: EventModelKind::MysticAction,
@@ -37523,6 +37941,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::DemonAction { demon, from, to } => EventModel {
                 kind: EventModelKind::DemonAction,
@@ -37532,6 +37951,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::DemonAdditionalStep { demon, from, to } => EventModel {
                 kind: EventModelKind::DemonAdditionalStep,
@@ -37541,6 +37961,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::SpiritTargetMove {
                 item,
@@ -37555,6 +37976,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::PickupBomb { by, at } => EventModel {
                 kind: EventModelKind::PickupBomb,
@@ -37564,6 +37986,7 @@ impl EventModel {
                 loc1: Some(*at),
                 loc2: None,
                 color: None,
+                reason: None,
             },
             Event::PickupPotion { by, at } => EventModel {
                 kind: EventModelKind::PickupPotion,
@@ -37573,6 +37996,7 @@ impl EventModel {
                 loc1: Some(*at),
                 loc2: None,
                 color: None,
+                reason: None,
             },
             Event::PickupMana { mana, by, at } => EventModel {
                 kind: EventModelKind::PickupMana,
@@ -37582,6 +38006,7 @@ impl EventModel {
                 loc1: Some(*at),
                 loc2: None,
                 color: None,
+                reason: None,
             },
             Event::MonFainted { mon, from, to } => EventModel {
                 kind: EventModelKind::MonFainted,
@@ -37591,6 +38016,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::ManaDropped { mana, at } => EventModel {
                 kind: EventModelKind::ManaDropped,
@@ -37600,6 +38026,7 @@ impl EventModel {
                 loc1: Some(*at),
                 loc2: None,
                 color: None,
+                reason: None,
             },
             Event::SupermanaBackToBase { from, to } => EventModel {
                 kind: EventModelKind::SupermanaBackToBase,
@@ -37609,6 +38036,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::BombAttack { by, from, to } => EventModel {
                 kind: EventModelKind::BombAttack,
@@ -37618,6 +38046,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
             Event::MonAwake { mon, at } => EventModel {
                 kind: EventModelKind::MonAwake,
@@ -37627,6 +38056,7 @@ impl EventModel {
                 loc1: Some(*at),
                 loc2: None,
                 color: None,
+                reason: None,
             },
             Event::BombExplosion { at } => EventModel {
                 kind: EventModelKind::BombExplosion,
@@ -37636,6 +38066,7 @@ impl EventModel {
                 loc1: Some(*at),
                 loc2: None,
                 color: None,
+                reason: None,
             },
             Event::NextTurn { color } => EventModel {
                 kind: EventModelKind::NextTurn,
@@ -37645,6 +38076,7 @@ impl EventModel {
                 loc1: None,
                 loc2: None,
                 color: Some(*color),
+                reason: None,
             },
             Event::GameOver { winner } => EventModel {
                 kind: EventModelKind::GameOver,
@@ -37654,6 +38086,17 @@ impl EventModel {
                 loc1: None,
                 loc2: None,
                 color: Some(*winner),
+                reason: None,
+            },
+            Event::GameTerminated { result, reason } => EventModel {
+                kind: EventModelKind::GameTerminated,
+                item: None,
+                mon: None,
+                mana: None,
+                loc1: None,
+                loc2: None,
+                color: result.winner(),
+                reason: Some(*reason),
             },
             Event::Takeback => EventModel {
                 kind: EventModelKind::Takeback,
@@ -37663,6 +38106,7 @@ impl EventModel {
                 loc1: None,
                 loc2: None,
                 color: None,
+                reason: None,
             },
             Event::UsePotion { from, to } => EventModel {
                 kind: EventModelKind::UsePotion,
@@ -37672,6 +38116,7 @@ impl EventModel {
                 loc1: Some(*from),
                 loc2: Some(*to),
                 color: None,
+                reason: None,
             },
         }
     }
@@ -37714,3 +38159,104 @@ impl VerboseTrackingEntityModel {
             .join(" ")
     }
 }
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct GameTreeModel {
+    tree: GameTree,
+}
+
+#[wasm_bindgen]
+impl GameTreeModel {
+    #[wasm_bindgen(js_name = fromRecord)]
+    pub fn from_record(record: &str) -> Option<GameTreeModel> {
+        let record = GameRecord::from_text(record).ok()?;
+        GameTree::from_record(&record)
+            .ok()
+            .map(|tree| GameTreeModel { tree })
+    }
+
+    #[wasm_bindgen(js_name = toRecord)]
+    pub fn to_record(&self) -> String {
+        self.tree.to_record().to_text()
+    }
+
+    pub fn root() -> usize {
+        GameTree::ROOT
+    }
+
+    #[wasm_bindgen(js_name = nodeCount)]
+    pub fn node_count(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Plays `input_fen` after `parent`, returning the new or existing node.
+    #[wasm_bindgen(js_name = addMove)]
+    pub fn add_move(&mut self, parent: usize, input_fen: &str) -> Option<usize> {
+        self.tree.add_move(parent, Input::array_from_fen(input_fen))
+    }
+
+    #[wasm_bindgen(js_name = promoteVariation)]
+    pub fn promote_variation(&mut self, node: usize) -> bool {
+        self.tree.promote_variation(node)
+    }
+
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.tree.node(node).and_then(GameTreeNode::parent)
+    }
+
+    pub fn children(&self, node: usize) -> Vec<usize> {
+        self.tree
+            .node(node)
+            .map(|node| node.children().to_vec())
+            .unwrap_or_default()
+    }
+
+    pub fn mainline(&self) -> Vec<usize> {
+        self.tree.mainline()
+    }
+
+    #[wasm_bindgen(js_name = pathTo)]
+    pub fn path_to(&self, node: usize) -> Vec<usize> {
+        self.tree.path_to(node)
+    }
+
+    #[wasm_bindgen(js_name = inputFen)]
+    pub fn input_fen(&self, node: usize) -> String {
+        self.tree
+            .node(node)
+            .map(|node| Input::fen_from_array(&node.inputs))
+            .unwrap_or_default()
+    }
+
+    pub fn events(&self, node: usize) -> Vec<EventModel> {
+        self.tree
+            .node(node)
+            .map(|node| node.events.iter().map(EventModel::new).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn comment(&self, node: usize) -> Option<String> {
+        self.tree.node(node).and_then(|node| node.comment.clone())
+    }
+
+    #[wasm_bindgen(js_name = setComment)]
+    pub fn set_comment(&mut self, node: usize, comment: Option<String>) -> bool {
+        self.tree.set_comment(node, comment)
+    }
+
+    pub fn evaluation(&self, node: usize) -> Option<i32> {
+        self.tree.node(node).and_then(|node| node.evaluation)
+    }
+
+    #[wasm_bindgen(js_name = setEvaluation)]
+    pub fn set_evaluation(&mut self, node: usize, evaluation: Option<i32>) -> bool {
+        self.tree.set_evaluation(node, evaluation)
+    }
+
+    /// Playable game at `node`, with verbose tracking of the path to it.
+    #[wasm_bindgen(js_name = gameAt)]
+    pub fn game_at(&self, node: usize) -> Option<MonsGameModel> {
+        self.tree.game_at(node, true).map(MonsGameModel::with_game)
+    }
+}
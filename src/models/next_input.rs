@@ -2,6 +2,7 @@ use crate::*;
 
 #[wasm_bindgen]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NextInputKind {
     MonMove,
     ManaMove,
@@ -15,6 +16,7 @@ pub enum NextInputKind {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NextInput {
     pub input: Input,
     pub kind: NextInputKind,
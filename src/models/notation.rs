@@ -0,0 +1,575 @@
+use crate::*;
+use std::fmt;
+
+const NOTATION_FILES: &[u8; 11] = b"abcdefghijk";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NotationError {
+    Syntax { notation: String },
+    NoActor { notation: String },
+    AmbiguousActor { notation: String },
+    ActorMismatch { location: Location },
+    ActionMismatch { notation: String },
+    IllegalMove { error: InputError },
+    IncompleteMove { notation: String },
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Syntax { notation } => write!(f, "malformed move \"{}\"", notation),
+            NotationError::NoActor { notation } => {
+                write!(f, "no piece on the board can play \"{}\"", notation)
+            }
+            NotationError::AmbiguousActor { notation } => {
+                write!(f, "\"{}\" needs an origin square", notation)
+            }
+            NotationError::ActorMismatch { location } => write!(
+                f,
+                "square {} does not hold the named piece",
+                location.notation()
+            ),
+            NotationError::ActionMismatch { notation } => {
+                write!(f, "\"{}\" describes a different kind of move", notation)
+            }
+            NotationError::IllegalMove { error } => write!(f, "illegal move: {}", error),
+            NotationError::IncompleteMove { notation } => {
+                write!(f, "\"{}\" does not complete a move", notation)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl Location {
+    /// Algebraic square name, `a1` being white's bottom-left corner.
+    pub fn notation(&self) -> String {
+        format!(
+            "{}{}",
+            NOTATION_FILES[self.j as usize] as char,
+            Config::BOARD_SIZE - self.i
+        )
+    }
+
+    pub fn from_notation(notation: &str) -> Option<Location> {
+        let mut chars = notation.chars();
+        let file = chars.next()?;
+        let j = NOTATION_FILES.iter().position(|f| *f as char == file)? as i32;
+        let rank: i32 = chars.as_str().parse().ok()?;
+        let location = Location::new(Config::BOARD_SIZE - rank, j);
+        location.is_valid().then_some(location)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum NotationActor {
+    Mon(MonKind, Color),
+    Mana(Color),
+}
+
+impl NotationActor {
+    fn letter(&self) -> char {
+        let (letter, color) = match *self {
+            NotationActor::Mon(kind, color) => (
+                match kind {
+                    MonKind::Demon => 'e',
+                    MonKind::Drainer => 'd',
+                    MonKind::Angel => 'a',
+                    MonKind::Spirit => 's',
+                    MonKind::Mystic => 'y',
+                },
+                color,
+            ),
+            NotationActor::Mana(color) => ('m', color),
+        };
+        match color {
+            Color::White => letter.to_ascii_uppercase(),
+            Color::Black => letter,
+        }
+    }
+
+    fn from_letter(letter: char) -> Option<Self> {
+        let color = if letter.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let kind = match letter.to_ascii_lowercase() {
+            'e' => MonKind::Demon,
+            'd' => MonKind::Drainer,
+            'a' => MonKind::Angel,
+            's' => MonKind::Spirit,
+            'y' => MonKind::Mystic,
+            'm' => return Some(NotationActor::Mana(color)),
+            _ => return None,
+        };
+        Some(NotationActor::Mon(kind, color))
+    }
+
+    fn matches(&self, item: Option<&Item>) -> bool {
+        match (*self, item) {
+            (NotationActor::Mon(kind, color), Some(item)) => item
+                .mon()
+                .is_some_and(|mon| mon.kind == kind && mon.color == color),
+            (
+                NotationActor::Mana(color),
+                Some(Item::Mana {
+                    mana: Mana::Regular(mana_color),
+                }),
+            ) => *mana_color == color,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum NotationAction {
+    Move {
+        from: Location,
+        to: Location,
+    },
+    Ability {
+        target: Location,
+        destination: Option<Location>,
+    },
+    Bomb {
+        target: Location,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct NotationMove {
+    actor: NotationActor,
+    origin: Option<Location>,
+    action: NotationAction,
+    pickup: Option<Modifier>,
+}
+
+impl NotationMove {
+    fn from_events(events: &[Event], color: Color) -> Option<Self> {
+        let (actor, origin, action) = match events.first()? {
+            Event::MonMove { item, from, to } => (
+                NotationActor::Mon(item.mon()?.kind, color),
+                *from,
+                NotationAction::Move {
+                    from: *from,
+                    to: *to,
+                },
+            ),
+            Event::ManaMove { from, to, .. } => (
+                NotationActor::Mana(color),
+                *from,
+                NotationAction::Move {
+                    from: *from,
+                    to: *to,
+                },
+            ),
+            Event::MysticAction { mystic, from, to } => (
+                NotationActor::Mon(mystic.kind, color),
+                *from,
+                NotationAction::Ability {
+                    target: *to,
+                    destination: None,
+                },
+            ),
+            Event::DemonAction { demon, from, to } => (
+                NotationActor::Mon(demon.kind, color),
+                *from,
+                NotationAction::Ability {
+                    target: *to,
+                    destination: events.iter().find_map(|event| match event {
+                        Event::DemonAdditionalStep { to, .. } => Some(*to),
+                        _ => None,
+                    }),
+                },
+            ),
+            Event::SpiritTargetMove { from, to, by, .. } => (
+                NotationActor::Mon(MonKind::Spirit, color),
+                *by,
+                NotationAction::Ability {
+                    target: *from,
+                    destination: Some(*to),
+                },
+            ),
+            Event::BombAttack { by, from, to } => (
+                NotationActor::Mon(by.kind, color),
+                *from,
+                NotationAction::Bomb { target: *to },
+            ),
+            _ => return None,
+        };
+        let pickup = events.iter().find_map(|event| match event {
+            Event::PickupBomb { .. } => Some(Modifier::SelectBomb),
+            Event::PickupPotion { .. } => Some(Modifier::SelectPotion),
+            _ => None,
+        });
+        Some(Self {
+            actor,
+            origin: Some(origin),
+            action,
+            pickup,
+        })
+    }
+
+    fn render(&self, explicit_origin: bool, events: &[Event]) -> String {
+        let mut notation = String::new();
+        notation.push(self.actor.letter());
+        match self.action {
+            NotationAction::Move { from, to } => {
+                notation.push_str(&format!(" {}-{}", from.notation(), to.notation()));
+            }
+            NotationAction::Ability {
+                target,
+                destination,
+            } => {
+                notation.push('*');
+                if explicit_origin {
+                    if let Some(origin) = self.origin {
+                        notation.push_str(&origin.notation());
+                    }
+                }
+                notation.push_str(&format!(" x@{}", target.notation()));
+                if let Some(destination) = destination {
+                    notation.push_str(&format!("->{}", destination.notation()));
+                }
+            }
+            NotationAction::Bomb { target } => {
+                if explicit_origin {
+                    if let Some(origin) = self.origin {
+                        notation.push_str(&origin.notation());
+                    }
+                }
+                notation.push_str(&format!(" Bomb {}", target.notation()));
+            }
+        }
+        match self.pickup {
+            Some(Modifier::SelectBomb) => notation.push_str("=Bomb"),
+            Some(Modifier::SelectPotion) => notation.push_str("=Potion"),
+            _ => {}
+        }
+        if events
+            .iter()
+            .any(|event| matches!(event, Event::ManaScored { .. }))
+        {
+            notation.push('+');
+        }
+        if events
+            .iter()
+            .any(|event| matches!(event, Event::GameOver { .. }))
+        {
+            notation.push('#');
+        }
+        notation
+    }
+
+    fn parse(notation: &str) -> Option<Self> {
+        let mut text = notation.trim().trim_end_matches(['+', '#']);
+        let mut pickup = None;
+        for (suffix, modifier) in [
+            ("=Bomb", Modifier::SelectBomb),
+            ("=Potion", Modifier::SelectPotion),
+        ] {
+            if let Some(stripped) = text.strip_suffix(suffix) {
+                text = stripped;
+                pickup = Some(modifier);
+            }
+        }
+
+        let (head, body) = text.split_once(' ')?;
+        let body = body.trim();
+        let mut head_chars = head.chars();
+        let actor = NotationActor::from_letter(head_chars.next()?)?;
+        let rest = head_chars.as_str();
+        let (is_ability, origin) = match rest.strip_prefix('*') {
+            Some(origin) => (true, origin),
+            None => (false, rest),
+        };
+        let origin = if origin.is_empty() {
+            None
+        } else {
+            Some(Location::from_notation(origin)?)
+        };
+
+        let action = if is_ability {
+            let target = body.strip_prefix("x@")?;
+            match target.split_once("->") {
+                Some((target, destination)) => NotationAction::Ability {
+                    target: Location::from_notation(target)?,
+                    destination: Some(Location::from_notation(destination)?),
+                },
+                None => NotationAction::Ability {
+                    target: Location::from_notation(target)?,
+                    destination: None,
+                },
+            }
+        } else if let Some(target) = body.strip_prefix("Bomb ") {
+            NotationAction::Bomb {
+                target: Location::from_notation(target.trim())?,
+            }
+        } else {
+            if origin.is_some() {
+                return None;
+            }
+            let (from, to) = body.split_once('-')?;
+            NotationAction::Move {
+                from: Location::from_notation(from)?,
+                to: Location::from_notation(to)?,
+            }
+        };
+        Some(Self {
+            actor,
+            origin,
+            action,
+            pickup,
+        })
+    }
+
+    /// Whether `self` and `other` describe the same kind of action, ignoring
+    /// origins and suffixes.
+    fn same_action(&self, other: &NotationMove) -> bool {
+        self.actor == other.actor && self.action == other.action
+    }
+}
+
+/// Renders the move that produced `events`, as played by `color`, in
+/// human-readable notation. Returns `None` for event lists that do not start
+/// with a move.
+///
+/// Squares use algebraic coordinates: files `a`..`k` left to right and ranks
+/// `1`..`11` from white's home row up. Every move starts with its actor, a
+/// mon letter as in FEN (`E`, `D`, `A`, `S`, `Y`) or `M` for mana, uppercase
+/// for white and lowercase for black:
+///
+/// - `D e7-e6`: a mon or mana moves from one square to another
+/// - `Y* x@c5`: a mystic or demon acts on a target, `E* x@f5->f6` when the
+///   demon has to step aside afterwards
+/// - `S* x@c4->c6`: a spirit moves the target to a destination
+/// - `D Bomb g5`: a bomb carrier throws its bomb
+///
+/// Since each side has a single mon of every kind, ability actors are named
+/// by letter alone; an origin square follows the letter (`S*f3`) only when the
+/// board has several candidates. Suffixes `=Bomb` / `=Potion` record a pickup
+/// choice, `+` marks scored mana and `#` the end of the game.
+pub fn notation_from_events(events: &[Event], color: Color) -> Option<String> {
+    NotationMove::from_events(events, color).map(|parsed| parsed.render(false, events))
+}
+
+impl MonsGame {
+    /// Notation of the input chain `inputs` played in the current position.
+    pub fn move_notation(&self, inputs: &[Input]) -> Result<String, NotationError> {
+        let mut game = self.clone_for_simulation();
+        let events = match game.process_input_checked(inputs.to_vec(), true, false) {
+            Ok(Output::Events(events)) => events,
+            Ok(_) => {
+                return Err(NotationError::IncompleteMove {
+                    notation: Input::fen_from_array(inputs),
+                })
+            }
+            Err(error) => return Err(NotationError::IllegalMove { error }),
+        };
+        let parsed = NotationMove::from_events(&events, self.active_color).ok_or_else(|| {
+            NotationError::IncompleteMove {
+                notation: Input::fen_from_array(inputs),
+            }
+        })?;
+        let explicit_origin = self.notation_actor_candidates(&parsed).len() > 1;
+        Ok(parsed.render(explicit_origin, &events))
+    }
+
+    /// Parses `notation` back into the input chain it describes, checking
+    /// that the chain is legal in the current position.
+    pub fn parse_move_notation(&self, notation: &str) -> Result<Vec<Input>, NotationError> {
+        let syntax_error = || NotationError::Syntax {
+            notation: notation.to_string(),
+        };
+        let parsed = NotationMove::parse(notation).ok_or_else(syntax_error)?;
+
+        let origin = match (parsed.action, parsed.origin) {
+            (NotationAction::Move { from, .. }, _) => from,
+            (_, Some(origin)) => origin,
+            (_, None) => {
+                let candidates = self.notation_actor_candidates(&parsed);
+                match candidates.as_slice() {
+                    [origin] => *origin,
+                    [] => {
+                        return Err(NotationError::NoActor {
+                            notation: notation.to_string(),
+                        })
+                    }
+                    _ => {
+                        return Err(NotationError::AmbiguousActor {
+                            notation: notation.to_string(),
+                        })
+                    }
+                }
+            }
+        };
+        if !parsed.actor.matches(self.board.item(origin)) {
+            return Err(NotationError::ActorMismatch { location: origin });
+        }
+
+        let mut inputs = vec![Input::Location(origin)];
+        match parsed.action {
+            NotationAction::Move { to, .. } => inputs.push(Input::Location(to)),
+            NotationAction::Ability {
+                target,
+                destination,
+            } => {
+                inputs.push(Input::Location(target));
+                inputs.extend(destination.map(Input::Location));
+            }
+            NotationAction::Bomb { target } => inputs.push(Input::Location(target)),
+        }
+
+        let mut game = self.clone_for_simulation();
+        let mut output = game.process_input_checked(inputs.clone(), true, false);
+        if let (Ok(Output::NextInputOptions(_)), Some(pickup)) = (&output, parsed.pickup) {
+            inputs.push(Input::Modifier(pickup));
+            output = game.process_input_checked(inputs.clone(), true, false);
+        }
+        let events = match output {
+            Ok(Output::Events(events)) => events,
+            Ok(_) => {
+                return Err(NotationError::IncompleteMove {
+                    notation: notation.to_string(),
+                })
+            }
+            Err(error) => return Err(NotationError::IllegalMove { error }),
+        };
+
+        let played = NotationMove::from_events(&events, self.active_color);
+        if !played.is_some_and(|played| {
+            played.same_action(&parsed)
+                && (parsed.pickup.is_none() || played.pickup == parsed.pickup)
+        }) {
+            return Err(NotationError::ActionMismatch {
+                notation: notation.to_string(),
+            });
+        }
+        Ok(inputs)
+    }
+
+    /// Notation of consecutive input chains starting from the current
+    /// position, separated by `, `.
+    pub fn turn_notation(&self, moves: &[Vec<Input>]) -> Result<String, NotationError> {
+        let mut game = self.clone_for_simulation();
+        let mut notations = Vec::with_capacity(moves.len());
+        for inputs in moves {
+            notations.push(game.move_notation(inputs)?);
+            game.process_input(inputs.clone(), false, false);
+        }
+        Ok(notations.join(", "))
+    }
+
+    /// Parses a comma separated `turn_notation` into its input chains.
+    pub fn parse_turn_notation(&self, notation: &str) -> Result<Vec<Vec<Input>>, NotationError> {
+        let mut game = self.clone_for_simulation();
+        let mut moves = Vec::new();
+        for part in notation.split(',').filter(|part| !part.trim().is_empty()) {
+            let inputs = game.parse_move_notation(part)?;
+            game.process_input(inputs.clone(), false, false);
+            moves.push(inputs);
+        }
+        Ok(moves)
+    }
+
+    fn notation_actor_candidates(&self, parsed: &NotationMove) -> Vec<Location> {
+        let NotationActor::Mon(kind, color) = parsed.actor else {
+            return parsed.origin.into_iter().collect();
+        };
+        self.board
+            .all_mons_locations(color)
+            .into_iter()
+            .filter(|location| match self.board.item(*location) {
+                Some(Item::MonWithConsumable {
+                    mon,
+                    consumable: Consumable::Bomb,
+                }) => mon.kind == kind,
+                Some(item) => {
+                    !matches!(parsed.action, NotationAction::Bomb { .. })
+                        && item.mon().is_some_and(|mon| mon.kind == kind)
+                }
+                None => false,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squares_use_algebraic_coordinates() {
+        assert_eq!(Location::new(10, 0).notation(), "a1");
+        assert_eq!(Location::new(0, 10).notation(), "k11");
+        assert_eq!(Location::new(4, 4).notation(), "e7");
+        assert_eq!(Location::from_notation("e7"), Some(Location::new(4, 4)));
+        assert_eq!(Location::from_notation("l1"), None);
+        assert_eq!(Location::from_notation("a12"), None);
+    }
+
+    #[test]
+    fn notation_round_trips_legal_moves() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let mut rendered_kinds = std::collections::HashSet::new();
+        for ply in 0..60 {
+            let legal_moves = game.legal_moves();
+            if legal_moves.is_empty() {
+                break;
+            }
+            for legal_move in legal_moves.iter() {
+                let notation = game.move_notation(&legal_move.inputs).unwrap();
+                assert_eq!(
+                    notation_from_events(&legal_move.events, game.active_color).as_deref(),
+                    Some(notation.as_str())
+                );
+                assert_eq!(
+                    game.parse_move_notation(&notation),
+                    Ok(legal_move.inputs.clone()),
+                    "{}",
+                    notation
+                );
+                rendered_kinds.insert(notation.contains('*'));
+            }
+            let inputs = legal_moves[(ply * 11) % legal_moves.len()].inputs.clone();
+            game.process_input(inputs, false, false);
+        }
+        assert!(rendered_kinds.contains(&true));
+    }
+
+    #[test]
+    fn parser_rejects_mismatched_or_illegal_moves() {
+        let game = MonsGame::new(false, GameVariant::Classic);
+        let drainer = Config::mon_base(MonKind::Drainer, Color::White);
+        let step = Location::new(drainer.i - 1, drainer.j);
+        let black_drainer = Config::mon_base(MonKind::Drainer, Color::Black);
+
+        let notation = format!("D {}-{}", drainer.notation(), step.notation());
+        assert_eq!(
+            game.parse_move_notation(&notation),
+            Ok(vec![Input::Location(drainer), Input::Location(step)])
+        );
+        assert!(matches!(
+            game.parse_move_notation("D a1-a2-a3"),
+            Err(NotationError::Syntax { .. })
+        ));
+        assert!(matches!(
+            game.parse_move_notation(&format!("E {}-{}", drainer.notation(), step.notation())),
+            Err(NotationError::ActorMismatch { .. })
+        ));
+        assert!(matches!(
+            game.parse_move_notation(&format!(
+                "d {}-{}",
+                black_drainer.notation(),
+                Location::new(black_drainer.i + 1, black_drainer.j).notation()
+            )),
+            Err(NotationError::IllegalMove { .. })
+        ));
+        assert!(matches!(
+            game.parse_move_notation("Y* x@f6"),
+            Err(NotationError::IllegalMove { .. })
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::*;
+
+impl MonsGame {
+    /// Number of move sequences of exactly `depth` complete input chains.
+    pub fn perft(&self, depth: usize) -> u64 {
+        self.perft_with_input_cache(depth, true)
+    }
+
+    /// Same as `perft`, optionally clearing the process input cache before
+    /// every query to measure its effect.
+    pub fn perft_with_input_cache(&self, depth: usize, use_input_cache: bool) -> u64 {
+        self.clone_for_simulation()
+            .perft_in_place(depth, use_input_cache)
+    }
+
+    /// `perft` split by the start location of the first input chain.
+    pub fn perft_divide(&self, depth: usize, use_input_cache: bool) -> Vec<(Location, u64)> {
+        let mut divide: Vec<(Location, u64)> = Vec::new();
+        if depth == 0 {
+            return divide;
+        }
+        let mut game = self.clone_for_simulation();
+        for inputs in game.legal_move_inputs_in_place(use_input_cache) {
+            let Some(Input::Location(start)) = inputs.first().copied() else {
+                continue;
+            };
+            let nodes = game.perft_after(&inputs, depth - 1, use_input_cache);
+            match divide.last_mut() {
+                Some((location, count)) if *location == start => *count += nodes,
+                _ => divide.push((start, nodes)),
+            }
+        }
+        divide
+    }
+
+    /// Number of distinct position sequences of exactly `depth` full turns,
+    /// with each ply enumerated by `legal_turns`.
+    pub fn perft_turns(&self, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let turns = self.legal_turns();
+        if depth == 1 {
+            return turns.len() as u64;
+        }
+        turns
+            .iter()
+            .map(|turn| turn.game.perft_turns(depth - 1))
+            .sum()
+    }
+
+    fn perft_in_place(&mut self, depth: usize, use_input_cache: bool) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let chains = self.legal_move_inputs_in_place(use_input_cache);
+        if depth == 1 {
+            return chains.len() as u64;
+        }
+        chains
+            .iter()
+            .map(|inputs| self.perft_after(inputs, depth - 1, use_input_cache))
+            .sum()
+    }
+
+    fn perft_after(&mut self, inputs: &[Input], depth: usize, use_input_cache: bool) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let Output::Events(events) = self.process_input_slice(inputs, true, false) else {
+            return 0;
+        };
+        let token = self.apply_events_with_undo(events);
+        let nodes = self.perft_in_place(depth, use_input_cache);
+        self.undo(token);
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perft_matches_legal_moves_and_divide_totals() {
+        let game = MonsGame::new(false, GameVariant::Classic);
+        assert_eq!(game.perft(0), 1);
+        assert_eq!(game.perft(1), game.legal_moves().len() as u64);
+
+        let depth_two = game.perft(2);
+        let divide = game.perft_divide(2, true);
+        assert_eq!(
+            divide.iter().map(|(_, nodes)| nodes).sum::<u64>(),
+            depth_two
+        );
+        assert_eq!(game.perft_with_input_cache(2, false), depth_two);
+    }
+}
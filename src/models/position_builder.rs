@@ -0,0 +1,159 @@
+use crate::*;
+use std::collections::HashMap;
+
+/// Composes puzzle and test positions item by item; `build` checks the
+/// result with a `PositionValidator`.
+#[derive(Debug, Clone)]
+pub struct PositionBuilder {
+    layout: &'static VariantLayout,
+    items: HashMap<Location, Item>,
+    white_score: i32,
+    black_score: i32,
+    active_color: Color,
+    actions_used_count: i32,
+    mana_moves_count: i32,
+    mons_moves_count: i32,
+    white_potions_count: i32,
+    black_potions_count: i32,
+    turn_number: i32,
+}
+
+impl PositionBuilder {
+    /// Board of `variant` with no items on it, white to move on turn 2.
+    /// Panics for `Custom`; use `empty_with_layout` for registered variants.
+    pub fn empty(variant: GameVariant) -> Self {
+        Self::empty_with_layout(VariantLayout::expect_builtin(variant))
+    }
+
+    /// Board of a built-in or registered layout with no items on it.
+    pub fn empty_with_layout(layout: &'static VariantLayout) -> Self {
+        Self {
+            layout,
+            items: HashMap::new(),
+            white_score: 0,
+            black_score: 0,
+            active_color: Color::White,
+            actions_used_count: 0,
+            mana_moves_count: 0,
+            mons_moves_count: 0,
+            white_potions_count: 0,
+            black_potions_count: 0,
+            turn_number: 2,
+        }
+    }
+
+    /// Initial setup of `variant`, white to move on turn 1. Panics for
+    /// `Custom`.
+    pub fn initial(variant: GameVariant) -> Self {
+        Self::from_game(&MonsGame::new(false, variant))
+    }
+
+    /// Starts from the position of `game`.
+    pub fn from_game(game: &MonsGame) -> Self {
+        Self {
+            layout: game.variant_layout(),
+            items: game
+                .board
+                .occupied()
+                .map(|(location, item)| (location, *item))
+                .collect(),
+            white_score: game.white_score,
+            black_score: game.black_score,
+            active_color: game.active_color,
+            actions_used_count: game.actions_used_count,
+            mana_moves_count: game.mana_moves_count,
+            mons_moves_count: game.mons_moves_count,
+            white_potions_count: game.white_potions_count,
+            black_potions_count: game.black_potions_count,
+            turn_number: game.turn_number,
+        }
+    }
+
+    pub fn item(mut self, location: Location, item: Item) -> Self {
+        self.items.insert(location, item);
+        self
+    }
+
+    pub fn mon(self, location: Location, kind: MonKind, color: Color) -> Self {
+        self.item(
+            location,
+            Item::Mon {
+                mon: Mon::new(kind, color, 0),
+            },
+        )
+    }
+
+    pub fn mana(self, location: Location, mana: Mana) -> Self {
+        self.item(location, Item::Mana { mana })
+    }
+
+    pub fn remove(mut self, location: Location) -> Self {
+        self.items.remove(&location);
+        self
+    }
+
+    pub fn score(mut self, color: Color, score: i32) -> Self {
+        match color {
+            Color::White => self.white_score = score,
+            Color::Black => self.black_score = score,
+        }
+        self
+    }
+
+    pub fn potions(mut self, color: Color, count: i32) -> Self {
+        match color {
+            Color::White => self.white_potions_count = count,
+            Color::Black => self.black_potions_count = count,
+        }
+        self
+    }
+
+    pub fn active_color(mut self, color: Color) -> Self {
+        self.active_color = color;
+        self
+    }
+
+    pub fn turn_number(mut self, turn_number: i32) -> Self {
+        self.turn_number = turn_number;
+        self
+    }
+
+    pub fn turn_counters(
+        mut self,
+        actions_used_count: i32,
+        mana_moves_count: i32,
+        mons_moves_count: i32,
+    ) -> Self {
+        self.actions_used_count = actions_used_count;
+        self.mana_moves_count = mana_moves_count;
+        self.mons_moves_count = mons_moves_count;
+        self
+    }
+
+    /// The position as a game, without any checks.
+    pub fn build_unchecked(&self) -> MonsGame {
+        let mut game = MonsGame::new_with_layout(false, self.layout);
+        game.replace_board_items(self.items.iter().map(|(location, item)| (*location, *item)));
+        game.white_score = self.white_score;
+        game.black_score = self.black_score;
+        game.active_color = self.active_color;
+        game.actions_used_count = self.actions_used_count;
+        game.mana_moves_count = self.mana_moves_count;
+        game.mons_moves_count = self.mons_moves_count;
+        game.white_potions_count = self.white_potions_count;
+        game.black_potions_count = self.black_potions_count;
+        game.turn_number = self.turn_number;
+        game
+    }
+
+    /// The position as a game, or every violation `validator` found in it.
+    pub fn build(&self, validator: &PositionValidator) -> Result<MonsGame, Vec<PositionViolation>> {
+        let game = self.build_unchecked();
+        let violations = validator.validate(&game);
+        if violations.is_empty() {
+            Ok(game)
+        } else {
+            Err(violations)
+        }
+    }
+}
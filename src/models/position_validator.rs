@@ -0,0 +1,466 @@
+use crate::*;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A way a position breaks the rules or cannot arise from play.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PositionViolation {
+    MissingMon {
+        kind: MonKind,
+        color: Color,
+    },
+    DuplicateMon {
+        kind: MonKind,
+        color: Color,
+        locations: Vec<Location>,
+    },
+    CooldownOutOfRange {
+        location: Location,
+        cooldown: i32,
+    },
+    /// Fainted mons are sent to their base and stay there until they wake up.
+    FaintedMonOffBase {
+        location: Location,
+    },
+    FaintedMonCarrying {
+        location: Location,
+    },
+    /// Only drainers carry mana, apart from supermana returning to a mon
+    /// standing on its base.
+    ManaCarriedByNonDrainer {
+        location: Location,
+    },
+    CarriedConsumableNotBomb {
+        location: Location,
+    },
+    /// Mana reaching a pool is scored right away.
+    ManaOnPool {
+        location: Location,
+    },
+    TooManySupermana {
+        count: usize,
+    },
+    TooManyMana {
+        color: Color,
+        count: usize,
+    },
+    NegativeScore {
+        color: Color,
+    },
+    BothPlayersWon,
+    NegativePotions {
+        color: Color,
+    },
+    TurnNumberOutOfRange {
+        turn_number: i32,
+    },
+    /// Per-turn counters outside their limits, or any action or mana move on
+    /// the first turn.
+    TurnCountersOutOfRange,
+}
+
+impl fmt::Display for PositionViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionViolation::MissingMon { kind, color } => {
+                write!(f, "{:?} {:?} is missing", color, kind)
+            }
+            PositionViolation::DuplicateMon {
+                kind,
+                color,
+                locations,
+            } => write!(
+                f,
+                "{:?} {:?} appears {} times",
+                color,
+                kind,
+                locations.len()
+            ),
+            PositionViolation::CooldownOutOfRange { location, cooldown } => {
+                write!(f, "mon at {} has cooldown {}", location.fen(), cooldown)
+            }
+            PositionViolation::FaintedMonOffBase { location } => {
+                write!(f, "fainted mon at {} is not on its base", location.fen())
+            }
+            PositionViolation::FaintedMonCarrying { location } => {
+                write!(f, "fainted mon at {} carries an item", location.fen())
+            }
+            PositionViolation::ManaCarriedByNonDrainer { location } => {
+                write!(f, "mon at {} cannot carry mana", location.fen())
+            }
+            PositionViolation::CarriedConsumableNotBomb { location } => {
+                write!(
+                    f,
+                    "mon at {} carries a consumable other than a bomb",
+                    location.fen()
+                )
+            }
+            PositionViolation::ManaOnPool { location } => {
+                write!(f, "mana at {} stands on a pool", location.fen())
+            }
+            PositionViolation::TooManySupermana { count } => {
+                write!(f, "{} supermana on the board", count)
+            }
+            PositionViolation::TooManyMana { color, count } => {
+                write!(f, "{} {:?} mana on the board", count, color)
+            }
+            PositionViolation::NegativeScore { color } => {
+                write!(f, "{:?} score is negative", color)
+            }
+            PositionViolation::BothPlayersWon => write!(f, "both players reached the target score"),
+            PositionViolation::NegativePotions { color } => {
+                write!(f, "{:?} potion count is negative", color)
+            }
+            PositionViolation::TurnNumberOutOfRange { turn_number } => {
+                write!(f, "turn number {} is out of range", turn_number)
+            }
+            PositionViolation::TurnCountersOutOfRange => {
+                write!(f, "per-turn counters are out of range")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PositionError {
+    InvalidFen,
+    Violations(Vec<PositionViolation>),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::InvalidFen => write!(f, "invalid fen"),
+            PositionError::Violations(violations) => {
+                let messages: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+                write!(f, "{}", messages.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+/// Checks positions set up by hand, e.g. through `replace_board_items`,
+/// `PositionBuilder` or FEN, for states play could never reach.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct PositionValidator {
+    /// Accept positions where a side lacks some of its mons, as in puzzles.
+    pub allow_missing_mons: bool,
+}
+
+impl PositionValidator {
+    pub fn validate(&self, game: &MonsGame) -> Vec<PositionViolation> {
+        let mut violations = Vec::new();
+        self.validate_board(
+            &game.board,
+            game.rules().faint_cooldown,
+            game.handicap(),
+            &mut violations,
+        );
+        Self::validate_counters(game, &mut violations);
+        violations
+    }
+
+    fn validate_board(
+        &self,
+        board: &Board,
+        max_cooldown: i32,
+        handicap: &Handicap,
+        violations: &mut Vec<PositionViolation>,
+    ) {
+        let mut mons: HashMap<(MonKind, Color), Vec<Location>> = HashMap::new();
+        let mut supermana_count = 0;
+        let mut mana_counts: HashMap<Color, usize> = HashMap::new();
+
+        for (location, item) in board.occupied() {
+            let square = board.square(location);
+            if let Some(mana) = item.mana() {
+                match mana {
+                    Mana::Supermana => supermana_count += 1,
+                    Mana::Regular(color) => *mana_counts.entry(*color).or_default() += 1,
+                }
+                if matches!(square, Square::ManaPool { .. }) {
+                    violations.push(PositionViolation::ManaOnPool { location });
+                }
+            }
+            let Some(mon) = item.mon() else {
+                continue;
+            };
+            mons.entry((mon.kind, mon.color))
+                .or_default()
+                .push(location);
+
+            if !(0..=max_cooldown).contains(&mon.cooldown) {
+                violations.push(PositionViolation::CooldownOutOfRange {
+                    location,
+                    cooldown: mon.cooldown,
+                });
+            }
+            if mon.is_fainted() {
+                if board.base(*mon) != location {
+                    violations.push(PositionViolation::FaintedMonOffBase { location });
+                }
+                if !matches!(item, Item::Mon { .. }) {
+                    violations.push(PositionViolation::FaintedMonCarrying { location });
+                }
+            }
+            match item {
+                Item::MonWithMana { mana, .. } if mon.kind != MonKind::Drainer => {
+                    let returned_supermana =
+                        *mana == Mana::Supermana && location == board.supermana_base();
+                    if !returned_supermana {
+                        violations.push(PositionViolation::ManaCarriedByNonDrainer { location });
+                    }
+                }
+                Item::MonWithConsumable { consumable, .. } if *consumable != Consumable::Bomb => {
+                    violations.push(PositionViolation::CarriedConsumableNotBomb { location });
+                }
+                _ => {}
+            }
+        }
+
+        for color in [Color::White, Color::Black] {
+            for kind in [
+                MonKind::Demon,
+                MonKind::Drainer,
+                MonKind::Angel,
+                MonKind::Spirit,
+                MonKind::Mystic,
+            ] {
+                match mons.remove(&(kind, color)) {
+                    Some(mut locations) if locations.len() > 1 => {
+                        locations.sort();
+                        violations.push(PositionViolation::DuplicateMon {
+                            kind,
+                            color,
+                            locations,
+                        });
+                    }
+                    Some(_) => {}
+                    None if !self.allow_missing_mons
+                        && !handicap.side(color).missing_mons.contains(&kind) =>
+                    {
+                        violations.push(PositionViolation::MissingMon { kind, color });
+                    }
+                    None => {}
+                }
+            }
+            let count = mana_counts.get(&color).copied().unwrap_or_default();
+            let bases = board
+                .layout()
+                .squares_map()
+                .values()
+                .filter(|square| **square == Square::ManaBase { color })
+                .count();
+            if count > bases {
+                violations.push(PositionViolation::TooManyMana { color, count });
+            }
+        }
+        if supermana_count > 1 {
+            violations.push(PositionViolation::TooManySupermana {
+                count: supermana_count,
+            });
+        }
+    }
+
+    fn validate_counters(game: &MonsGame, violations: &mut Vec<PositionViolation>) {
+        for (color, score, potions) in [
+            (Color::White, game.white_score, game.white_potions_count),
+            (Color::Black, game.black_score, game.black_potions_count),
+        ] {
+            if score < 0 {
+                violations.push(PositionViolation::NegativeScore { color });
+            }
+            if potions < 0 {
+                violations.push(PositionViolation::NegativePotions { color });
+            }
+        }
+        let rules = game.rules();
+        if game.white_score >= rules.target_score && game.black_score >= rules.target_score {
+            violations.push(PositionViolation::BothPlayersWon);
+        }
+        if game.turn_number < 1 {
+            violations.push(PositionViolation::TurnNumberOutOfRange {
+                turn_number: game.turn_number,
+            });
+        }
+        let counters_in_range = (0..=rules.actions_per_turn).contains(&game.actions_used_count)
+            && (0..=rules.mana_moves_per_turn).contains(&game.mana_moves_count)
+            && (0..=rules.mons_moves_per_turn).contains(&game.mons_moves_count)
+            && !(game.is_first_turn()
+                && (game.actions_used_count > 0 || game.mana_moves_count > 0));
+        if !counters_in_range {
+            violations.push(PositionViolation::TurnCountersOutOfRange);
+        }
+    }
+}
+
+impl MonsGame {
+    /// Violations of the current position under the default validator.
+    pub fn validate_position(&self) -> Vec<PositionViolation> {
+        PositionValidator::default().validate(self)
+    }
+
+    /// Same as `from_fen`, also running `validator` on the loaded position
+    /// when one is given.
+    pub fn from_fen_validated(
+        fen: &str,
+        with_verbose_tracking: bool,
+        validator: Option<&PositionValidator>,
+    ) -> Result<MonsGame, PositionError> {
+        let game =
+            MonsGame::from_fen(fen, with_verbose_tracking).ok_or(PositionError::InvalidFen)?;
+        let violations = validator
+            .map(|validator| validator.validate(&game))
+            .unwrap_or_default();
+        if violations.is_empty() {
+            Ok(game)
+        } else {
+            Err(PositionError::Violations(violations))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_VARIANTS: [GameVariant; 12] = [
+        GameVariant::Classic,
+        GameVariant::SwappedManaRows,
+        GameVariant::OffsetArcManaRows,
+        GameVariant::CenterSpokeManaRows,
+        GameVariant::AlternatingManaRows,
+        GameVariant::InnerWedgeManaRows,
+        GameVariant::OuterWedgeManaRows,
+        GameVariant::BentCenterManaRows,
+        GameVariant::OuterEdgeManaRows,
+        GameVariant::SplitFlankManaRows,
+        GameVariant::ForwardBridgeManaRows,
+        GameVariant::CornerChainManaRows,
+    ];
+
+    #[test]
+    fn initial_and_played_positions_are_valid() {
+        for variant in ALL_VARIANTS {
+            let mut game = MonsGame::new(false, variant);
+            assert_eq!(game.validate_position(), vec![]);
+            for ply in 0..40 {
+                let legal_moves = game.legal_move_inputs(true);
+                if legal_moves.is_empty() {
+                    break;
+                }
+                let inputs = legal_moves[(ply * 11) % legal_moves.len()].clone();
+                game.process_input(inputs, false, false);
+                assert_eq!(game.validate_position(), vec![], "{}", game.fen());
+            }
+        }
+    }
+
+    #[test]
+    fn hand_made_positions_report_violations() {
+        let base = PositionBuilder::initial(GameVariant::Classic).turn_number(2);
+        let drainer_base = Config::mon_base(MonKind::Drainer, Color::White);
+        let angel_base = Config::mon_base(MonKind::Angel, Color::White);
+        let pool = Location::new(0, 0);
+
+        let violations = base
+            .clone()
+            .mon(Location::new(5, 0), MonKind::Drainer, Color::White)
+            .remove(angel_base)
+            .mana(Location::new(5, 1), Mana::Supermana)
+            .mana(pool, Mana::Regular(Color::Black))
+            .score(Color::Black, -1)
+            .turn_counters(0, 2, 0)
+            .build(&PositionValidator::default())
+            .unwrap_err();
+        for expected in [
+            PositionViolation::DuplicateMon {
+                kind: MonKind::Drainer,
+                color: Color::White,
+                locations: vec![Location::new(5, 0), drainer_base],
+            },
+            PositionViolation::MissingMon {
+                kind: MonKind::Angel,
+                color: Color::White,
+            },
+            PositionViolation::TooManySupermana { count: 2 },
+            PositionViolation::ManaOnPool { location: pool },
+            PositionViolation::TooManyMana {
+                color: Color::Black,
+                count: 6,
+            },
+            PositionViolation::NegativeScore {
+                color: Color::Black,
+            },
+            PositionViolation::TurnCountersOutOfRange,
+        ] {
+            assert!(violations.contains(&expected), "{:?}", expected);
+        }
+
+        let violations = base
+            .clone()
+            .remove(angel_base)
+            .item(
+                Location::new(5, 0),
+                Item::MonWithMana {
+                    mon: Mon::new(MonKind::Angel, Color::White, 1),
+                    mana: Mana::Regular(Color::White),
+                },
+            )
+            .build(&PositionValidator {
+                allow_missing_mons: true,
+            })
+            .unwrap_err();
+        assert_eq!(
+            violations,
+            vec![
+                PositionViolation::FaintedMonOffBase {
+                    location: Location::new(5, 0)
+                },
+                PositionViolation::FaintedMonCarrying {
+                    location: Location::new(5, 0)
+                },
+                PositionViolation::ManaCarriedByNonDrainer {
+                    location: Location::new(5, 0)
+                },
+                PositionViolation::TooManyMana {
+                    color: Color::White,
+                    count: 6,
+                },
+            ]
+        );
+
+        let puzzle = PositionBuilder::empty(GameVariant::Classic)
+            .mon(Location::new(5, 1), MonKind::Drainer, Color::White)
+            .mon(Location::new(5, 9), MonKind::Drainer, Color::Black)
+            .mana(Location::new(4, 1), Mana::Regular(Color::White))
+            .build(&PositionValidator {
+                allow_missing_mons: true,
+            })
+            .unwrap();
+        assert_eq!(puzzle.turn_number, 2);
+        assert!(!puzzle.validate_position().is_empty());
+    }
+
+    #[test]
+    fn from_fen_validated_runs_the_validator_on_request() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        game.white_score = Config::TARGET_SCORE;
+        game.black_score = Config::TARGET_SCORE;
+        let fen = game.fen();
+
+        assert!(MonsGame::from_fen_validated(fen.as_str(), false, None).is_ok());
+        assert_eq!(
+            MonsGame::from_fen_validated(fen.as_str(), false, Some(&PositionValidator::default()))
+                .unwrap_err(),
+            PositionError::Violations(vec![PositionViolation::BothPlayersWon])
+        );
+        assert_eq!(
+            MonsGame::from_fen_validated("nonsense", false, None).unwrap_err(),
+            PositionError::InvalidFen
+        );
+    }
+}
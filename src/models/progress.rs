@@ -0,0 +1,317 @@
+use crate::*;
+
+/// Optional limits that end a stalled game as a drawn adjudication.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct ProgressRules {
+    /// Ends the game once the same position has occurred this many times.
+    pub max_repetitions: Option<u32>,
+    /// Ends the game after this many complete turns without progress.
+    pub max_turns_without_progress: Option<u32>,
+}
+
+impl ProgressRules {
+    /// Set limits as `key=value` pairs, e.g. `rep=3,stall=50`. Empty when
+    /// neither limit is set.
+    pub fn code(&self) -> String {
+        [
+            ("rep", self.max_repetitions),
+            ("stall", self.max_turns_without_progress),
+        ]
+        .iter()
+        .filter_map(|(key, value)| value.map(|value| format!("{}={}", key, value)))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+
+    /// Inverse of `code`; limits must be positive and at least one set.
+    pub fn from_code(code: &str) -> Option<ProgressRules> {
+        let mut rules = ProgressRules::default();
+        for pair in code.split(',').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=')?;
+            let value = value.parse().ok().filter(|value| *value > 0)?;
+            match key {
+                "rep" => rules.max_repetitions = Some(value),
+                "stall" => rules.max_turns_without_progress = Some(value),
+                _ => return None,
+            }
+        }
+        (!rules.is_empty()).then_some(rules)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max_repetitions.is_none() && self.max_turns_without_progress.is_none()
+    }
+}
+
+/// Position reached after an input chain, with the no-progress counters as
+/// they stood at that point so takebacks can simply pop the entry.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct PositionRecord {
+    key: u64,
+    turns_since_progress: u32,
+    progress_in_turn: bool,
+}
+
+impl MonsGame {
+    /// Number of times the current position has occurred in this game,
+    /// counting the current occurrence. Positions are compared by `zobrist`
+    /// without the turn number, so the active color and per-turn counters
+    /// must match as well.
+    pub fn repetition_count(&self) -> usize {
+        let key = self.repetition_key();
+        self.position_history
+            .iter()
+            .filter(|record| record.key == key)
+            .count()
+            .max(1)
+    }
+
+    /// Complete turns played since the last score, faint, pickup or
+    /// consumable use. Zero while the current turn has made progress.
+    pub fn turns_since_progress(&self) -> u32 {
+        match self.position_history.last() {
+            Some(record) if !record.progress_in_turn => record.turns_since_progress,
+            _ => 0,
+        }
+    }
+
+    pub fn progress_rules(&self) -> Option<ProgressRules> {
+        self.progress_rules
+    }
+
+    /// Progress rules are saved with the game in every format, but the
+    /// position history is not. Serde rebuilds it by replaying `input_history`;
+    /// FEN, binary and records start it over from the loaded position, and
+    /// `from_record` then rebuilds it by replaying the recorded turns.
+    pub fn set_progress_rules(&mut self, rules: Option<ProgressRules>) {
+        self.progress_rules = rules.filter(|rules| !rules.is_empty());
+    }
+
+    /// Seeds the history with the position an input chain starts from.
+    pub(crate) fn record_start_position(&mut self) {
+        if self.position_history.is_empty() {
+            self.position_history.push(PositionRecord {
+                key: self.repetition_key(),
+                turns_since_progress: 0,
+                progress_in_turn: false,
+            });
+        }
+    }
+
+    /// Records the position reached through `events` and applies
+    /// `progress_rules`, returning the adjudication event if one fired.
+    pub(crate) fn record_position(&mut self, events: &[Event]) -> Option<Event> {
+        let (mut turns_since_progress, mut progress_in_turn) = self
+            .position_history
+            .last()
+            .map(|record| (record.turns_since_progress, record.progress_in_turn))
+            .unwrap_or_default();
+        for event in events {
+            if is_progress_event(event) {
+                turns_since_progress = 0;
+                progress_in_turn = true;
+            } else if let Event::NextTurn { .. } = event {
+                if !progress_in_turn {
+                    turns_since_progress += 1;
+                }
+                progress_in_turn = false;
+            }
+        }
+        self.position_history.push(PositionRecord {
+            key: self.repetition_key(),
+            turns_since_progress,
+            progress_in_turn,
+        });
+
+        let rules = self.progress_rules?;
+        let repeated = rules
+            .max_repetitions
+            .is_some_and(|limit| self.repetition_count() >= limit as usize);
+        let stalled = rules
+            .max_turns_without_progress
+            .is_some_and(|limit| self.turns_since_progress() >= limit);
+        if repeated || stalled {
+            self.adjudicate(GameResult::Draw, TerminationReason::Adjudication)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn pop_position_record(&mut self) {
+        if self.position_history.len() > 1 {
+            self.position_history.pop();
+        }
+    }
+
+    fn repetition_key(&self) -> u64 {
+        self.zobrist() ^ zobrist_turn_number_key(self.turn_number)
+    }
+}
+
+fn is_progress_event(event: &Event) -> bool {
+    match event {
+        Event::ManaScored { .. }
+        | Event::MonFainted { .. }
+        | Event::PickupBomb { .. }
+        | Event::PickupPotion { .. }
+        | Event::PickupMana { .. }
+        | Event::UsePotion { .. }
+        | Event::BombAttack { .. }
+        | Event::BombExplosion { .. } => true,
+        Event::MonMove { .. }
+        | Event::ManaMove { .. }
+        | Event::MysticAction { .. }
+        | Event::DemonAction { .. }
+        | Event::DemonAdditionalStep { .. }
+        | Event::SpiritTargetMove { .. }
+        | Event::ManaDropped { .. }
+        | Event::SupermanaBackToBase { .. }
+        | Event::MonAwake { .. }
+        | Event::NextTurn { .. }
+        | Event::GameOver { .. }
+        | Event::GameTerminated { .. }
+        | Event::Takeback => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Spirit and angel of each side alone on the board, so turns can be
+    /// shuffled back and forth without any progress.
+    fn shuffle_game() -> MonsGame {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        game.replace_board_items([
+            (
+                Location::new(5, 1),
+                Item::Mon {
+                    mon: Mon::new(MonKind::Angel, Color::White, 0),
+                },
+            ),
+            (
+                Location::new(5, 9),
+                Item::Mon {
+                    mon: Mon::new(MonKind::Angel, Color::Black, 0),
+                },
+            ),
+        ]);
+        game.turn_number = 2;
+        game
+    }
+
+    fn shuffle(game: &mut MonsGame, from: Location, to: Location) -> Vec<Event> {
+        let chain = vec![Input::Location(from), Input::Location(to)];
+        match game.process_input(chain, false, false) {
+            Output::Events(events) => events,
+            output => panic!("unexpected output {:?}", output),
+        }
+    }
+
+    fn play_shuffle_turn(game: &mut MonsGame) -> Vec<Event> {
+        let (a, b) = match game.active_color {
+            Color::White => (Location::new(5, 1), Location::new(4, 1)),
+            Color::Black => (Location::new(5, 9), Location::new(4, 9)),
+        };
+        let (from, to) = if game.board.item(a).is_some() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let mut events = Vec::new();
+        while !events
+            .iter()
+            .any(|event| matches!(event, Event::NextTurn { .. } | Event::GameTerminated { .. }))
+        {
+            events = shuffle(game, from, to);
+            if !events
+                .iter()
+                .any(|event| matches!(event, Event::NextTurn { .. } | Event::GameTerminated { .. }))
+            {
+                events = shuffle(game, to, from);
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn shuffling_counts_repetitions_and_turns_without_progress() {
+        let mut game = shuffle_game();
+        assert_eq!(game.repetition_count(), 1);
+        assert_eq!(game.turns_since_progress(), 0);
+
+        for _ in 0..4 {
+            play_shuffle_turn(&mut game);
+        }
+        assert_eq!(game.turns_since_progress(), 4);
+        assert!(game.repetition_count() > 1);
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn progress_rules_adjudicate_stalled_games() {
+        let mut game = shuffle_game();
+        game.set_progress_rules(Some(ProgressRules {
+            max_repetitions: None,
+            max_turns_without_progress: Some(3),
+        }));
+        let mut last_events = Vec::new();
+        for _ in 0..3 {
+            last_events = play_shuffle_turn(&mut game);
+        }
+        assert!(last_events.contains(&Event::GameTerminated {
+            result: GameResult::Draw,
+            reason: TerminationReason::Adjudication,
+        }));
+        assert!(game.is_game_over());
+
+        let mut game = shuffle_game();
+        game.set_progress_rules(Some(ProgressRules {
+            max_repetitions: Some(3),
+            max_turns_without_progress: None,
+        }));
+        for _ in 0..12 {
+            if game.is_game_over() {
+                break;
+            }
+            play_shuffle_turn(&mut game);
+        }
+        assert!(game.is_game_over());
+        assert_eq!(game.repetition_count(), 3);
+    }
+
+    #[test]
+    fn progress_rules_survive_fen_bytes_and_records() {
+        let mut game = shuffle_game();
+        let progress_rules = ProgressRules {
+            max_repetitions: Some(4),
+            max_turns_without_progress: Some(30),
+        };
+        game.set_progress_rules(Some(progress_rules));
+        assert_eq!(progress_rules.code(), "rep=4,stall=30");
+        assert_eq!(
+            ProgressRules::from_code("rep=4,stall=30"),
+            Some(progress_rules)
+        );
+        assert_eq!(ProgressRules::from_code("rep=0"), None);
+        assert_eq!(ProgressRules::from_code(""), None);
+
+        let fen = game.fen();
+        assert!(fen.ends_with(" %rep=4,stall=30"));
+        let from_fen = MonsGame::from_fen(fen.as_str(), false).unwrap();
+        assert_eq!(from_fen.progress_rules(), Some(progress_rules));
+        let from_bytes = MonsGame::from_bytes(&game.to_bytes(), false).unwrap();
+        assert_eq!(from_bytes.progress_rules(), Some(progress_rules));
+        assert_eq!(from_bytes.fen(), fen);
+
+        play_shuffle_turn(&mut game);
+        play_shuffle_turn(&mut game);
+        let text = game.to_record().to_text();
+        assert!(text.contains("[Progress \"rep=4,stall=30\"]"));
+        let replayed =
+            MonsGame::from_record(&GameRecord::from_text(&text).unwrap(), false).unwrap();
+        assert_eq!(replayed.progress_rules(), Some(progress_rules));
+        assert_eq!(replayed.turns_since_progress(), game.turns_since_progress());
+    }
+}
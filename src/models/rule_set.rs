@@ -0,0 +1,203 @@
+use crate::*;
+
+/// Rule parameters a game is played under.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct RuleSet {
+    pub target_score: i32,
+    pub mons_moves_per_turn: i32,
+    pub mana_moves_per_turn: i32,
+    pub actions_per_turn: i32,
+    /// Turns of its owner a fainted mon sits out.
+    pub faint_cooldown: i32,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl RuleSet {
+    pub const STANDARD: RuleSet = RuleSet {
+        target_score: Config::TARGET_SCORE,
+        mons_moves_per_turn: Config::MONS_MOVES_PER_TURN,
+        mana_moves_per_turn: Config::MANA_MOVES_PER_TURN,
+        actions_per_turn: Config::ACTIONS_PER_TURN,
+        faint_cooldown: Mon::FAINT_COOLDOWN,
+    };
+
+    /// Standard rules with the overrides `spec` declares.
+    pub fn for_variant(spec: &VariantSpec) -> RuleSet {
+        let standard = Self::STANDARD;
+        RuleSet {
+            target_score: spec.target_score.unwrap_or(standard.target_score),
+            mons_moves_per_turn: spec
+                .mons_moves_per_turn
+                .unwrap_or(standard.mons_moves_per_turn),
+            mana_moves_per_turn: spec
+                .mana_moves_per_turn
+                .unwrap_or(standard.mana_moves_per_turn),
+            actions_per_turn: spec.actions_per_turn.unwrap_or(standard.actions_per_turn),
+            faint_cooldown: standard.faint_cooldown,
+        }
+    }
+
+    /// Longest faint cooldown FEN and binary encodings can hold, as both keep
+    /// a single digit of a mon's cooldown.
+    pub const MAX_FAINT_COOLDOWN: i32 = 9;
+
+    /// Whether every parameter is in the range the rules can handle: a
+    /// positive target score, at least one mon and mana move and a fainted
+    /// mon that sits out between one and `MAX_FAINT_COOLDOWN` turns.
+    pub fn is_valid(&self) -> bool {
+        self.target_score >= 1
+            && self.mons_moves_per_turn >= 1
+            && self.mana_moves_per_turn >= 1
+            && self.actions_per_turn >= 0
+            && (1..=Self::MAX_FAINT_COOLDOWN).contains(&self.faint_cooldown)
+    }
+
+    fn fields(&self) -> [(&'static str, i32); 5] {
+        [
+            ("target", self.target_score),
+            ("mons", self.mons_moves_per_turn),
+            ("mana", self.mana_moves_per_turn),
+            ("actions", self.actions_per_turn),
+            ("faint", self.faint_cooldown),
+        ]
+    }
+
+    /// Comma separated `key=value` pairs for the parameters that differ from
+    /// `base`, such as `target=3,faint=1`. Empty when there are none.
+    pub fn code(&self, base: &RuleSet) -> String {
+        self.fields()
+            .iter()
+            .zip(base.fields())
+            .filter(|((_, value), (_, base_value))| value != base_value)
+            .map(|((key, value), _)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// `base` with the parameters listed in `code` replaced.
+    pub fn from_code(code: &str, base: &RuleSet) -> Option<RuleSet> {
+        let mut rules = *base;
+        for pair in code.split(',').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=')?;
+            let value = value.parse().ok()?;
+            match key {
+                "target" => rules.target_score = value,
+                "mons" => rules.mons_moves_per_turn = value,
+                "mana" => rules.mana_moves_per_turn = value,
+                "actions" => rules.actions_per_turn = value,
+                "faint" => rules.faint_cooldown = value,
+                _ => return None,
+            }
+        }
+        rules.is_valid().then_some(rules)
+    }
+}
+
+impl MonsGame {
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Replaces the rules the game is played under. Meant for setting up a
+    /// game; changing them mid-game applies from the next input on. Leaves
+    /// the game as is and returns `false` when `rules` are not valid or the
+    /// game's handicap does not fit them.
+    pub fn set_rules(&mut self, rules: RuleSet) -> bool {
+        if !rules.is_valid() || !self.handicap.fits_rules(&rules) {
+            return false;
+        }
+        self.rules = rules;
+        self.invalidate_process_input_cache();
+        true
+    }
+
+    /// Initial setup of this game's variant with its handicap, played under
+    /// this game's rules.
+    pub(crate) fn initial_game(&self, with_verbose_tracking: bool) -> MonsGame {
+        let mut game = MonsGame::new_with_layout(with_verbose_tracking, self.variant_layout());
+        game.rules = self.rules;
+        self.handicap.apply(&mut game);
+        game
+    }
+
+    /// Rules a fresh game of this variant would start with.
+    pub(crate) fn variant_rules(&self) -> RuleSet {
+        RuleSet::for_variant(self.variant_layout().spec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blitz() -> RuleSet {
+        RuleSet {
+            target_score: 1,
+            mons_moves_per_turn: 2,
+            ..RuleSet::STANDARD
+        }
+    }
+
+    #[test]
+    fn rules_codes_round_trip_relative_to_base() {
+        let rules = blitz();
+        assert_eq!(rules.code(&RuleSet::STANDARD), "target=1,mons=2");
+        assert_eq!(RuleSet::STANDARD.code(&RuleSet::STANDARD), "");
+        assert_eq!(
+            RuleSet::from_code("target=1,mons=2", &RuleSet::STANDARD),
+            Some(rules)
+        );
+        assert_eq!(RuleSet::from_code("target=0", &RuleSet::STANDARD), None);
+        assert_eq!(RuleSet::from_code("faint=10", &RuleSet::STANDARD), None);
+        assert!(RuleSet::from_code("faint=9", &RuleSet::STANDARD).is_some());
+        assert_eq!(RuleSet::from_code("speed=2", &RuleSet::STANDARD), None);
+
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        assert!(!game.set_rules(RuleSet {
+            faint_cooldown: RuleSet::MAX_FAINT_COOLDOWN + 1,
+            ..rules
+        }));
+        assert_eq!(game.rules(), &RuleSet::STANDARD);
+        assert!(game.set_rules(rules));
+        let fen = game.fen();
+        assert!(fen.ends_with(" ~target=1,mons=2"));
+        let loaded = MonsGame::from_fen(fen.as_str(), false).unwrap();
+        assert_eq!(loaded.rules(), &rules);
+        assert_eq!(
+            MonsGame::from_bytes(&game.to_bytes(), false).map(|game| *game.rules()),
+            Some(rules)
+        );
+
+        let record = game.to_record();
+        assert_eq!(record.rules, Some(rules));
+        assert_eq!(record.start_fen, None);
+        let parsed = GameRecord::from_text(record.to_text().as_str()).unwrap();
+        assert_eq!(
+            MonsGame::from_record(&parsed, false).map(|game| *game.rules()),
+            Ok(rules)
+        );
+    }
+
+    #[test]
+    fn game_follows_its_rules() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        game.set_rules(blitz());
+        assert_eq!(game.available_move_kinds()[&AvailableMoveKind::MonMove], 2);
+        for _ in 0..2 {
+            let inputs = game.legal_move_inputs(true).remove(0);
+            game.process_input(inputs, false, false);
+        }
+        assert_eq!(game.turn_number, 2);
+        assert_eq!(game.active_color, Color::Black);
+        assert_eq!(game.winner_color(), None);
+        game.white_score = 1;
+        assert_eq!(game.winner_color(), Some(Color::White));
+        assert_eq!(game.validate_position(), vec![]);
+    }
+}
@@ -1123,8 +1123,7 @@ pub fn evaluate_preferability_breakdown_with_weights(
         weights.include_regular_mana_move_windows && !use_legacy_formula;
     let include_match_point_window = weights.include_match_point_window && !use_legacy_formula;
     let next_turn_window_scale_bp = weights.next_turn_window_scale_bp.clamp(0, 20_000);
-    let remaining_mon_moves_for_active =
-        (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+    let remaining_mon_moves_for_active = (game.mons_moves_limit() - game.mons_moves_count).max(0);
     let exact_analysis = (!use_legacy_formula).then(|| exact_strategic_analysis(game));
     let my_exact_summary = exact_analysis.map(|analysis| analysis.color_summary(color));
     let opponent_exact_summary =
@@ -1264,7 +1263,7 @@ pub fn evaluate_preferability_breakdown_with_weights(
                     &game.board,
                     &mana_snapshot,
                     color.other(),
-                    Config::MONS_MOVES_PER_TURN,
+                    game.rules().mons_moves_per_turn,
                     true,
                     include_regular_mana_move_windows,
                     include_regular_mana_move_windows,
@@ -1293,10 +1292,11 @@ pub fn evaluate_preferability_breakdown_with_weights(
             );
 
             if include_match_point_window {
-                if my_score_now + my_immediate_window.best_score >= Config::TARGET_SCORE {
+                if my_score_now + my_immediate_window.best_score >= game.rules().target_score {
                     terms.match_point_window += weights.immediate_winning_carrier;
                 }
-                if opponent_score_now + opponent_immediate_window.best_score >= Config::TARGET_SCORE
+                if opponent_score_now + opponent_immediate_window.best_score
+                    >= game.rules().target_score
                 {
                     terms.match_point_window -= weights.immediate_winning_carrier;
                 }
@@ -1337,7 +1337,7 @@ pub fn evaluate_preferability_breakdown_with_weights(
                     &game.board,
                     &mana_snapshot,
                     color,
-                    Config::MONS_MOVES_PER_TURN,
+                    game.rules().mons_moves_per_turn,
                     true,
                     include_regular_mana_move_windows,
                     include_regular_mana_move_windows,
@@ -1366,11 +1366,12 @@ pub fn evaluate_preferability_breakdown_with_weights(
             );
 
             if include_match_point_window {
-                if opponent_score_now + opponent_immediate_window.best_score >= Config::TARGET_SCORE
+                if opponent_score_now + opponent_immediate_window.best_score
+                    >= game.rules().target_score
                 {
                     terms.match_point_window -= weights.immediate_winning_carrier;
                 }
-                if my_score_now + my_immediate_window.best_score >= Config::TARGET_SCORE {
+                if my_score_now + my_immediate_window.best_score >= game.rules().target_score {
                     terms.match_point_window += weights.immediate_winning_carrier;
                 }
             }
@@ -1448,8 +1449,7 @@ pub(crate) fn evaluate_preferability_with_context(
     let include_match_point_window = weights.include_match_point_window && !use_legacy_formula;
     let next_turn_window_scale_bp = weights.next_turn_window_scale_bp.clamp(0, 20_000);
     let supermana_base = game.board.supermana_base();
-    let remaining_mon_moves_for_active =
-        (Config::MONS_MOVES_PER_TURN - game.mons_moves_count).max(0);
+    let remaining_mon_moves_for_active = (game.mons_moves_limit() - game.mons_moves_count).max(0);
     let exact_analysis = if use_legacy_formula {
         None
     } else {
@@ -1459,7 +1459,7 @@ pub(crate) fn evaluate_preferability_with_context(
     let opponent_exact_summary =
         exact_analysis.map(|analysis| analysis.color_summary(color.other()));
 
-    let mons_bases = Config::mons_bases_ref();
+    let mons_bases = game.board.layout().mons_bases();
     let my_score_now = if color == Color::White {
         game.white_score
     } else {
@@ -1510,6 +1510,7 @@ pub(crate) fn evaluate_preferability_with_context(
                         use_legacy_formula,
                         weights.drainer_walk_threat_boolean != 0,
                         Some(context),
+                        game.rules(),
                     );
                     score += my_mon_multiplier * weights.drainer_close_to_mana / safety.min_mana;
                     score += my_mon_multiplier * weights.drainer_close_to_own_pool
@@ -1665,6 +1666,7 @@ pub(crate) fn evaluate_preferability_with_context(
                         use_legacy_formula,
                         weights.drainer_walk_threat_boolean != 0,
                         Some(context),
+                        game.rules(),
                     );
                     score += my_mon_multiplier * weights.drainer_close_to_mana / safety.min_mana;
                     score += my_mon_multiplier * weights.drainer_close_to_own_pool
@@ -1898,7 +1900,7 @@ pub(crate) fn evaluate_preferability_with_context(
                         game.black_score
                     };
                     let score_if_scored_now = carrier_score + mana.score(mon.color);
-                    if score_if_scored_now >= Config::TARGET_SCORE {
+                    if score_if_scored_now >= game.rules().target_score {
                         score += my_mon_multiplier * weights.immediate_winning_carrier;
                     }
                 }
@@ -1914,6 +1916,7 @@ pub(crate) fn evaluate_preferability_with_context(
                     use_legacy_formula,
                     weights.mana_carrier_walk_threat_boolean != 0 || carries_high_value_mana,
                     Some(context),
+                    game.rules(),
                 );
                 score += my_mon_multiplier * weights.mana_carrier_at_risk / safety.risk_danger;
                 if safety.guarded_against_exact_attack() {
@@ -1936,7 +1939,7 @@ pub(crate) fn evaluate_preferability_with_context(
                     } else {
                         game.white_score
                     };
-                    let opponent_score_limit = (Config::TARGET_SCORE
+                    let opponent_score_limit = (game.rules().target_score
                         - PROTECTED_HIGH_VALUE_CARRIER_OPPONENT_SCORE_MARGIN)
                         .max(0);
                     let protected = if use_legacy_formula {
@@ -2134,7 +2137,7 @@ pub(crate) fn evaluate_preferability_with_context(
                     &game.board,
                     context,
                     color.other(),
-                    Config::MONS_MOVES_PER_TURN,
+                    game.rules().mons_moves_per_turn,
                     true,
                     include_regular_mana_move_windows,
                     include_regular_mana_move_windows,
@@ -2163,10 +2166,11 @@ pub(crate) fn evaluate_preferability_with_context(
                 defense_scale_bp,
             );
             if include_match_point_window {
-                if my_score_now + immediate_window.best_score >= Config::TARGET_SCORE {
+                if my_score_now + immediate_window.best_score >= game.rules().target_score {
                     score += weights.immediate_winning_carrier;
                 }
-                if opponent_score_now + opponent_next_turn_window.best_score >= Config::TARGET_SCORE
+                if opponent_score_now + opponent_next_turn_window.best_score
+                    >= game.rules().target_score
                 {
                     score -= weights.immediate_winning_carrier;
                 }
@@ -2209,7 +2213,7 @@ pub(crate) fn evaluate_preferability_with_context(
                     &game.board,
                     context,
                     color,
-                    Config::MONS_MOVES_PER_TURN,
+                    game.rules().mons_moves_per_turn,
                     true,
                     include_regular_mana_move_windows,
                     include_regular_mana_move_windows,
@@ -2238,11 +2242,12 @@ pub(crate) fn evaluate_preferability_with_context(
                 offense_scale_bp,
             );
             if include_match_point_window {
-                if opponent_score_now + opponent_immediate_window.best_score >= Config::TARGET_SCORE
+                if opponent_score_now + opponent_immediate_window.best_score
+                    >= game.rules().target_score
                 {
                     score -= weights.immediate_winning_carrier;
                 }
-                if my_score_now + my_next_turn_window.best_score >= Config::TARGET_SCORE {
+                if my_score_now + my_next_turn_window.best_score >= game.rules().target_score {
                     score += weights.immediate_winning_carrier;
                 }
             }
@@ -2685,7 +2690,7 @@ fn spirit_action_utility(
             .then_some(())
         })
         .map(|_| {
-            let mut game = MonsGame::new(false, board.variant());
+            let mut game = MonsGame::new_with_layout(false, board.layout());
             game.board = board.clone();
             game.active_color = spirit_color;
             game.turn_number = 2;
@@ -3133,6 +3138,7 @@ fn drainer_safety_snapshot(
     location: Location,
     use_legacy_formula: bool,
     include_walk_threat: bool,
+    rules: &RuleSet,
 ) -> DrainerSafetySnapshot {
     drainer_safety_snapshot_with_context(
         board,
@@ -3141,6 +3147,7 @@ fn drainer_safety_snapshot(
         use_legacy_formula,
         include_walk_threat,
         None,
+        rules,
     )
 }
 
@@ -3151,6 +3158,7 @@ fn drainer_safety_snapshot_with_context(
     use_legacy_formula: bool,
     include_walk_threat: bool,
     context: Option<&ScoringEvalContext>,
+    rules: &RuleSet,
 ) -> DrainerSafetySnapshot {
     let (raw_danger, min_mana, angel_nearby) =
         drainer_distances_with_context(board, color, location, use_legacy_formula, context);
@@ -3161,6 +3169,7 @@ fn drainer_safety_snapshot_with_context(
         angel_nearby,
         use_legacy_formula,
         context,
+        rules,
     );
     let walk_threat = include_walk_threat
         && !exact_danger_threat
@@ -3277,6 +3286,7 @@ fn is_drainer_under_danger_threat(
     location: Location,
     angel_nearby: bool,
     use_legacy_formula: bool,
+    rules: &RuleSet,
 ) -> bool {
     is_drainer_under_danger_threat_with_context(
         board,
@@ -3285,6 +3295,7 @@ fn is_drainer_under_danger_threat(
         angel_nearby,
         use_legacy_formula,
         None,
+        rules,
     )
 }
 
@@ -3295,6 +3306,7 @@ fn is_drainer_under_danger_threat_with_context(
     angel_nearby: bool,
     use_legacy_formula: bool,
     context: Option<&ScoringEvalContext>,
+    rules: &RuleSet,
 ) -> bool {
     if use_legacy_formula {
         return is_drainer_under_immediate_threat(board, color, location, angel_nearby);
@@ -3310,7 +3322,7 @@ fn is_drainer_under_danger_threat_with_context(
             color.other(),
             color,
             location,
-            Config::MONS_MOVES_PER_TURN,
+            rules.mons_moves_per_turn,
             true,
         )
     } else {
@@ -3320,7 +3332,7 @@ fn is_drainer_under_danger_threat_with_context(
             color.other(),
             color,
             location,
-            Config::MONS_MOVES_PER_TURN,
+            rules.mons_moves_per_turn,
             true,
         )
     }
@@ -3578,7 +3590,7 @@ mod tests {
             .occupied()
             .map(|(location, item)| (mirror_location(location), mirror_item(item)))
             .collect::<std::collections::HashMap<_, _>>();
-        let mut mirrored = MonsGame::new(false, game.variant());
+        let mut mirrored = MonsGame::new_with_layout(false, game.board.layout());
         mirrored.replace_board_items(mirrored_items);
         mirrored.active_color = swapped_color(game.active_color);
         mirrored.actions_used_count = game.actions_used_count;
@@ -0,0 +1,199 @@
+use crate::*;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Version written alongside serialized `Board` and `MonsGame` values.
+/// Deserialization rejects any other version.
+pub const SERDE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct BoardRepr {
+    version: u32,
+    variant: GameVariant,
+    /// Name of the registered layout when `variant` is `Custom`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variant_name: Option<String>,
+    fen: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MonsGameRepr {
+    version: u32,
+    fen: String,
+    takeback_fens: Vec<String>,
+    is_moves_verified: bool,
+    with_verbose_tracking: bool,
+    verbose_tracking_entities: Vec<VerboseTrackingEntity>,
+    input_history_start_fen: Option<String>,
+    input_history: Vec<Vec<Input>>,
+}
+
+fn check_version<E: serde::de::Error>(version: u32) -> Result<(), E> {
+    if version == SERDE_FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(E::custom(format!(
+            "unsupported serialization version {}, expected {}",
+            version, SERDE_FORMAT_VERSION
+        )))
+    }
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BoardRepr {
+            version: SERDE_FORMAT_VERSION,
+            variant: self.variant(),
+            variant_name: (self.variant() == GameVariant::Custom)
+                .then(|| self.layout().spec().name.clone()),
+            fen: self.fen(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = BoardRepr::deserialize(deserializer)?;
+        check_version(repr.version)?;
+        let layout = match (repr.variant, repr.variant_name) {
+            (GameVariant::Custom, Some(name)) => VariantLayout::registered(&name)
+                .ok_or_else(|| D::Error::custom(format!("unknown variant \"{}\"", name)))?,
+            (variant, _) => VariantLayout::builtin(variant)
+                .ok_or_else(|| D::Error::custom("custom variant without a name"))?,
+        };
+        Board::from_fen_with_layout(&repr.fen, layout)
+            .ok_or_else(|| D::Error::custom(format!("invalid board fen \"{}\"", repr.fen)))
+    }
+}
+
+impl Serialize for MonsGame {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MonsGameRepr {
+            version: SERDE_FORMAT_VERSION,
+            fen: self.fen(),
+            takeback_fens: self.takeback_fens.clone(),
+            is_moves_verified: self.is_moves_verified,
+            with_verbose_tracking: self.with_verbose_tracking,
+            verbose_tracking_entities: self.verbose_tracking_entities.clone(),
+            input_history_start_fen: self.input_history_start_fen().map(str::to_string),
+            input_history: self.input_history().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MonsGame {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = MonsGameRepr::deserialize(deserializer)?;
+        check_version(repr.version)?;
+        let mut game = MonsGame::from_fen(&repr.fen, repr.with_verbose_tracking)
+            .ok_or_else(|| D::Error::custom(format!("invalid game fen \"{}\"", repr.fen)))?;
+        game.takeback_fens = repr.takeback_fens;
+        game.is_moves_verified = repr.is_moves_verified;
+        game.verbose_tracking_entities = repr.verbose_tracking_entities;
+        game.set_input_history(repr.input_history_start_fen, repr.input_history);
+        rebuild_position_history(&mut game);
+        Ok(game)
+    }
+}
+
+/// Recovers the repetition and no-progress counts of a deserialized game by
+/// replaying its input history. Leaves the history alone when the replay
+/// does not reach the saved position.
+fn rebuild_position_history(game: &mut MonsGame) {
+    if game.input_history().is_empty() {
+        return;
+    }
+    if let Some(replay) = game.replay_from_history_start(game.input_history()) {
+        if replay.zobrist() == game.zobrist() {
+            game.position_history = replay.position_history;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_and_board_round_trip_through_json_and_fen() {
+        for variant in [GameVariant::Classic, GameVariant::CornerChainManaRows] {
+            let mut game = MonsGame::new(true, variant);
+            for ply in 0..12 {
+                let legal_moves = game.legal_move_inputs(true);
+                let inputs = legal_moves[(ply * 3) % legal_moves.len()].clone();
+                game.process_input(inputs, false, false);
+            }
+
+            let json = serde_json::to_string(&game).unwrap();
+            let restored: MonsGame = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.fen(), game.fen());
+            assert_eq!(restored.zobrist(), game.zobrist());
+            assert_eq!(restored.takeback_fens, game.takeback_fens);
+            assert_eq!(restored.input_history(), game.input_history());
+            assert_eq!(
+                restored.input_history_start_fen(),
+                game.input_history_start_fen()
+            );
+            assert_eq!(
+                restored.verbose_tracking_entities.len(),
+                game.verbose_tracking_entities.len()
+            );
+
+            let board_json = serde_json::to_string(&game.board).unwrap();
+            let board: Board = serde_json::from_str(&board_json).unwrap();
+            assert_eq!(board, game.board);
+            assert_eq!(board.fen(), game.board.fen());
+        }
+    }
+
+    #[test]
+    fn progress_rules_and_position_history_survive_json() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        game.set_progress_rules(Some(ProgressRules {
+            max_repetitions: Some(5),
+            max_turns_without_progress: None,
+        }));
+        for ply in 0..8 {
+            let legal_moves = game.legal_move_inputs(true);
+            game.process_input(
+                legal_moves[(ply * 5) % legal_moves.len()].clone(),
+                false,
+                false,
+            );
+        }
+
+        let json = serde_json::to_string(&game).unwrap();
+        let restored: MonsGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.progress_rules(), game.progress_rules());
+        assert_eq!(restored.position_history, game.position_history);
+    }
+
+    #[test]
+    fn events_and_outputs_round_trip() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        for legal_move in game.legal_moves() {
+            let output = Output::Events(legal_move.events.clone());
+            let json = serde_json::to_string(&output).unwrap();
+            assert_eq!(serde_json::from_str::<Output>(&json).unwrap(), output);
+            for event in legal_move.events {
+                let json = serde_json::to_string(&event).unwrap();
+                let restored: Event = serde_json::from_str(&json).unwrap();
+                assert_eq!(restored.fen(), event.fen());
+            }
+        }
+
+        let output = game.process_input(vec![], false, false);
+        let json = serde_json::to_string(&output).unwrap();
+        assert_eq!(serde_json::from_str::<Output>(&json).unwrap(), output);
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        let json = serde_json::to_string(&Board::new())
+            .unwrap()
+            .replace("\"version\":1", "\"version\":2");
+        assert!(serde_json::from_str::<Board>(&json).is_err());
+    }
+}
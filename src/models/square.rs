@@ -1,6 +1,7 @@
 use crate::*;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Square {
     Regular,
     ConsumableBase,
@@ -0,0 +1,220 @@
+use crate::*;
+
+/// Why a finished game ended.
+#[wasm_bindgen]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TerminationReason {
+    TargetScore,
+    Resignation,
+    Timeout,
+    Abandonment,
+    Adjudication,
+    Agreement,
+}
+
+impl TerminationReason {
+    /// Short code used in FEN, game records and `winner`.
+    pub fn code(self) -> &'static str {
+        match self {
+            TerminationReason::TargetScore => "score",
+            TerminationReason::Resignation => "resign",
+            TerminationReason::Timeout => "timeout",
+            TerminationReason::Abandonment => "abandon",
+            TerminationReason::Adjudication => "adjudication",
+            TerminationReason::Agreement => "agreement",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "score" => Some(TerminationReason::TargetScore),
+            "resign" => Some(TerminationReason::Resignation),
+            "timeout" => Some(TerminationReason::Timeout),
+            "abandon" => Some(TerminationReason::Abandonment),
+            "adjudication" => Some(TerminationReason::Adjudication),
+            "agreement" => Some(TerminationReason::Agreement),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum GameResult {
+    Win(Color),
+    Draw,
+}
+
+impl GameResult {
+    pub fn winner(self) -> Option<Color> {
+        match self {
+            GameResult::Win(color) => Some(color),
+            GameResult::Draw => None,
+        }
+    }
+
+    /// `w`, `b` or `d`.
+    pub fn code(self) -> String {
+        match self {
+            GameResult::Win(color) => color.fen(),
+            GameResult::Draw => "d".to_string(),
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "d" => Some(GameResult::Draw),
+            _ => Color::from_fen(code).map(GameResult::Win),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Termination {
+    pub result: GameResult,
+    pub reason: TerminationReason,
+}
+
+impl Termination {
+    pub fn winner(&self) -> Option<Color> {
+        self.result.winner()
+    }
+
+    /// `<result>:<reason>`, e.g. `b:resign` or `d:agreement`.
+    pub fn code(&self) -> String {
+        format!("{}:{}", self.result.code(), self.reason.code())
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        let (result, reason) = code.split_once(':')?;
+        Some(Termination {
+            result: GameResult::from_code(result)?,
+            reason: TerminationReason::from_code(reason)?,
+        })
+    }
+}
+
+impl MonsGame {
+    /// How the game ended, or `None` while it is still in progress.
+    pub fn termination(&self) -> Option<Termination> {
+        if self.termination.is_some() {
+            return self.termination;
+        }
+        self.score_winner().map(|winner| Termination {
+            result: GameResult::Win(winner),
+            reason: TerminationReason::TargetScore,
+        })
+    }
+
+    /// Whether the game has ended, by score or by any other termination.
+    #[inline]
+    pub fn is_game_over(&self) -> bool {
+        self.termination.is_some() || self.score_winner().is_some()
+    }
+
+    /// `color` resigns; the opponent wins.
+    pub fn resign(&mut self, color: Color) -> Option<Event> {
+        self.adjudicate(
+            GameResult::Win(color.other()),
+            TerminationReason::Resignation,
+        )
+    }
+
+    /// Ends the game because `color` ran out of time.
+    pub fn flag_fall(&mut self, color: Color) -> Option<Event> {
+        self.adjudicate(GameResult::Win(color.other()), TerminationReason::Timeout)
+    }
+
+    /// Ends the game with `result` for a reason decided outside of play, such
+    /// as abandonment, adjudication or agreement. Returns the resulting
+    /// `GameTerminated` event, or `None` if the game was already over or
+    /// `reason` is `TargetScore`, which only play can produce.
+    pub fn adjudicate(&mut self, result: GameResult, reason: TerminationReason) -> Option<Event> {
+        if self.is_game_over() || reason == TerminationReason::TargetScore {
+            return None;
+        }
+        self.termination = Some(Termination { result, reason });
+        if self.is_tracking_takeback_history() {
+            self.takeback_fens.clear();
+        }
+        self.invalidate_process_input_cache();
+        let event = Event::GameTerminated { result, reason };
+        if self.with_verbose_tracking {
+            self.verbose_tracking_entities.push(VerboseTrackingEntity {
+                fen: self.fen(),
+                color: self.active_color,
+                events: vec![event.clone()],
+            });
+        }
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resignation_ends_game_and_blocks_inputs() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        assert_eq!(game.termination(), None);
+        assert_eq!(
+            game.resign(Color::White),
+            Some(Event::GameTerminated {
+                result: GameResult::Win(Color::Black),
+                reason: TerminationReason::Resignation,
+            })
+        );
+        assert_eq!(game.winner_color(), Some(Color::Black));
+        assert_eq!(game.flag_fall(Color::Black), None);
+        assert_eq!(
+            game.process_input_checked(vec![], false, false),
+            Err(InputError::GameOver)
+        );
+        assert_eq!(
+            game.clone_for_simulation().winner_color(),
+            Some(Color::Black)
+        );
+
+        let reloaded = MonsGame::from_fen(&game.fen(), false).unwrap();
+        assert_eq!(reloaded.termination(), game.termination());
+        assert_eq!(reloaded.fen(), game.fen());
+    }
+
+    #[test]
+    fn drawn_adjudication_has_no_winner() {
+        let mut game = MonsGame::new(false, GameVariant::SwappedManaRows);
+        assert_eq!(
+            game.adjudicate(
+                GameResult::Win(Color::White),
+                TerminationReason::TargetScore
+            ),
+            None
+        );
+        assert!(game
+            .adjudicate(GameResult::Draw, TerminationReason::Agreement)
+            .is_some());
+        assert!(game.is_game_over());
+        assert_eq!(game.winner_color(), None);
+        assert_eq!(
+            game.termination().map(|termination| termination.code()),
+            Some("d:agreement".to_string())
+        );
+        assert_eq!(
+            game.process_input_checked(vec![], false, false),
+            Err(InputError::GameOver)
+        );
+
+        let reloaded = MonsGame::from_fen(&game.fen(), false).unwrap();
+        assert_eq!(reloaded.variant(), GameVariant::SwappedManaRows);
+        assert_eq!(reloaded.termination(), game.termination());
+        assert_eq!(
+            MonsGame::from_bytes(&game.to_bytes(), false)
+                .unwrap()
+                .termination(),
+            game.termination()
+        );
+    }
+}
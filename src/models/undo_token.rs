@@ -0,0 +1,210 @@
+use crate::*;
+
+/// Cells a typical move touches; longer event lists grow the buffer.
+const UNDO_TOUCHED_ITEMS_CAPACITY: usize = 32;
+
+/// Everything `MonsGame::undo` needs to revert one `apply_events_with_undo`.
+#[derive(Debug, Clone)]
+pub struct UndoToken {
+    events: Vec<Event>,
+    white_score: i32,
+    black_score: i32,
+    active_color: Color,
+    actions_used_count: i32,
+    mana_moves_count: i32,
+    mons_moves_count: i32,
+    white_potions_count: i32,
+    black_potions_count: i32,
+    turn_number: i32,
+    touched_items: Vec<(Location, Option<Item>)>,
+    takeback_fens: Option<Vec<String>>,
+    verbose_tracking_len: usize,
+}
+
+impl UndoToken {
+    /// Events as returned by `apply_and_add_resulting_events`.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    fn capture(game: &MonsGame, events: &[Event]) -> Self {
+        let mut token = Self {
+            events: vec![],
+            white_score: game.white_score,
+            black_score: game.black_score,
+            active_color: game.active_color,
+            actions_used_count: game.actions_used_count,
+            mana_moves_count: game.mana_moves_count,
+            mons_moves_count: game.mons_moves_count,
+            white_potions_count: game.white_potions_count,
+            black_potions_count: game.black_potions_count,
+            turn_number: game.turn_number,
+            touched_items: Vec::with_capacity(UNDO_TOUCHED_ITEMS_CAPACITY),
+            takeback_fens: game
+                .is_tracking_takeback_history()
+                .then(|| game.takeback_fens.clone()),
+            verbose_tracking_len: game.verbose_tracking_entities.len(),
+        };
+
+        let mut seen_mask = 0u128;
+        let mut touch = |location: Location| {
+            let bit = 1u128 << location.index();
+            if seen_mask & bit == 0 {
+                seen_mask |= bit;
+                token
+                    .touched_items
+                    .push((location, game.board.item(location).copied()));
+            }
+        };
+        for event in events {
+            match *event {
+                Event::MonMove { from, to, .. }
+                | Event::ManaMove { from, to, .. }
+                | Event::MysticAction { from, to, .. }
+                | Event::DemonAction { from, to, .. }
+                | Event::DemonAdditionalStep { from, to, .. }
+                | Event::UsePotion { from, to }
+                | Event::MonFainted { from, to, .. }
+                | Event::SupermanaBackToBase { from, to }
+                | Event::BombAttack { from, to, .. } => {
+                    touch(from);
+                    touch(to);
+                }
+                Event::SpiritTargetMove { from, to, by, .. } => {
+                    touch(from);
+                    touch(to);
+                    touch(by);
+                }
+                Event::ManaScored { at, .. }
+                | Event::PickupBomb { at, .. }
+                | Event::PickupPotion { at, .. }
+                | Event::PickupMana { at, .. }
+                | Event::ManaDropped { at, .. }
+                | Event::MonAwake { at, .. }
+                | Event::BombExplosion { at } => touch(at),
+                Event::NextTurn { .. }
+                | Event::GameOver { .. }
+                | Event::GameTerminated { .. }
+                | Event::Takeback => {}
+            }
+        }
+        // Cooldowns of the opponent's fainted mons tick down when the turn passes.
+        for location in game.board.fainted_mons_locations(game.active_color.other()) {
+            touch(location);
+        }
+        token
+    }
+}
+
+impl MonsGame {
+    /// Applies `events` like `apply_and_add_resulting_events` and returns a
+    /// token that reverts the game to its exact prior state via `undo`.
+    /// The board Zobrist key is kept up to date in both directions.
+    pub fn apply_events_with_undo(&mut self, events: Vec<Event>) -> UndoToken {
+        let mut token = UndoToken::capture(self, &events);
+        token.events = self.apply_and_add_resulting_events(events);
+        token
+    }
+
+    /// Reverts the most recent `apply_events_with_undo`. Tokens must be
+    /// undone in reverse order of application.
+    pub fn undo(&mut self, token: UndoToken) {
+        self.white_score = token.white_score;
+        self.black_score = token.black_score;
+        self.active_color = token.active_color;
+        self.actions_used_count = token.actions_used_count;
+        self.mana_moves_count = token.mana_moves_count;
+        self.mons_moves_count = token.mons_moves_count;
+        self.white_potions_count = token.white_potions_count;
+        self.black_potions_count = token.black_potions_count;
+        self.turn_number = token.turn_number;
+
+        for &(location, item) in token.touched_items.iter() {
+            match item {
+                Some(item) => self.board.put(item, location),
+                None => self.board.remove_item(location),
+            }
+        }
+
+        if let Some(takeback_fens) = token.takeback_fens {
+            self.takeback_fens = takeback_fens;
+        }
+        self.verbose_tracking_entities
+            .truncate(token.verbose_tracking_len);
+        self.invalidate_process_input_cache();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_restores_exact_state_and_board_zobrist() {
+        for (variant, with_tracking) in [
+            (GameVariant::Classic, false),
+            (GameVariant::SwappedManaRows, true),
+        ] {
+            let mut game = MonsGame::new(with_tracking, variant);
+            let mut stack = Vec::new();
+
+            for ply in 0..40 {
+                let legal_moves = game.legal_move_inputs(true);
+                if legal_moves.is_empty() {
+                    break;
+                }
+                let inputs = &legal_moves[(ply * 7) % legal_moves.len()];
+                let Output::Events(events) = game.process_input_slice(inputs, true, false) else {
+                    panic!("legal move did not produce events");
+                };
+
+                let mut expected = game.clone();
+                let expected_events = expected.apply_and_add_resulting_events(events.clone());
+                let before = (
+                    game.fen(),
+                    game.board.clone(),
+                    game.takeback_fens.clone(),
+                    game.verbose_tracking_entities.len(),
+                );
+                let token = game.apply_events_with_undo(events);
+
+                assert_eq!(token.events(), expected_events.as_slice());
+                assert_eq!(game.fen(), expected.fen());
+                assert_eq!(game.board.zobrist(), zobrist_board_key(&game.board.items));
+                stack.push((token, before));
+            }
+            assert!(stack.len() > 10);
+
+            while let Some((token, (fen, board, takeback_fens, verbose_len))) = stack.pop() {
+                game.undo(token);
+                assert_eq!(game.fen(), fen);
+                assert_eq!(game.board, board);
+                assert_eq!(game.board.zobrist(), board.zobrist());
+                assert_eq!(game.takeback_fens, takeback_fens);
+                assert_eq!(game.verbose_tracking_entities.len(), verbose_len);
+            }
+        }
+    }
+
+    #[test]
+    fn undo_restores_events_touching_more_cells_than_the_inline_capacity() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        let before = (game.fen(), game.board.clone());
+        let mut events = game
+            .board
+            .occupied()
+            .map(|(at, _)| Event::BombExplosion { at })
+            .collect::<Vec<_>>();
+        events.extend((0..Config::BOARD_SIZE).map(|j| Event::MonAwake {
+            mon: Mon::new(MonKind::Angel, Color::White, 0),
+            at: Location::new(5, j),
+        }));
+        assert!(events.len() > UNDO_TOUCHED_ITEMS_CAPACITY);
+
+        let token = game.apply_events_with_undo(events);
+        assert_eq!(game.board.occupied().count(), 0);
+        game.undo(token);
+        assert_eq!((game.fen(), game.board.clone()), before);
+        assert_eq!(game.board.zobrist(), before.1.zobrist());
+    }
+}
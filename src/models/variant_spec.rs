@@ -0,0 +1,764 @@
+use crate::models::location::BOARD_CELLS;
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
+
+const MANA_POOL_LOCATIONS: [(Location, Color); 4] = [
+    (Location { i: 0, j: 0 }, Color::Black),
+    (Location { i: 0, j: 10 }, Color::Black),
+    (Location { i: 10, j: 0 }, Color::White),
+    (Location { i: 10, j: 10 }, Color::White),
+];
+
+/// Longest variant name, so binary encodings can length-prefix it in a byte.
+pub const MAX_VARIANT_NAME_LEN: usize = 64;
+
+pub(crate) const MON_KINDS: [MonKind; 5] = [
+    MonKind::Demon,
+    MonKind::Drainer,
+    MonKind::Angel,
+    MonKind::Spirit,
+    MonKind::Mystic,
+];
+
+/// Owners of `Config::MONS_BASE_LOCATIONS`, in the same order.
+const STANDARD_MON_BASE_OWNERS: [(MonKind, Color); 10] = [
+    (MonKind::Mystic, Color::Black),
+    (MonKind::Spirit, Color::Black),
+    (MonKind::Drainer, Color::Black),
+    (MonKind::Angel, Color::Black),
+    (MonKind::Demon, Color::Black),
+    (MonKind::Demon, Color::White),
+    (MonKind::Angel, Color::White),
+    (MonKind::Drainer, Color::White),
+    (MonKind::Spirit, Color::White),
+    (MonKind::Mystic, Color::White),
+];
+
+static BUILTIN_LAYOUTS: LazyLock<Vec<VariantLayout>> = LazyLock::new(|| {
+    GameVariant::BUILTIN
+        .iter()
+        .map(|&variant| {
+            let spec = VariantSpec::builtin(variant).expect("built-in variants have a spec");
+            VariantLayout::new(spec, variant, variant.id())
+        })
+        .collect()
+});
+
+static REGISTERED_LAYOUTS: RwLock<Vec<&'static VariantLayout>> = RwLock::new(Vec::new());
+
+/// Base square of one mon in a `VariantSpec`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonBaseSpec {
+    pub kind: MonKind,
+    pub color: Color,
+    pub location: Location,
+}
+
+/// Data definition of a variant's board: where every base sits, plus rule
+/// overrides games of the variant start with. Mana pools always stay in the
+/// corners.
+///
+/// The text form has one `key: value` line per field; omitted fields keep
+/// Classic's layout and `#` starts a comment.
+///
+/// ```text
+/// name: wide-rows
+/// black mana: b7 d7 f7 h7 j7
+/// white mana: b5 d5 f5 h5 j5
+/// target score: 7
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct VariantSpec {
+    /// Lowercase letters, digits and dashes, at most `MAX_VARIANT_NAME_LEN`
+    /// of them. Custom variants are written into FENs by this name.
+    pub name: String,
+    pub black_mana_bases: Vec<Location>,
+    pub white_mana_bases: Vec<Location>,
+    pub consumable_bases: Vec<Location>,
+    pub supermana_base: Location,
+    pub mon_bases: Vec<MonBaseSpec>,
+    pub target_score: Option<i32>,
+    pub mons_moves_per_turn: Option<i32>,
+    pub mana_moves_per_turn: Option<i32>,
+    pub actions_per_turn: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariantSpecError {
+    Syntax { line: usize, message: String },
+    Json { message: String },
+    InvalidName { name: String },
+    NameTaken { name: String },
+    InvalidLocation { location: Location },
+    SquareReused { location: Location },
+    MissingManaBases { color: Color },
+    MissingMonBase { kind: MonKind, color: Color },
+    DuplicateMonBase { kind: MonKind, color: Color },
+    InvalidRule { rule: &'static str, value: i32 },
+}
+
+impl fmt::Display for VariantSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariantSpecError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            VariantSpecError::Json { message } => write!(f, "invalid json: {}", message),
+            VariantSpecError::InvalidName { name } => {
+                write!(f, "invalid variant name \"{}\"", name)
+            }
+            VariantSpecError::NameTaken { name } => {
+                write!(f, "variant \"{}\" is already defined", name)
+            }
+            VariantSpecError::InvalidLocation { location } => {
+                write!(f, "location {:?} is off the board", location)
+            }
+            VariantSpecError::SquareReused { location } => {
+                write!(f, "square {} holds more than one base", location.notation())
+            }
+            VariantSpecError::MissingManaBases { color } => {
+                write!(f, "{:?} has no mana bases", color)
+            }
+            VariantSpecError::MissingMonBase { kind, color } => {
+                write!(f, "{:?} {:?} has no base", color, kind)
+            }
+            VariantSpecError::DuplicateMonBase { kind, color } => {
+                write!(f, "{:?} {:?} has more than one base", color, kind)
+            }
+            VariantSpecError::InvalidRule { rule, value } => {
+                write!(f, "{} cannot be {}", rule, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VariantSpecError {}
+
+impl Default for VariantSpec {
+    /// Classic's layout without a name.
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            ..Self::builtin(GameVariant::Classic).expect("Classic has a spec")
+        }
+    }
+}
+
+impl VariantSpec {
+    /// Spec of a built-in variant. `None` for `Custom`, which has no layout
+    /// of its own.
+    pub fn builtin(variant: GameVariant) -> Option<Self> {
+        let mon_bases = Config::MONS_BASE_LOCATIONS
+            .iter()
+            .zip(STANDARD_MON_BASE_OWNERS)
+            .map(|(&location, (kind, color))| MonBaseSpec {
+                kind,
+                color,
+                location,
+            })
+            .collect();
+        Some(Self {
+            name: variant.builtin_name().to_string(),
+            black_mana_bases: Config::mana_base_locations(variant, Color::Black)?.to_vec(),
+            white_mana_bases: Config::mana_base_locations(variant, Color::White)?.to_vec(),
+            consumable_bases: Config::CONSUMABLE_BASE_LOCATIONS.to_vec(),
+            supermana_base: Config::SUPERMANA_BASE,
+            mon_bases,
+            target_score: None,
+            mons_moves_per_turn: None,
+            mana_moves_per_turn: None,
+            actions_per_turn: None,
+        })
+    }
+
+    pub fn mana_bases(&self, color: Color) -> &[Location] {
+        match color {
+            Color::White => &self.white_mana_bases,
+            Color::Black => &self.black_mana_bases,
+        }
+    }
+
+    /// First problem that keeps the spec from describing a playable board.
+    pub fn validate(&self) -> Result<(), VariantSpecError> {
+        let name_is_valid = self
+            .name
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+            && self.name.chars().any(|ch| ch.is_ascii_lowercase())
+            && self.name.len() <= MAX_VARIANT_NAME_LEN;
+        if !name_is_valid {
+            return Err(VariantSpecError::InvalidName {
+                name: self.name.clone(),
+            });
+        }
+
+        let mut used: HashSet<Location> = MANA_POOL_LOCATIONS
+            .iter()
+            .map(|(location, _)| *location)
+            .collect();
+        let bases = self
+            .black_mana_bases
+            .iter()
+            .chain(&self.white_mana_bases)
+            .chain(&self.consumable_bases)
+            .chain(std::iter::once(&self.supermana_base))
+            .chain(self.mon_bases.iter().map(|base| &base.location));
+        for &location in bases {
+            if !location.is_valid() {
+                return Err(VariantSpecError::InvalidLocation { location });
+            }
+            if !used.insert(location) {
+                return Err(VariantSpecError::SquareReused { location });
+            }
+        }
+
+        for color in [Color::White, Color::Black] {
+            if self.mana_bases(color).is_empty() {
+                return Err(VariantSpecError::MissingManaBases { color });
+            }
+            for kind in MON_KINDS {
+                match self
+                    .mon_bases
+                    .iter()
+                    .filter(|base| base.kind == kind && base.color == color)
+                    .count()
+                {
+                    0 => return Err(VariantSpecError::MissingMonBase { kind, color }),
+                    1 => {}
+                    _ => return Err(VariantSpecError::DuplicateMonBase { kind, color }),
+                }
+            }
+        }
+
+        for (rule, value, min) in self.rules() {
+            if let Some(value) = value {
+                if value < min {
+                    return Err(VariantSpecError::InvalidRule { rule, value });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn rules(&self) -> [(&'static str, Option<i32>, i32); 4] {
+        [
+            ("target score", self.target_score, 1),
+            ("mons moves per turn", self.mons_moves_per_turn, 1),
+            ("mana moves per turn", self.mana_moves_per_turn, 1),
+            ("actions per turn", self.actions_per_turn, 0),
+        ]
+    }
+
+    fn rule_mut(&mut self, rule: &str) -> Option<&mut Option<i32>> {
+        match rule {
+            "target score" => Some(&mut self.target_score),
+            "mons moves per turn" => Some(&mut self.mons_moves_per_turn),
+            "mana moves per turn" => Some(&mut self.mana_moves_per_turn),
+            "actions per turn" => Some(&mut self.actions_per_turn),
+            _ => None,
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let locations = |locations: &[Location]| {
+            locations
+                .iter()
+                .map(Location::notation)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut text = format!("name: {}\n", self.name);
+        text.push_str(format!("black mana: {}\n", locations(&self.black_mana_bases)).as_str());
+        text.push_str(format!("white mana: {}\n", locations(&self.white_mana_bases)).as_str());
+        text.push_str(format!("consumables: {}\n", locations(&self.consumable_bases)).as_str());
+        text.push_str(format!("supermana: {}\n", self.supermana_base.notation()).as_str());
+        for base in &self.mon_bases {
+            text.push_str(
+                format!(
+                    "{} {}: {}\n",
+                    color_name(base.color),
+                    mon_kind_name(base.kind),
+                    base.location.notation()
+                )
+                .as_str(),
+            );
+        }
+        for (rule, value, _) in self.rules() {
+            if let Some(value) = value {
+                text.push_str(format!("{}: {}\n", rule, value).as_str());
+            }
+        }
+        text
+    }
+
+    /// Parses and validates the text form.
+    pub fn from_text(text: &str) -> Result<Self, VariantSpecError> {
+        let mut spec = VariantSpec::default();
+        for (index, line) in text.lines().enumerate() {
+            let syntax = |message: String| VariantSpecError::Syntax {
+                line: index + 1,
+                message,
+            };
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| syntax(format!("expected `key: value`, found `{}`", line)))?;
+            let key = key.split_whitespace().collect::<Vec<_>>().join(" ");
+            let value = value.trim();
+            let locations = || {
+                value
+                    .split_whitespace()
+                    .map(|notation| {
+                        Location::from_notation(notation)
+                            .ok_or_else(|| syntax(format!("unknown square `{}`", notation)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            let location = || match locations()?.as_slice() {
+                [location] => Ok(*location),
+                _ => Err(syntax(format!("`{}` expects one square", key))),
+            };
+            match key.as_str() {
+                "name" => spec.name = value.to_string(),
+                "black mana" => spec.black_mana_bases = locations()?,
+                "white mana" => spec.white_mana_bases = locations()?,
+                "consumables" => spec.consumable_bases = locations()?,
+                "supermana" => spec.supermana_base = location()?,
+                _ => {
+                    if let Some((color, kind)) = parse_mon_key(key.as_str()) {
+                        let location = location()?;
+                        spec.mon_bases
+                            .retain(|base| base.kind != kind || base.color != color);
+                        spec.mon_bases.push(MonBaseSpec {
+                            kind,
+                            color,
+                            location,
+                        });
+                    } else if let Some(rule) = spec.rule_mut(key.as_str()) {
+                        let value = value
+                            .parse()
+                            .map_err(|_| syntax(format!("`{}` is not a number", value)))?;
+                        *rule = Some(value);
+                    } else {
+                        return Err(syntax(format!("unknown key `{}`", key)));
+                    }
+                }
+            }
+        }
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("variant spec serializes to json")
+    }
+
+    /// Parses and validates the JSON form; omitted fields keep Classic's
+    /// layout, as in the text form.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, VariantSpecError> {
+        let spec: VariantSpec =
+            serde_json::from_str(json).map_err(|error| VariantSpecError::Json {
+                message: error.to_string(),
+            })?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Makes the variant available to FEN, record and binary loading under
+    /// its name. Registering an identical spec again returns the existing
+    /// layout; built-in names and names already used by a different spec are
+    /// rejected.
+    pub fn register(self) -> Result<&'static VariantLayout, VariantSpecError> {
+        self.validate()?;
+        let builtin_name = GameVariant::BUILTIN
+            .iter()
+            .any(|variant| variant.builtin_name() == self.name);
+        if builtin_name {
+            return Err(VariantSpecError::NameTaken { name: self.name });
+        }
+        let mut registered = REGISTERED_LAYOUTS
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(layout) = registered
+            .iter()
+            .find(|layout| layout.spec.name == self.name)
+        {
+            return if layout.spec == self {
+                Ok(layout)
+            } else {
+                Err(VariantSpecError::NameTaken { name: self.name })
+            };
+        }
+        let key = GameVariant::Custom.id() + registered.len() as i32;
+        let layout: &'static VariantLayout =
+            Box::leak(Box::new(VariantLayout::new(self, GameVariant::Custom, key)));
+        registered.push(layout);
+        Ok(layout)
+    }
+}
+
+impl fmt::Display for VariantSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_text().as_str())
+    }
+}
+
+impl FromStr for VariantSpec {
+    type Err = VariantSpecError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::from_text(text)
+    }
+}
+
+/// A `VariantSpec` resolved into the square and initial item tables boards
+/// read from. Built-in layouts and registered ones live for the whole
+/// program, so boards hold them by reference.
+#[derive(Debug)]
+pub struct VariantLayout {
+    spec: VariantSpec,
+    variant: GameVariant,
+    key: i32,
+    squares: [Square; BOARD_CELLS],
+    squares_map: HashMap<Location, Square>,
+    initial_items: [Option<Item>; BOARD_CELLS],
+    mon_bases: [Location; 10],
+    mons_bases_set: HashSet<Location>,
+}
+
+impl PartialEq for VariantLayout {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for VariantLayout {}
+
+impl VariantLayout {
+    fn new(spec: VariantSpec, variant: GameVariant, key: i32) -> Self {
+        let mut squares = [Square::Regular; BOARD_CELLS];
+        for (location, color) in MANA_POOL_LOCATIONS {
+            squares[location.index()] = Square::ManaPool { color };
+        }
+        for color in [Color::White, Color::Black] {
+            for location in spec.mana_bases(color) {
+                squares[location.index()] = Square::ManaBase { color };
+            }
+        }
+        for location in &spec.consumable_bases {
+            squares[location.index()] = Square::ConsumableBase;
+        }
+        squares[spec.supermana_base.index()] = Square::SupermanaBase;
+        let mut mon_bases = [Location::new(0, 0); 10];
+        for base in &spec.mon_bases {
+            squares[base.location.index()] = Square::MonBase {
+                kind: base.kind,
+                color: base.color,
+            };
+            mon_bases[mon_base_index(base.kind, base.color)] = base.location;
+        }
+
+        let squares_map = squares
+            .iter()
+            .enumerate()
+            .filter(|(_, square)| **square != Square::Regular)
+            .map(|(index, square)| (Location::from_index(index), *square))
+            .collect();
+        let mut initial_items = [None; BOARD_CELLS];
+        for (index, square) in squares.iter().enumerate() {
+            initial_items[index] = Config::initial_item_for_square(*square);
+        }
+        Self {
+            mons_bases_set: mon_bases.iter().copied().collect(),
+            spec,
+            variant,
+            key,
+            squares,
+            squares_map,
+            initial_items,
+            mon_bases,
+        }
+    }
+
+    /// Layout of a built-in variant. `None` for `Custom`, which stands for
+    /// every registered layout rather than one of its own.
+    pub fn builtin(variant: GameVariant) -> Option<&'static VariantLayout> {
+        BUILTIN_LAYOUTS.get(variant.id() as usize)
+    }
+
+    /// `builtin` for the constructors that take a `GameVariant` and panic on
+    /// `Custom`.
+    pub(crate) fn expect_builtin(variant: GameVariant) -> &'static VariantLayout {
+        Self::builtin(variant)
+            .expect("`GameVariant::Custom` has no layout; build registered variants from theirs")
+    }
+
+    pub fn registered(name: &str) -> Option<&'static VariantLayout> {
+        REGISTERED_LAYOUTS
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .find(|layout| layout.spec.name == name)
+            .copied()
+    }
+
+    /// Names of every variant registered so far, in registration order.
+    pub fn registered_names() -> Vec<String> {
+        REGISTERED_LAYOUTS
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|layout| layout.spec.name.clone())
+            .collect()
+    }
+
+    /// Layout named by a FEN variant field: a built-in id or the name of a
+    /// registered variant.
+    pub fn from_fen(field: &str) -> Option<&'static VariantLayout> {
+        match GameVariant::from_fen(field) {
+            Some(variant) => Self::builtin(variant),
+            None => Self::registered(field),
+        }
+    }
+
+    /// FEN variant field, or `None` for the default variant, which is written
+    /// without one.
+    pub fn fen(&self) -> Option<String> {
+        match self.variant {
+            GameVariant::DEFAULT => None,
+            GameVariant::Custom => Some(self.spec.name.clone()),
+            variant => Some(variant.id().to_string()),
+        }
+    }
+
+    pub fn spec(&self) -> &VariantSpec {
+        &self.spec
+    }
+
+    /// Built-in variant, or `Custom` for registered layouts.
+    #[inline]
+    pub fn variant(&self) -> GameVariant {
+        self.variant
+    }
+
+    /// Distinguishes layouts within this process, for hashing.
+    #[inline]
+    pub(crate) fn key(&self) -> i32 {
+        self.key
+    }
+
+    #[inline]
+    pub fn square(&self, location: Location) -> Square {
+        self.squares[location.index()]
+    }
+
+    pub fn squares(&self) -> &[Square; BOARD_CELLS] {
+        &self.squares
+    }
+
+    /// Every square that is not `Regular`.
+    pub fn squares_map(&self) -> &HashMap<Location, Square> {
+        &self.squares_map
+    }
+
+    pub fn initial_items(&self) -> &[Option<Item>; BOARD_CELLS] {
+        &self.initial_items
+    }
+
+    #[inline]
+    pub fn mon_base(&self, kind: MonKind, color: Color) -> Location {
+        self.mon_bases[mon_base_index(kind, color)]
+    }
+
+    pub fn mons_bases(&self) -> &HashSet<Location> {
+        &self.mons_bases_set
+    }
+
+    #[inline]
+    pub fn supermana_base(&self) -> Location {
+        self.spec.supermana_base
+    }
+}
+
+fn mon_base_index(kind: MonKind, color: Color) -> usize {
+    let kind_index = match kind {
+        MonKind::Demon => 0,
+        MonKind::Drainer => 1,
+        MonKind::Angel => 2,
+        MonKind::Spirit => 3,
+        MonKind::Mystic => 4,
+    };
+    match color {
+        Color::White => kind_index,
+        Color::Black => kind_index + 5,
+    }
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+pub(crate) fn mon_kind_name(kind: MonKind) -> &'static str {
+    match kind {
+        MonKind::Demon => "demon",
+        MonKind::Drainer => "drainer",
+        MonKind::Angel => "angel",
+        MonKind::Spirit => "spirit",
+        MonKind::Mystic => "mystic",
+    }
+}
+
+fn parse_mon_key(key: &str) -> Option<(Color, MonKind)> {
+    let (color_key, kind_key) = key.split_once(' ')?;
+    let color = [Color::White, Color::Black]
+        .into_iter()
+        .find(|color| color_name(*color) == color_key)?;
+    let kind = MON_KINDS
+        .into_iter()
+        .find(|kind| mon_kind_name(*kind) == kind_key)?;
+    Some((color, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDE_ROWS: &str = "\
+# five mana per side, spread across the rows
+name: wide-rows-test
+black mana: b7 d7 f7 h7 j7
+white mana: b5 d5 f5 h5 j5
+white demon: c2
+target score: 7
+";
+
+    #[test]
+    fn builtin_specs_reproduce_builtin_boards() {
+        for variant in GameVariant::BUILTIN {
+            let spec = VariantSpec::builtin(variant).unwrap();
+            assert_eq!(spec.validate(), Ok(()));
+            assert_eq!(VariantSpec::from_text(spec.to_text().as_str()), Ok(spec));
+            let board = Board::new_with_variant(variant);
+            assert_eq!(board.variant(), variant);
+            for (location, item) in board.occupied() {
+                assert_eq!(
+                    Config::initial_item_for_square(board.square(location)),
+                    Some(*item)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn custom_variant_builds_nothing_without_a_registered_layout() {
+        let classic_fen = Board::new_with_variant(GameVariant::Classic).fen();
+        assert!(VariantLayout::builtin(GameVariant::Custom).is_none());
+        assert!(VariantSpec::builtin(GameVariant::Custom).is_none());
+        assert!(Board::from_fen(classic_fen.as_str(), GameVariant::Custom).is_none());
+        assert!(MonsGameModel::new(GameVariant::Custom).is_none());
+        assert!(MonsGameModel::new_for_simulation(GameVariant::Custom).is_none());
+        assert!(MonsGameModel::new_with_rules(GameVariant::Custom, "target=3").is_none());
+        assert!(
+            MonsGame::new_with_handicap(false, GameVariant::Custom, &Handicap::default()).is_none()
+        );
+        assert!(std::panic::catch_unwind(|| MonsGame::new(false, GameVariant::Custom)).is_err());
+    }
+
+    #[test]
+    fn registered_variant_plays_and_round_trips_through_fen() {
+        let spec = VariantSpec::from_text(WIDE_ROWS).unwrap();
+        assert_eq!(spec.target_score, Some(7));
+        assert_eq!(
+            VariantSpec::from_text("name: wide-rows-test\nwhite mana: f5 f5"),
+            Err(VariantSpecError::SquareReused {
+                location: Location::from_notation("f5").unwrap(),
+            })
+        );
+        assert_eq!(
+            VariantSpec::from_text("name: 42"),
+            Err(VariantSpecError::InvalidName {
+                name: "42".to_string(),
+            })
+        );
+        let long_name = "a".repeat(MAX_VARIANT_NAME_LEN + 1);
+        assert_eq!(
+            VariantSpec::from_text(format!("name: {}", long_name).as_str()),
+            Err(VariantSpecError::InvalidName { name: long_name })
+        );
+
+        let layout = spec.clone().register().unwrap();
+        assert_eq!(spec.clone().register(), Ok(layout));
+        assert_eq!(
+            VariantSpec::builtin(GameVariant::Classic)
+                .unwrap()
+                .register(),
+            Err(VariantSpecError::NameTaken {
+                name: "classic".to_string(),
+            })
+        );
+        assert_eq!(VariantLayout::registered("wide-rows-test"), Some(layout));
+
+        let mut game = MonsGame::new_with_layout(false, layout);
+        assert_eq!(game.variant(), GameVariant::Custom);
+        let demon_base = Location::from_notation("c2").unwrap();
+        assert_eq!(
+            game.board.base(Mon::new(MonKind::Demon, Color::White, 0)),
+            demon_base
+        );
+        assert!(matches!(
+            game.board.item(demon_base),
+            Some(Item::Mon { mon }) if mon.kind == MonKind::Demon
+        ));
+        assert_eq!(
+            game.board.item(Location::from_notation("b7").unwrap()),
+            Some(&Item::Mana {
+                mana: Mana::Regular(Color::Black),
+            })
+        );
+
+        let inputs = game.legal_move_inputs(true).remove(0);
+        game.process_input(inputs, false, false);
+        let fen = game.fen();
+        assert!(fen.ends_with(" wide-rows-test"));
+        let loaded = MonsGame::from_fen(fen.as_str(), false).unwrap();
+        assert_eq!(loaded.board, game.board);
+        assert_eq!(loaded.zobrist(), game.zobrist());
+        assert_eq!(
+            MonsGame::from_bytes(&game.to_bytes(), false).map(|game| game.fen()),
+            Some(fen)
+        );
+        let unregistered = format!(
+            "{} unregistered-name",
+            MonsGame::new(false, GameVariant::Classic).fen()
+        );
+        assert!(MonsGame::from_fen(unregistered.as_str(), false).is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_definition_fills_in_classic_defaults() {
+        let spec = VariantSpec::from_json(
+            r#"{"name":"json-rows-test","black_mana_bases":[{"i":4,"j":4}],"white_mana_bases":[{"i":6,"j":6}]}"#,
+        )
+        .unwrap();
+        assert_eq!(spec.supermana_base, Config::SUPERMANA_BASE);
+        assert_eq!(spec.mon_bases.len(), 10);
+        assert_eq!(VariantSpec::from_json(spec.to_json().as_str()), Ok(spec));
+        assert!(matches!(
+            VariantSpec::from_json("{\"name\":"),
+            Err(VariantSpecError::Json { .. })
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::models::location::BOARD_CELLS;
+use crate::*;
+
+#[inline]
+pub(crate) fn zobrist_mix(value: u64) -> u64 {
+    let mut mixed = value;
+    mixed ^= mixed >> 30;
+    mixed = mixed.wrapping_mul(0xbf58476d1ce4e5b9);
+    mixed ^= mixed >> 27;
+    mixed = mixed.wrapping_mul(0x94d049bb133111eb);
+    mixed ^= mixed >> 31;
+    mixed
+}
+
+/// Key of `item` standing on the cell with `index`. Board keys are the XOR of
+/// the keys of every occupied cell, so a single `put` / `remove_item` updates
+/// them in constant time.
+#[inline]
+pub(crate) fn zobrist_item_key(index: usize, item: Item) -> u64 {
+    let entry = ((index as u64)
+        .wrapping_add(1)
+        .wrapping_mul(0x9e3779b185ebca87))
+        ^ zobrist_item_code(item).wrapping_mul(0x94d049bb133111eb);
+    zobrist_mix(entry)
+}
+
+pub(crate) fn zobrist_board_key(items: &[Option<Item>; BOARD_CELLS]) -> u64 {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| item.map(|item| zobrist_item_key(index, item)))
+        .fold(0, |key, item_key| key ^ item_key)
+}
+
+#[cfg(test)]
+thread_local! {
+    static ZOBRIST_STATE_KEY_CALLS: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// Number of `zobrist_state_key` calls made on this thread so far.
+#[cfg(test)]
+pub(crate) fn zobrist_state_key_calls() -> u64 {
+    ZOBRIST_STATE_KEY_CALLS.with(|calls| calls.get())
+}
+
+/// Key of everything in `MonsGame` that lives outside the board. Unlike the
+/// board key it is not maintained: the counters are public fields written in
+/// place, so it is recomputed on every call from a fixed number of fields.
+/// `smart_automove_zobrist_state_key_cost_benchmark` measures what that costs
+/// a Pro search.
+pub(crate) fn zobrist_state_key(game: &MonsGame) -> u64 {
+    #[cfg(test)]
+    ZOBRIST_STATE_KEY_CALLS.with(|calls| calls.set(calls.get() + 1));
+    let fields = [
+        game.white_score,
+        game.black_score,
+        zobrist_color_code(game.active_color) as i32,
+        game.actions_used_count,
+        game.mana_moves_count,
+        game.mons_moves_count,
+        game.white_potions_count,
+        game.black_potions_count,
+        game.turn_number,
+        game.variant_layout().key(),
+    ];
+    let key = fields.iter().enumerate().fold(0, |key, (slot, value)| {
+        key ^ zobrist_field_key(slot, *value)
+    });
+    key ^ zobrist_rules_key(game)
+}
+
+/// Key of the rules `game` is played under and its mon move limit for this
+/// turn. Parameters at their standard value add nothing.
+fn zobrist_rules_key(game: &MonsGame) -> u64 {
+    let standard = RuleSet::STANDARD;
+    let fields = [
+        (game.rules.target_score, standard.target_score),
+        (game.rules.mons_moves_per_turn, standard.mons_moves_per_turn),
+        (game.rules.mana_moves_per_turn, standard.mana_moves_per_turn),
+        (game.rules.actions_per_turn, standard.actions_per_turn),
+        (game.rules.faint_cooldown, standard.faint_cooldown),
+        (game.mons_moves_limit(), standard.mons_moves_per_turn),
+    ];
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, (value, standard_value))| value != standard_value)
+        .fold(0, |key, (index, (value, _))| {
+            key ^ zobrist_field_key(ZOBRIST_RULES_FIRST_SLOT + index, *value)
+        })
+}
+
+/// Part of `zobrist_state_key` contributed by the turn number, so it can be
+/// folded back out when comparing positions across turns.
+pub(crate) fn zobrist_turn_number_key(turn_number: i32) -> u64 {
+    zobrist_field_key(ZOBRIST_TURN_NUMBER_SLOT, turn_number)
+}
+
+const ZOBRIST_TURN_NUMBER_SLOT: usize = 8;
+const ZOBRIST_RULES_FIRST_SLOT: usize = 10;
+
+#[inline]
+fn zobrist_field_key(slot: usize, value: i32) -> u64 {
+    zobrist_mix(((value as i64 as u64) << 8 | slot as u64).wrapping_add(0x243f6a8885a308d3))
+}
+
+#[inline]
+fn zobrist_item_code(item: Item) -> u64 {
+    match item {
+        Item::Mon { mon } => 0x100 | zobrist_mon_code(mon),
+        Item::Mana { mana } => 0x200 | zobrist_mana_code(mana),
+        Item::MonWithMana { mon, mana } => {
+            0x300 | zobrist_mon_code(mon) | (zobrist_mana_code(mana) << 16)
+        }
+        Item::MonWithConsumable { mon, consumable } => {
+            0x400 | zobrist_mon_code(mon) | (zobrist_consumable_code(consumable) << 16)
+        }
+        Item::Consumable { consumable } => 0x500 | zobrist_consumable_code(consumable),
+    }
+}
+
+#[inline]
+fn zobrist_mon_code(mon: Mon) -> u64 {
+    let kind = match mon.kind {
+        MonKind::Demon => 1,
+        MonKind::Drainer => 2,
+        MonKind::Angel => 3,
+        MonKind::Spirit => 4,
+        MonKind::Mystic => 5,
+    };
+    kind | (zobrist_color_code(mon.color) << 4) | (((mon.cooldown as i64 as u64) & 0xff) << 8)
+}
+
+#[inline]
+fn zobrist_color_code(color: Color) -> u64 {
+    match color {
+        Color::White => 1,
+        Color::Black => 2,
+    }
+}
+
+#[inline]
+fn zobrist_mana_code(mana: Mana) -> u64 {
+    match mana {
+        Mana::Regular(color) => 1 | (zobrist_color_code(color) << 4),
+        Mana::Supermana => 2,
+    }
+}
+
+#[inline]
+fn zobrist_consumable_code(consumable: Consumable) -> u64 {
+    match consumable {
+        Consumable::Bomb => 1,
+        Consumable::Potion => 2,
+        Consumable::BombOrPotion => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_zobrist_is_incremental_and_covers_counters() {
+        let mut game = MonsGame::new(false, GameVariant::Classic);
+        for ply in 0..30 {
+            let legal_moves = game.legal_move_inputs(true);
+            if legal_moves.is_empty() {
+                break;
+            }
+            let inputs = legal_moves[(ply * 5) % legal_moves.len()].clone();
+            assert!(matches!(
+                game.process_input(inputs, false, false),
+                Output::Events(_)
+            ));
+            assert_eq!(game.board.zobrist(), zobrist_board_key(&game.board.items));
+            let reloaded = MonsGame::from_fen(game.fen().as_str(), false).unwrap();
+            assert_eq!(game.zobrist(), reloaded.zobrist());
+            assert_eq!(
+                exact_board_hash(&game.board),
+                exact_board_hash(&reloaded.board)
+            );
+        }
+
+        let key = game.zobrist();
+        let mut changed = game.clone_for_simulation();
+        changed.white_potions_count += 1;
+        assert_ne!(changed.zobrist(), key);
+        changed.white_potions_count -= 1;
+        changed.active_color = changed.active_color.other();
+        assert_ne!(changed.zobrist(), key);
+        changed.active_color = changed.active_color.other();
+        assert_eq!(changed.zobrist(), key);
+        changed.set_rules(RuleSet::from_code("target=3,mons=2", &RuleSet::STANDARD).unwrap());
+        assert_ne!(changed.zobrist(), key);
+    }
+}